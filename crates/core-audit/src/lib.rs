@@ -7,6 +7,7 @@
 //!
 //! - **Telemetry Log**: Append-only JSON Lines stream of transfer events
 //! - **Beacon**: Final job summary with optional cryptographic signature
+//! - **Redaction**: Credentials are scrubbed from events before they are written
 //!
 //! # Architecture
 //!
@@ -52,12 +53,14 @@
 pub mod beacon;
 pub mod error;
 pub mod provenance;
+pub mod redact;
 pub mod telemetry;
 
 // Re-export main types
 pub use beacon::{Beacon, BeaconBuilder};
 pub use error::{Error, Result};
 pub use provenance::{ProvenanceEvent, ProvenanceType};
+pub use redact::Redactor;
 pub use telemetry::{EventType, TelemetryEvent, TelemetryLogger};
 
 #[cfg(test)]
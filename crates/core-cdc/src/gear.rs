@@ -7,8 +7,11 @@
 /// 256 random 64-bit values for uniform hash distribution.
 /// Generated deterministically from BLAKE3("gear_table_{i}") for i in 0..256.
 /// Each value is an independently derived random u64.
+///
+/// This is the table [`ChunkConfig`](crate::ChunkConfig) uses unless another
+/// one is set with [`ChunkConfig::with_gear_table`](crate::ChunkConfig::with_gear_table).
 #[rustfmt::skip]
-static GEAR_TABLE: [u64; 256] = [
+pub static GEAR_TABLE: [u64; 256] = [
     // 0x00..0x0F
     0x4d5a82553972c337, 0x4d026217c2e92217, 0x02d412548412c445, 0x769bdd30ec6d923d,
     0xec37b3af4d87b4ac, 0xf826dc9365c5a7e7, 0x9e035d2fbcf017bd, 0x89ddbc0605375f2c,
@@ -91,19 +94,31 @@ static GEAR_TABLE: [u64; 256] = [
     0xd77be53cd4d6f1e7, 0x7b62ee2a41d65f9d, 0x42dc8668aae31ed8, 0x874f2144fb356e2d,
 ];
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct GearHash {
     hash: u64,
+    table: &'static [u64; 256],
+}
+
+impl Default for GearHash {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GearHash {
     pub fn new() -> Self {
-        Self { hash: 0 }
+        Self::with_table(&GEAR_TABLE)
+    }
+
+    /// Hash with a caller-supplied gear table
+    pub fn with_table(table: &'static [u64; 256]) -> Self {
+        Self { hash: 0, table }
     }
 
     #[inline(always)]
     pub fn next(&mut self, byte: u8) -> u64 {
-        self.hash = (self.hash.wrapping_shl(1)).wrapping_add(GEAR_TABLE[byte as usize]);
+        self.hash = (self.hash.wrapping_shl(1)).wrapping_add(self.table[byte as usize]);
         self.hash
     }
 
@@ -6,8 +6,15 @@
 
 mod gear;
 
+// Testing utilities
+pub mod testing;
+
+pub use gear::GEAR_TABLE;
+
 use gear::GearHash;
 use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Errors that can occur during chunking
@@ -21,7 +28,7 @@ pub enum ChunkError {
 }
 
 /// Configuration for the chunking algorithm
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ChunkConfig {
     /// Minimum chunk size in bytes (prevents too many small chunks)
     pub min_size: usize,
@@ -31,6 +38,31 @@ pub struct ChunkConfig {
 
     /// Maximum chunk size in bytes (prevents unbounded chunks)
     pub max_size: usize,
+
+    /// Gear hash table used to find cut points ([`GEAR_TABLE`] by default)
+    ///
+    /// Changing the table moves every chunk boundary, and with them every
+    /// chunk hash, so chunks from different tables never deduplicate
+    /// against each other.
+    pub gear_table: &'static [u64; 256],
+}
+
+impl std::fmt::Debug for ChunkConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkConfig")
+            .field("min_size", &self.min_size)
+            .field("avg_size", &self.avg_size)
+            .field("max_size", &self.max_size)
+            .field(
+                "gear_table",
+                &if self.has_default_gear_table() {
+                    "default"
+                } else {
+                    "custom"
+                },
+            )
+            .finish()
+    }
 }
 
 impl ChunkConfig {
@@ -56,15 +88,65 @@ impl ChunkConfig {
             min_size,
             avg_size,
             max_size,
+            gear_table: &GEAR_TABLE,
         })
     }
 
+    /// Use a different gear table, e.g. to match another tool's boundaries
+    ///
+    /// Every boundary and chunk hash depends on the table, so all parties
+    /// that need to share chunks must use the same one. A table loaded at
+    /// runtime can be given a `'static` lifetime with `Box::leak`.
+    pub fn with_gear_table(mut self, table: &'static [u64; 256]) -> Self {
+        self.gear_table = table;
+        self
+    }
+
+    /// Whether this config uses the built-in [`GEAR_TABLE`]
+    pub fn has_default_gear_table(&self) -> bool {
+        std::ptr::eq(self.gear_table, &GEAR_TABLE)
+    }
+
     /// Default configuration: 8KB min, 64KB avg, 256KB max
     pub fn default_config() -> Self {
         Self {
             min_size: 8 * 1024,   // 8 KB
             avg_size: 64 * 1024,  // 64 KB
             max_size: 256 * 1024, // 256 KB
+            gear_table: &GEAR_TABLE,
+        }
+    }
+
+    /// Tiered configuration for a file of the given size.
+    ///
+    /// Very large ("Gigantor") files use larger chunks so that the chunk index
+    /// does not grow unboundedly. Min and max keep the same ratios as
+    /// [`ChunkConfig::default_config`] (avg / 8 and avg * 4).
+    ///
+    /// | File size      | Average chunk |
+    /// |----------------|---------------|
+    /// | < 1 GiB        | 64 KiB        |
+    /// | 1 GiB – 10 GiB | 256 KiB       |
+    /// | 10 – 100 GiB   | 1 MiB         |
+    /// | >= 100 GiB     | 4 MiB         |
+    pub fn for_file_size(file_size: u64) -> Self {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        let avg_size = if file_size < GIB {
+            return Self::default_config();
+        } else if file_size < 10 * GIB {
+            256 * 1024
+        } else if file_size < 100 * GIB {
+            1024 * 1024
+        } else {
+            4 * 1024 * 1024
+        };
+
+        Self {
+            min_size: avg_size / 8,
+            avg_size,
+            max_size: avg_size * 4,
+            gear_table: &GEAR_TABLE,
         }
     }
 
@@ -119,6 +201,7 @@ pub struct ChunkStream<R: Read> {
     buffer_pos: usize,
     stream_offset: u64,
     finished: bool,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl<R: Read> ChunkStream<R> {
@@ -135,9 +218,28 @@ impl<R: Read> ChunkStream<R> {
             buffer_pos: 0,
             stream_offset: 0,
             finished: false,
+            cancel: None,
         }
     }
 
+    /// Stop producing chunks once `cancel` is set.
+    ///
+    /// The flag is checked before each chunk and between reads while filling
+    /// the buffer, so a cancelled stream stops reading from a slow reader
+    /// without waiting for the buffer to fill. After cancellation the
+    /// iterator returns `None`; any partially buffered chunk is discarded.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Whether the cancellation flag has been set
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+    }
+
     /// Fill the buffer from the reader
     fn fill_buffer(&mut self) -> io::Result<()> {
         // Move unprocessed data to the start of the buffer
@@ -154,6 +256,9 @@ impl<R: Read> ChunkStream<R> {
 
         // Fill the rest of the buffer
         while self.buffer_len < self.buffer.len() {
+            if self.is_cancelled() {
+                break;
+            }
             match self.reader.read(&mut self.buffer[self.buffer_len..]) {
                 Ok(0) => {
                     // EOF reached
@@ -178,7 +283,7 @@ impl<R: Read> ChunkStream<R> {
 
     /// Find the next chunk boundary using the Gear hash
     fn find_cut_point(&self, start: usize, end: usize) -> Option<usize> {
-        let mut hasher = GearHash::new();
+        let mut hasher = GearHash::with_table(self.config.gear_table);
         let mask = self.config.cut_mask();
 
         // Threshold-based cut detection: check if the masked hash value
@@ -209,11 +314,20 @@ impl<R: Read> ChunkStream<R> {
 
     /// Extract the next chunk from the buffer
     fn next_chunk(&mut self) -> Result<Option<Chunk>, ChunkError> {
+        if self.is_cancelled() {
+            return Ok(None);
+        }
+
         // Try to fill buffer if needed (check available data, not total buffer size)
         if self.buffer_len - self.buffer_pos < self.config.max_size && !self.finished {
             self.fill_buffer()?;
         }
 
+        // A fill interrupted by cancellation holds a partial buffer
+        if self.is_cancelled() {
+            return Ok(None);
+        }
+
         // Check if we're done
         if self.buffer_pos >= self.buffer_len {
             return Ok(None);
@@ -234,6 +348,9 @@ impl<R: Read> ChunkStream<R> {
         } else {
             // Not enough data yet and not finished - refill buffer
             self.fill_buffer()?;
+            if self.is_cancelled() {
+                return Ok(None);
+            }
             if self.buffer_len - self.buffer_pos < self.config.min_size && !self.finished {
                 // Still not enough, wait for more
                 return Ok(None);
@@ -346,6 +463,7 @@ pub use async_hash::hash_file_range;
 
 #[cfg(test)]
 mod tests {
+    use super::testing::xorshift_bytes;
     use super::*;
     use std::io::Cursor;
 
@@ -364,6 +482,31 @@ mod tests {
         assert!(ChunkConfig::new(8192, 60000, 262144).is_err());
     }
 
+    #[test]
+    fn test_tiered_config_for_file_size() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        let cases = [
+            (0, 64 * 1024),
+            (500 * 1024 * 1024, 64 * 1024),
+            (GIB, 256 * 1024),
+            (5 * GIB, 256 * 1024),
+            (10 * GIB, 1024 * 1024),
+            (50 * GIB, 1024 * 1024),
+            (100 * GIB, 4 * 1024 * 1024),
+            (2048 * GIB, 4 * 1024 * 1024),
+        ];
+
+        for (file_size, expected_avg) in cases {
+            let config = ChunkConfig::for_file_size(file_size);
+            assert_eq!(config.avg_size, expected_avg, "file size {}", file_size);
+            assert!(config.min_size < config.avg_size);
+            assert!(config.avg_size < config.max_size);
+            // Tiered configs must pass the same validation as hand-built ones
+            assert!(ChunkConfig::new(config.min_size, config.avg_size, config.max_size).is_ok());
+        }
+    }
+
     #[test]
     fn test_basic_chunking() {
         let data = vec![0u8; 1024 * 1024]; // 1MB of zeros
@@ -404,4 +547,131 @@ mod tests {
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0].data, data);
     }
+
+    /// Endless pseudo-random reader that counts bytes handed out and can
+    /// raise a cancel flag after a number of reads
+    struct CountingReader {
+        read: Arc<std::sync::atomic::AtomicU64>,
+        read_size: usize,
+        reads: usize,
+        cancel_after_reads: Option<(usize, Arc<AtomicBool>)>,
+        state: u64,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.read_size);
+            for b in &mut buf[..n] {
+                self.state ^= self.state << 13;
+                self.state ^= self.state >> 7;
+                self.state ^= self.state << 17;
+                *b = self.state as u8;
+            }
+            self.read.fetch_add(n as u64, Ordering::Relaxed);
+            self.reads += 1;
+            if let Some((after, flag)) = &self.cancel_after_reads {
+                if self.reads >= *after {
+                    flag.store(true, Ordering::Relaxed);
+                }
+            }
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_cancel_stops_between_chunks() {
+        let read = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let reader = CountingReader {
+            read: read.clone(),
+            read_size: usize::MAX,
+            reads: 0,
+            cancel_after_reads: None,
+            state: 0x9E37_79B9_7F4A_7C15,
+        };
+        let config = ChunkConfig::new(2048, 8192, 32768).unwrap();
+        let mut stream = ChunkStream::new(reader, config).with_cancel(cancel.clone());
+
+        assert!(stream.next().unwrap().is_ok());
+        let read_before_cancel = read.load(Ordering::Relaxed);
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(stream.next().is_none());
+        assert!(stream.next().is_none());
+        assert!(stream.is_cancelled());
+
+        // Nothing further was pulled from the (endless) reader
+        assert_eq!(read.load(Ordering::Relaxed), read_before_cancel);
+        assert!(read_before_cancel <= 2 * 32768);
+    }
+
+    #[test]
+    fn test_cancel_interrupts_buffer_fill() {
+        let read = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        // A slow reader: 1 KiB per call, cancelled during the third call
+        let reader = CountingReader {
+            read: read.clone(),
+            read_size: 1024,
+            reads: 0,
+            cancel_after_reads: Some((3, cancel.clone())),
+            state: 1,
+        };
+        let config = ChunkConfig::new(2048, 8192, 32768).unwrap();
+        let mut stream = ChunkStream::new(reader, config).with_cancel(cancel);
+
+        // The fill stops after the read that raised the flag, well short of
+        // the 64 KiB buffer, and no partial chunk is emitted
+        assert!(stream.next().is_none());
+        assert_eq!(read.load(Ordering::Relaxed), 3 * 1024);
+    }
+
+    fn boundaries(data: &[u8], config: ChunkConfig) -> Vec<u64> {
+        ChunkStream::new(Cursor::new(data), config)
+            .map(|c| c.unwrap().offset)
+            .collect()
+    }
+
+    #[test]
+    fn test_default_gear_table_keeps_boundaries() {
+        let data = xorshift_bytes(64 * 1024, 0x9E37_79B9_7F4A_7C15);
+        let config = ChunkConfig::new(1024, 4096, 16384).unwrap();
+        assert!(config.has_default_gear_table());
+
+        // Boundaries from before the table became configurable
+        let offsets = boundaries(&data, config.clone());
+        assert_eq!(offsets.len(), 44);
+        assert_eq!(
+            offsets[..10],
+            [0, 1202, 2417, 3486, 4978, 6191, 7332, 8373, 9411, 10451]
+        );
+
+        let explicit = config.with_gear_table(&GEAR_TABLE);
+        assert!(explicit.has_default_gear_table());
+        assert_eq!(boundaries(&data, explicit), offsets);
+    }
+
+    #[test]
+    fn test_custom_gear_table_moves_boundaries() {
+        static REVERSED: [u64; 256] = {
+            let mut table = [0u64; 256];
+            let mut i = 0;
+            while i < 256 {
+                table[i] = GEAR_TABLE[255 - i];
+                i += 1;
+            }
+            table
+        };
+
+        let data = xorshift_bytes(64 * 1024, 0x9E37_79B9_7F4A_7C15);
+        let config = ChunkConfig::new(1024, 4096, 16384).unwrap();
+        let custom = config.clone().with_gear_table(&REVERSED);
+        assert!(!custom.has_default_gear_table());
+        assert!(format!("{:?}", custom).contains("custom"));
+
+        let default_offsets = boundaries(&data, config);
+        let custom_offsets = boundaries(&data, custom);
+        assert_ne!(default_offsets, custom_offsets);
+        assert_eq!(custom_offsets[0], 0);
+    }
 }
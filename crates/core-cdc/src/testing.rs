@@ -0,0 +1,33 @@
+//! Test fixtures for chunking
+//!
+//! CDC only finds realistic boundaries in high-entropy input, so tests across
+//! the workspace share this generator instead of each carrying a copy.
+
+/// Deterministic pseudo-random bytes from an xorshift64 generator
+///
+/// The same `len` and `seed` always give the same bytes. `seed` must be
+/// non-zero.
+pub fn xorshift_bytes(len: usize, seed: u64) -> Vec<u8> {
+    debug_assert_ne!(seed, 0, "xorshift64 never leaves a zero state");
+    let mut x = seed;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x as u8
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift_bytes_is_deterministic_per_seed() {
+        assert_eq!(xorshift_bytes(1024, 7), xorshift_bytes(1024, 7));
+        assert_ne!(xorshift_bytes(1024, 7), xorshift_bytes(1024, 8));
+        assert_eq!(xorshift_bytes(1024, 7)[..16], xorshift_bytes(16, 7)[..]);
+    }
+}
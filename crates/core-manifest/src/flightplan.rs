@@ -6,7 +6,11 @@
 use crate::error::{Error, Result};
 use crate::FLIGHT_PLAN_SCHEMA_VERSION;
 use chrono::{DateTime, Utc};
+use serde::ser::{Error as _, SerializeSeq, SerializeStruct, Serializer};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::str::FromStr;
 
@@ -230,6 +234,46 @@ impl FlightPlan {
         Ok(())
     }
 
+    /// Save the Flight Plan with its file list taken from `files` rather
+    /// than `self.files`, writing each entry as it is produced
+    ///
+    /// Lets callers keep file references on disk for jobs too large to hold
+    /// in memory. The output is identical to [`FlightPlan::save`] with the
+    /// same files.
+    ///
+    /// The plan is written to a temporary file next to `path` and renamed
+    /// into place once complete, so a failure part-way through leaves any
+    /// previous plan at `path` untouched.
+    pub fn save_with_files<P, I>(&self, path: P, files: I) -> Result<()>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = Result<FileRef>>,
+    {
+        let path = path.as_ref();
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let streamed = StreamedFlightPlan {
+            plan: self,
+            files: RefCell::new(Some(files.into_iter())),
+        };
+        let written = (|| -> Result<()> {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            serde_json::to_writer_pretty(&mut writer, &streamed)?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+            Ok(())
+        })();
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
     /// Load Flight Plan from a JSON file
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
@@ -252,6 +296,60 @@ impl FlightPlan {
     }
 }
 
+/// [`FlightPlan`] serialized with a streamed file list; field order and
+/// skipping must match the derived `Serialize`
+struct StreamedFlightPlan<'a, I> {
+    plan: &'a FlightPlan,
+    files: RefCell<Option<I>>,
+}
+
+/// File list serialized straight from an iterator
+struct StreamedFiles<'a, I>(&'a RefCell<Option<I>>);
+
+impl<I> Serialize for StreamedFlightPlan<'_, I>
+where
+    I: Iterator<Item = Result<FileRef>>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let plan = self.plan;
+        let mut state = serializer.serialize_struct("FlightPlan", 9)?;
+        state.serialize_field("schema", &plan.schema)?;
+        state.serialize_field("job_id", &plan.job_id)?;
+        state.serialize_field("created_utc", &plan.created_utc)?;
+        state.serialize_field("source", &plan.source)?;
+        state.serialize_field("target", &plan.target)?;
+        state.serialize_field("policy", &plan.policy)?;
+        match &plan.capacity_vector {
+            Some(capacity) => state.serialize_field("capacity_vector", capacity)?,
+            None => state.skip_field("capacity_vector")?,
+        }
+        state.serialize_field("files", &StreamedFiles(&self.files))?;
+        match &plan.job_digest {
+            Some(digest) => state.serialize_field("job_digest", digest)?,
+            None => state.skip_field("job_digest")?,
+        }
+        state.end()
+    }
+}
+
+impl<I> Serialize for StreamedFiles<'_, I>
+where
+    I: Iterator<Item = Result<FileRef>>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let files = self
+            .0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| S::Error::custom("file list already written"))?;
+        let mut seq = serializer.serialize_seq(None)?;
+        for file in files {
+            seq.serialize_element(&file.map_err(S::Error::custom)?)?;
+        }
+        seq.end()
+    }
+}
+
 impl Endpoint {
     /// Create a filesystem endpoint
     pub fn filesystem<S: Into<String>>(root: S) -> Self {
@@ -367,6 +465,63 @@ mod tests {
         assert!(!flight_plan.is_finalized());
     }
 
+    #[test]
+    fn test_save_with_files_matches_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = Policy::default_with_encryption(Encryption::aes256_gcm("env:ORBIT_KEY"));
+        let mut plan = FlightPlan::new(
+            Endpoint::filesystem("/data/source"),
+            Endpoint::filesystem("/data/target"),
+            policy,
+        );
+        plan.finalize("sha256:abc".to_string());
+
+        let files: Vec<FileRef> = (0..3)
+            .map(|i| FileRef::new(format!("f{}.bin", i), format!("f{}.cargo.json", i)))
+            .collect();
+        let streamed_path = dir.path().join("streamed.json");
+        plan.save_with_files(&streamed_path, files.clone().into_iter().map(Ok))
+            .unwrap();
+
+        let mut in_memory = plan.clone();
+        for file in files {
+            in_memory.add_file(file);
+        }
+        let saved_path = dir.path().join("saved.json");
+        in_memory.save(&saved_path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&streamed_path).unwrap(),
+            std::fs::read_to_string(&saved_path).unwrap()
+        );
+        assert_eq!(FlightPlan::load(&streamed_path).unwrap(), in_memory);
+    }
+
+    #[test]
+    fn test_save_with_files_keeps_previous_plan_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = FlightPlan::new(
+            Endpoint::filesystem("/data/source"),
+            Endpoint::filesystem("/data/target"),
+            Policy::default_with_encryption(Encryption::aes256_gcm("env:ORBIT_KEY")),
+        );
+        let path = dir.path().join("job.flightplan.json");
+        plan.save(&path).unwrap();
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        let files = vec![
+            Ok(FileRef::new(
+                "a.bin".to_string(),
+                "a.cargo.json".to_string(),
+            )),
+            Err(Error::Other("file list unavailable".to_string())),
+        ];
+        assert!(plan.save_with_files(&path, files).is_err());
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_endpoint_types() {
         let fs = Endpoint::filesystem("/tmp");
@@ -166,6 +166,96 @@ pub trait SemanticAdapter: Send + Sync {
     fn analyze(&self, path: &Path, head_bytes: &[u8]) -> ReplicationIntent;
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// Format Detection
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Format identified from a file's leading magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MagicKind {
+    Gzip,
+    Zstd,
+    Xz,
+    Bzip2,
+    Lz4,
+    SevenZip,
+    Rar,
+    Png,
+    Jpeg,
+    /// ISO base media (MP4, MOV, ...): `ftyp` box at offset 4
+    IsoMedia,
+}
+
+impl MagicKind {
+    /// Whether the content is already compressed as a whole stream
+    pub fn is_compressed(self) -> bool {
+        matches!(
+            self,
+            MagicKind::Gzip
+                | MagicKind::Zstd
+                | MagicKind::Xz
+                | MagicKind::Bzip2
+                | MagicKind::Lz4
+                | MagicKind::SevenZip
+                | MagicKind::Rar
+        )
+    }
+}
+
+/// Identify a file format from its first bytes
+pub fn sniff_magic(head: &[u8]) -> Option<MagicKind> {
+    const SIGNATURES: &[(&[u8], MagicKind)] = &[
+        (&[0x1F, 0x8B], MagicKind::Gzip),
+        (&[0x28, 0xB5, 0x2F, 0xFD], MagicKind::Zstd),
+        (&[0xFD, b'7', b'z', b'X', b'Z', 0x00], MagicKind::Xz),
+        (b"BZh", MagicKind::Bzip2),
+        (&[0x04, 0x22, 0x4D, 0x18], MagicKind::Lz4),
+        (&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C], MagicKind::SevenZip),
+        (b"Rar!\x1A\x07", MagicKind::Rar),
+        (&[0x89, b'P', b'N', b'G'], MagicKind::Png),
+        (&[0xFF, 0xD8, 0xFF], MagicKind::Jpeg),
+    ];
+
+    if let Some((_, kind)) = SIGNATURES.iter().find(|(sig, _)| head.starts_with(sig)) {
+        return Some(*kind);
+    }
+    if head.len() >= 8 && &head[4..8] == b"ftyp" {
+        return Some(MagicKind::IsoMedia);
+    }
+    None
+}
+
+/// Lowercased extensions of a file name, innermost first
+///
+/// `archive.tar.zst` yields `["tar", "zst"]`. At most the last three
+/// suffixes are considered, and only short alphanumeric ones, so names like
+/// `report.v1.2.pdf` do not produce spurious inner extensions.
+pub fn extension_chain(path: &Path) -> Vec<String> {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+
+    let mut chain = Vec::new();
+    // A leading dot marks a hidden file, not an extension (`.env`)
+    let mut parts = name
+        .trim_start_matches('.')
+        .split('.')
+        .skip(1)
+        .collect::<Vec<_>>();
+    while let Some(part) = parts.pop() {
+        let is_ext = !part.is_empty()
+            && part.len() <= 5
+            && part.chars().all(|c| c.is_ascii_alphanumeric())
+            && !part.chars().all(|c| c.is_ascii_digit());
+        if !is_ext || chain.len() == 3 {
+            break;
+        }
+        chain.push(part.to_ascii_lowercase());
+    }
+    chain.reverse();
+    chain
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Built-in Adapters
 // ────────────────────────────────────────────────────────────────────────────
@@ -235,6 +325,74 @@ impl SemanticAdapter for WalAdapter {
     }
 }
 
+/// Adapter for compressed and encrypted files
+///
+/// Handles compound extensions (`.tar.gz`, `.tar.zst`, `.tgz`), single
+/// compression extensions (`.zst`, `.xz`, ...) and encryption wrappers
+/// (`.enc`, `.gpg`, `.age`). Magic bytes win over the extension: a `data.png`
+/// that starts with a zstd frame is treated as compressed, and a `photo.gz`
+/// that is really a PNG is left to [`MediaAdapter`].
+///
+/// Both kinds are high-entropy, so content-defined chunking finds no reusable
+/// chunks and re-compressing gains nothing; they are replaced atomically.
+pub struct PackedAdapter;
+
+impl PackedAdapter {
+    const COMPRESSED: &'static [&'static str] = &[
+        "gz", "tgz", "zst", "tzst", "xz", "txz", "bz2", "tbz2", "lz4", "7z", "rar",
+    ];
+    const ENCRYPTED: &'static [&'static str] = &["enc", "gpg", "pgp", "age", "aes"];
+
+    fn outer_extension(path: &Path) -> Option<String> {
+        extension_chain(path).pop()
+    }
+}
+
+impl SemanticAdapter for PackedAdapter {
+    fn matches(&self, path: &Path, head: &[u8]) -> bool {
+        match sniff_magic(head) {
+            Some(kind) if kind.is_compressed() => return true,
+            // Recognised as something else; the extension is misleading
+            Some(_) => return false,
+            None => {}
+        }
+
+        Self::outer_extension(path).is_some_and(|ext| {
+            Self::COMPRESSED.contains(&ext.as_str()) || Self::ENCRYPTED.contains(&ext.as_str())
+        })
+    }
+
+    fn analyze(&self, path: &Path, head: &[u8]) -> ReplicationIntent {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let chain = extension_chain(path);
+        let outer = chain.last().map(String::as_str).unwrap_or("");
+
+        if sniff_magic(head).is_none() && Self::ENCRYPTED.contains(&outer) {
+            return ReplicationIntent::new(
+                Priority::Normal,
+                SyncStrategy::AtomicReplace,
+                format!("Encrypted: {}", name),
+            );
+        }
+
+        let is_tar = matches!(outer, "tgz" | "tzst" | "txz" | "tbz2")
+            || (chain.len() >= 2 && chain[chain.len() - 2] == "tar");
+        let kind = if is_tar {
+            "Compressed archive"
+        } else {
+            "Compressed"
+        };
+        ReplicationIntent::new(
+            Priority::Low,
+            SyncStrategy::AtomicReplace,
+            format!("{}: {}", kind, name),
+        )
+    }
+}
+
 /// Adapter for media files (videos, images, audio) and large archives
 ///
 /// These are typically large, immutable, and low-priority for recovery.
@@ -392,12 +550,15 @@ impl Default for SemanticRegistry {
     /// Order matters - adapters are checked in sequence:
     /// 1. ConfigAdapter (highest priority)
     /// 2. WalAdapter (database logs)
-    /// 3. MediaAdapter (low priority blobs)
-    /// 4. DefaultAdapter (catch-all)
+    /// 3. PackedAdapter (compressed / encrypted blobs, before media so that
+    ///    content magic overrides a misleading media extension)
+    /// 4. MediaAdapter (low priority blobs)
+    /// 5. DefaultAdapter (catch-all)
     fn default() -> Self {
         Self::new(vec![
             Box::new(ConfigAdapter),
             Box::new(WalAdapter),
+            Box::new(PackedAdapter),
             Box::new(MediaAdapter),
             // DefaultAdapter is applied via fallback, not registered
         ])
@@ -517,6 +678,78 @@ mod tests {
         assert_eq!(intent.priority, Priority::Normal);
     }
 
+    const GZIP_HEAD: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03";
+    const ZSTD_HEAD: &[u8] = b"\x28\xb5\x2f\xfd\x24\x00\x01\x00\x00\x99";
+
+    #[test]
+    fn test_extension_chain() {
+        assert_eq!(
+            extension_chain(Path::new("a/archive.tar.zst")),
+            ["tar", "zst"]
+        );
+        assert_eq!(extension_chain(Path::new("image.PNG.enc")), ["png", "enc"]);
+        assert_eq!(extension_chain(Path::new("report.v1.2.pdf")), ["pdf"]);
+        assert!(extension_chain(Path::new("Makefile")).is_empty());
+        assert!(extension_chain(Path::new(".env")).is_empty());
+    }
+
+    #[test]
+    fn test_tar_gz_is_compressed_archive() {
+        let registry = SemanticRegistry::default();
+
+        for head in [b"" as &[u8], GZIP_HEAD] {
+            let intent = registry.determine_intent(Path::new("backup.tar.gz"), head);
+            assert_eq!(intent.priority, Priority::Low);
+            assert_eq!(intent.strategy, SyncStrategy::AtomicReplace);
+            assert!(intent.description.starts_with("Compressed archive"));
+        }
+    }
+
+    #[test]
+    fn test_tar_zst_is_compressed_archive() {
+        let registry = SemanticRegistry::default();
+
+        let intent = registry.determine_intent(Path::new("archive.tar.zst"), ZSTD_HEAD);
+        assert_eq!(intent.priority, Priority::Low);
+        assert_eq!(intent.strategy, SyncStrategy::AtomicReplace);
+        assert_eq!(intent.description, "Compressed archive: archive.tar.zst");
+
+        // A bare .zst is compressed but not an archive
+        let intent = registry.determine_intent(Path::new("dump.zst"), ZSTD_HEAD);
+        assert_eq!(intent.description, "Compressed: dump.zst");
+    }
+
+    #[test]
+    fn test_magic_wins_over_extension() {
+        let registry = SemanticRegistry::default();
+
+        // Named like an image, but the content is a zstd frame
+        let intent = registry.determine_intent(Path::new("data.png"), ZSTD_HEAD);
+        assert_eq!(intent.priority, Priority::Low);
+        assert_eq!(intent.strategy, SyncStrategy::AtomicReplace);
+        assert!(intent.description.starts_with("Compressed"));
+
+        // Named like gzip, but the content is a PNG
+        let png_header = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0DIHDR";
+        let intent = registry.determine_intent(Path::new("photo.gz"), png_header);
+        assert_eq!(intent.strategy, SyncStrategy::ContentDefined);
+        assert!(intent.description.starts_with("Media"));
+
+        // Compressed content without any telling extension
+        let intent = registry.determine_intent(Path::new("blob"), GZIP_HEAD);
+        assert_eq!(intent.strategy, SyncStrategy::AtomicReplace);
+    }
+
+    #[test]
+    fn test_encrypted_is_opaque_blob() {
+        let registry = SemanticRegistry::default();
+
+        let intent = registry.determine_intent(Path::new("image.png.enc"), b"\x9a\x13\xe0\x41");
+        assert_eq!(intent.priority, Priority::Normal);
+        assert_eq!(intent.strategy, SyncStrategy::AtomicReplace);
+        assert!(intent.description.starts_with("Encrypted"));
+    }
+
     #[test]
     fn test_registry_order_matters() {
         let registry = SemanticRegistry::default();
@@ -573,6 +806,16 @@ mod tests {
         ) -> orbit_core_interface::Result<[u8; 32]> {
             Err(OrbitSystemError::NotFound(path.to_path_buf()))
         }
+
+        async fn calculate_keyed_hash(
+            &self,
+            path: &Path,
+            _offset: u64,
+            _len: u64,
+            _key: &[u8; 32],
+        ) -> orbit_core_interface::Result<[u8; 32]> {
+            Err(OrbitSystemError::NotFound(path.to_path_buf()))
+        }
     }
 
     #[tokio::test]
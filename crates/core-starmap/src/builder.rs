@@ -1,7 +1,7 @@
 //! Star Map builder for constructing binary indices
 
 use crate::error::{Error, Result};
-use crate::{BloomFilter, ChunkMeta, RankSelectBitmap, StarMapData, WindowMeta};
+use crate::{BloomFilter, ChunkMeta, HashAlgorithm, RankSelectBitmap, StarMapData, WindowMeta};
 use crate::{STARMAP_MAGIC, STARMAP_VERSION};
 
 /// Builder for constructing Star Map binary indices
@@ -16,7 +16,8 @@ use crate::{STARMAP_MAGIC, STARMAP_VERSION};
 /// builder.add_window(0, 0, 2, &[0u8; 32], 0);
 ///
 /// let data = builder.build().unwrap();
-/// std::fs::write("file.starmap.bin", &data).unwrap();
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(dir.path().join("file.starmap.bin"), &data).unwrap();
 /// ```
 #[derive(Debug)]
 pub struct StarMapBuilder {
@@ -28,6 +29,8 @@ pub struct StarMapBuilder {
     windows: Vec<WindowMeta>,
     /// Expected number of chunks (for bloom filter sizing)
     expected_chunks: u32,
+    /// Algorithm of the content IDs passed to `add_chunk`
+    hash_algorithm: HashAlgorithm,
 }
 
 impl StarMapBuilder {
@@ -44,6 +47,7 @@ impl StarMapBuilder {
             chunks: Vec::new(),
             windows: Vec::new(),
             expected_chunks,
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
@@ -57,20 +61,39 @@ impl StarMapBuilder {
             chunks: Vec::new(),
             windows: Vec::new(),
             expected_chunks,
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
+    /// Set the algorithm that produced the content IDs (default BLAKE3)
+    ///
+    /// Must be set before chunks are added; every chunk in a map shares it.
+    pub fn with_hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        for chunk in &mut self.chunks {
+            chunk.algorithm = hash_algorithm;
+        }
+        self
+    }
+
+    /// Algorithm recorded for this map's content IDs
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
     /// Add a chunk to the Star Map
     ///
     /// # Arguments
     /// * `offset` - Byte offset in the source file
     /// * `length` - Length of the chunk in bytes
-    /// * `content_id` - BLAKE3 hash of the chunk (32 bytes)
+    /// * `content_id` - Hash of the chunk (32 bytes) under the builder's
+    ///   [`HashAlgorithm`]
     pub fn add_chunk(&mut self, offset: u64, length: u32, content_id: &[u8; 32]) -> Result<()> {
         self.chunks.push(ChunkMeta {
             offset,
             length,
             content_id: *content_id,
+            algorithm: self.hash_algorithm,
         });
         Ok(())
     }
@@ -148,6 +171,7 @@ impl StarMapBuilder {
         // Create StarMapData structure
         let starmap_data = StarMapData {
             version: STARMAP_VERSION,
+            hash_algorithm: self.hash_algorithm,
             file_size: self.file_size,
             chunk_count: self.chunks.len() as u32,
             window_count: self.windows.len() as u32,
@@ -175,13 +199,14 @@ impl StarMapBuilder {
         Ok(buffer)
     }
 
-    /// Build bloom filter from all chunk content IDs
+    /// Build bloom filter from all chunk content IDs, tagged with their
+    /// algorithm so lookups cannot cross ID spaces
     fn build_bloom_filter(&self) -> BloomFilter {
         // Use 1% false positive rate
         let mut bloom = BloomFilter::new(self.expected_chunks.max(self.chunks.len() as u32), 0.01);
 
         for chunk in &self.chunks {
-            bloom.insert(&chunk.content_id);
+            bloom.insert(&chunk.algorithm.tagged_id(&chunk.content_id));
         }
 
         bloom
@@ -10,10 +10,10 @@
 //! ┌──────────────────────────────────────────────────┐
 //! │              Container File (.orbitpak)           │
 //! ├──────────────────────────────────────────────────┤
-//! │ [Header: magic + version + created_at]           │
-//! │ [Chunk 0: raw bytes]                             │
-//! │ [Chunk 1: raw bytes]                             │
-//! │ [Chunk 2: raw bytes]                             │
+//! │ [Header: magic + version + reserved]             │
+//! │ [Chunk 0: raw bytes, or frame + payload]         │
+//! │ [Chunk 1: raw bytes, or frame + payload]         │
+//! │ [Chunk 2: raw bytes, or frame + payload]         │
 //! │ ...                                              │
 //! └──────────────────────────────────────────────────┘
 //!
@@ -24,6 +24,16 @@
 //! └──────────────────────────────────────────────────┘
 //! ```
 //!
+//! # Compression
+//!
+//! Containers created with [`ChunkCompression::Zstd`] are format version 2:
+//! every chunk is preceded by a [`FRAME_HEADER_SIZE`]-byte frame holding a
+//! codec flag and the original length. Chunks that do not shrink are stored
+//! raw under a raw flag. The `PackedChunkRef` covers frame and payload, and
+//! [`ContainerReader::read_chunk`] returns the original bytes, so content IDs
+//! (computed over uncompressed data) are unaffected. Uncompressed containers
+//! keep the version 1 layout.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -50,15 +60,50 @@ use std::path::{Path, PathBuf};
 /// Magic bytes for container files
 pub const CONTAINER_MAGIC: &[u8; 8] = b"ORBITPAK";
 
-/// Current container format version
+/// Container format version for uncompressed containers
 pub const CONTAINER_VERSION: u16 = 1;
 
+/// Container format version whose chunks carry a compression frame
+pub const CONTAINER_VERSION_FRAMED: u16 = 2;
+
+/// Size of the per-chunk frame in version 2 containers (flag:1 + original length:4)
+pub const FRAME_HEADER_SIZE: u64 = 5;
+
+/// Frame flag: payload is stored as-is
+const FRAME_RAW: u8 = 0;
+
+/// Frame flag: payload is a zstd frame
+const FRAME_ZSTD: u8 = 1;
+
+/// Default zstd level for container chunks
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
 /// Header size in bytes (magic:8 + version:2 + reserved:6 = 16)
 pub const HEADER_SIZE: u64 = 16;
 
 /// Default maximum container file size (4 GiB)
 pub const DEFAULT_MAX_CONTAINER_SIZE: u64 = 4 * 1024 * 1024 * 1024;
 
+/// How chunks are compressed when packed into a container
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChunkCompression {
+    /// Store chunks raw (version 1 layout)
+    #[default]
+    None,
+    /// Compress each chunk with zstd at the given level, storing
+    /// incompressible chunks raw
+    Zstd { level: i32 },
+}
+
+impl ChunkCompression {
+    /// zstd at [`DEFAULT_ZSTD_LEVEL`]
+    pub fn zstd() -> Self {
+        ChunkCompression::Zstd {
+            level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+}
+
 /// A reference to a chunk packed inside a container file
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PackedChunkRef {
@@ -68,7 +113,8 @@ pub struct PackedChunkRef {
     /// Byte offset within the container file (after header)
     pub offset: u64,
 
-    /// Length of the chunk data in bytes
+    /// Length of the chunk data in bytes (including the frame in
+    /// compressed containers)
     pub length: u32,
 }
 
@@ -95,6 +141,7 @@ pub struct ContainerWriter {
     bytes_written: u64,
     max_size: u64,
     path: PathBuf,
+    compression: ChunkCompression,
 }
 
 impl ContainerWriter {
@@ -105,6 +152,15 @@ impl ContainerWriter {
 
     /// Create a new container file with custom maximum size
     pub fn create_with_max_size(path: &Path, max_size: u64) -> io::Result<Self> {
+        Self::create_with_compression(path, max_size, ChunkCompression::None)
+    }
+
+    /// Create a new container file whose chunks are compressed as they are appended
+    pub fn create_with_compression(
+        path: &Path,
+        max_size: u64,
+        compression: ChunkCompression,
+    ) -> io::Result<Self> {
         let container_id = path
             .file_stem()
             .and_then(|s| s.to_str())
@@ -121,7 +177,11 @@ impl ContainerWriter {
 
         // Write header
         writer.write_all(CONTAINER_MAGIC)?;
-        writer.write_all(&CONTAINER_VERSION.to_le_bytes())?;
+        let version = match compression {
+            ChunkCompression::None => CONTAINER_VERSION,
+            ChunkCompression::Zstd { .. } => CONTAINER_VERSION_FRAMED,
+        };
+        writer.write_all(&version.to_le_bytes())?;
         writer.write_all(&[0u8; 6])?; // Reserved
         writer.flush()?;
 
@@ -133,10 +193,14 @@ impl ContainerWriter {
             bytes_written: 0,
             max_size,
             path: path.to_path_buf(),
+            compression,
         })
     }
 
     /// Open an existing container file for appending
+    ///
+    /// Version 2 containers keep compressing new chunks at
+    /// [`DEFAULT_ZSTD_LEVEL`].
     pub fn open_append(path: &Path) -> io::Result<Self> {
         let container_id = path
             .file_stem()
@@ -147,15 +211,12 @@ impl ContainerWriter {
         let file = OpenOptions::new().read(true).append(true).open(path)?;
 
         // Verify header
-        let mut reader = BufReader::new(&file);
-        let mut magic = [0u8; 8];
-        reader.read_exact(&mut magic)?;
-        if &magic != CONTAINER_MAGIC {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid container magic",
-            ));
-        }
+        let version = read_header(&mut BufReader::new(&file))?;
+        let compression = if version == CONTAINER_VERSION_FRAMED {
+            ChunkCompression::zstd()
+        } else {
+            ChunkCompression::None
+        };
 
         let current_offset = file.metadata()?.len();
 
@@ -167,26 +228,45 @@ impl ContainerWriter {
             bytes_written: 0,
             max_size: DEFAULT_MAX_CONTAINER_SIZE,
             path: path.to_path_buf(),
+            compression,
         })
     }
 
     /// Append a chunk to the container.
     ///
     /// Returns a `PackedChunkRef` that can be stored in the Universe index.
-    /// Returns `None` if the container is full (would exceed max_size).
+    /// Returns an error if the container is full (would exceed max_size).
     pub fn append_chunk(&mut self, data: &[u8]) -> io::Result<PackedChunkRef> {
-        let chunk_len = data.len() as u32;
+        let (frame, payload) = match self.compression {
+            ChunkCompression::None => (None, std::borrow::Cow::Borrowed(data)),
+            ChunkCompression::Zstd { level } => {
+                let compressed = zstd::bulk::compress(data, level)?;
+                if compressed.len() < data.len() {
+                    (Some(FRAME_ZSTD), std::borrow::Cow::Owned(compressed))
+                } else {
+                    (Some(FRAME_RAW), std::borrow::Cow::Borrowed(data))
+                }
+            }
+        };
+
+        let stored_len = payload.len() as u64 + frame.map_or(0, |_| FRAME_HEADER_SIZE);
+        let chunk_len = u32::try_from(stored_len)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Chunk too large"))?;
 
         // Check if container would exceed max size
-        if self.current_offset + data.len() as u64 > self.max_size {
+        if self.current_offset + stored_len > self.max_size {
             return Err(io::Error::other("Container full: would exceed max size"));
         }
 
         let offset = self.current_offset;
-        self.writer.write_all(data)?;
-        self.current_offset += data.len() as u64;
+        if let Some(flag) = frame {
+            self.writer.write_all(&[flag])?;
+            self.writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        }
+        self.writer.write_all(&payload)?;
+        self.current_offset += stored_len;
         self.chunks_written += 1;
-        self.bytes_written += data.len() as u64;
+        self.bytes_written += stored_len;
 
         Ok(PackedChunkRef {
             container_id: self.container_id.clone(),
@@ -215,9 +295,21 @@ impl ContainerWriter {
         self.chunks_written
     }
 
-    /// Check if the container has room for more data
+    /// Check if the container has room for a chunk of `bytes` uncompressed bytes
+    ///
+    /// Compressed containers reserve room for the frame and a chunk that
+    /// does not shrink.
     pub fn has_capacity(&self, bytes: u64) -> bool {
-        self.current_offset + bytes <= self.max_size
+        let framing = match self.compression {
+            ChunkCompression::None => 0,
+            ChunkCompression::Zstd { .. } => FRAME_HEADER_SIZE,
+        };
+        self.current_offset + bytes + framing <= self.max_size
+    }
+
+    /// Compression applied to appended chunks
+    pub fn compression(&self) -> ChunkCompression {
+        self.compression
     }
 
     /// Get the file path
@@ -226,37 +318,91 @@ impl ContainerWriter {
     }
 }
 
+/// Verify the container header, returning its format version
+fn read_header<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != CONTAINER_MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid container magic",
+        ));
+    }
+
+    let mut version = [0u8; 2];
+    reader.read_exact(&mut version)?;
+    let version = u16::from_le_bytes(version);
+    if !(CONTAINER_VERSION..=CONTAINER_VERSION_FRAMED).contains(&version) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unsupported container version {}", version),
+        ));
+    }
+    Ok(version)
+}
+
 /// Reads chunks from a container file by offset and length.
 pub struct ContainerReader {
     #[allow(dead_code)]
     file: File,
     path: PathBuf,
+    version: u16,
 }
 
 impl ContainerReader {
     /// Open a container file for reading
     pub fn open(path: &Path) -> io::Result<Self> {
         let mut file = File::open(path)?;
-
-        // Verify header
-        let mut magic = [0u8; 8];
-        file.read_exact(&mut magic)?;
-        if &magic != CONTAINER_MAGIC {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid container magic",
-            ));
-        }
+        let version = read_header(&mut file)?;
 
         Ok(Self {
             file,
             path: path.to_path_buf(),
+            version,
         })
     }
 
+    /// Whether chunks in this container carry a compression frame
+    pub fn is_framed(&self) -> bool {
+        self.version == CONTAINER_VERSION_FRAMED
+    }
+
     /// Read a chunk from the container using a packed reference
+    ///
+    /// Compressed chunks are decompressed, so this always returns the
+    /// original bytes.
     pub fn read_chunk(&self, chunk_ref: &PackedChunkRef) -> io::Result<Vec<u8>> {
-        self.read_at(chunk_ref.offset, chunk_ref.length)
+        let stored = self.read_at(chunk_ref.offset, chunk_ref.length)?;
+        if !self.is_framed() {
+            return Ok(stored);
+        }
+
+        if stored.len() < FRAME_HEADER_SIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Chunk shorter than its frame",
+            ));
+        }
+        let (frame, payload) = stored.split_at(FRAME_HEADER_SIZE as usize);
+        let original_len = u32::from_le_bytes([frame[1], frame[2], frame[3], frame[4]]) as usize;
+
+        let data = match frame[0] {
+            FRAME_RAW => payload.to_vec(),
+            FRAME_ZSTD => zstd::bulk::decompress(payload, original_len)?,
+            flag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unknown chunk frame flag {}", flag),
+                ))
+            }
+        };
+        if data.len() != original_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Chunk length does not match its frame",
+            ));
+        }
+        Ok(data)
     }
 
     /// Read data at a specific offset and length
@@ -291,6 +437,12 @@ pub struct ContainerPool {
 
     /// Total bytes packed across all containers
     total_bytes: u64,
+
+    /// Total bytes occupied in containers (after compression and framing)
+    stored_bytes: u64,
+
+    /// Compression for newly created containers
+    compression: ChunkCompression,
 }
 
 impl ContainerPool {
@@ -303,7 +455,46 @@ impl ContainerPool {
             next_id: 0,
             total_chunks: 0,
             total_bytes: 0,
+            stored_bytes: 0,
+            compression: ChunkCompression::None,
+        }
+    }
+
+    /// Compress chunks in containers this pool creates
+    ///
+    /// Existing containers are not rewritten; readers handle both layouts.
+    pub fn with_compression(mut self, compression: ChunkCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Open a pool over a directory that may already hold containers
+    ///
+    /// Existing `container_NNNNNN.orbitpak` files are left untouched; new
+    /// containers are numbered after the highest one found, so chunks already
+    /// referenced from the Universe stay readable.
+    pub fn open(directory: PathBuf, max_container_size: u64) -> io::Result<Self> {
+        let mut next_id = 0;
+        for entry in std::fs::read_dir(&directory)? {
+            let name = entry?.file_name();
+            let id = name
+                .to_str()
+                .and_then(|n| n.strip_prefix("container_"))
+                .and_then(|n| n.strip_suffix(".orbitpak"))
+                .and_then(|n| n.parse::<u64>().ok());
+            if let Some(id) = id {
+                next_id = next_id.max(id + 1);
+            }
         }
+
+        let mut pool = Self::new(directory, max_container_size);
+        pool.next_id = next_id;
+        Ok(pool)
+    }
+
+    /// Path of the container file with the given ID
+    pub fn container_path(&self, container_id: &str) -> PathBuf {
+        self.directory.join(format!("{}.orbitpak", container_id))
     }
 
     /// Pack a chunk into the pool, rotating containers as needed
@@ -323,6 +514,7 @@ impl ContainerPool {
         let chunk_ref = writer.append_chunk(data)?;
         self.total_chunks += 1;
         self.total_bytes += data.len() as u64;
+        self.stored_bytes += chunk_ref.length as u64;
 
         Ok(chunk_ref)
     }
@@ -339,7 +531,11 @@ impl ContainerPool {
         self.next_id += 1;
 
         let path = self.directory.join(&container_name);
-        let writer = ContainerWriter::create_with_max_size(&path, self.max_container_size)?;
+        let writer = ContainerWriter::create_with_compression(
+            &path,
+            self.max_container_size,
+            self.compression,
+        )?;
         self.active_writer = Some(writer);
 
         Ok(())
@@ -359,6 +555,7 @@ impl ContainerPool {
             containers_created: self.next_id,
             total_chunks: self.total_chunks,
             total_bytes: self.total_bytes,
+            stored_bytes: self.stored_bytes,
             active_container_size: self
                 .active_writer
                 .as_ref()
@@ -377,6 +574,8 @@ pub struct ContainerPoolStats {
     pub total_chunks: u64,
     /// Total bytes packed
     pub total_bytes: u64,
+    /// Bytes occupied in containers (smaller than `total_bytes` when compressed)
+    pub stored_bytes: u64,
     /// Current active container size
     pub active_container_size: u64,
 }
@@ -384,6 +583,7 @@ pub struct ContainerPoolStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use orbit_core_cdc::testing::xorshift_bytes;
     use tempfile::TempDir;
 
     #[test]
@@ -552,6 +752,26 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_pool_open_continues_numbering() {
+        let dir = TempDir::new().unwrap();
+
+        let first = {
+            let mut pool = ContainerPool::new(dir.path().to_path_buf(), HEADER_SIZE + 1024);
+            let r = pool.pack_chunk(b"kept").unwrap();
+            pool.flush().unwrap();
+            r
+        };
+
+        let mut pool = ContainerPool::open(dir.path().to_path_buf(), HEADER_SIZE + 1024).unwrap();
+        let second = pool.pack_chunk(b"new").unwrap();
+        pool.flush().unwrap();
+
+        assert_eq!(second.container_id, "container_000001");
+        let reader = ContainerReader::open(&pool.container_path(&first.container_id)).unwrap();
+        assert_eq!(reader.read_chunk(&first).unwrap(), b"kept");
+    }
+
     #[test]
     fn test_pool_flush_no_active_writer() {
         let dir = TempDir::new().unwrap();
@@ -642,4 +862,134 @@ mod tests {
         let writer3 = ContainerWriter::create(&path3).unwrap();
         assert_eq!(writer3.container_id(), "bare_name");
     }
+
+    fn incompressible(len: usize) -> Vec<u8> {
+        xorshift_bytes(len, 0x9E37_79B9_7F4A_7C15)
+    }
+
+    #[test]
+    fn test_compressed_container_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("compressed.orbitpak");
+
+        let text = b"orbit container chunk ".repeat(200);
+        let noise = incompressible(4096);
+
+        let mut writer = ContainerWriter::create_with_compression(
+            &path,
+            DEFAULT_MAX_CONTAINER_SIZE,
+            ChunkCompression::zstd(),
+        )
+        .unwrap();
+        let text_ref = writer.append_chunk(&text).unwrap();
+        let noise_ref = writer.append_chunk(&noise).unwrap();
+        let empty_ref = writer.append_chunk(&[]).unwrap();
+        writer.flush().unwrap();
+
+        // Compressible chunk shrinks; incompressible one is stored raw
+        assert!((text_ref.length as usize) < text.len() / 4);
+        assert_eq!(
+            noise_ref.length as u64,
+            noise.len() as u64 + FRAME_HEADER_SIZE
+        );
+        assert_eq!(noise_ref.offset, text_ref.offset + text_ref.length as u64);
+
+        let reader = ContainerReader::open(&path).unwrap();
+        assert!(reader.is_framed());
+        assert_eq!(reader.read_chunk(&text_ref).unwrap(), text);
+        assert_eq!(reader.read_chunk(&noise_ref).unwrap(), noise);
+        assert!(reader.read_chunk(&empty_ref).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compressed_pool_stores_fewer_bytes() {
+        let dir = TempDir::new().unwrap();
+        let mut pool = ContainerPool::new(dir.path().to_path_buf(), DEFAULT_MAX_CONTAINER_SIZE)
+            .with_compression(ChunkCompression::zstd());
+
+        let chunks = [
+            vec![0x41u8; 8192],
+            incompressible(8192),
+            b"abc".repeat(1000),
+        ];
+        let refs: Vec<_> = chunks.iter().map(|c| pool.pack_chunk(c).unwrap()).collect();
+        pool.flush().unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(stats.total_bytes, (8192 * 2 + 3000) as u64);
+        assert!(stats.stored_bytes < stats.total_bytes);
+        assert_eq!(
+            std::fs::metadata(pool.container_path(&refs[0].container_id))
+                .unwrap()
+                .len(),
+            HEADER_SIZE + stats.stored_bytes
+        );
+
+        let reader = ContainerReader::open(&pool.container_path(&refs[0].container_id)).unwrap();
+        for (chunk, chunk_ref) in chunks.iter().zip(&refs) {
+            assert_eq!(&reader.read_chunk(chunk_ref).unwrap(), chunk);
+        }
+    }
+
+    #[test]
+    fn test_open_append_keeps_compression() {
+        let dir = TempDir::new().unwrap();
+        let plain = dir.path().join("plain.orbitpak");
+        let packed = dir.path().join("packed.orbitpak");
+
+        ContainerWriter::create(&plain).unwrap().flush().unwrap();
+        ContainerWriter::create_with_compression(&packed, 1024 * 1024, ChunkCompression::zstd())
+            .unwrap()
+            .flush()
+            .unwrap();
+
+        let mut writer = ContainerWriter::open_append(&plain).unwrap();
+        assert_eq!(writer.compression(), ChunkCompression::None);
+        let plain_ref = writer.append_chunk(&[7u8; 512]).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(plain_ref.length, 512);
+
+        let mut writer = ContainerWriter::open_append(&packed).unwrap();
+        assert_eq!(writer.compression(), ChunkCompression::zstd());
+        let packed_ref = writer.append_chunk(&[7u8; 512]).unwrap();
+        writer.flush().unwrap();
+        assert!(packed_ref.length < 512);
+
+        let reader = ContainerReader::open(&packed).unwrap();
+        assert_eq!(reader.read_chunk(&packed_ref).unwrap(), vec![7u8; 512]);
+    }
+
+    #[test]
+    fn test_corrupt_frame_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("corrupt.orbitpak");
+
+        let mut writer =
+            ContainerWriter::create_with_compression(&path, 1024 * 1024, ChunkCompression::zstd())
+                .unwrap();
+        let chunk_ref = writer.append_chunk(&[1u8; 256]).unwrap();
+        writer.flush().unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[chunk_ref.offset as usize] = 0xFF;
+        std::fs::write(&path, &bytes).unwrap();
+
+        let reader = ContainerReader::open(&path).unwrap();
+        let err = reader.read_chunk(&chunk_ref).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_reader_rejects_unknown_version() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("future.orbitpak");
+
+        let mut bytes = CONTAINER_MAGIC.to_vec();
+        bytes.extend_from_slice(&99u16.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 6]);
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(ContainerReader::open(&path).is_err());
+        assert!(ContainerWriter::open_append(&path).is_err());
+    }
 }
@@ -19,7 +19,7 @@
 //! ├─────────────────────────────────────┤
 //! │ Magic Number (8 bytes)              │
 //! │ Version (2 bytes)                   │
-//! │ Header (counts, sizes)              │
+//! │ Header (hash algorithm, counts)     │
 //! │ Chunk Entries (offset, len, CID)    │
 //! │ Window Entries (id, merkle, etc)    │
 //! │ Bloom Filter (serialized)           │
@@ -78,17 +78,65 @@ pub use reader::StarMapReader;
 pub use universe::{ChunkLocation, DedupStats, Location, Universe, UniverseMap};
 
 /// Current Star Map format version
-pub const STARMAP_VERSION: u16 = 1;
+///
+/// Version 2 added the content ID hash algorithm tag. Version 1 maps are
+/// still readable and report [`HashAlgorithm::Blake3`].
+pub const STARMAP_VERSION: u16 = 2;
+
+/// Oldest Star Map format version the reader accepts
+pub const STARMAP_MIN_VERSION: u16 = 1;
 
 /// Magic number for Star Map files (used for quick format detection)
 pub const STARMAP_MAGIC: &[u8; 8] = b"ORBITMAP";
 
-/// Content ID size in bytes (BLAKE3 hash)
+/// Content ID size in bytes (BLAKE3 or SHA-256 digest)
 pub const CONTENT_ID_SIZE: usize = 32;
 
 /// Merkle root size in bytes
 pub const MERKLE_ROOT_SIZE: usize = 32;
 
+/// Hash algorithm that produced a Star Map's content IDs
+///
+/// IDs from different algorithms live in separate ID spaces: the same 32
+/// bytes under BLAKE3 and SHA-256 name unrelated chunks, so a map only
+/// answers queries for its own algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    /// BLAKE3 (Orbit's native content IDs)
+    #[default]
+    Blake3,
+    /// SHA-256, for interop with SHA-256 addressed stores
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Canonical lowercase name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha256 => "sha256",
+        }
+    }
+
+    /// Content ID prefixed with this algorithm's tag byte, as inserted into
+    /// and queried from the bloom filter
+    pub fn tagged_id(&self, content_id: &[u8; CONTENT_ID_SIZE]) -> [u8; CONTENT_ID_SIZE + 1] {
+        let mut tagged = [0u8; CONTENT_ID_SIZE + 1];
+        tagged[0] = match self {
+            HashAlgorithm::Blake3 => 0,
+            HashAlgorithm::Sha256 => 1,
+        };
+        tagged[1..].copy_from_slice(content_id);
+        tagged
+    }
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Chunk metadata
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChunkMeta {
@@ -96,8 +144,10 @@ pub struct ChunkMeta {
     pub offset: u64,
     /// Length in bytes
     pub length: u32,
-    /// Content ID (BLAKE3 hash)
+    /// Content ID
     pub content_id: [u8; 32],
+    /// Algorithm that produced `content_id`
+    pub algorithm: HashAlgorithm,
 }
 
 /// Window metadata
@@ -120,6 +170,8 @@ pub struct WindowMeta {
 pub(crate) struct StarMapData {
     /// Schema version
     pub version: u16,
+    /// Algorithm of every chunk's content ID
+    pub hash_algorithm: HashAlgorithm,
     /// File size
     pub file_size: u64,
     /// Chunk count
@@ -142,13 +194,66 @@ pub(crate) struct StarMapData {
     pub bitmaps: Vec<Vec<u8>>,
 }
 
+/// Version 1 chunk entry, before content IDs were tagged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ChunkMetaV1 {
+    pub offset: u64,
+    pub length: u32,
+    pub content_id: [u8; 32],
+}
+
+/// Version 1 layout of [`StarMapData`]: no hash algorithm, and the bloom
+/// filter holds untagged content IDs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StarMapDataV1 {
+    pub version: u16,
+    pub file_size: u64,
+    pub chunk_count: u32,
+    pub window_count: u32,
+    pub chunks: Vec<ChunkMetaV1>,
+    pub windows: Vec<WindowMeta>,
+    pub bloom_data: Vec<u8>,
+    pub bloom_hashes: u32,
+    pub bloom_elements: u32,
+    pub bloom_bits: usize,
+    pub bitmaps: Vec<Vec<u8>>,
+}
+
+impl From<StarMapDataV1> for StarMapData {
+    fn from(v1: StarMapDataV1) -> Self {
+        Self {
+            version: v1.version,
+            hash_algorithm: HashAlgorithm::Blake3,
+            file_size: v1.file_size,
+            chunk_count: v1.chunk_count,
+            window_count: v1.window_count,
+            chunks: v1
+                .chunks
+                .into_iter()
+                .map(|c| ChunkMeta {
+                    offset: c.offset,
+                    length: c.length,
+                    content_id: c.content_id,
+                    algorithm: HashAlgorithm::Blake3,
+                })
+                .collect(),
+            windows: v1.windows,
+            bloom_data: v1.bloom_data,
+            bloom_hashes: v1.bloom_hashes,
+            bloom_elements: v1.bloom_elements,
+            bloom_bits: v1.bloom_bits,
+            bitmaps: v1.bitmaps,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_constants() {
-        assert_eq!(STARMAP_VERSION, 1);
+        assert_eq!(STARMAP_VERSION, 2);
         assert_eq!(STARMAP_MAGIC.len(), 8);
         assert_eq!(CONTENT_ID_SIZE, 32);
         assert_eq!(MERKLE_ROOT_SIZE, 32);
@@ -158,7 +263,7 @@ mod tests {
     fn test_chunk_meta_size() {
         // Ensure ChunkMeta is reasonably sized
         let size = std::mem::size_of::<ChunkMeta>();
-        // offset(8) + length(4) + content_id(32) = 44 bytes (plus alignment)
+        // offset(8) + length(4) + content_id(32) + algorithm(1) = 45 bytes (plus alignment)
         assert!(size <= 48, "ChunkMeta size: {} bytes", size);
     }
 
@@ -176,6 +281,7 @@ mod tests {
             offset: 1024,
             length: 4096,
             content_id: [42u8; 32],
+            algorithm: HashAlgorithm::Sha256,
         };
 
         let serialized = bincode::serialize(&chunk).unwrap();
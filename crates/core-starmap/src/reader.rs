@@ -1,8 +1,10 @@
 //! Star Map reader for querying binary indices
 
 use crate::error::{Error, Result};
-use crate::{BloomFilter, ChunkMeta, RankSelectBitmap, StarMapData, WindowMeta};
-use crate::{STARMAP_MAGIC, STARMAP_VERSION};
+use crate::{
+    BloomFilter, ChunkMeta, HashAlgorithm, RankSelectBitmap, StarMapData, StarMapDataV1, WindowMeta,
+};
+use crate::{STARMAP_MAGIC, STARMAP_MIN_VERSION, STARMAP_VERSION};
 use memmap2::Mmap;
 use std::fs::File;
 use std::path::Path;
@@ -40,6 +42,8 @@ pub struct StarMapReader {
     bloom: BloomFilter,
     /// Cached bitmaps (one per window)
     bitmaps: Vec<RankSelectBitmap>,
+    /// Version 1 maps have an untagged bloom filter
+    legacy_bloom: bool,
 }
 
 impl StarMapReader {
@@ -68,15 +72,27 @@ impl StarMapReader {
             });
         }
 
-        // Deserialize from bincode (skip magic header)
-        let data: StarMapData = bincode::deserialize(&mmap[8..])
-            .map_err(|e| Error::invalid_format(format!("Deserialization failed: {}", e)))?;
-
-        // Validate version
-        if data.version != STARMAP_VERSION {
-            return Err(Error::version_mismatch(STARMAP_VERSION, data.version));
+        // The version leads the bincode payload and selects its layout
+        let version = mmap
+            .get(8..10)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or_else(|| Error::invalid_format("File too small"))?;
+        if !(STARMAP_MIN_VERSION..=STARMAP_VERSION).contains(&version) {
+            return Err(Error::version_mismatch(STARMAP_VERSION, version));
         }
 
+        // Deserialize from bincode (skip magic header)
+        let deserialize_error =
+            |e: bincode::Error| Error::invalid_format(format!("Deserialization failed: {}", e));
+        let legacy_bloom = version == 1;
+        let data: StarMapData = if legacy_bloom {
+            bincode::deserialize::<StarMapDataV1>(&mmap[8..])
+                .map_err(deserialize_error)?
+                .into()
+        } else {
+            bincode::deserialize(&mmap[8..]).map_err(deserialize_error)?
+        };
+
         // Reconstruct bloom filter from data
         let bloom = BloomFilter::from_bytes(
             &data.bloom_data,
@@ -101,15 +117,35 @@ impl StarMapReader {
             data,
             bloom,
             bitmaps,
+            legacy_bloom,
         })
     }
 
+    /// Algorithm that produced this map's content IDs
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.data.hash_algorithm
+    }
+
     /// Check if a chunk with the given content ID might exist
     ///
+    /// `content_id` is taken to be in this map's [`HashAlgorithm`] ID space.
     /// Returns `true` if the chunk might be present (may be false positive).
     /// Returns `false` if the chunk is definitely not present.
     pub fn has_chunk(&self, content_id: &[u8; 32]) -> Result<bool> {
-        Ok(self.bloom.contains(content_id))
+        self.has_chunk_with(self.data.hash_algorithm, content_id)
+    }
+
+    /// Check if a chunk whose ID was produced by `algorithm` might exist
+    ///
+    /// Always `false` when `algorithm` is not this map's algorithm.
+    pub fn has_chunk_with(&self, algorithm: HashAlgorithm, content_id: &[u8; 32]) -> Result<bool> {
+        if algorithm != self.data.hash_algorithm {
+            return Ok(false);
+        }
+        if self.legacy_bloom {
+            return Ok(self.bloom.contains(content_id));
+        }
+        Ok(self.bloom.contains(&algorithm.tagged_id(content_id)))
     }
 
     /// Get the total number of chunks
@@ -329,6 +365,75 @@ mod tests {
         assert!(reader.has_chunk(&[2u8; 32]).unwrap());
     }
 
+    #[test]
+    fn test_hash_algorithm_round_trips_and_scopes_queries() {
+        let cid = [7u8; 32];
+        let mut files = Vec::new();
+        for algorithm in [HashAlgorithm::Blake3, HashAlgorithm::Sha256] {
+            let mut builder = StarMapBuilder::new(4096).with_hash_algorithm(algorithm);
+            builder.add_chunk(0, 4096, &cid).unwrap();
+            builder.add_window(0, 0, 1, &[0u8; 32], 0).unwrap();
+            let temp_file = NamedTempFile::new().unwrap();
+            std::fs::write(temp_file.path(), builder.build().unwrap()).unwrap();
+            files.push((algorithm, temp_file));
+        }
+
+        for (algorithm, temp_file) in &files {
+            let reader = StarMapReader::open(temp_file.path()).unwrap();
+            assert_eq!(reader.hash_algorithm(), *algorithm);
+            assert_eq!(reader.get_chunk(0).unwrap().algorithm, *algorithm);
+            assert!(reader.has_chunk(&cid).unwrap());
+            assert!(reader.has_chunk_with(*algorithm, &cid).unwrap());
+        }
+
+        // The same ID bytes from the other algorithm are a different chunk
+        let blake3 = StarMapReader::open(files[0].1.path()).unwrap();
+        let sha256 = StarMapReader::open(files[1].1.path()).unwrap();
+        assert!(!blake3.has_chunk_with(HashAlgorithm::Sha256, &cid).unwrap());
+        assert!(!sha256.has_chunk_with(HashAlgorithm::Blake3, &cid).unwrap());
+    }
+
+    #[test]
+    fn test_version_1_map_reads_as_blake3() {
+        let mut bloom = BloomFilter::new(1, 0.01);
+        bloom.insert(&[3u8; 32]);
+        let v1 = StarMapDataV1 {
+            version: 1,
+            file_size: 4096,
+            chunk_count: 1,
+            window_count: 1,
+            chunks: vec![crate::ChunkMetaV1 {
+                offset: 0,
+                length: 4096,
+                content_id: [3u8; 32],
+            }],
+            windows: vec![WindowMeta {
+                id: 0,
+                first_chunk: 0,
+                count: 1,
+                merkle_root: [0u8; 32],
+                overlap: 0,
+            }],
+            bloom_data: bloom.to_bytes(),
+            bloom_hashes: bloom.num_hashes(),
+            bloom_elements: bloom.num_elements(),
+            bloom_bits: bloom.num_bits(),
+            bitmaps: vec![RankSelectBitmap::new(1).to_bytes()],
+        };
+        let mut data = STARMAP_MAGIC.to_vec();
+        data.extend(bincode::serialize(&v1).unwrap());
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), data).unwrap();
+
+        let reader = StarMapReader::open(temp_file.path()).unwrap();
+        assert_eq!(reader.hash_algorithm(), HashAlgorithm::Blake3);
+        assert_eq!(
+            reader.get_chunk(0).unwrap().algorithm,
+            HashAlgorithm::Blake3
+        );
+        assert!(reader.has_chunk(&[3u8; 32]).unwrap());
+    }
+
     #[test]
     fn test_next_missing() {
         let temp_file = create_test_starmap();
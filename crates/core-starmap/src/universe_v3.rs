@@ -24,6 +24,10 @@
 //! - **Streaming Reads:** Iterator-based access prevents memory exhaustion
 //! - **Scalability:** Handles millions of duplicates per chunk efficiently
 //!
+//! A second pair of tables tracks how many locations each chunk has and
+//! indexes chunks by that count, so [`Universe::iter_under_replicated`] can
+//! find at-risk chunks without scanning the whole Universe.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -48,7 +52,11 @@
 //! ```
 
 use crate::error::{Error, Result};
-use redb::{Database, MultimapTableDefinition, ReadableTableMetadata};
+use redb::{
+    Database, MultimapRange, MultimapTable, MultimapTableDefinition, MultimapValue,
+    ReadOnlyMultimapTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, Table,
+    TableDefinition,
+};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -61,6 +69,18 @@ pub const UNIVERSE_V3_VERSION: u16 = 3;
 const CHUNKS_TABLE_V3: MultimapTableDefinition<&[u8; 32], &[u8]> =
     MultimapTableDefinition::new("chunks_v3");
 
+/// Table definition for replica counts
+/// Key: [u8; 32] (BLAKE3 hash)
+/// Value: u32 (number of locations in `chunks_v3`)
+const REPLICA_COUNTS_TABLE_V3: TableDefinition<&[u8; 32], u32> =
+    TableDefinition::new("replica_counts_v3");
+
+/// Index from replica count to chunk hashes (Multimap)
+/// Key: u32 (replica count)
+/// Values: [u8; 32] (BLAKE3 hashes with that many locations)
+const REPLICA_INDEX_TABLE_V3: MultimapTableDefinition<u32, &[u8; 32]> =
+    MultimapTableDefinition::new("replica_index_v3");
+
 /// A location where a chunk exists in the Orbit Grid
 ///
 /// This is serialized individually for each entry in the multimap,
@@ -128,9 +148,35 @@ impl Universe {
             .begin_write()
             .map_err(|e| Error::Other(format!("Failed to begin transaction: {}", e)))?;
         {
-            let _ = write_txn
+            let chunks = write_txn
                 .open_multimap_table(CHUNKS_TABLE_V3)
                 .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+            let mut counts = write_txn
+                .open_table(REPLICA_COUNTS_TABLE_V3)
+                .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+            let mut index = write_txn
+                .open_multimap_table(REPLICA_INDEX_TABLE_V3)
+                .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+
+            // Databases written before the replica index existed get it
+            // built once here
+            let needs_backfill = counts
+                .is_empty()
+                .map_err(|e| Error::Other(format!("Failed to get length: {}", e)))?
+                && !chunks
+                    .is_empty()
+                    .map_err(|e| Error::Other(format!("Failed to get length: {}", e)))?;
+            if needs_backfill {
+                let range = chunks
+                    .range::<&[u8; 32]>(..)
+                    .map_err(|e| Error::Other(format!("Failed to create range: {}", e)))?;
+                for item in range {
+                    let (hash_ref, locations) =
+                        item.map_err(|e| Error::Other(format!("DB error: {}", e)))?;
+                    let replicas = locations.len() as u32;
+                    set_replica_count(&mut counts, &mut index, hash_ref.value(), 0, replicas)?;
+                }
+            }
         }
         write_txn
             .commit()
@@ -176,9 +222,13 @@ impl Universe {
 
             // Insert as a new discrete entry for this key
             // This is O(log N) B-Tree insertion, not O(N) blob rewrite
-            table
+            let existed = table
                 .insert(&hash, serialized.as_slice())
                 .map_err(|e| Error::Other(format!("Failed to insert: {}", e)))?;
+
+            if !existed {
+                adjust_replica_count(&write_txn, &hash, 1)?;
+            }
         }
 
         write_txn
@@ -188,6 +238,41 @@ impl Universe {
         Ok(())
     }
 
+    /// Remove one location of a chunk
+    ///
+    /// Returns `true` if the location was present. The replica index is
+    /// updated in the same transaction.
+    pub fn remove_chunk(&self, hash: &[u8; 32], location: &ChunkLocation) -> Result<bool> {
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|e| Error::Other(format!("Failed to begin write: {}", e)))?;
+
+        let removed = {
+            let mut table = write_txn
+                .open_multimap_table(CHUNKS_TABLE_V3)
+                .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+
+            let serialized = bincode::serialize(location)
+                .map_err(|e| Error::SerializationError(e.to_string()))?;
+
+            let removed = table
+                .remove(hash, serialized.as_slice())
+                .map_err(|e| Error::Other(format!("Failed to remove: {}", e)))?;
+
+            if removed {
+                adjust_replica_count(&write_txn, hash, -1)?;
+            }
+            removed
+        };
+
+        write_txn
+            .commit()
+            .map_err(|e| Error::Other(format!("Failed to commit: {}", e)))?;
+
+        Ok(removed)
+    }
+
     /// Check if a chunk exists
     ///
     /// Returns true if at least one location exists for this hash.
@@ -433,6 +518,158 @@ impl Universe {
 
         Ok(())
     }
+
+    /// Stream the chunks that have fewer than `min_redundancy` replicas
+    ///
+    /// A chunk's replica count is the number of locations recorded for it.
+    /// Unlike [`scan_all_chunks`](Self::scan_all_chunks), this walks the
+    /// replica-count index, so a Sentinel sweep costs O(at-risk chunks)
+    /// rather than O(total chunks). Chunks are yielded in ascending replica
+    /// count, most at-risk first.
+    ///
+    /// The iterator reads from a snapshot taken when it is created; inserts
+    /// and removals made while it is running are not visible to it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use orbit_core_starmap::universe_v3::Universe;
+    /// let universe = Universe::open("db.redb").unwrap();
+    ///
+    /// for entry in universe.iter_under_replicated(3).unwrap() {
+    ///     let (hash, locations) = entry.unwrap();
+    ///     println!("Chunk {:x?} has only {} replicas", hash, locations.len());
+    /// }
+    /// ```
+    pub fn iter_under_replicated(&self, min_redundancy: u32) -> Result<UnderReplicated> {
+        let read_txn = self
+            .db
+            .begin_read()
+            .map_err(|e| Error::Other(format!("Failed to begin read: {}", e)))?;
+
+        let chunks = read_txn
+            .open_multimap_table(CHUNKS_TABLE_V3)
+            .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+        let index = read_txn
+            .open_multimap_table(REPLICA_INDEX_TABLE_V3)
+            .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+
+        let counts = index
+            .range(1..min_redundancy.max(1))
+            .map_err(|e| Error::Other(format!("Failed to create range: {}", e)))?;
+
+        Ok(UnderReplicated {
+            chunks,
+            counts,
+            hashes: None,
+        })
+    }
+}
+
+/// Add `delta` to a chunk's replica count within `write_txn`
+fn adjust_replica_count(
+    write_txn: &redb::WriteTransaction,
+    hash: &[u8; 32],
+    delta: i64,
+) -> Result<()> {
+    let mut counts = write_txn
+        .open_table(REPLICA_COUNTS_TABLE_V3)
+        .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+    let mut index = write_txn
+        .open_multimap_table(REPLICA_INDEX_TABLE_V3)
+        .map_err(|e| Error::Other(format!("Failed to open table: {}", e)))?;
+
+    let old = counts
+        .get(hash)
+        .map_err(|e| Error::Other(format!("Failed to get replica count: {}", e)))?
+        .map(|count| count.value())
+        .unwrap_or(0);
+    let new = (old as i64 + delta).max(0) as u32;
+
+    set_replica_count(&mut counts, &mut index, hash, old, new)
+}
+
+/// Move a chunk from `old` to `new` in the replica count table and index
+fn set_replica_count(
+    counts: &mut Table<&[u8; 32], u32>,
+    index: &mut MultimapTable<u32, &[u8; 32]>,
+    hash: &[u8; 32],
+    old: u32,
+    new: u32,
+) -> Result<()> {
+    if old > 0 {
+        index
+            .remove(old, hash)
+            .map_err(|e| Error::Other(format!("Failed to update replica index: {}", e)))?;
+    }
+
+    if new > 0 {
+        counts
+            .insert(hash, new)
+            .map_err(|e| Error::Other(format!("Failed to update replica count: {}", e)))?;
+        index
+            .insert(new, hash)
+            .map_err(|e| Error::Other(format!("Failed to update replica index: {}", e)))?;
+    } else {
+        counts
+            .remove(hash)
+            .map_err(|e| Error::Other(format!("Failed to update replica count: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Streaming iterator over under-replicated chunks
+///
+/// Returned by [`Universe::iter_under_replicated`]. Each item is a chunk hash
+/// together with all of its current locations.
+pub struct UnderReplicated {
+    chunks: ReadOnlyMultimapTable<&'static [u8; 32], &'static [u8]>,
+    counts: MultimapRange<'static, u32, &'static [u8; 32]>,
+    hashes: Option<MultimapValue<'static, &'static [u8; 32]>>,
+}
+
+impl UnderReplicated {
+    fn locations(&self, hash: &[u8; 32]) -> Result<Vec<ChunkLocation>> {
+        let iter = self
+            .chunks
+            .get(hash)
+            .map_err(|e| Error::Other(format!("Failed to get chunk: {}", e)))?;
+
+        let mut locations = Vec::new();
+        for item in iter {
+            let item = item.map_err(|e| Error::Other(format!("DB error: {}", e)))?;
+            let loc: ChunkLocation = bincode::deserialize(item.value())
+                .map_err(|e| Error::DeserializationError(e.to_string()))?;
+            locations.push(loc);
+        }
+
+        Ok(locations)
+    }
+}
+
+impl Iterator for UnderReplicated {
+    type Item = Result<([u8; 32], Vec<ChunkLocation>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(hashes) = self.hashes.as_mut() {
+                match hashes.next() {
+                    Some(Ok(hash_ref)) => {
+                        let hash = *hash_ref.value();
+                        return Some(self.locations(&hash).map(|locations| (hash, locations)));
+                    }
+                    Some(Err(e)) => return Some(Err(Error::Other(format!("DB error: {}", e)))),
+                    None => self.hashes = None,
+                }
+            }
+
+            match self.counts.next()? {
+                Ok((_, hashes)) => self.hashes = Some(hashes),
+                Err(e) => return Some(Err(Error::Other(format!("DB error: {}", e)))),
+            }
+        }
+    }
 }
 
 /// Iterator wrapper for locations
@@ -575,6 +812,88 @@ mod tests {
         assert_eq!(iter.len(), 0);
     }
 
+    #[test]
+    fn test_iter_under_replicated_mixed_counts() {
+        let tmp_file = NamedTempFile::new().unwrap();
+        let universe = Universe::open(tmp_file.path()).unwrap();
+
+        let location = |star: usize| {
+            ChunkLocation::new(format!("star-{}", star), PathBuf::from("file.bin"), 0, 1024)
+        };
+
+        // Hash [n; 32] gets n replicas
+        for replicas in 1..=4u8 {
+            for star in 0..replicas as usize {
+                universe
+                    .insert_chunk([replicas; 32], location(star))
+                    .unwrap();
+            }
+        }
+        // Re-inserting an existing location does not add a replica
+        universe.insert_chunk([1; 32], location(0)).unwrap();
+
+        let under: Vec<_> = universe
+            .iter_under_replicated(3)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+        let hashes: Vec<_> = under.iter().map(|(hash, _)| *hash).collect();
+        assert_eq!(hashes, vec![[1; 32], [2; 32]]);
+        assert_eq!(under[1].1.len(), 2);
+
+        // Losing a replica moves a healthy chunk below the threshold
+        assert!(universe.remove_chunk(&[3; 32], &location(0)).unwrap());
+        assert!(!universe.remove_chunk(&[3; 32], &location(0)).unwrap());
+        let hashes: Vec<_> = universe
+            .iter_under_replicated(3)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect();
+        assert_eq!(hashes, vec![[1; 32], [2; 32], [3; 32]]);
+
+        // Removing the last replica drops the chunk from the index
+        assert!(universe.remove_chunk(&[1; 32], &location(0)).unwrap());
+        let hashes: Vec<_> = universe
+            .iter_under_replicated(3)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect();
+        assert_eq!(hashes, vec![[2; 32], [3; 32]]);
+
+        assert_eq!(universe.iter_under_replicated(1).unwrap().count(), 0);
+        assert_eq!(universe.iter_under_replicated(0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_replica_index_backfilled_on_open() {
+        let tmp_file = NamedTempFile::new().unwrap();
+
+        // Write chunks without the replica tables, as older databases did
+        {
+            let db = Database::create(tmp_file.path()).unwrap();
+            let write_txn = db.begin_write().unwrap();
+            {
+                let mut table = write_txn.open_multimap_table(CHUNKS_TABLE_V3).unwrap();
+                for star in 0..2 {
+                    let loc =
+                        ChunkLocation::new(format!("star-{}", star), PathBuf::from("f"), 0, 1);
+                    let serialized = bincode::serialize(&loc).unwrap();
+                    table.insert(&[0x11; 32], serialized.as_slice()).unwrap();
+                }
+            }
+            write_txn.commit().unwrap();
+        }
+
+        let universe = Universe::open(tmp_file.path()).unwrap();
+        let hashes: Vec<_> = universe
+            .iter_under_replicated(3)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect();
+        assert_eq!(hashes, vec![[0x11; 32]]);
+        assert_eq!(universe.iter_under_replicated(2).unwrap().count(), 0);
+    }
+
     #[test]
     fn test_chunk_location_serialization() {
         let loc = ChunkLocation::new(
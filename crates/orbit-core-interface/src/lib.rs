@@ -44,7 +44,7 @@
 
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -60,6 +60,56 @@ pub enum OrbitSystemError {
 
     #[error("System error: {0}")]
     System(String),
+
+    /// The operation did not complete in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// The connection to the system was reset or dropped mid-operation
+    #[error("Connection reset: {0}")]
+    ConnectionReset(String),
+
+    /// The system is shedding load; retry after `retry_after` if given
+    #[error("Throttled: {message}")]
+    Throttled {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+}
+
+impl OrbitSystemError {
+    /// Whether retrying the same operation may succeed
+    ///
+    /// Timeouts, resets and throttling are transient. `Io` errors are judged
+    /// by their kind. Missing paths, permission failures and generic
+    /// `System` errors are permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OrbitSystemError::Timeout(_)
+            | OrbitSystemError::ConnectionReset(_)
+            | OrbitSystemError::Throttled { .. } => true,
+            OrbitSystemError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+            ),
+            OrbitSystemError::NotFound(_)
+            | OrbitSystemError::PermissionDenied(_)
+            | OrbitSystemError::System(_) => false,
+        }
+    }
+
+    /// Server-suggested delay before retrying, for throttled operations
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            OrbitSystemError::Throttled { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, OrbitSystemError>;
@@ -302,6 +352,37 @@ pub trait OrbitSystem: Send + Sync + 'static {
     /// # }
     /// ```
     async fn calculate_hash(&self, path: &Path, offset: u64, len: u64) -> Result<[u8; 32]>;
+
+    /// Calculate a keyed BLAKE3 hash (MAC) of a specific file range
+    ///
+    /// Same range semantics as [`calculate_hash`](Self::calculate_hash), but
+    /// uses BLAKE3's keyed mode so the result depends on `key`. Jobs or
+    /// tenants that use different keys derive different content IDs for the
+    /// same bytes, and an ID cannot be forged without the key.
+    ///
+    /// The key is secret: implementations must not log it, include it in
+    /// errors, or persist it. A remote implementation sends it to the data
+    /// side only for the duration of the call.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use orbit_core_interface::{OrbitSystem, Result};
+    /// # use std::path::Path;
+    /// # async fn example<S: OrbitSystem>(system: &S, tenant_key: &[u8; 32]) -> Result<()> {
+    /// let id = system
+    ///     .calculate_keyed_hash(Path::new("/data/large.bin"), 0, 1024 * 1024, tenant_key)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn calculate_keyed_hash(
+        &self,
+        path: &Path,
+        offset: u64,
+        len: u64,
+        key: &[u8; 32],
+    ) -> Result<[u8; 32]>;
 }
 
 /// Helper trait for implementing additional convenience methods
@@ -361,4 +442,37 @@ mod tests {
         assert!(dir.is_dir);
         assert_eq!(dir.len, 0);
     }
+
+    #[test]
+    fn test_error_retryability() {
+        use std::io::{Error, ErrorKind};
+
+        let retryable = [
+            OrbitSystemError::Timeout("read_dir".into()),
+            OrbitSystemError::ConnectionReset("peer closed".into()),
+            OrbitSystemError::Throttled {
+                message: "slow down".into(),
+                retry_after: Some(Duration::from_secs(2)),
+            },
+            OrbitSystemError::Io(Error::from(ErrorKind::TimedOut)),
+            OrbitSystemError::Io(Error::from(ErrorKind::ConnectionReset)),
+            OrbitSystemError::Io(Error::from(ErrorKind::Interrupted)),
+        ];
+        for err in &retryable {
+            assert!(err.is_retryable(), "{err} should be retryable");
+        }
+
+        let permanent = [
+            OrbitSystemError::NotFound("/missing".into()),
+            OrbitSystemError::PermissionDenied("/secret".into()),
+            OrbitSystemError::System("bad request".into()),
+            OrbitSystemError::Io(Error::from(ErrorKind::InvalidData)),
+        ];
+        for err in &permanent {
+            assert!(!err.is_retryable(), "{err} should not be retryable");
+        }
+
+        assert_eq!(retryable[2].retry_after(), Some(Duration::from_secs(2)));
+        assert_eq!(retryable[0].retry_after(), None);
+    }
 }
@@ -10,6 +10,7 @@
 //! `HMAC(secret, previous_hash || canonical_event_bytes)`
 
 use crate::event::OrbitEvent;
+use crate::head::ChainHead;
 use crate::signer::AuditSigner;
 use ring::hmac;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -36,6 +37,12 @@ pub enum ChainError {
 
     #[error("Missing integrity hash at sequence {0}")]
     MissingHash(u64),
+
+    #[error("Resume line for sequence {0} is not signed")]
+    UnsignedResume(u64),
+
+    #[error("Resume line for sequence {0} has an invalid HMAC")]
+    ResumeIntegrityFailure(u64),
 }
 
 /// Result type for audit chain operations
@@ -86,6 +93,27 @@ impl AuditChain {
         }
     }
 
+    /// Continue a chain from a previously saved head
+    ///
+    /// The next event gets `head.sequence` and links to `head.hash`, so a
+    /// restarted logger extends the existing log instead of starting over.
+    pub fn resume(signer: AuditSigner, head: ChainHead) -> Self {
+        Self {
+            key: signer.key(),
+            sequence: AtomicU64::new(head.sequence),
+            prev_hash: Arc::new(Mutex::new(head.hash)),
+        }
+    }
+
+    /// Current head: the next sequence number and the hash it links to
+    pub fn head(&self) -> ChainHead {
+        let hash = self.prev_hash.lock().unwrap();
+        ChainHead {
+            sequence: self.sequence.load(Ordering::SeqCst),
+            hash: hash.clone(),
+        }
+    }
+
     /// Sign an event and link it into the chain
     ///
     /// This method:
@@ -137,8 +165,20 @@ impl AuditChain {
     ///
     /// Returns a ValidationReport with details about any failures.
     pub fn verify_chain(events: &[OrbitEvent], signer: &AuditSigner) -> Result<ValidationReport> {
+        Self::verify_chain_from(events, signer, &ChainHead::genesis())
+    }
+
+    /// Verify a chain of events that continues from `start`
+    ///
+    /// Used for log segments that begin with a resume line: the first event
+    /// must carry `start.sequence` and link to `start.hash`.
+    pub fn verify_chain_from(
+        events: &[OrbitEvent],
+        signer: &AuditSigner,
+        start: &ChainHead,
+    ) -> Result<ValidationReport> {
         let key = signer.key();
-        let mut prev_hash = vec![0u8; 32];
+        let mut prev_hash = start.hash.clone();
         let mut report = ValidationReport {
             total_events: events.len(),
             valid_events: 0,
@@ -158,7 +198,12 @@ impl AuditChain {
             };
 
             // Check sequence monotonicity
-            if index > 0 {
+            if index == 0 && event.sequence != start.sequence {
+                report.failures.push(ChainError::SequenceGap {
+                    expected: start.sequence,
+                    actual: event.sequence,
+                });
+            } else if index > 0 {
                 let expected_seq = events[index - 1].sequence + 1;
                 if event.sequence != expected_seq {
                     report.failures.push(ChainError::SequenceGap {
@@ -264,6 +309,8 @@ impl Clone for ChainError {
                 actual: *actual,
             },
             Self::MissingHash(seq) => Self::MissingHash(*seq),
+            Self::UnsignedResume(seq) => Self::UnsignedResume(*seq),
+            Self::ResumeIntegrityFailure(seq) => Self::ResumeIntegrityFailure(*seq),
         }
     }
 }
@@ -0,0 +1,306 @@
+//! Persistence for the audit chain head
+//!
+//! The chain head is the next sequence number and the hash it links to.
+//! [`UnifiedLogger`](crate::UnifiedLogger) saves it after every event so a
+//! restarted process keeps chaining from where the last one stopped, even if
+//! the log has been rotated away in the meantime. On startup the logger also
+//! reads the last line of an existing log, and continues from it when the
+//! saved head is behind (the process stopped between the two writes).
+//!
+//! [`FileChainHeadStore`] keeps the head in a small JSON file next to the log.
+//! Other stores, such as a key in a job database, implement [`ChainHeadStore`]
+//! and are passed to [`UnifiedLogger::with_head_store`](crate::UnifiedLogger::with_head_store).
+//!
+//! When a resumed logger starts a new, empty segment (after rotation, or
+//! next to a `.head` file whose log is gone), it first writes a resume line
+//! holding the head it continues from. The line carries an HMAC of the head
+//! under the audit key, so verifiers can start from it and check the segment
+//! on its own as well as after its predecessors. Unsigned resume lines are
+//! rejected.
+
+use crate::chain::ChainError;
+use crate::signer::AuditSigner;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Domain separator for resume line HMACs, so they never collide with an
+/// event hash
+const RESUME_CONTEXT: &[u8] = b"orbit-chain-resume";
+
+/// Position of an audit chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Sequence number of the next event
+    pub sequence: u64,
+
+    /// HMAC of the last event (32 zero bytes for an empty chain)
+    pub hash: Vec<u8>,
+}
+
+impl ChainHead {
+    /// Head of an empty chain: sequence 0, linking to 32 zero bytes
+    pub fn genesis() -> Self {
+        Self {
+            sequence: 0,
+            hash: vec![0u8; 32],
+        }
+    }
+
+    /// Resume line written at the top of a segment that continues a chain,
+    /// signed with the audit key
+    pub fn to_resume_line(&self, signer: &AuditSigner) -> String {
+        let line = ResumeLine {
+            chain_resume: ResumeRecord {
+                sequence: self.sequence,
+                hash: hex::encode(&self.hash),
+                mac: Some(hex::encode(self.resume_tag(signer).as_ref())),
+            },
+        };
+        serde_json::to_string(&line).expect("resume line serializes")
+    }
+
+    /// Parse and authenticate a resume line
+    ///
+    /// Returns None if `line` is not a resume line, and an error if it is
+    /// one but is unsigned or its HMAC does not match under `signer`.
+    pub fn from_resume_line(line: &str, signer: &AuditSigner) -> Option<Result<Self, ChainError>> {
+        let (head, mac) = parse_resume_line(line)?;
+        let Some(mac) = mac else {
+            return Some(Err(ChainError::UnsignedResume(head.sequence)));
+        };
+        let valid = hex::decode(&mac)
+            .is_ok_and(|mac| hmac::verify(&signer.key(), &head.resume_message(), &mac).is_ok());
+        Some(if valid {
+            Ok(head)
+        } else {
+            Err(ChainError::ResumeIntegrityFailure(head.sequence))
+        })
+    }
+
+    /// Whether `line` has the shape of a resume line, signed or not
+    pub(crate) fn is_resume_line(line: &str) -> bool {
+        parse_resume_line(line).is_some()
+    }
+
+    fn resume_tag(&self, signer: &AuditSigner) -> hmac::Tag {
+        hmac::sign(&signer.key(), &self.resume_message())
+    }
+
+    /// Bytes covered by a resume line's HMAC
+    fn resume_message(&self) -> Vec<u8> {
+        let mut message = RESUME_CONTEXT.to_vec();
+        message.extend_from_slice(&self.sequence.to_be_bytes());
+        message.extend_from_slice(&self.hash);
+        message
+    }
+}
+
+/// Head and HMAC of a resume line; None if `line` is not one
+fn parse_resume_line(line: &str) -> Option<(ChainHead, Option<String>)> {
+    let record = serde_json::from_str::<ResumeLine>(line).ok()?.chain_resume;
+    let head = ChainHead {
+        sequence: record.sequence,
+        hash: hex::decode(&record.hash).ok()?,
+    };
+    Some((head, record.mac))
+}
+
+/// Storage for the chain head between logger restarts
+pub trait ChainHeadStore: Send {
+    /// Load the saved head, or None if nothing has been saved yet
+    fn load(&self) -> io::Result<Option<ChainHead>>;
+
+    /// Save the head after an event has been written
+    fn save(&mut self, head: &ChainHead) -> io::Result<()>;
+}
+
+/// On-disk form of a [`ChainHead`]
+#[derive(Serialize, Deserialize)]
+struct HeadRecord {
+    sequence: u64,
+    hash: String,
+}
+
+/// On-disk form of a segment's resume line
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResumeLine {
+    chain_resume: ResumeRecord,
+}
+
+/// Head recorded in a resume line, with its HMAC
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResumeRecord {
+    sequence: u64,
+    hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mac: Option<String>,
+}
+
+/// Chain head stored as a JSON file
+///
+/// Saves go through a temporary file and a rename, so a crash mid-save
+/// leaves the previous head intact.
+#[derive(Debug, Clone)]
+pub struct FileChainHeadStore {
+    path: PathBuf,
+}
+
+impl FileChainHeadStore {
+    /// Store the head at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Default store for a log: `audit.jsonl` keeps its head in
+    /// `audit.jsonl.head`
+    ///
+    /// The suffix is appended rather than replacing the extension, so
+    /// `audit.log` and `audit.jsonl` in one directory get separate heads.
+    pub fn for_log(log_path: &Path) -> Self {
+        Self::new(with_suffix(log_path, ".head"))
+    }
+
+    /// Path of the head file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ChainHeadStore for FileChainHeadStore {
+    fn load(&self) -> io::Result<Option<ChainHead>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let record: HeadRecord = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let hash =
+            hex::decode(&record.hash).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Some(ChainHead {
+            sequence: record.sequence,
+            hash,
+        }))
+    }
+
+    fn save(&mut self, head: &ChainHead) -> io::Result<()> {
+        let record = HeadRecord {
+            sequence: head.sequence,
+            hash: hex::encode(&head.hash),
+        };
+        let json = serde_json::to_string(&record)?;
+        let tmp = with_suffix(&self.path, ".tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+/// `path` with `suffix` appended to its file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_head_loads_none() {
+        let dir = TempDir::new().unwrap();
+        let store = FileChainHeadStore::for_log(&dir.path().join("audit.jsonl"));
+        assert_eq!(store.path(), dir.path().join("audit.jsonl.head"));
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_logs_differing_only_in_extension_get_separate_heads() {
+        let dir = TempDir::new().unwrap();
+        let log = FileChainHeadStore::for_log(&dir.path().join("audit.log"));
+        let jsonl = FileChainHeadStore::for_log(&dir.path().join("audit.jsonl"));
+        assert_ne!(log.path(), jsonl.path());
+    }
+
+    #[test]
+    fn test_head_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let mut store = FileChainHeadStore::for_log(&dir.path().join("audit.jsonl"));
+        let head = ChainHead {
+            sequence: 42,
+            hash: vec![0xAB; 32],
+        };
+
+        store.save(&head).unwrap();
+        assert_eq!(store.load().unwrap(), Some(head));
+        assert!(!dir.path().join("audit.jsonl.head.tmp").exists());
+    }
+
+    #[test]
+    fn test_resume_line_round_trip() {
+        let signer = AuditSigner::from_bytes(b"resume-secret");
+        let head = ChainHead {
+            sequence: 7,
+            hash: vec![0x5A; 32],
+        };
+
+        let line = head.to_resume_line(&signer);
+        assert_eq!(
+            ChainHead::from_resume_line(&line, &signer)
+                .unwrap()
+                .unwrap(),
+            head
+        );
+        assert!(ChainHead::from_resume_line(r#"{"sequence":7}"#, &signer).is_none());
+    }
+
+    #[test]
+    fn test_unsigned_or_forged_resume_lines_are_rejected() {
+        let signer = AuditSigner::from_bytes(b"resume-secret");
+        let head = ChainHead {
+            sequence: 7,
+            hash: vec![0x5A; 32],
+        };
+
+        let unsigned = format!(
+            r#"{{"chain_resume":{{"sequence":7,"hash":"{}"}}}}"#,
+            hex::encode(&head.hash)
+        );
+        assert!(matches!(
+            ChainHead::from_resume_line(&unsigned, &signer),
+            Some(Err(ChainError::UnsignedResume(7)))
+        ));
+
+        // Moving the start forward invalidates the HMAC
+        let forged = head
+            .to_resume_line(&signer)
+            .replace(r#""sequence":7"#, r#""sequence":9"#);
+        assert!(matches!(
+            ChainHead::from_resume_line(&forged, &signer),
+            Some(Err(ChainError::ResumeIntegrityFailure(9)))
+        ));
+
+        let other = AuditSigner::from_bytes(b"other-secret");
+        assert!(matches!(
+            ChainHead::from_resume_line(&head.to_resume_line(&other), &signer),
+            Some(Err(ChainError::ResumeIntegrityFailure(7)))
+        ));
+    }
+
+    #[test]
+    fn test_corrupt_head_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let store = FileChainHeadStore::new(dir.path().join("audit.head"));
+        fs::write(store.path(), "not json").unwrap();
+
+        let err = store.load().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -109,6 +109,7 @@
 //! metrics::inc_transfer_retry("s3");
 //! metrics::record_backend_latency("s3", "write", 0.05);
 //! metrics::inc_transfer_bytes("s3", "success", 1024);
+//! metrics::record_chunk(64 * 1024);
 //!
 //! // Get metrics in Prometheus format
 //! let metrics_text = metrics::metrics_text();
@@ -118,6 +119,7 @@
 pub mod chain;
 pub mod context;
 pub mod event;
+pub mod head;
 pub mod logger;
 pub mod signer;
 
@@ -132,14 +134,16 @@ pub mod testing;
 pub use chain::{AuditChain, ChainError, ValidationReport};
 pub use context::TraceContext;
 pub use event::{EventPayload, OrbitEvent};
+pub use head::{ChainHead, ChainHeadStore, FileChainHeadStore};
 pub use logger::{LoggerError, UnifiedLogger};
+pub use orbit_core_audit::Redactor;
 pub use signer::{AuditSigner, SignerError};
 
 // Re-export bridge layer
 pub use bridge::AuditBridgeLayer;
 
 // Re-export testing utilities
-pub use testing::{load_events_from_file, validate_audit_file, EventCapture};
+pub use testing::{audit_segments, load_events_from_file, validate_audit_file, EventCapture};
 
 /// Prelude module for convenient imports
 ///
@@ -3,7 +3,10 @@
 //! This module provides Prometheus metrics for monitoring Orbit operations.
 //! Metrics are derived from the audit event stream.
 
-use prometheus::{Counter, CounterVec, HistogramOpts, HistogramVec, Opts, Registry};
+use prometheus::{
+    exponential_buckets, Counter, CounterVec, Histogram, HistogramOpts, HistogramVec, Opts,
+    Registry,
+};
 use std::sync::OnceLock;
 
 /// Global Prometheus registry for Orbit metrics
@@ -50,6 +53,19 @@ pub static TRANSFER_BYTES_TOTAL: OnceLock<CounterVec> = OnceLock::new();
 /// Buckets: 1s, 10s, 30s, 1m, 5m, 15m, 30m, 1h
 pub static JOB_DURATION_SECONDS: OnceLock<HistogramVec> = OnceLock::new();
 
+/// Chunk size histogram
+///
+/// Records the length of every chunk produced by the transfer and manifest
+/// chunking paths, for tuning CDC sizes and tier selection.
+/// Buckets: powers of two from 1 KiB to 64 MiB, covering fixed manifest
+/// chunks up to the largest tier's maximum CDC chunk
+pub static CHUNK_SIZE_BYTES: OnceLock<Histogram> = OnceLock::new();
+
+/// Chunk counter
+///
+/// Total chunks produced by the transfer and manifest chunking paths.
+pub static CHUNKS_TOTAL: OnceLock<Counter> = OnceLock::new();
+
 /// Register all metrics with the registry
 fn register_metrics(registry: &Registry) {
     // Transfer retries
@@ -114,6 +130,31 @@ fn register_metrics(registry: &Registry) {
         .register(Box::new(duration.clone()))
         .expect("Failed to register job_duration");
     JOB_DURATION_SECONDS.set(duration).ok();
+
+    // Chunk sizes
+    let chunk_sizes = Histogram::with_opts(
+        HistogramOpts::new(
+            "orbit_chunk_size_bytes",
+            "Size of chunks produced by chunking",
+        )
+        .buckets(exponential_buckets(1024.0, 2.0, 17).expect("valid chunk size buckets")),
+    )
+    .expect("Failed to create chunk_size metric");
+    registry
+        .register(Box::new(chunk_sizes.clone()))
+        .expect("Failed to register chunk_size");
+    CHUNK_SIZE_BYTES.set(chunk_sizes).ok();
+
+    // Chunk count
+    let chunks = Counter::with_opts(Opts::new(
+        "orbit_chunks_total",
+        "Total number of chunks produced by chunking",
+    ))
+    .expect("Failed to create chunks_total metric");
+    registry
+        .register(Box::new(chunks.clone()))
+        .expect("Failed to register chunks_total");
+    CHUNKS_TOTAL.set(chunks).ok();
 }
 
 /// Increment transfer retry counter
@@ -157,6 +198,16 @@ pub fn record_job_duration(protocol: &str, status: &str, duration_secs: f64) {
     }
 }
 
+/// Record one chunk of `length` bytes
+pub fn record_chunk(length: usize) {
+    if let Some(histogram) = CHUNK_SIZE_BYTES.get() {
+        histogram.observe(length as f64);
+    }
+    if let Some(counter) = CHUNKS_TOTAL.get() {
+        counter.inc();
+    }
+}
+
 /// Get metrics in Prometheus text format
 pub fn metrics_text() -> String {
     use prometheus::{Encoder, TextEncoder};
@@ -225,6 +276,16 @@ mod tests {
         assert!(metrics_output.contains("orbit_job_duration_seconds"));
     }
 
+    #[test]
+    fn test_record_chunk() {
+        let _ = registry();
+        record_chunk(64 * 1024);
+
+        let metrics_output = metrics_text();
+        assert!(metrics_output.contains("orbit_chunk_size_bytes_bucket"));
+        assert!(metrics_output.contains("orbit_chunks_total"));
+    }
+
     #[test]
     fn test_metrics_text_format() {
         inc_transfer_retry("local");
@@ -5,9 +5,11 @@
 
 use crate::chain::{AuditChain, ValidationReport};
 use crate::event::OrbitEvent;
+use crate::head::ChainHead;
 use crate::logger::UnifiedLogger;
 use crate::signer::AuditSigner;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 /// Event capture utility for testing
@@ -171,7 +173,8 @@ impl CapturingLogger {
 
 /// Load events from a JSON Lines audit log file
 ///
-/// This is useful for forensic analysis of existing audit logs.
+/// This is useful for forensic analysis of existing audit logs. A resume
+/// line at the top of the segment is skipped.
 pub fn load_events_from_file(path: &Path) -> std::io::Result<Vec<OrbitEvent>> {
     let contents = std::fs::read_to_string(path)?;
     let mut events = Vec::new();
@@ -181,6 +184,10 @@ pub fn load_events_from_file(path: &Path) -> std::io::Result<Vec<OrbitEvent>> {
             continue;
         }
 
+        if events.is_empty() && ChainHead::is_resume_line(line) {
+            continue;
+        }
+
         let event: OrbitEvent = serde_json::from_str(line).map_err(|e| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
@@ -194,13 +201,104 @@ pub fn load_events_from_file(path: &Path) -> std::io::Result<Vec<OrbitEvent>> {
     Ok(events)
 }
 
-/// Validate an audit log file
+/// Validate an audit log file, or a directory of its rotated segments
+///
+/// Segments (see [`audit_segments`]) are read oldest first, with `.zst`
+/// rotations decompressed, and checked as one chain. The chain starts from
+/// the first segment's resume line if it has one. Every resume line must
+/// carry a valid HMAC under `signer`.
 ///
-/// Returns a ValidationReport with details about the chain integrity.
+/// Integrity problems are `InvalidData` errors: a broken chain wraps the
+/// [`ChainError`](crate::ChainError), and a line that is not an event gets
+/// a message naming its segment and line. Other error kinds mean the log
+/// could not be read.
 pub fn validate_audit_file(path: &Path, signer: &AuditSigner) -> std::io::Result<ValidationReport> {
-    let events = load_events_from_file(path)?;
-    AuditChain::verify_chain(&events, signer)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    let invalid = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+    let segments = audit_segments(path)?;
+    if segments.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no audit log segments found in {}", path.display()),
+        ));
+    }
+
+    let mut start = None;
+    let mut events: Vec<OrbitEvent> = Vec::new();
+    for segment in &segments {
+        let contents = read_segment(segment)?;
+        for (line_num, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A resume line only sets the starting head when nothing before
+            // it was read; later segments are already linked by the events
+            if let Some(head) = ChainHead::from_resume_line(line, signer) {
+                let head = head.map_err(invalid)?;
+                if events.is_empty() && start.is_none() {
+                    start = Some(head);
+                }
+                continue;
+            }
+            let event = serde_json::from_str(line).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{} line {}: {}", segment.display(), line_num + 1, e),
+                )
+            })?;
+            events.push(event);
+        }
+    }
+
+    let start = start.unwrap_or_else(ChainHead::genesis);
+    AuditChain::verify_chain_from(&events, signer, &start).map_err(invalid)
+}
+
+/// Segments of the audit log at `path`, oldest first
+///
+/// A file is a single segment. A directory holds one log's rotations:
+/// `<name>.N` (optionally `.zst`) are older the larger N is, and the
+/// unnumbered live file is newest. Chain head files are skipped.
+pub fn audit_segments(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut segments = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.contains(".jsonl") && !name.ends_with(".head") && !name.ends_with(".head.tmp") {
+                segments.push((rotation_index(&name), entry.path()));
+            }
+        }
+    }
+    // Live file (no index) sorts last
+    segments.sort_by(|a, b| match (a.0, b.0) {
+        (Some(x), Some(y)) => y.cmp(&x),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.1.cmp(&b.1),
+    });
+    Ok(segments.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Rotation number of a segment name such as `audit.jsonl.3.zst`
+fn rotation_index(name: &str) -> Option<u32> {
+    let name = name.strip_suffix(".zst").unwrap_or(name);
+    name.rsplit_once('.')?.1.parse().ok()
+}
+
+/// Read a segment, decompressing `.zst` rotations
+fn read_segment(path: &Path) -> std::io::Result<String> {
+    let file = std::fs::File::open(path)?;
+    let mut contents = String::new();
+    if path.extension().is_some_and(|ext| ext == "zst") {
+        zstd::stream::read::Decoder::new(file)?.read_to_string(&mut contents)?;
+    } else {
+        std::io::BufReader::new(file).read_to_string(&mut contents)?;
+    }
+    Ok(contents)
 }
 
 #[cfg(test)]
@@ -346,4 +444,36 @@ mod tests {
         capture.clear();
         assert_eq!(capture.event_count(), 0);
     }
+
+    #[test]
+    fn test_validate_rotated_segments_as_one_chain() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let live = dir.path().join("audit.jsonl");
+        let signer = AuditSigner::from_bytes(b"rotation-secret");
+        let ctx = TraceContext::new_root();
+
+        let logger = UnifiedLogger::new(Some(&live), signer.clone()).unwrap();
+        logger.emit_job_start(&ctx, 1, 100, "s3").unwrap();
+        drop(logger);
+        let rotated = std::fs::read(&live).unwrap();
+        std::fs::write(
+            dir.path().join("audit.jsonl.1.zst"),
+            zstd::encode_all(rotated.as_slice(), 3).unwrap(),
+        )
+        .unwrap();
+        std::fs::remove_file(&live).unwrap();
+
+        let logger = UnifiedLogger::new(Some(&live), signer.clone()).unwrap();
+        logger.emit_job_complete(&ctx, 10, "digest").unwrap();
+        logger.flush().unwrap();
+
+        // audit.jsonl.head sits beside the segments and is not one of them
+        let segments = audit_segments(dir.path()).unwrap();
+        assert_eq!(
+            segments,
+            vec![dir.path().join("audit.jsonl.1.zst"), live.clone()]
+        );
+        let report = validate_audit_file(dir.path(), &signer).unwrap();
+        assert_eq!(report.valid_events, 2);
+    }
 }
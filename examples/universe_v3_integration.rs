@@ -47,6 +47,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         min_size: 8 * 1024,   // 8 KB
         avg_size: 64 * 1024,  // 64 KB
         max_size: 256 * 1024, // 256 KB
+        ..ChunkConfig::default()
     };
 
     let stream = ChunkStream::new(reader, config.clone());
@@ -0,0 +1,137 @@
+//! Circuit breaker shared by the S3 retry logic and backend failover
+//!
+//! A breaker counts consecutive failures against one service. Once the
+//! threshold is reached it opens and callers fail fast. After the timeout
+//! it lets requests through again (half-open); the next success closes it
+//! and the next failure opens it again.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Circuit is closed, requests flow normally
+    Closed,
+
+    /// Circuit is open, requests fail fast
+    Open,
+
+    /// Circuit is half-open, testing if service recovered
+    HalfOpen,
+}
+
+/// Circuit breaker for preventing cascading failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    state: Arc<RwLock<CircuitState>>,
+    failure_count: Arc<RwLock<u32>>,
+    last_failure: Arc<RwLock<Option<Instant>>>,
+    threshold: u32,
+    timeout: Duration,
+}
+
+impl CircuitBreaker {
+    /// Create a new circuit breaker
+    pub fn new(threshold: u32, timeout: Duration) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(CircuitState::Closed)),
+            failure_count: Arc::new(RwLock::new(0)),
+            last_failure: Arc::new(RwLock::new(None)),
+            threshold,
+            timeout,
+        }
+    }
+
+    /// Check if request should be allowed
+    pub async fn allow_request(&self) -> bool {
+        let state = *self.state.read().await;
+
+        match state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                // Check if timeout elapsed
+                if let Some(last) = *self.last_failure.read().await {
+                    if last.elapsed() >= self.timeout {
+                        // Transition to half-open
+                        *self.state.write().await = CircuitState::HalfOpen;
+                        true
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            CircuitState::HalfOpen => true,
+        }
+    }
+
+    /// Record a successful request
+    pub async fn record_success(&self) {
+        *self.failure_count.write().await = 0;
+        *self.state.write().await = CircuitState::Closed;
+    }
+
+    /// Record a failed request
+    pub async fn record_failure(&self) {
+        let mut count = self.failure_count.write().await;
+        *count += 1;
+        *self.last_failure.write().await = Some(Instant::now());
+
+        if *count >= self.threshold {
+            *self.state.write().await = CircuitState::Open;
+        }
+    }
+
+    /// Get current state
+    pub async fn state(&self) -> CircuitState {
+        *self.state.read().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::sleep;
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens() {
+        let cb = CircuitBreaker::new(3, Duration::from_secs(1));
+
+        // Should allow requests initially
+        assert!(cb.allow_request().await);
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        // Record failures
+        for _ in 0..3 {
+            cb.record_failure().await;
+        }
+
+        // Circuit should be open
+        assert_eq!(cb.state().await, CircuitState::Open);
+        assert!(!cb.allow_request().await);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_recovery() {
+        let cb = CircuitBreaker::new(2, Duration::from_millis(50));
+
+        // Trigger circuit breaker
+        cb.record_failure().await;
+        cb.record_failure().await;
+        assert_eq!(cb.state().await, CircuitState::Open);
+
+        // Wait for timeout
+        sleep(Duration::from_millis(60)).await;
+
+        // Should transition to half-open
+        assert!(cb.allow_request().await);
+        assert_eq!(cb.state().await, CircuitState::HalfOpen);
+
+        // Success should close it
+        cb.record_success().await;
+        assert_eq!(cb.state().await, CircuitState::Closed);
+    }
+}
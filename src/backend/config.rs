@@ -35,6 +35,9 @@ pub struct S3BackendConfig {
     pub region: Option<String>,
     /// Custom endpoint URL (for MinIO, LocalStack, etc.)
     pub endpoint: Option<String>,
+    /// Further endpoints serving the same bucket, tried in order when the
+    /// ones before them are unhealthy (each has its own circuit breaker)
+    pub fallback_endpoints: Vec<String>,
     /// AWS access key (falls back to environment / credential chain if unset)
     pub access_key: Option<String>,
     /// AWS secret key (falls back to environment / credential chain if unset)
@@ -300,6 +303,7 @@ impl BackendConfig {
 /// S3 URIs:
 /// - `region=us-east-1` - AWS region
 /// - `endpoint=http://localhost:9000` - Custom endpoint (MinIO, etc.)
+/// - `fallback_endpoints=https://a,https://b` - Replica endpoints to fail over to
 /// - `access_key=KEY` - AWS access key
 /// - `secret_key=SECRET` - AWS secret key
 /// - `path_style=true` - Force path-style addressing
@@ -440,6 +444,10 @@ pub fn parse_uri(uri: &str) -> BackendResult<(BackendConfig, PathBuf)> {
                 s3_config.endpoint = Some(endpoint.clone());
             }
 
+            if let Some(endpoints) = query_pairs.get("fallback_endpoints") {
+                s3_config.fallback_endpoints = split_endpoints(endpoints);
+            }
+
             if let Some(access_key) = query_pairs.get("access_key") {
                 s3_config.access_key = Some(access_key.clone());
             }
@@ -670,7 +678,8 @@ pub fn parse_uri(uri: &str) -> BackendResult<(BackendConfig, PathBuf)> {
 /// Looks for variables like:
 /// - `ORBIT_BACKEND_TYPE` - Backend type (local, ssh, s3, smb, azure)
 /// - `ORBIT_SSH_HOST`, `ORBIT_SSH_USER`, `ORBIT_SSH_KEY` - SSH config
-/// - `ORBIT_S3_BUCKET`, `ORBIT_S3_REGION`, `ORBIT_S3_ENDPOINT` - S3 config
+/// - `ORBIT_S3_BUCKET`, `ORBIT_S3_REGION`, `ORBIT_S3_ENDPOINT`,
+///   `ORBIT_S3_FALLBACK_ENDPOINTS` - S3 config
 /// - `ORBIT_SMB_HOST`, `ORBIT_SMB_SHARE`, `ORBIT_SMB_USER`, `ORBIT_SMB_PASSWORD` - SMB config
 /// - `ORBIT_AZURE_CONTAINER`, `AZURE_STORAGE_CONNECTION_STRING`, `AZURE_STORAGE_ACCOUNT`, `AZURE_STORAGE_KEY` - Azure config
 #[allow(dead_code)]
@@ -740,6 +749,10 @@ pub fn from_env() -> BackendResult<BackendConfig> {
                 s3_config.endpoint = Some(endpoint);
             }
 
+            if let Ok(endpoints) = std::env::var("ORBIT_S3_FALLBACK_ENDPOINTS") {
+                s3_config.fallback_endpoints = split_endpoints(&endpoints);
+            }
+
             if let Ok(access_key) = std::env::var("AWS_ACCESS_KEY_ID") {
                 s3_config.access_key = Some(access_key);
             }
@@ -874,6 +887,16 @@ pub fn from_env() -> BackendResult<BackendConfig> {
     }
 }
 
+/// Split a comma-separated endpoint list, dropping empty entries
+#[cfg(feature = "s3-native")]
+fn split_endpoints(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|endpoint| !endpoint.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -913,6 +936,24 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "s3-native")]
+    fn test_parse_s3_uri_fallback_endpoints() {
+        let (config, _path) = parse_uri(
+            "s3://my-bucket/data?endpoint=https://eu.example.com\
+             &fallback_endpoints=https://us.example.com,,https://ap.example.com",
+        )
+        .unwrap();
+        let BackendConfig::S3 { config, .. } = config else {
+            panic!("Expected S3 config");
+        };
+        assert_eq!(config.endpoint.as_deref(), Some("https://eu.example.com"));
+        assert_eq!(
+            config.fallback_endpoints,
+            vec!["https://us.example.com", "https://ap.example.com"]
+        );
+    }
+
     #[test]
     fn test_backend_type() {
         let config = BackendConfig::local();
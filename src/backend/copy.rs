@@ -0,0 +1,252 @@
+//! Bounded streaming copy between backends
+//!
+//! [`copy_between`] connects one backend's [`read`](super::Backend::read)
+//! stream to another's [`write`](super::Backend::write) through a bounded
+//! channel. When the sink is slower than the source (local disk into a
+//! throttled bucket), the reader waits for the channel to drain instead of
+//! buffering the rest of the file, so memory stays at
+//! [`COPY_CHANNEL_DEPTH`] source chunks however large the file is.
+
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures::StreamExt;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+use super::error::{BackendError, BackendResult};
+use super::types::WriteOptions;
+use super::Backend;
+use crate::core::bandwidth::BandwidthLimiter;
+
+/// Source chunks that may wait for the sink before the reader is paused
+pub const COPY_CHANNEL_DEPTH: usize = 4;
+
+/// Copy `src` on `source` to `dest` on `sink`, returning the bytes written
+///
+/// The source is read only as fast as the sink accepts data, and no faster
+/// than `limiter` allows when one is given.
+pub async fn copy_between<S, D>(
+    source: &S,
+    src: &Path,
+    sink: &D,
+    dest: &Path,
+    options: WriteOptions,
+    limiter: Option<&BandwidthLimiter>,
+) -> BackendResult<u64>
+where
+    S: Backend + ?Sized,
+    D: Backend + ?Sized,
+{
+    let size_hint = source.stat(src).await.ok().map(|m| m.size);
+    let mut stream = source.read(src).await?;
+    let limiter = limiter.filter(|l| l.is_enabled()).cloned();
+    let (tx, rx) = mpsc::channel::<io::Result<Bytes>>(COPY_CHANNEL_DEPTH);
+
+    let pump = async move {
+        while let Some(chunk) = stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    let err = BackendError::from_io_with_context(
+                        io::Error::new(e.kind(), e.to_string()),
+                        src,
+                        source.backend_name(),
+                    );
+                    // Fail the write too, so it does not commit a short object
+                    let _ = tx.send(Err(e)).await;
+                    return Err(err);
+                }
+            };
+
+            if let Some(limiter) = &limiter {
+                let limiter = limiter.clone();
+                let len = chunk.len() as u64;
+                tokio::task::spawn_blocking(move || limiter.wait_for_capacity(len))
+                    .await
+                    .map_err(|e| BackendError::Other {
+                        backend: sink.backend_name().to_string(),
+                        message: format!("Bandwidth limiter task failed: {}", e),
+                    })?;
+            }
+
+            // A closed channel means the sink stopped; its result says why
+            if tx.send(Ok(chunk)).await.is_err() {
+                break;
+            }
+        }
+        Ok(())
+    };
+
+    let reader = Box::new(ChannelReader {
+        rx,
+        current: Bytes::new(),
+    });
+    let (pumped, written) = futures::join!(pump, sink.write(dest, reader, size_hint, options));
+    let written = written?;
+    pumped?;
+    Ok(written)
+}
+
+/// `AsyncRead` over chunks received from the copy pump
+struct ChannelReader {
+    rx: mpsc::Receiver<io::Result<Bytes>>,
+    current: Bytes,
+}
+
+impl AsyncRead for ChannelReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.current.is_empty() {
+            match self.rx.poll_recv(cx) {
+                Poll::Ready(Some(Ok(chunk))) => self.current = chunk,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                // Sender dropped: end of the source stream
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let n = self.current.len().min(buf.remaining());
+        buf.put_slice(&self.current[..n]);
+        self.current.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::types::{DirEntry, ListOptions, ListStream, Metadata, ReadStream};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    const CHUNK: usize = 64 * 1024;
+    const CHUNKS: u64 = 256;
+
+    /// Instantly produces `CHUNKS` chunks and drains writes slowly, tracking
+    /// how many bytes were read but not yet written
+    #[derive(Default)]
+    struct FastToSlow {
+        produced: Arc<AtomicU64>,
+        consumed: Arc<AtomicU64>,
+        peak_in_flight: Arc<AtomicU64>,
+    }
+
+    #[async_trait]
+    impl Backend for FastToSlow {
+        async fn stat(&self, _path: &Path) -> BackendResult<Metadata> {
+            Ok(Metadata::file(CHUNK as u64 * CHUNKS))
+        }
+
+        async fn list(&self, _path: &Path, _options: ListOptions) -> BackendResult<ListStream> {
+            Ok(Box::pin(futures::stream::empty::<BackendResult<DirEntry>>()))
+        }
+
+        async fn read(&self, _path: &Path) -> BackendResult<ReadStream> {
+            let produced = self.produced.clone();
+            let consumed = self.consumed.clone();
+            let peak = self.peak_in_flight.clone();
+            Ok(Box::pin(futures::stream::iter(0..CHUNKS).map(move |_| {
+                let total = produced.fetch_add(CHUNK as u64, Ordering::SeqCst) + CHUNK as u64;
+                peak.fetch_max(total - consumed.load(Ordering::SeqCst), Ordering::SeqCst);
+                Ok(Bytes::from(vec![0xA5u8; CHUNK]))
+            })))
+        }
+
+        async fn write(
+            &self,
+            _path: &Path,
+            mut reader: Box<dyn AsyncRead + Unpin + Send>,
+            _size_hint: Option<u64>,
+            _options: WriteOptions,
+        ) -> BackendResult<u64> {
+            let mut buf = vec![0u8; CHUNK];
+            let mut total = 0u64;
+            loop {
+                let n = reader
+                    .read(&mut buf)
+                    .await
+                    .map_err(|e| BackendError::Other {
+                        backend: self.backend_name().to_string(),
+                        message: e.to_string(),
+                    })?;
+                if n == 0 {
+                    return Ok(total);
+                }
+                total += n as u64;
+                self.consumed.fetch_add(n as u64, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_micros(200)).await;
+            }
+        }
+
+        async fn delete(&self, _path: &Path, _recursive: bool) -> BackendResult<()> {
+            Ok(())
+        }
+
+        async fn mkdir(&self, _path: &Path, _recursive: bool) -> BackendResult<()> {
+            Ok(())
+        }
+
+        async fn rename(&self, _src: &Path, _dest: &Path) -> BackendResult<()> {
+            Ok(())
+        }
+
+        fn backend_name(&self) -> &str {
+            "fast-to-slow"
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_sink_keeps_in_flight_bytes_bounded() {
+        let backend = FastToSlow::default();
+        let written = backend
+            .copy(Path::new("src"), Path::new("dst"), WriteOptions::default())
+            .await
+            .unwrap();
+
+        let file_size = CHUNK as u64 * CHUNKS;
+        assert_eq!(written, file_size);
+
+        // Channel slots, the chunk being sent and the chunk being drained
+        let bound = (COPY_CHANNEL_DEPTH as u64 + 2) * CHUNK as u64;
+        let peak = backend.peak_in_flight.load(Ordering::SeqCst);
+        assert!(
+            peak <= bound,
+            "peak {} bytes in flight exceeds {} (file is {})",
+            peak,
+            bound,
+            file_size
+        );
+    }
+
+    #[tokio::test]
+    async fn limiter_paces_the_source() {
+        let backend = FastToSlow::default();
+        // The first bucket's burst is free, so only time the remainder
+        let limiter = BandwidthLimiter::new(64 * 1024 * 1024);
+        let started = std::time::Instant::now();
+        let written = copy_between(
+            &backend,
+            Path::new("src"),
+            &backend,
+            Path::new("dst"),
+            WriteOptions::default(),
+            Some(&limiter),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(written, CHUNK as u64 * CHUNKS);
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+}
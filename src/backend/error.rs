@@ -50,6 +50,16 @@ pub enum BackendError {
     /// Resource already exists (e.g., during exclusive create)
     AlreadyExists { path: PathBuf },
 
+    /// A conditional write's precondition (If-Match / If-None-Match) failed
+    PreconditionFailed { path: PathBuf, message: String },
+
+    /// Downloaded content does not match its expected checksum
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
     /// Directory is not empty (e.g., during delete)
     DirectoryNotEmpty { path: PathBuf },
 
@@ -119,6 +129,8 @@ impl BackendError {
             BackendError::Unsupported { .. } => false,
             BackendError::InvalidPath { .. } => false,
             BackendError::AlreadyExists { .. } => false,
+            BackendError::PreconditionFailed { .. } => false,
+            BackendError::ChecksumMismatch { .. } => false,
             BackendError::DirectoryNotEmpty { .. } => false,
             BackendError::QuotaExceeded { .. } => false,
             BackendError::Serialization { .. } => false,
@@ -205,6 +217,22 @@ impl fmt::Display for BackendError {
             BackendError::AlreadyExists { path } => {
                 write!(f, "Path already exists: {}", path.display())
             }
+            BackendError::PreconditionFailed { path, message } => {
+                write!(f, "Precondition failed for {}: {}", path.display(), message)
+            }
+            BackendError::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Checksum mismatch for {}: expected {}, got {}",
+                    path.display(),
+                    expected,
+                    actual
+                )
+            }
             BackendError::DirectoryNotEmpty { path } => {
                 write!(f, "Directory not empty: {}", path.display())
             }
@@ -312,6 +340,19 @@ mod tests {
         assert!(backend_err.is_not_found());
     }
 
+    #[test]
+    fn test_precondition_failed_not_retriable() {
+        let err = BackendError::PreconditionFailed {
+            path: PathBuf::from("bucket/key"),
+            message: "ETag mismatch".to_string(),
+        };
+        assert!(!err.is_retriable());
+        assert_eq!(
+            err.to_string(),
+            "Precondition failed for bucket/key: ETag mismatch"
+        );
+    }
+
     #[test]
     fn test_backend_name() {
         let err = BackendError::ConnectionFailed {
@@ -0,0 +1,287 @@
+//! Endpoint failover for S3 replicas
+//!
+//! [`FailoverStore`] wraps one object store per endpoint of the same bucket
+//! (regional endpoints, or replicas behind different hosts) and sends each
+//! request to the first endpoint whose [`CircuitBreaker`] is closed. An
+//! endpoint that keeps failing with transport or server errors opens its
+//! breaker and is skipped until the breaker's timeout elapses; when every
+//! breaker is open, requests fail immediately instead of waiting on dead
+//! endpoints.
+//!
+//! Only errors that say nothing about the object (`Generic`, `JoinError`)
+//! count against an endpoint. `NotFound`, precondition failures and the
+//! like are answers from a healthy endpoint and are returned as-is.
+//!
+//! Multipart uploads stay on the endpoint that created them: upload IDs are
+//! prefixed with the endpoint's index, so parts, completion and abort are
+//! sent to the same endpoint (also after a restart, provided the endpoint
+//! list keeps its order).
+
+use super::circuit::CircuitBreaker;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use object_store::multipart::{MultipartStore, PartId};
+use object_store::path::Path;
+use object_store::{
+    Error, GetOptions, GetResult, ListResult, MultipartId, MultipartUpload, ObjectMeta,
+    ObjectStore, PutMultipartOpts, PutOptions, PutPayload, PutResult, Result,
+};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Consecutive failures that open an endpoint's breaker
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+/// How long an open breaker skips its endpoint before a trial request
+pub const DEFAULT_BREAKER_TIMEOUT: Duration = Duration::from_secs(30);
+
+const STORE: &str = "S3Failover";
+
+/// An object store that also supports part-level multipart uploads
+pub(crate) trait EndpointStore: ObjectStore + MultipartStore {}
+
+impl<T: ObjectStore + MultipartStore> EndpointStore for T {}
+
+#[derive(Debug)]
+struct Endpoint {
+    name: String,
+    store: Arc<dyn EndpointStore>,
+    breaker: CircuitBreaker,
+}
+
+impl Endpoint {
+    /// Feed the outcome of a request into the breaker
+    async fn record(&self, error: Option<&Error>) {
+        match error {
+            Some(e) if is_endpoint_failure(e) => {
+                tracing::warn!(endpoint = %self.name, "S3 endpoint request failed: {}", e);
+                self.breaker.record_failure().await;
+            }
+            _ => self.breaker.record_success().await,
+        }
+    }
+}
+
+/// Routes requests across endpoints of one bucket, skipping unhealthy ones
+#[derive(Debug)]
+pub(crate) struct FailoverStore {
+    endpoints: Vec<Endpoint>,
+}
+
+impl FailoverStore {
+    /// Wrap `(name, store)` pairs, tried in the given order
+    pub(crate) fn new(
+        stores: Vec<(String, Arc<dyn EndpointStore>)>,
+        failure_threshold: u32,
+        breaker_timeout: Duration,
+    ) -> Self {
+        let endpoints = stores
+            .into_iter()
+            .map(|(name, store)| Endpoint {
+                name,
+                store,
+                breaker: CircuitBreaker::new(failure_threshold, breaker_timeout),
+            })
+            .collect();
+        Self { endpoints }
+    }
+
+    /// Breaker state of each endpoint, in order
+    #[cfg(test)]
+    pub(crate) async fn endpoint_states(&self) -> Vec<(String, super::CircuitState)> {
+        let mut states = Vec::with_capacity(self.endpoints.len());
+        for endpoint in &self.endpoints {
+            states.push((endpoint.name.clone(), endpoint.breaker.state().await));
+        }
+        states
+    }
+
+    /// Run `op` on the first healthy endpoint, failing over on endpoint
+    /// errors; returns the index of the endpoint that answered
+    async fn route<'a, T>(
+        &'a self,
+        op: impl Fn(&'a dyn EndpointStore) -> BoxFuture<'a, Result<T>>,
+    ) -> Result<(usize, T)> {
+        let mut last_error = None;
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            if !endpoint.breaker.allow_request().await {
+                continue;
+            }
+            let result = op(endpoint.store.as_ref()).await;
+            endpoint.record(result.as_ref().err()).await;
+            match result {
+                Err(e) if is_endpoint_failure(&e) => last_error = Some(e),
+                result => return result.map(|value| (index, value)),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| self.unavailable()))
+    }
+
+    /// Run `op` on the endpoint that owns an upload ID from `create_multipart`
+    async fn on_upload<'a, T>(
+        &'a self,
+        id: &str,
+        op: impl FnOnce(&'a dyn EndpointStore, MultipartId) -> BoxFuture<'a, Result<T>>,
+    ) -> Result<T> {
+        let (index, upload_id) = id
+            .split_once(':')
+            .and_then(|(index, upload_id)| Some((index.parse::<usize>().ok()?, upload_id)))
+            .filter(|(index, _)| *index < self.endpoints.len())
+            .ok_or_else(|| Error::Generic {
+                store: STORE,
+                source: format!("Multipart upload ID {} does not name an endpoint", id).into(),
+            })?;
+        let endpoint = &self.endpoints[index];
+        let result = op(endpoint.store.as_ref(), upload_id.to_string()).await;
+        endpoint.record(result.as_ref().err()).await;
+        result
+    }
+
+    fn unavailable(&self) -> Error {
+        Error::Generic {
+            store: STORE,
+            source: format!(
+                "All {} endpoints are unavailable (circuit breakers open)",
+                self.endpoints.len()
+            )
+            .into(),
+        }
+    }
+}
+
+/// Whether an error reflects on the endpoint rather than the request
+fn is_endpoint_failure(e: &Error) -> bool {
+    matches!(e, Error::Generic { .. } | Error::JoinError { .. })
+}
+
+impl fmt::Display for FailoverStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.endpoints.iter().map(|e| e.name.as_str()).collect();
+        write!(f, "{}({})", STORE, names.join(", "))
+    }
+}
+
+#[async_trait]
+impl ObjectStore for FailoverStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        self.route(|store| store.put_opts(location, payload.clone(), opts.clone()))
+            .await
+            .map(|(_, result)| result)
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        self.route(|store| store.put_multipart_opts(location, opts.clone()))
+            .await
+            .map(|(_, upload)| upload)
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        self.route(|store| store.get_opts(location, options.clone()))
+            .await
+            .map(|(_, result)| result)
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.route(|store| store.delete(location))
+            .await
+            .map(|(_, ())| ())
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        // Fail over only until the first entry arrives; errors in later
+        // pages are returned to the caller
+        let prefix = prefix.cloned();
+        stream::once(async move {
+            let mut last_error = None;
+            for endpoint in &self.endpoints {
+                if !endpoint.breaker.allow_request().await {
+                    continue;
+                }
+                let mut entries = endpoint.store.list(prefix.as_ref());
+                match entries.next().await {
+                    Some(Err(e)) if is_endpoint_failure(&e) => {
+                        endpoint.record(Some(&e)).await;
+                        last_error = Some(e);
+                    }
+                    first => {
+                        endpoint.breaker.record_success().await;
+                        return stream::iter(first).chain(entries).boxed();
+                    }
+                }
+            }
+            let error = last_error.unwrap_or_else(|| self.unavailable());
+            stream::iter([Err(error)]).boxed()
+        })
+        .flatten()
+        .boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.route(|store| store.list_with_delimiter(prefix))
+            .await
+            .map(|(_, result)| result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.route(|store| store.copy(from, to))
+            .await
+            .map(|(_, ())| ())
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.route(|store| store.copy_if_not_exists(from, to))
+            .await
+            .map(|(_, ())| ())
+    }
+}
+
+#[async_trait]
+impl MultipartStore for FailoverStore {
+    async fn create_multipart(&self, path: &Path) -> Result<MultipartId> {
+        self.route(|store| store.create_multipart(path))
+            .await
+            .map(|(index, id)| format!("{}:{}", index, id))
+    }
+
+    async fn put_part(
+        &self,
+        path: &Path,
+        id: &MultipartId,
+        part_idx: usize,
+        data: PutPayload,
+    ) -> Result<PartId> {
+        self.on_upload(id, |store, id| {
+            Box::pin(async move { store.put_part(path, &id, part_idx, data).await })
+        })
+        .await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &Path,
+        id: &MultipartId,
+        parts: Vec<PartId>,
+    ) -> Result<PutResult> {
+        self.on_upload(id, |store, id| {
+            Box::pin(async move { store.complete_multipart(path, &id, parts).await })
+        })
+        .await
+    }
+
+    async fn abort_multipart(&self, path: &Path, id: &MultipartId) -> Result<()> {
+        self.on_upload(id, |store, id| {
+            Box::pin(async move { store.abort_multipart(path, &id).await })
+        })
+        .await
+    }
+}
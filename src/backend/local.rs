@@ -3,15 +3,43 @@
 //! Provides async access to the local filesystem using Tokio's async I/O.
 
 use super::error::{BackendError, BackendResult};
-use super::types::{DirEntry, ListOptions, ListStream, Metadata, ReadStream, WriteOptions};
+use super::types::{
+    guess_content_type, DirEntry, ListOptions, ListStream, Metadata, ReadStream, WriteOptions,
+};
 use super::Backend;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 
+/// xattr that [`LocalBackend::write`](Backend::write) stores
+/// `WriteOptions::content_type` in (the freedesktop shared-mime-info name)
+pub const CONTENT_TYPE_XATTR: &str = "user.mime_type";
+
+/// Prefix of the xattrs that hold `WriteOptions::metadata` entries
+pub const METADATA_XATTR_PREFIX: &str = "user.orbit.meta.";
+
+/// xattrs recording a write's content type and custom metadata
+fn write_options_xattrs(options: &WriteOptions) -> HashMap<String, Vec<u8>> {
+    let mut xattrs = HashMap::new();
+    if let Some(content_type) = &options.content_type {
+        xattrs.insert(
+            CONTENT_TYPE_XATTR.to_string(),
+            content_type.as_bytes().to_vec(),
+        );
+    }
+    for (key, value) in options.metadata.iter().flatten() {
+        xattrs.insert(
+            format!("{}{}", METADATA_XATTR_PREFIX, key),
+            value.as_bytes().to_vec(),
+        );
+    }
+    xattrs
+}
+
 /// Local filesystem backend
 ///
 /// This backend provides async access to the local filesystem using Tokio.
@@ -117,19 +145,7 @@ impl LocalBackend {
         }
 
         // Guess content type from extension
-        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            metadata.content_type = match ext {
-                "txt" => Some("text/plain".to_string()),
-                "html" | "htm" => Some("text/html".to_string()),
-                "json" => Some("application/json".to_string()),
-                "xml" => Some("application/xml".to_string()),
-                "pdf" => Some("application/pdf".to_string()),
-                "jpg" | "jpeg" => Some("image/jpeg".to_string()),
-                "png" => Some("image/png".to_string()),
-                "gif" => Some("image/gif".to_string()),
-                _ => None,
-            };
-        }
+        metadata.content_type = guess_content_type(path);
 
         metadata
     }
@@ -158,8 +174,9 @@ impl LocalBackend {
                 }
             }
 
-            let resolved = self.resolve_path(path);
-            let mut read_dir = fs::read_dir(&resolved).await.map_err(|e| {
+            // `path` is already resolved; resolving it again would nest a
+            // rooted backend's root inside itself
+            let mut read_dir = fs::read_dir(path).await.map_err(|e| {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     BackendError::NotFound {
                         path: path.to_path_buf(),
@@ -405,6 +422,13 @@ impl Backend for LocalBackend {
                 .map_err(BackendError::from)?;
         }
 
+        // A plain file has nowhere else to keep content type and custom
+        // metadata; without xattr support they are dropped
+        let xattrs = write_options_xattrs(&options);
+        if !xattrs.is_empty() && self.supports("set_xattrs") {
+            self.set_xattrs(path, &xattrs).await?;
+        }
+
         Ok(bytes_written)
     }
 
@@ -804,6 +828,7 @@ impl Backend for LocalBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::{trash_entry_path, DeleteOptions, TRASH_DIR};
     use futures::StreamExt;
     use tempfile::TempDir;
     use tokio::io::AsyncWriteExt;
@@ -826,6 +851,74 @@ mod tests {
         assert_eq!(meta.size, 9);
     }
 
+    #[tokio::test]
+    async fn test_local_backend_keyed_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"test data").await.unwrap();
+
+        let backend = LocalBackend::new();
+        let key = [3u8; 32];
+        let keyed = backend
+            .calculate_keyed_hash(&file_path, &key)
+            .await
+            .unwrap();
+
+        assert_eq!(keyed, *blake3::keyed_hash(&key, b"test data").as_bytes());
+        assert_ne!(keyed, *blake3::hash(b"test data").as_bytes());
+
+        let missing = backend
+            .calculate_keyed_hash(&temp_dir.path().join("missing"), &key)
+            .await;
+        assert!(matches!(missing, Err(BackendError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_write_options_xattrs() {
+        assert!(write_options_xattrs(&WriteOptions::new()).is_empty());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("owner".to_string(), "alice".to_string());
+        let options = WriteOptions::new()
+            .with_content_type("application/json".to_string())
+            .with_metadata(metadata);
+        let xattrs = write_options_xattrs(&options);
+
+        assert_eq!(xattrs.len(), 2);
+        assert_eq!(xattrs[CONTENT_TYPE_XATTR], b"application/json");
+        assert_eq!(xattrs["user.orbit.meta.owner"], b"alice");
+    }
+
+    #[cfg(all(feature = "extended-metadata", unix))]
+    #[tokio::test]
+    async fn test_write_stores_content_type_and_metadata_xattrs() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Skip on filesystems without user xattrs (e.g. some tmpfs setups)
+        let probe = temp_dir.path().join("probe");
+        std::fs::write(&probe, b"").unwrap();
+        if xattr::set(&probe, "user.orbit.probe", b"1").is_err() {
+            return;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), "ci".to_string());
+        let options = WriteOptions::new()
+            .with_content_type("text/csv".to_string())
+            .with_metadata(metadata);
+
+        let backend = LocalBackend::with_root(temp_dir.path());
+        let reader: Box<dyn AsyncRead + Unpin + Send> = Box::new(&b"a,b\n1,2\n"[..]);
+        backend
+            .write(Path::new("report.dat"), reader, None, options)
+            .await
+            .unwrap();
+
+        let xattrs = backend.get_xattrs(Path::new("report.dat")).await.unwrap();
+        assert_eq!(xattrs[CONTENT_TYPE_XATTR], b"text/csv");
+        assert_eq!(xattrs["user.orbit.meta.source"], b"ci");
+    }
+
     #[tokio::test]
     async fn test_local_backend_list() {
         let temp_dir = TempDir::new().unwrap();
@@ -937,4 +1030,117 @@ mod tests {
         // Verify file exists in root
         assert!(temp_dir.path().join("test.txt").exists());
     }
+
+    #[tokio::test]
+    async fn test_local_backend_with_root_list() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs/sub"))
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("docs/a.txt"), b"a")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("docs/sub/b.txt"), b"b")
+            .await
+            .unwrap();
+
+        let backend = LocalBackend::with_root(temp_dir.path());
+        let mut stream = backend
+            .list(Path::new("docs"), ListOptions::recursive())
+            .await
+            .unwrap();
+
+        use futures::StreamExt;
+        let mut names = Vec::new();
+        while let Some(entry) = stream.next().await {
+            names.push(entry.unwrap().path);
+        }
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("sub"),
+                PathBuf::from("sub/b.txt")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recoverable_delete_moves_to_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs"))
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("docs/report.txt"), b"keep me")
+            .await
+            .unwrap();
+
+        let backend = LocalBackend::with_root(temp_dir.path());
+        let trashed = backend
+            .delete_with(
+                Path::new("docs/report.txt"),
+                DeleteOptions::default().with_trash(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!temp_dir.path().join("docs/report.txt").exists());
+        assert!(trashed.starts_with(TRASH_DIR));
+        assert!(trashed.ends_with("docs/report.txt"));
+        assert_eq!(
+            fs::read(temp_dir.path().join(&trashed)).await.unwrap(),
+            b"keep me"
+        );
+
+        // A plain delete is still permanent
+        fs::write(temp_dir.path().join("gone.txt"), b"x")
+            .await
+            .unwrap();
+        let result = backend
+            .delete_with(Path::new("gone.txt"), DeleteOptions::default())
+            .await
+            .unwrap();
+        assert!(result.is_none());
+        assert!(!temp_dir.path().join("gone.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_purge_trash_removes_aged_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = LocalBackend::with_root(temp_dir.path());
+
+        // A group trashed a week ago, and one trashed just now
+        let week_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(7 * 86400);
+        let old = trash_entry_path(Path::new(TRASH_DIR), Path::new("old.txt"), week_ago);
+        fs::create_dir_all(temp_dir.path().join(old.parent().unwrap()))
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join(&old), b"old").await.unwrap();
+        fs::write(temp_dir.path().join("new.txt"), b"new")
+            .await
+            .unwrap();
+        let fresh = backend.move_to_trash(Path::new("new.txt")).await.unwrap();
+
+        let purged = backend
+            .purge_trash(std::time::Duration::from_secs(86400))
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 1);
+        assert!(!temp_dir.path().join(old.parent().unwrap()).exists());
+        assert!(temp_dir.path().join(&fresh).exists());
+
+        // Nothing trashed yet is not an error
+        let empty = TempDir::new().unwrap();
+        let backend = LocalBackend::with_root(empty.path());
+        assert_eq!(
+            backend
+                .purge_trash(std::time::Duration::ZERO)
+                .await
+                .unwrap(),
+            0
+        );
+    }
 }
@@ -69,6 +69,9 @@ mod ssh;
 #[cfg(all(feature = "backend-abstraction", feature = "s3-native"))]
 mod s3;
 
+#[cfg(all(feature = "backend-abstraction", feature = "s3-native"))]
+mod failover;
+
 #[cfg(all(feature = "backend-abstraction", feature = "smb-native"))]
 mod smb;
 
@@ -81,21 +84,36 @@ mod gcs;
 #[cfg(feature = "backend-abstraction")]
 mod config;
 
+#[cfg(feature = "backend-abstraction")]
+mod copy;
+
 #[cfg(feature = "backend-abstraction")]
 mod registry;
 
+#[cfg(feature = "backend-abstraction")]
+mod circuit;
+
 // Re-export main types
 pub use error::{BackendError, BackendResult};
-pub use types::{DirEntry, ListOptions, Metadata, WriteOptions};
+pub use types::{
+    guess_content_type, trash_entry_path, trash_entry_time, trash_group_name, DeleteOptions,
+    DirEntry, ListOptions, Metadata, WriteOptions, TRASH_DIR,
+};
 
 #[cfg(feature = "backend-abstraction")]
-pub use local::LocalBackend;
+pub use local::{LocalBackend, CONTENT_TYPE_XATTR, METADATA_XATTR_PREFIX};
 
 #[cfg(all(feature = "backend-abstraction", feature = "ssh-backend"))]
 pub use ssh::{SshAuth, SshBackend, SshConfig};
 
 #[cfg(all(feature = "backend-abstraction", feature = "s3-native"))]
-pub use s3::S3Backend;
+pub use s3::{
+    plan_window_parts, S3Backend, VerifiedDownloadReport, WindowPart, WindowedUploadReport,
+    PARTIAL_DOWNLOAD_SUFFIX,
+};
+
+#[cfg(all(feature = "backend-abstraction", feature = "s3-cli"))]
+pub use s3::S3ObjectVersion;
 
 #[cfg(all(feature = "backend-abstraction", feature = "smb-native"))]
 pub use smb::{SmbBackend, SmbConfig};
@@ -121,6 +139,12 @@ pub use config::GcsConfig;
 #[cfg(feature = "backend-abstraction")]
 pub use registry::{BackendFactory, BackendRegistry};
 
+#[cfg(feature = "backend-abstraction")]
+pub use circuit::{CircuitBreaker, CircuitState};
+
+#[cfg(feature = "backend-abstraction")]
+pub use copy::{copy_between, COPY_CHANNEL_DEPTH};
+
 #[cfg(feature = "backend-abstraction")]
 use async_trait::async_trait;
 
@@ -283,6 +307,87 @@ pub trait Backend: Send + Sync {
     /// Returns `BackendError::DirectoryNotEmpty` if trying to delete non-empty dir without recursive.
     async fn delete(&self, path: &Path, recursive: bool) -> BackendResult<()>;
 
+    /// Delete a file or directory, or move it to the trash
+    ///
+    /// With `options.trash` set this is [`move_to_trash`](Self::move_to_trash)
+    /// and returns where the entry went; otherwise it is
+    /// [`delete`](Self::delete) and returns `None`.
+    async fn delete_with(
+        &self,
+        path: &Path,
+        options: DeleteOptions,
+    ) -> BackendResult<Option<std::path::PathBuf>> {
+        if options.trash {
+            self.move_to_trash(path).await.map(Some)
+        } else {
+            self.delete(path, options.recursive).await.map(|()| None)
+        }
+    }
+
+    /// Trash directory (or key prefix) for recoverable deletes
+    fn trash_dir(&self) -> &Path {
+        Path::new(TRASH_DIR)
+    }
+
+    /// Move `path` into the trash instead of deleting it
+    ///
+    /// The entry keeps its path under a new group named by the deletion
+    /// time (see [`trash_entry_path`]), so it can be restored with
+    /// [`rename`](Self::rename). The default implementation creates the
+    /// group directory and renames into it; backends without real
+    /// directories override it so no marker objects are written.
+    ///
+    /// # Returns
+    ///
+    /// The entry's path in the trash
+    async fn move_to_trash(&self, path: &Path) -> BackendResult<std::path::PathBuf> {
+        let trashed = trash_entry_path(self.trash_dir(), path, std::time::SystemTime::now());
+        if let Some(parent) = trashed.parent() {
+            match self.mkdir(parent, true).await {
+                Ok(()) | Err(BackendError::AlreadyExists { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.rename(path, &trashed).await?;
+        tracing::info!("Moved {} to {}", path.display(), trashed.display());
+        Ok(trashed)
+    }
+
+    /// Permanently delete trash groups older than `older_than`
+    ///
+    /// # Returns
+    ///
+    /// Number of trash groups removed
+    async fn purge_trash(&self, older_than: std::time::Duration) -> BackendResult<u64> {
+        use futures::StreamExt;
+
+        let trash_dir = self.trash_dir().to_path_buf();
+        let mut entries = match self.list(&trash_dir, ListOptions::shallow()).await {
+            Ok(entries) => entries,
+            Err(BackendError::NotFound { .. }) => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let cutoff = std::time::SystemTime::now()
+            .checked_sub(older_than)
+            .unwrap_or(std::time::UNIX_EPOCH);
+
+        let mut expired = Vec::new();
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+            let Some(name) = entry.path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if trash_entry_time(name).is_some_and(|deleted_at| deleted_at < cutoff) {
+                expired.push(trash_dir.join(name));
+            }
+        }
+
+        for group in &expired {
+            self.delete(group, true).await?;
+        }
+        Ok(expired.len() as u64)
+    }
+
     /// Create a directory
     ///
     /// # Arguments
@@ -446,6 +551,47 @@ pub trait Backend: Send + Sync {
         }
     }
 
+    /// Copy a file within this backend by streaming it through the client
+    ///
+    /// The default implementation is [`copy_between`] with no bandwidth
+    /// limit: `read` feeds `write` through a bounded channel, so a slow write
+    /// side pauses reading rather than buffering the file. Backends with a
+    /// server-side copy should override this.
+    ///
+    /// # Returns
+    ///
+    /// Number of bytes written
+    async fn copy(&self, src: &Path, dest: &Path, options: WriteOptions) -> BackendResult<u64> {
+        copy_between(self, src, self, dest, options, None).await
+    }
+
+    /// Compute a keyed BLAKE3 hash (MAC) of an entire object
+    ///
+    /// The default implementation streams [`read`](Self::read) through
+    /// `blake3::Hasher::new_keyed`. The key is secret and must never be
+    /// logged or echoed into errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Object to hash
+    /// * `key` - 32-byte integrity-domain key
+    ///
+    /// # Returns
+    ///
+    /// The 32-byte keyed hash of the object's content
+    async fn calculate_keyed_hash(&self, path: &Path, key: &[u8; 32]) -> BackendResult<[u8; 32]> {
+        use futures::StreamExt;
+
+        let mut stream = self.read(path).await?;
+        let mut hasher = blake3::Hasher::new_keyed(key);
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk
+                .map_err(|e| BackendError::from_io_with_context(e, path, self.backend_name()))?;
+            hasher.update(&chunk);
+        }
+        Ok(*hasher.finalize().as_bytes())
+    }
+
     /// Get the backend name/type
     ///
     /// # Returns
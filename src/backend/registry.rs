@@ -2,11 +2,17 @@
 //!
 //! Provides a registry system for backend implementations with support for
 //! custom backends and plugin-style architecture.
+//!
+//! Built-in S3 and SSH backends keep their clients in a per-registry cache,
+//! keyed by connection parameters, so repeated jobs to the same bucket or
+//! host reuse a warm client instead of resolving DNS and handshaking again.
 
 use super::error::{BackendError, BackendResult};
 use super::{Backend, BackendConfig};
+use std::any::Any;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "backend-abstraction")]
 use super::LocalBackend;
@@ -33,6 +39,214 @@ pub type BackendFactory =
 /// Box future for async factory functions
 pub type BoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send>>;
 
+/// How long a cached client may sit unused before it is reconnected
+pub const DEFAULT_CLIENT_IDLE_TTL: Duration = Duration::from_secs(300);
+
+/// Warm backend clients, one per endpoint
+///
+/// Entries are keyed by everything that identifies the endpoint except the
+/// credentials, which are kept as a fingerprint beside the client. A lookup
+/// with different credentials drops the entry, so a rotated key never
+/// reaches a client authenticated with the old one.
+struct ClientCache {
+    idle_ttl: Mutex<Duration>,
+    entries: Mutex<HashMap<String, CachedClient>>,
+}
+
+#[cfg_attr(
+    not(any(feature = "s3-native", feature = "ssh-backend")),
+    allow(dead_code)
+)]
+struct CachedClient {
+    credentials: [u8; 32],
+    client: Arc<dyn Any + Send + Sync>,
+    last_used: Instant,
+}
+
+#[cfg_attr(
+    not(any(feature = "s3-native", feature = "ssh-backend")),
+    allow(dead_code)
+)]
+impl ClientCache {
+    fn new(idle_ttl: Duration) -> Self {
+        Self {
+            idle_ttl: Mutex::new(idle_ttl),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn idle_ttl(&self) -> Duration {
+        *self.idle_ttl.lock().unwrap()
+    }
+
+    /// The cached client for `endpoint`, if it is fresh and was built with
+    /// the same credentials
+    fn get<T: Clone + Send + Sync + 'static>(
+        &self,
+        endpoint: &str,
+        credentials: &[u8; 32],
+    ) -> Option<T> {
+        let idle_ttl = self.idle_ttl();
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(endpoint)?;
+        if entry.credentials != *credentials || entry.last_used.elapsed() >= idle_ttl {
+            entries.remove(endpoint);
+            return None;
+        }
+        entry.last_used = Instant::now();
+        entry.client.downcast_ref::<T>().cloned()
+    }
+
+    fn insert<T: Send + Sync + 'static>(&self, endpoint: String, credentials: [u8; 32], client: T) {
+        if self.idle_ttl().is_zero() {
+            return;
+        }
+        self.entries.lock().unwrap().insert(
+            endpoint,
+            CachedClient {
+                credentials,
+                client: Arc::new(client),
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+/// Fingerprint of credential fields, so secrets are not kept in cache keys
+#[cfg_attr(
+    not(any(feature = "s3-native", feature = "ssh-backend")),
+    allow(dead_code)
+)]
+fn credential_fingerprint(fields: &[Option<&str>]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    for field in fields {
+        match field {
+            Some(value) => {
+                hasher.update(&[1]);
+                hasher.update(&(value.len() as u64).to_le_bytes());
+                hasher.update(value.as_bytes());
+            }
+            None => {
+                hasher.update(&[0]);
+            }
+        }
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// `AWS_*` variables from `vars` as sorted `NAME=value` fields
+///
+/// `S3Backend::new` starts from `AmazonS3Builder::from_env`, so keys and
+/// settings in the environment shape the client as much as its config does.
+#[cfg(feature = "s3-native")]
+fn aws_env_fields(vars: impl IntoIterator<Item = (String, String)>) -> Vec<String> {
+    let mut fields: Vec<String> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with("AWS_"))
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Create an S3 backend, reusing the cached client for the same bucket
+#[cfg(feature = "s3-native")]
+async fn cached_s3(
+    cache: &ClientCache,
+    config: super::S3BackendConfig,
+    prefix: Option<String>,
+) -> BackendResult<S3Backend> {
+    let endpoint = format!(
+        "s3|{}|{}|{}|{}|{}|{}",
+        config.endpoint.as_deref().unwrap_or_default(),
+        config.fallback_endpoints.join(","),
+        config.region.as_deref().unwrap_or_default(),
+        config.bucket,
+        config.force_path_style,
+        config.skip_signature
+    );
+    let env = aws_env_fields(std::env::vars_os().map(|(name, value)| {
+        (
+            name.to_string_lossy().into_owned(),
+            value.to_string_lossy().into_owned(),
+        )
+    }));
+    let mut fields = vec![
+        config.access_key.as_deref(),
+        config.secret_key.as_deref(),
+        config.session_token.as_deref(),
+    ];
+    fields.extend(env.iter().map(|field| Some(field.as_str())));
+    let credentials = credential_fingerprint(&fields);
+
+    let client = match cache.get::<S3Backend>(&endpoint, &credentials) {
+        Some(client) => client,
+        None => {
+            let client = S3Backend::new(config).await?;
+            cache.insert(endpoint, credentials, client.clone());
+            client
+        }
+    };
+    Ok(client.scoped(prefix))
+}
+
+/// Connect an SSH backend, reusing the cached session for the same host
+#[cfg(feature = "ssh-backend")]
+async fn cached_ssh(cache: &ClientCache, config: super::SshConfig) -> BackendResult<SshBackend> {
+    let endpoint = format!(
+        "ssh|{}@{}:{}|{}|{}",
+        config.username, config.host, config.port, config.compress, config.timeout_secs
+    );
+    let credentials = ssh_credentials(&config.auth).await;
+
+    if let Some(client) = cache.get::<SshBackend>(&endpoint, &credentials) {
+        return Ok(client);
+    }
+    let client = SshBackend::connect(config).await?;
+    cache.insert(endpoint, credentials, client.clone());
+    Ok(client)
+}
+
+/// Fingerprint of SSH credentials
+///
+/// A key file can be replaced in place, so its contents are hashed along
+/// with its path.
+#[cfg(feature = "ssh-backend")]
+async fn ssh_credentials(auth: &super::SshAuth) -> [u8; 32] {
+    use super::SshAuth;
+    use secrecy::ExposeSecret;
+
+    match auth {
+        SshAuth::Password(password) => {
+            credential_fingerprint(&[Some("password"), Some(password.expose_secret())])
+        }
+        SshAuth::KeyFile {
+            key_path,
+            passphrase,
+        } => {
+            let key_hash = tokio::fs::read(key_path)
+                .await
+                .ok()
+                .map(|key| blake3::hash(&key).to_hex());
+            credential_fingerprint(&[
+                Some("key"),
+                Some(key_path.to_string_lossy().as_ref()),
+                key_hash.as_ref().map(|hash| hash.as_str()),
+                passphrase.as_ref().map(|p| p.expose_secret()),
+            ])
+        }
+        SshAuth::Agent => credential_fingerprint(&[Some("agent")]),
+    }
+}
+
 /// Backend registry for managing backend implementations
 ///
 /// This registry allows registration of custom backend factories,
@@ -65,13 +279,21 @@ pub type BoxFuture<T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> +
 /// ```
 pub struct BackendRegistry {
     factories: RwLock<HashMap<String, BackendFactory>>,
+    clients: Arc<ClientCache>,
 }
 
 impl BackendRegistry {
     /// Create a new backend registry with default implementations
     pub fn new() -> Self {
+        Self::with_client_idle_ttl(DEFAULT_CLIENT_IDLE_TTL)
+    }
+
+    /// Create a registry whose cached S3 and SSH clients are dropped after
+    /// `idle_ttl` without use (zero disables client reuse)
+    pub fn with_client_idle_ttl(idle_ttl: Duration) -> Self {
         let registry = Self {
             factories: RwLock::new(HashMap::new()),
+            clients: Arc::new(ClientCache::new(idle_ttl)),
         };
 
         // Register built-in backends
@@ -80,6 +302,24 @@ impl BackendRegistry {
         registry
     }
 
+    /// Change the idle TTL of cached clients (zero disables client reuse)
+    pub fn set_client_idle_ttl(&self, idle_ttl: Duration) {
+        *self.clients.idle_ttl.lock().unwrap() = idle_ttl;
+        if idle_ttl.is_zero() {
+            self.clients.clear();
+        }
+    }
+
+    /// Drop every cached client, so the next backend of each kind reconnects
+    pub fn clear_client_cache(&self) {
+        self.clients.clear();
+    }
+
+    /// Number of clients currently cached
+    pub fn cached_clients(&self) -> usize {
+        self.clients.len()
+    }
+
     /// Register built-in backend factories
     fn register_builtin(&self) {
         // Local filesystem backend
@@ -113,14 +353,17 @@ impl BackendRegistry {
 
         // SSH backend
         #[cfg(feature = "ssh-backend")]
+        let clients = self.clients.clone();
+        #[cfg(feature = "ssh-backend")]
         self.register(
             "ssh",
-            Arc::new(|config| {
+            Arc::new(move |config| {
                 let config = config.clone();
+                let clients = clients.clone();
                 Box::pin(async move {
                     match config {
                         BackendConfig::Ssh(ssh_config) => {
-                            let backend = SshBackend::connect(ssh_config).await?;
+                            let backend = cached_ssh(&clients, ssh_config).await?;
                             Ok(Box::new(backend) as Box<dyn Backend>)
                         }
                         _ => Err(BackendError::InvalidConfig {
@@ -134,18 +377,17 @@ impl BackendRegistry {
 
         // S3 backend
         #[cfg(feature = "s3-native")]
+        let clients = self.clients.clone();
+        #[cfg(feature = "s3-native")]
         self.register(
             "s3",
-            Arc::new(|config| {
+            Arc::new(move |config| {
                 let config = config.clone();
+                let clients = clients.clone();
                 Box::pin(async move {
                     match config {
                         BackendConfig::S3 { config, prefix } => {
-                            let backend = if let Some(prefix) = prefix {
-                                S3Backend::with_prefix(config, prefix).await?
-                            } else {
-                                S3Backend::new(config).await?
-                            };
+                            let backend = cached_s3(&clients, config, prefix).await?;
                             Ok(Box::new(backend) as Box<dyn Backend>)
                         }
                         _ => Err(BackendError::InvalidConfig {
@@ -415,6 +657,128 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "s3-native")]
+    fn s3_config(access_key: &str) -> super::super::S3BackendConfig {
+        super::super::S3BackendConfig {
+            region: Some("us-east-1".to_string()),
+            access_key: Some(access_key.to_string()),
+            secret_key: Some("secret".to_string()),
+            ..super::super::S3BackendConfig::new("bucket")
+        }
+    }
+
+    #[cfg(feature = "s3-native")]
+    #[tokio::test]
+    async fn test_identical_s3_params_reuse_client() {
+        let registry = BackendRegistry::new();
+        let first = cached_s3(&registry.clients, s3_config("AKIA1"), None)
+            .await
+            .unwrap();
+        let second = cached_s3(&registry.clients, s3_config("AKIA1"), Some("logs".into()))
+            .await
+            .unwrap();
+        assert!(first.shares_client_with(&second));
+        assert_eq!(registry.cached_clients(), 1);
+
+        // Rotated credentials replace the cached client
+        let rotated = cached_s3(&registry.clients, s3_config("AKIA2"), None)
+            .await
+            .unwrap();
+        assert!(!rotated.shares_client_with(&first));
+        assert_eq!(registry.cached_clients(), 1);
+        let again = cached_s3(&registry.clients, s3_config("AKIA2"), None)
+            .await
+            .unwrap();
+        assert!(again.shares_client_with(&rotated));
+
+        // Through the factory as well
+        let config = BackendConfig::S3 {
+            config: s3_config("AKIA2"),
+            prefix: None,
+        };
+        assert!(registry.create(&config).await.is_ok());
+        assert_eq!(registry.cached_clients(), 1);
+    }
+
+    #[cfg(feature = "s3-native")]
+    #[tokio::test]
+    async fn test_idle_client_is_reconnected() {
+        let registry = BackendRegistry::with_client_idle_ttl(Duration::from_millis(20));
+        let first = cached_s3(&registry.clients, s3_config("AKIA1"), None)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let second = cached_s3(&registry.clients, s3_config("AKIA1"), None)
+            .await
+            .unwrap();
+        assert!(!first.shares_client_with(&second));
+
+        registry.set_client_idle_ttl(Duration::ZERO);
+        assert_eq!(registry.cached_clients(), 0);
+        cached_s3(&registry.clients, s3_config("AKIA1"), None)
+            .await
+            .unwrap();
+        assert_eq!(registry.cached_clients(), 0);
+    }
+
+    #[test]
+    fn test_client_cache_invalidates_on_credential_change() {
+        let cache = ClientCache::new(DEFAULT_CLIENT_IDLE_TTL);
+        let alice = credential_fingerprint(&[Some("alice"), None]);
+        let bob = credential_fingerprint(&[Some("bob"), None]);
+        cache.insert("ssh|host".to_string(), alice, 7u32);
+
+        assert_eq!(cache.get::<u32>("ssh|host", &alice), Some(7));
+        assert_eq!(cache.get::<u32>("ssh|host", &bob), None);
+        // The stale entry is gone, not just skipped
+        assert_eq!(cache.get::<u32>("ssh|host", &alice), None);
+        assert_ne!(
+            credential_fingerprint(&[Some("ab"), Some("c")]),
+            credential_fingerprint(&[Some("a"), Some("bc")])
+        );
+    }
+
+    #[cfg(feature = "s3-native")]
+    #[test]
+    fn test_aws_environment_is_part_of_s3_fingerprint() {
+        let env = |secret: &str| {
+            aws_env_fields([
+                ("HOME".to_string(), "/root".to_string()),
+                ("AWS_SECRET_ACCESS_KEY".to_string(), secret.to_string()),
+                ("AWS_ACCESS_KEY_ID".to_string(), "AKIA1".to_string()),
+            ])
+        };
+        assert_eq!(
+            env("old"),
+            vec!["AWS_ACCESS_KEY_ID=AKIA1", "AWS_SECRET_ACCESS_KEY=old"]
+        );
+
+        let fingerprint = |fields: &[String]| {
+            let fields: Vec<_> = fields.iter().map(|f| Some(f.as_str())).collect();
+            credential_fingerprint(&fields)
+        };
+        assert_ne!(fingerprint(&env("old")), fingerprint(&env("rotated")));
+    }
+
+    #[cfg(feature = "ssh-backend")]
+    #[tokio::test]
+    async fn test_ssh_key_file_contents_are_part_of_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_path = dir.path().join("id_ed25519");
+        let auth = super::super::SshAuth::KeyFile {
+            key_path: key_path.clone(),
+            passphrase: None,
+        };
+
+        std::fs::write(&key_path, "old key").unwrap();
+        let old = ssh_credentials(&auth).await;
+        assert_eq!(old, ssh_credentials(&auth).await);
+
+        // Same path, rotated key
+        std::fs::write(&key_path, "new key").unwrap();
+        assert_ne!(old, ssh_credentials(&auth).await);
+    }
+
     #[test]
     fn test_global_registry() {
         let registry = global_registry();
@@ -11,17 +11,28 @@
 
 use super::config::S3BackendConfig;
 use super::error::{BackendError, BackendResult};
-use super::types::{DirEntry, ListOptions, ListStream, Metadata, ReadStream, WriteOptions};
+use super::failover::{
+    EndpointStore, FailoverStore, DEFAULT_BREAKER_TIMEOUT, DEFAULT_FAILURE_THRESHOLD,
+};
+use super::types::{
+    guess_content_type, trash_entry_path, DirEntry, ListOptions, ListStream, Metadata, ReadStream,
+    WriteOptions,
+};
 use super::Backend;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::{StreamExt, TryStreamExt};
-use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey, S3ConditionalPut};
+use object_store::multipart::{MultipartStore, PartId};
 use object_store::{
-    path::Path as ObjectPath, Attribute, AttributeValue, Attributes, ObjectMeta, ObjectStore,
-    PutMode, PutMultipartOpts, PutOptions, PutPayload, WriteMultipart,
+    path::Path as ObjectPath, Attribute, AttributeValue, Attributes, GetOptions, GetRange,
+    ObjectMeta, ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, UpdateVersion,
+    WriteMultipart,
 };
+use orbit_core_manifest::CargoManifest;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::io::AsyncRead;
@@ -37,22 +48,72 @@ const READ_BUF_SIZE: usize = 64 * 1024;
 const MULTIPART_CONCURRENCY: usize = 4;
 /// Channel buffer for the lazy list stream.
 const LIST_CHANNEL_CAPACITY: usize = 64;
+/// Ranges closer than this are fetched as one span by `read_ranges`; reading
+/// the gap is cheaper than another round trip.
+const RANGE_COALESCE_GAP: u64 = 64 * 1024;
+/// Smallest size S3 accepts for any multipart part except the last.
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+/// Largest size S3 accepts for a multipart part.
+const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// Most parts S3 accepts in one multipart upload.
+const MAX_PARTS: u64 = 10_000;
+/// Largest object S3 accepts in a single PUT.
+const MAX_SINGLE_PUT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// Largest conditional write. `object_store` sends a single PUT from an
+/// in-memory payload, so conditional bodies are buffered and capped well
+/// below the 5 GiB S3 would take.
+const MAX_CONDITIONAL_PUT_SIZE: u64 = 64 * 1024 * 1024;
+/// Appended to the destination name to form the partial file of a
+/// resumable download.
+pub const PARTIAL_DOWNLOAD_SUFFIX: &str = ".orbit_s3_partial";
 
 /// S3 backend using the `object_store` crate
+#[derive(Clone)]
 pub struct S3Backend {
     store: Arc<dyn ObjectStore>,
+    /// Same store, for part-level uploads that outlive a single process
+    multipart: Arc<dyn MultipartStore>,
     /// Prefix applied to all paths (like a "root" directory)
     prefix: Option<String>,
+    /// aws-sdk client for version listing and restore, which `object_store`
+    /// does not expose. Built on first use.
+    #[cfg(feature = "s3-cli")]
+    versions: Option<Arc<VersionClient>>,
 }
 
 impl S3Backend {
     /// Create a new S3 backend from configuration
+    ///
+    /// With `fallback_endpoints` set, requests go through a [`FailoverStore`]
+    /// that moves on to the next endpoint when one's circuit breaker opens.
     pub async fn new(config: S3BackendConfig) -> BackendResult<Self> {
-        let store = build_store(&config)?;
-        Ok(Self {
-            store: Arc::new(store),
-            prefix: None,
-        })
+        #[cfg_attr(not(feature = "s3-cli"), allow(unused_mut))]
+        let mut backend = if config.fallback_endpoints.is_empty() {
+            let store = Arc::new(build_store(&config)?);
+            Self::from_stores(store.clone(), store, None)
+        } else {
+            let store = Arc::new(build_failover_store(&config)?);
+            Self::from_stores(store.clone(), store, None)
+        };
+        #[cfg(feature = "s3-cli")]
+        {
+            backend.versions = Some(Arc::new(VersionClient::new(config)));
+        }
+        Ok(backend)
+    }
+
+    fn from_stores(
+        store: Arc<dyn ObjectStore>,
+        multipart: Arc<dyn MultipartStore>,
+        prefix: Option<String>,
+    ) -> Self {
+        Self {
+            store,
+            multipart,
+            prefix,
+            #[cfg(feature = "s3-cli")]
+            versions: None,
+        }
     }
 
     /// Create a new S3 backend with a prefix applied to all operations
@@ -65,6 +126,20 @@ impl S3Backend {
         Ok(backend)
     }
 
+    /// The same client with a different prefix
+    pub(crate) fn scoped(&self, prefix: Option<String>) -> Self {
+        Self {
+            prefix,
+            ..self.clone()
+        }
+    }
+
+    /// Whether both backends send requests through the same client
+    #[cfg(test)]
+    pub(crate) fn shares_client_with(&self, other: &S3Backend) -> bool {
+        Arc::ptr_eq(&self.store, &other.store)
+    }
+
     /// Convert a Path to an `ObjectPath` (object_store's normalized key).
     ///
     /// The same key is used for both exact-object operations and as a
@@ -83,7 +158,7 @@ impl S3Backend {
         ObjectPath::from(full)
     }
 
-    #[allow(dead_code)]
+    #[cfg_attr(not(feature = "s3-cli"), allow(dead_code))]
     fn key_to_path(&self, object_path: &ObjectPath) -> PathBuf {
         strip_prefix(object_path.as_ref(), self.prefix.as_deref())
     }
@@ -94,10 +169,807 @@ impl S3Backend {
         metadata.etag = meta.e_tag.clone();
         metadata
     }
+
+    /// Read several byte ranges of one object with as few requests as possible.
+    ///
+    /// S3 does not serve multi-range (`multipart/byteranges`) GETs, so ranges
+    /// that overlap, touch, or sit within [`RANGE_COALESCE_GAP`] of each other
+    /// are merged into spans, the spans are fetched concurrently with
+    /// single-range GETs, and each requested range is sliced (without copying)
+    /// out of its span. Buffers are returned in the order of `ranges`.
+    ///
+    /// As with a plain ranged GET, a range that runs past the end of the
+    /// object is truncated to the bytes that exist.
+    #[tracing::instrument(
+        skip(self, ranges),
+        fields(otel.kind = "client", backend = "s3", path = %path.display(), ranges = ranges.len())
+    )]
+    pub async fn read_ranges(
+        &self,
+        path: &Path,
+        ranges: &[Range<u64>],
+    ) -> BackendResult<Vec<Bytes>> {
+        if let Some(bad) = ranges.iter().find(|r| r.start > r.end) {
+            return Err(BackendError::InvalidPath {
+                path: path.to_path_buf(),
+                reason: format!("invalid byte range {}..{}", bad.start, bad.end),
+            });
+        }
+
+        let spans = coalesce_ranges(ranges, RANGE_COALESCE_GAP);
+        if spans.is_empty() {
+            return Ok(vec![Bytes::new(); ranges.len()]);
+        }
+
+        let object_path = self.path_to_key(path);
+        let span_ranges: Vec<Range<usize>> = spans
+            .iter()
+            .map(|s| s.start as usize..s.end as usize)
+            .collect();
+        let buffers = self
+            .store
+            .get_ranges(&object_path, &span_ranges)
+            .await
+            .map_err(|e| map_store_err(e, path))?;
+
+        Ok(ranges
+            .iter()
+            .map(|r| {
+                if r.is_empty() {
+                    return Bytes::new();
+                }
+                // Spans are sorted and disjoint; the last one starting at or
+                // before `r.start` contains the whole range.
+                let idx = spans.partition_point(|s| s.start <= r.start) - 1;
+                // The span's GET comes back short when it runs past EOF
+                let buffer = &buffers[idx];
+                let offset = ((r.start - spans[idx].start) as usize).min(buffer.len());
+                let end = (offset + (r.end - r.start) as usize).min(buffer.len());
+                buffer.slice(offset..end)
+            })
+            .collect())
+    }
+}
+
+/// Merge ranges that overlap or lie within `gap` bytes of each other.
+///
+/// Empty ranges are dropped; the result is sorted by start offset.
+fn coalesce_ranges(ranges: &[Range<u64>], gap: u64) -> Vec<Range<u64>> {
+    let mut sorted: Vec<Range<u64>> = ranges.iter().filter(|r| !r.is_empty()).cloned().collect();
+    sorted.sort_by_key(|r| r.start);
+
+    let mut spans: Vec<Range<u64>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match spans.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(gap) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => spans.push(range),
+        }
+    }
+    spans
+}
+
+/// One multipart part of a window-aligned upload
+///
+/// Cargo manifest windows overlap by a few chunks, so each window owns the
+/// bytes from its first chunk up to the next window's first chunk. A part
+/// carries the owned bytes of one or more consecutive windows, which keeps
+/// part boundaries on window boundaries and lets S3 progress be mapped back
+/// to the windows that verification checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowPart {
+    /// Zero-based part index (S3 part number minus one)
+    pub index: usize,
+    /// Byte range of the source file carried by this part
+    pub range: Range<u64>,
+    /// IDs of the windows whose owned bytes this part carries
+    pub windows: Vec<u32>,
+}
+
+/// Outcome of [`S3Backend::upload_windowed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowedUploadReport {
+    /// Every part of the upload, in order
+    pub parts: Vec<WindowPart>,
+    /// Parts sent by this call
+    pub parts_uploaded: usize,
+    /// Parts already recorded in the resume sidecar and not re-sent
+    pub parts_skipped: usize,
+    /// Windows carried by the skipped parts
+    pub windows_skipped: Vec<u32>,
+}
+
+/// Split a file into multipart parts aligned to its cargo manifest windows.
+///
+/// Consecutive windows are grouped until a part reaches `min_part_size`, so
+/// every part except the last is a whole number of windows and at least the
+/// minimum size S3 accepts. For large files the target grows so the upload
+/// fits in S3's 10,000 parts, and a part is closed early rather than grow
+/// past S3's 5 GiB part limit. Files that cannot meet both limits with
+/// whole windows are an error.
+pub fn plan_window_parts(
+    cargo: &CargoManifest,
+    min_part_size: u64,
+) -> BackendResult<Vec<WindowPart>> {
+    let chunk_kib = cargo
+        .chunking
+        .fixed_kib
+        .or(cargo.chunking.avg_kib)
+        .ok_or_else(|| BackendError::InvalidConfig {
+            backend: "s3".to_string(),
+            message: format!("cargo manifest for {} has no chunk size", cargo.path),
+        })?;
+    let chunk_size = chunk_kib as u64 * 1024;
+    let unplannable = |reason: String| BackendError::InvalidConfig {
+        backend: "s3".to_string(),
+        message: format!(
+            "cannot split {} into at most {} parts of at most {} bytes: {}",
+            cargo.path, MAX_PARTS, MAX_PART_SIZE, reason
+        ),
+    };
+
+    // With every part but the last at least this large, there are at most
+    // MAX_PARTS of them
+    let min_part_size = min_part_size.max(cargo.size.div_ceil(MAX_PARTS - 1));
+    if min_part_size > MAX_PART_SIZE {
+        return Err(unplannable(format!("the file is {} bytes", cargo.size)));
+    }
+
+    let mut windows: Vec<_> = cargo.windows.iter().collect();
+    windows.sort_by_key(|w| w.first_chunk);
+
+    let mut parts: Vec<WindowPart> = Vec::new();
+    let mut current: Option<WindowPart> = None;
+    for (i, window) in windows.iter().enumerate() {
+        let start = (window.first_chunk as u64 * chunk_size).min(cargo.size);
+        let end = match windows.get(i + 1) {
+            Some(next) => (next.first_chunk as u64 * chunk_size).min(cargo.size),
+            None => cargo.size,
+        };
+        if end - start > MAX_PART_SIZE {
+            return Err(unplannable(format!(
+                "window {} alone is {} bytes",
+                window.id,
+                end - start
+            )));
+        }
+
+        // Close the open part before this window would push it past the
+        // part limit, as long as it is already big enough to stand alone
+        let open_len = current.as_ref().map_or(0, |p| p.range.end - p.range.start);
+        if open_len >= MIN_PART_SIZE && open_len + (end - start) > MAX_PART_SIZE {
+            parts.extend(current.take());
+        }
+
+        let part = current.get_or_insert_with(|| WindowPart {
+            index: parts.len(),
+            range: start..start,
+            windows: Vec::new(),
+        });
+        part.range.end = end;
+        part.windows.push(window.id);
+
+        if part.range.end - part.range.start >= min_part_size {
+            parts.extend(current.take());
+        }
+    }
+    parts.extend(current);
+
+    if parts.len() as u64 > MAX_PARTS {
+        return Err(unplannable(format!("{} parts are needed", parts.len())));
+    }
+    if let Some(part) = parts
+        .iter()
+        .find(|p| p.range.end - p.range.start > MAX_PART_SIZE)
+    {
+        return Err(unplannable(format!(
+            "part {} would be {} bytes",
+            part.index,
+            part.range.end - part.range.start
+        )));
+    }
+    Ok(parts)
+}
+
+/// Resume sidecar for a window-aligned upload
+#[derive(Debug, Serialize, Deserialize)]
+struct WindowedUploadState {
+    key: String,
+    multipart_id: String,
+    size: u64,
+    /// Digest of the manifest windows, so a sidecar written for a different
+    /// version of the file is never resumed
+    windows_digest: String,
+    parts: Vec<CompletedWindowPart>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CompletedWindowPart {
+    index: usize,
+    windows: Vec<u32>,
+    content_id: String,
+}
+
+impl WindowedUploadState {
+    fn load(sidecar: &Path) -> Option<Self> {
+        let data = std::fs::read(sidecar).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Write via a temp file and rename so a crash never leaves a torn sidecar
+    fn save(&self, sidecar: &Path) -> BackendResult<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(|e| BackendError::Serialization {
+            message: e.to_string(),
+        })?;
+        let tmp = sidecar.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, sidecar)?;
+        Ok(())
+    }
+}
+
+fn windows_digest(cargo: &CargoManifest) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&cargo.size.to_le_bytes());
+    for window in &cargo.windows {
+        hasher.update(&window.id.to_le_bytes());
+        hasher.update(&window.first_chunk.to_le_bytes());
+        hasher.update(&window.count.to_le_bytes());
+        hasher.update(window.merkle_root.as_bytes());
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+async fn read_part(local_path: &Path, range: &Range<u64>) -> BackendResult<Bytes> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = tokio::fs::File::open(local_path).await?;
+    file.seek(std::io::SeekFrom::Start(range.start)).await?;
+    let mut buf = vec![0u8; (range.end - range.start) as usize];
+    file.read_exact(&mut buf).await?;
+    Ok(Bytes::from(buf))
+}
+
+impl S3Backend {
+    /// Upload a local file as a multipart upload whose parts line up with the
+    /// windows of its cargo manifest, resuming from `sidecar` if present.
+    ///
+    /// After each part is stored, its index, windows and part ID are recorded
+    /// in the sidecar. If the upload is interrupted, calling this again with
+    /// the same sidecar continues the same S3 multipart upload and only sends
+    /// parts that were not recorded. A sidecar for a different key, size or
+    /// manifest is discarded and its multipart upload aborted. The sidecar is
+    /// removed once the object is complete.
+    #[tracing::instrument(
+        skip(self, cargo),
+        fields(otel.kind = "client", backend = "s3", path = %dest.display(), size = cargo.size)
+    )]
+    pub async fn upload_windowed(
+        &self,
+        local_path: &Path,
+        dest: &Path,
+        cargo: &CargoManifest,
+        sidecar: &Path,
+    ) -> BackendResult<WindowedUploadReport> {
+        let object_path = self.path_to_key(dest);
+        let parts = plan_window_parts(cargo, MIN_PART_SIZE)?;
+
+        let local_size = tokio::fs::metadata(local_path).await?.len();
+        if local_size != cargo.size {
+            return Err(BackendError::InvalidPath {
+                path: local_path.to_path_buf(),
+                reason: format!(
+                    "file is {} bytes but its manifest describes {} bytes",
+                    local_size, cargo.size
+                ),
+            });
+        }
+
+        if parts.is_empty() {
+            // S3 rejects a multipart upload with no parts
+            self.store
+                .put(&object_path, PutPayload::new())
+                .await
+                .map_err(|e| map_store_err(e, dest))?;
+            let _ = std::fs::remove_file(sidecar);
+            return Ok(WindowedUploadReport {
+                parts,
+                parts_uploaded: 0,
+                parts_skipped: 0,
+                windows_skipped: Vec::new(),
+            });
+        }
+
+        let digest = windows_digest(cargo);
+        let resumable = WindowedUploadState::load(sidecar).filter(|state| {
+            state.key == object_path.as_ref()
+                && state.size == cargo.size
+                && state.windows_digest == digest
+        });
+        let mut state = match resumable {
+            Some(state) => state,
+            None => {
+                if let Some(stale) = WindowedUploadState::load(sidecar) {
+                    let _ = self
+                        .multipart
+                        .abort_multipart(&ObjectPath::from(stale.key), &stale.multipart_id)
+                        .await;
+                }
+                let multipart_id = self
+                    .multipart
+                    .create_multipart(&object_path)
+                    .await
+                    .map_err(|e| map_store_err(e, dest))?;
+                let state = WindowedUploadState {
+                    key: object_path.to_string(),
+                    multipart_id,
+                    size: cargo.size,
+                    windows_digest: digest,
+                    parts: Vec::new(),
+                };
+                state.save(sidecar)?;
+                state
+            }
+        };
+
+        let done: std::collections::HashSet<usize> = state.parts.iter().map(|p| p.index).collect();
+        let parts_skipped = done.len();
+        let windows_skipped: Vec<u32> = state
+            .parts
+            .iter()
+            .flat_map(|p| p.windows.iter().copied())
+            .collect();
+
+        let mut pending = parts.iter().filter(|p| !done.contains(&p.index));
+        let mut in_flight = futures::stream::FuturesUnordered::new();
+        let mut parts_uploaded = 0;
+        let mut failure = None;
+        loop {
+            // After a failure, stop starting parts but record the ones
+            // already in flight so a resume does not send them again.
+            while failure.is_none() && in_flight.len() < MULTIPART_CONCURRENCY {
+                let Some(part) = pending.next() else { break };
+                let object_path = &object_path;
+                let multipart_id = &state.multipart_id;
+                in_flight.push(async move {
+                    let data = read_part(local_path, &part.range).await?;
+                    let id = self
+                        .multipart
+                        .put_part(object_path, multipart_id, part.index, data.into())
+                        .await
+                        .map_err(|e| map_store_err(e, dest))?;
+                    Ok::<_, BackendError>((part, id))
+                });
+            }
+
+            match in_flight.next().await {
+                Some(Ok((part, id))) => {
+                    parts_uploaded += 1;
+                    state.parts.push(CompletedWindowPart {
+                        index: part.index,
+                        windows: part.windows.clone(),
+                        content_id: id.content_id,
+                    });
+                    state.save(sidecar)?;
+                }
+                Some(Err(e)) => {
+                    failure.get_or_insert(e);
+                }
+                None => break,
+            }
+        }
+        drop(in_flight);
+        if let Some(e) = failure {
+            return Err(e);
+        }
+
+        state.parts.sort_by_key(|p| p.index);
+        let part_ids = state
+            .parts
+            .iter()
+            .map(|p| PartId {
+                content_id: p.content_id.clone(),
+            })
+            .collect();
+        self.multipart
+            .complete_multipart(&object_path, &state.multipart_id, part_ids)
+            .await
+            .map_err(|e| map_store_err(e, dest))?;
+        let _ = std::fs::remove_file(sidecar);
+
+        Ok(WindowedUploadReport {
+            parts,
+            parts_uploaded,
+            parts_skipped,
+            windows_skipped,
+        })
+    }
+}
+
+/// Outcome of [`S3Backend::download_resumable_verified`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedDownloadReport {
+    /// Bytes already in the partial file and not fetched again
+    pub resumed_from: u64,
+    /// Bytes fetched from S3 by this call
+    pub bytes_fetched: u64,
+    /// Size of the completed object
+    pub size: u64,
+}
+
+fn partial_download_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(PARTIAL_DOWNLOAD_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Sidecar holding the ETag of the object a partial download belongs to
+fn partial_etag_path(partial: &Path) -> PathBuf {
+    let mut name = partial.as_os_str().to_owned();
+    name.push(".etag");
+    PathBuf::from(name)
+}
+
+async fn blake3_file(path: &Path) -> BackendResult<String> {
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0u8; READ_BUF_SIZE];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+impl S3Backend {
+    /// Download an object to `dest`, resuming an interrupted download, and
+    /// verify the whole file against `expected_checksum` (hex BLAKE3).
+    ///
+    /// Bytes are written to `dest` plus [`PARTIAL_DOWNLOAD_SUFFIX`]. If that
+    /// file exists, only the remainder of the object is requested with a
+    /// ranged GET; a partial longer than the object is discarded. The ETag
+    /// the partial was started from is kept in a sidecar file and every GET
+    /// is made conditional on it, so a partial is never completed with bytes
+    /// of a different version of the object: if the object changed, the
+    /// partial is restarted from zero. If the transfer fails, the bytes
+    /// received so far stay in the partial for the next call. Once the
+    /// object is complete the partial is hashed: on a match it is renamed to
+    /// `dest`, on a mismatch it is deleted (its bytes cannot be trusted as a
+    /// resume point) and [`BackendError::ChecksumMismatch`] is returned.
+    ///
+    /// `object_store` does not expose S3's stored `x-amz-checksum-*` values,
+    /// so the checksum is always computed locally.
+    #[tracing::instrument(
+        skip(self, expected_checksum),
+        fields(otel.kind = "client", backend = "s3", path = %key.display())
+    )]
+    pub async fn download_resumable_verified(
+        &self,
+        key: &Path,
+        dest: &Path,
+        expected_checksum: &str,
+    ) -> BackendResult<VerifiedDownloadReport> {
+        use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+        let object_path = self.path_to_key(key);
+        let partial = partial_download_path(dest);
+        let etag_path = partial_etag_path(&partial);
+        let meta = self
+            .store
+            .head(&object_path)
+            .await
+            .map_err(|e| map_store_err(e, key))?;
+        let size = meta.size as u64;
+
+        let mut resumed_from = match tokio::fs::metadata(&partial).await {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(BackendError::from_io_with_context(e, &partial, "local")),
+        };
+        if resumed_from > 0 {
+            let saved_etag = tokio::fs::read_to_string(&etag_path).await.ok();
+            if meta.e_tag.is_none() || saved_etag != meta.e_tag {
+                tracing::warn!(
+                    "Partial download {} was started from a different version of the object, restarting",
+                    partial.display()
+                );
+                resumed_from = 0;
+            }
+        }
+        if resumed_from > size {
+            tracing::warn!(
+                "Partial download {} is larger than the object ({} > {} bytes), restarting",
+                partial.display(),
+                resumed_from,
+                size
+            );
+            resumed_from = 0;
+        }
+
+        // Created even when there is nothing to fetch, so a zero-byte object
+        // still leaves a partial to verify and rename
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&partial)
+            .await?;
+        file.set_len(resumed_from).await?;
+        match &meta.e_tag {
+            Some(etag) => tokio::fs::write(&etag_path, etag).await?,
+            None => {
+                let _ = tokio::fs::remove_file(&etag_path).await;
+            }
+        }
+
+        let mut bytes_fetched = 0u64;
+        if resumed_from < size {
+            file.seek(std::io::SeekFrom::End(0)).await?;
+
+            let options = GetOptions {
+                range: Some(GetRange::Offset(resumed_from as usize)),
+                if_match: meta.e_tag.clone(),
+                ..Default::default()
+            };
+            let mut stream = self
+                .store
+                .get_opts(&object_path, options)
+                .await
+                .map_err(|e| map_store_err(e, key))?
+                .into_stream();
+
+            // Keep what was received before a failure so the next call
+            // resumes after it
+            let mut failure = None;
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(chunk) => {
+                        file.write_all(&chunk).await?;
+                        bytes_fetched += chunk.len() as u64;
+                    }
+                    Err(e) => {
+                        failure = Some(map_store_err(e, key));
+                        break;
+                    }
+                }
+            }
+            file.flush().await?;
+            file.sync_all().await?;
+            if let Some(e) = failure {
+                return Err(e);
+            }
+        }
+        drop(file);
+
+        let received = resumed_from + bytes_fetched;
+        if received != size {
+            return Err(BackendError::Network {
+                message: format!(
+                    "download of {} ended after {} of {} bytes",
+                    key.display(),
+                    received,
+                    size
+                ),
+                source: None,
+            });
+        }
+
+        let actual = blake3_file(&partial).await?;
+        if !actual.eq_ignore_ascii_case(expected_checksum) {
+            let _ = tokio::fs::remove_file(&partial).await;
+            let _ = tokio::fs::remove_file(&etag_path).await;
+            return Err(BackendError::ChecksumMismatch {
+                path: key.to_path_buf(),
+                expected: expected_checksum.to_string(),
+                actual,
+            });
+        }
+        tokio::fs::rename(&partial, dest).await?;
+        let _ = tokio::fs::remove_file(&etag_path).await;
+
+        Ok(VerifiedDownloadReport {
+            resumed_from,
+            bytes_fetched,
+            size,
+        })
+    }
+}
+
+/// One stored version of an object in a versioned bucket
+#[cfg(feature = "s3-cli")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3ObjectVersion {
+    /// Object path relative to the backend prefix
+    pub path: PathBuf,
+    /// S3 version ID
+    pub version_id: String,
+    /// Whether this is the current version of the object
+    pub is_latest: bool,
+    /// When this version was written
+    pub last_modified: std::time::SystemTime,
+    /// Size of this version in bytes
+    pub size: u64,
+}
+
+/// Lazily built aws-sdk client sharing the backend's configuration
+#[cfg(feature = "s3-cli")]
+struct VersionClient {
+    config: S3BackendConfig,
+    client: tokio::sync::OnceCell<crate::protocol::s3::S3Client>,
+}
+
+#[cfg(feature = "s3-cli")]
+impl VersionClient {
+    fn new(config: S3BackendConfig) -> Self {
+        Self {
+            config,
+            client: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    async fn get(&self) -> BackendResult<&crate::protocol::s3::S3Client> {
+        self.client
+            .get_or_try_init(|| async {
+                let mut config = crate::protocol::s3::S3Config::new(self.config.bucket.clone());
+                config.region = self.config.region.clone();
+                config.endpoint = self.config.endpoint.clone();
+                config.access_key = self.config.access_key.clone();
+                config.secret_key = self.config.secret_key.clone();
+                config.session_token = self.config.session_token.clone();
+                config.force_path_style = self.config.force_path_style;
+                config.no_sign_request = self.config.skip_signature;
+                crate::protocol::s3::S3Client::new(config)
+                    .await
+                    .map_err(|e| BackendError::InvalidConfig {
+                        backend: "s3".to_string(),
+                        message: format!("Failed to build S3 versioning client: {}", e),
+                    })
+            })
+            .await
+    }
+}
+
+#[cfg(feature = "s3-cli")]
+impl S3Backend {
+    /// List every stored version of the objects under `prefix`.
+    ///
+    /// Versions are grouped by path and ordered newest first within each
+    /// path. Delete markers are not included. Fails with
+    /// `BackendError::InvalidConfig` if versioning has never been enabled on
+    /// the bucket.
+    pub async fn list_object_versions(&self, prefix: &Path) -> BackendResult<Vec<S3ObjectVersion>> {
+        use crate::protocol::s3::VersioningOperations;
+
+        let client = self.versioned_client("list versions").await?;
+        let key = self.path_to_key(prefix);
+
+        let mut versions = Vec::new();
+        let mut key_marker = None;
+        let mut version_marker = None;
+        loop {
+            let page = client
+                .list_object_versions_paginated(key.as_ref(), key_marker, version_marker, None)
+                .await
+                .map_err(|e| map_s3_err(e, prefix))?;
+
+            versions.extend(page.versions.into_iter().map(|v| S3ObjectVersion {
+                path: self.key_to_path(&ObjectPath::from(v.key)),
+                version_id: v.version_id,
+                is_latest: v.is_latest,
+                last_modified: v.last_modified,
+                size: v.size,
+            }));
+
+            if !page.is_truncated {
+                break;
+            }
+            key_marker = page.next_key_marker;
+            version_marker = page.next_version_id_marker;
+        }
+
+        versions.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(b.last_modified.cmp(&a.last_modified))
+                .then(b.is_latest.cmp(&a.is_latest))
+        });
+        Ok(versions)
+    }
+
+    /// Make an older version of `path` current again.
+    ///
+    /// The version is copied over the current key, so it becomes a new latest
+    /// version and the versions in between are kept. Returns the new version
+    /// ID. Fails with `BackendError::InvalidConfig` if versioning has never
+    /// been enabled on the bucket.
+    pub async fn restore_version(&self, path: &Path, version_id: &str) -> BackendResult<String> {
+        use crate::protocol::s3::VersioningOperations;
+
+        let client = self.versioned_client("restore a version").await?;
+        let key = self.path_to_key(path);
+        client
+            .restore_version(key.as_ref(), version_id, None)
+            .await
+            .map_err(|e| map_s3_err(e, path))
+    }
+
+    /// The aws-sdk client, once the bucket is known to keep versions
+    async fn versioned_client(
+        &self,
+        operation: &str,
+    ) -> BackendResult<&crate::protocol::s3::S3Client> {
+        use crate::protocol::s3::VersioningOperations;
+
+        let versions = self
+            .versions
+            .as_ref()
+            .ok_or_else(|| BackendError::Unsupported {
+                backend: "s3".to_string(),
+                operation: format!("{} without an S3 configuration", operation),
+            })?;
+        let client = versions.get().await?;
+        let status = client
+            .get_versioning_status()
+            .await
+            .map_err(|e| map_s3_err(e, Path::new(client.bucket())))?;
+        require_versioning(status, client.bucket(), operation)?;
+        Ok(client)
+    }
+}
+
+/// Suspended buckets still hold their earlier versions, so only a bucket
+/// that never had versioning enabled is rejected
+#[cfg(feature = "s3-cli")]
+fn require_versioning(
+    status: crate::protocol::s3::versioning::VersioningStatus,
+    bucket: &str,
+    operation: &str,
+) -> BackendResult<()> {
+    use crate::protocol::s3::versioning::VersioningStatus;
+
+    match status {
+        VersioningStatus::Enabled | VersioningStatus::Suspended => Ok(()),
+        VersioningStatus::Disabled => Err(BackendError::InvalidConfig {
+            backend: "s3".to_string(),
+            message: format!(
+                "cannot {}: versioning is not enabled on bucket '{}'",
+                operation, bucket
+            ),
+        }),
+    }
+}
+
+#[cfg(feature = "s3-cli")]
+fn map_s3_err(e: crate::protocol::s3::S3Error, path: &Path) -> BackendError {
+    use crate::protocol::s3::S3Error;
+    match e {
+        S3Error::NotFound { .. } => BackendError::NotFound {
+            path: path.to_path_buf(),
+            backend: "s3".to_string(),
+        },
+        S3Error::AccessDenied(message) => BackendError::PermissionDenied {
+            path: path.to_path_buf(),
+            message,
+        },
+        other => BackendError::Other {
+            backend: "s3".to_string(),
+            message: other.to_string(),
+        },
+    }
 }
 
 fn build_store(config: &S3BackendConfig) -> BackendResult<object_store::aws::AmazonS3> {
-    let mut builder = AmazonS3Builder::from_env().with_bucket_name(&config.bucket);
+    // ETagMatch sends native If-Match / If-None-Match headers, which backs
+    // both `PutMode::Create` and the conditional writes in `WriteOptions`.
+    let mut builder = AmazonS3Builder::from_env()
+        .with_bucket_name(&config.bucket)
+        .with_conditional_put(S3ConditionalPut::ETagMatch);
 
     if let Some(region) = &config.region {
         builder = builder.with_region(region);
@@ -130,6 +1002,26 @@ fn build_store(config: &S3BackendConfig) -> BackendResult<object_store::aws::Ama
     })
 }
 
+/// One store per endpoint, primary first, behind per-endpoint breakers
+fn build_failover_store(config: &S3BackendConfig) -> BackendResult<FailoverStore> {
+    let endpoints = std::iter::once(config.endpoint.clone())
+        .chain(config.fallback_endpoints.iter().cloned().map(Some));
+    let mut stores: Vec<(String, Arc<dyn EndpointStore>)> = Vec::new();
+    for endpoint in endpoints {
+        let name = endpoint.clone().unwrap_or_else(|| "aws".to_string());
+        let endpoint_config = S3BackendConfig {
+            endpoint,
+            ..config.clone()
+        };
+        stores.push((name, Arc::new(build_store(&endpoint_config)?)));
+    }
+    Ok(FailoverStore::new(
+        stores,
+        DEFAULT_FAILURE_THRESHOLD,
+        DEFAULT_BREAKER_TIMEOUT,
+    ))
+}
+
 fn map_store_err(e: object_store::Error, path: &Path) -> BackendError {
     use object_store::Error as OsErr;
     match &e {
@@ -146,6 +1038,10 @@ fn map_store_err(e: object_store::Error, path: &Path) -> BackendError {
         OsErr::AlreadyExists { .. } => BackendError::AlreadyExists {
             path: path.to_path_buf(),
         },
+        OsErr::Precondition { .. } => BackendError::PreconditionFailed {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        },
         _ => BackendError::Other {
             backend: "s3".to_string(),
             message: e.to_string(),
@@ -157,10 +1053,76 @@ fn is_not_found(e: &object_store::Error) -> bool {
     matches!(e, object_store::Error::NotFound { .. })
 }
 
-fn build_attributes(options: &WriteOptions) -> Attributes {
+/// Buffer the body of a single-PUT upload, failing once it exceeds `limit`
+/// bytes rather than buffering an object that would be rejected anyway.
+///
+/// A size hint over the limit fails before anything is read.
+async fn read_single_put_body(
+    reader: &mut (dyn AsyncRead + Unpin + Send),
+    size_hint: Option<u64>,
+    limit: u64,
+    path: &Path,
+) -> BackendResult<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
+
+    let too_large = || BackendError::Other {
+        backend: "s3".to_string(),
+        message: format!(
+            "{} exceeds the {} byte limit for a single PUT; conditional writes \
+             (If-Match / If-None-Match) cannot use multipart, so write larger \
+             objects without a precondition",
+            path.display(),
+            limit
+        ),
+    };
+    if size_hint.is_some_and(|s| s > limit) {
+        return Err(too_large());
+    }
+
+    let cap = size_hint.unwrap_or(0).min(MULTIPART_THRESHOLD) as usize;
+    let mut buffer = Vec::with_capacity(cap);
+    reader
+        .take(limit + 1)
+        .read_to_end(&mut buffer)
+        .await
+        .map_err(BackendError::from)?;
+    if buffer.len() as u64 > limit {
+        return Err(too_large());
+    }
+    Ok(buffer)
+}
+
+/// Resolve the PUT mode from overwrite and ETag preconditions.
+fn put_mode(options: &WriteOptions, path: &Path) -> BackendResult<PutMode> {
+    match (&options.if_match, options.if_none_match.as_deref()) {
+        (Some(_), Some(_)) => Err(BackendError::InvalidConfig {
+            backend: "s3".to_string(),
+            message: "if_match and if_none_match cannot be combined".to_string(),
+        }),
+        (Some(etag), None) => Ok(PutMode::Update(UpdateVersion {
+            e_tag: Some(etag.clone()),
+            version: None,
+        })),
+        (None, Some("*")) => Ok(PutMode::Create),
+        (None, Some(other)) => Err(BackendError::InvalidPath {
+            path: path.to_path_buf(),
+            reason: format!("S3 only supports If-None-Match: *, got {:?}", other),
+        }),
+        (None, None) if options.overwrite => Ok(PutMode::Overwrite),
+        (None, None) => Ok(PutMode::Create),
+    }
+}
+
+/// Object attributes for a write: the content type (guessed from the
+/// extension when not given) and custom `x-amz-meta-*` metadata
+fn build_attributes(path: &Path, options: &WriteOptions) -> Attributes {
     let mut attrs = Attributes::new();
-    if let Some(ct) = &options.content_type {
-        attrs.insert(Attribute::ContentType, AttributeValue::from(ct.clone()));
+    if let Some(ct) = options
+        .content_type
+        .clone()
+        .or_else(|| guess_content_type(path))
+    {
+        attrs.insert(Attribute::ContentType, AttributeValue::from(ct));
     }
     if let Some(meta) = &options.metadata {
         for (k, v) in meta {
@@ -204,12 +1166,33 @@ impl Backend for S3Backend {
     )]
     async fn stat(&self, path: &Path) -> BackendResult<Metadata> {
         let object_path = self.path_to_key(path);
-        let meta = self
+        // A HEAD through get_opts also returns the content type and
+        // x-amz-meta-* attributes, which head() drops
+        let options = GetOptions {
+            head: true,
+            ..Default::default()
+        };
+        let result = self
             .store
-            .head(&object_path)
+            .get_opts(&object_path, options)
             .await
             .map_err(|e| map_store_err(e, path))?;
-        Ok(self.convert_meta(&meta))
+
+        let mut metadata = self.convert_meta(&result.meta);
+        let mut custom = std::collections::HashMap::new();
+        for (attribute, value) in &result.attributes {
+            match attribute {
+                Attribute::ContentType => metadata.content_type = Some(value.as_ref().to_string()),
+                Attribute::Metadata(key) => {
+                    custom.insert(key.to_string(), value.as_ref().to_string());
+                }
+                _ => {}
+            }
+        }
+        if !custom.is_empty() {
+            metadata.custom_metadata = Some(custom);
+        }
+        Ok(metadata)
     }
 
     #[tracing::instrument(
@@ -325,38 +1308,53 @@ impl Backend for S3Backend {
         use tokio::io::AsyncReadExt;
 
         let object_path = self.path_to_key(path);
-        let attributes = build_attributes(&options);
-        let use_multipart = size_hint.is_none_or(|s| s >= MULTIPART_THRESHOLD);
+        let attributes = build_attributes(path, &options);
+        let mode = put_mode(&options, path)?;
+        // Multipart uploads cannot carry preconditions, so conditional writes
+        // always take the single-PUT path, which buffers the body and is
+        // capped at MAX_CONDITIONAL_PUT_SIZE.
+        let use_multipart =
+            !options.is_conditional() && size_hint.is_none_or(|s| s >= MULTIPART_THRESHOLD);
 
         if !use_multipart {
             // Small object: single PUT preserves atomic Create semantics
             // for the `!overwrite` case via PutMode::Create.
-            let cap = size_hint.unwrap_or(0).min(MULTIPART_THRESHOLD) as usize;
-            let mut buffer = Vec::with_capacity(cap);
-            let bytes_read = reader
-                .read_to_end(&mut buffer)
-                .await
-                .map_err(BackendError::from)?;
+            let limit = if options.is_conditional() {
+                MAX_CONDITIONAL_PUT_SIZE
+            } else {
+                MAX_SINGLE_PUT_SIZE
+            };
+            let buffer = read_single_put_body(&mut *reader, size_hint, limit, path).await?;
+            let bytes_read = buffer.len();
 
             let put_opts = PutOptions {
-                mode: if options.overwrite {
-                    PutMode::Overwrite
-                } else {
-                    PutMode::Create
-                },
+                mode,
                 attributes,
                 ..Default::default()
             };
 
-            self.store
+            let result = self
+                .store
                 .put_opts(
                     &object_path,
                     PutPayload::from_bytes(Bytes::from(buffer)),
                     put_opts,
                 )
-                .await
-                .map_err(|e| map_store_err(e, path))?;
-            return Ok(bytes_read as u64);
+                .await;
+            return match result {
+                Ok(_) => Ok(bytes_read as u64),
+                // A create-only conflict surfaces as AlreadyExists; report it
+                // as the precondition the caller asked for.
+                Err(object_store::Error::AlreadyExists { source, .. })
+                    if options.if_none_match.is_some() =>
+                {
+                    Err(BackendError::PreconditionFailed {
+                        path: path.to_path_buf(),
+                        message: format!("If-None-Match: * failed: {}", source),
+                    })
+                }
+                Err(e) => Err(map_store_err(e, path)),
+            };
         }
 
         // Streaming multipart path. object_store's multipart API has no
@@ -528,8 +1526,75 @@ impl Backend for S3Backend {
         "s3"
     }
 
-    fn supports(&self, operation: &str) -> bool {
-        matches!(
+    /// Trashed objects are copied under `trash/` within the backend prefix
+    fn trash_dir(&self) -> &Path {
+        Path::new("trash")
+    }
+
+    /// Copy the object, or every object under the prefix, into a new trash
+    /// group and then delete the originals. No directory marker is written
+    /// for the group.
+    #[tracing::instrument(
+        skip(self),
+        fields(otel.kind = "client", backend = "s3", path = %path.display())
+    )]
+    async fn move_to_trash(&self, path: &Path) -> BackendResult<PathBuf> {
+        let trashed = trash_entry_path(self.trash_dir(), path, std::time::SystemTime::now());
+        let src = self.path_to_key(path);
+        let dest = self.path_to_key(&trashed);
+
+        let mut moves = Vec::new();
+        match self.store.head(&src).await {
+            Ok(_) => moves.push((src.clone(), dest.clone())),
+            Err(e) if is_not_found(&e) => {}
+            Err(e) => return Err(map_store_err(e, path)),
+        }
+        let listing = self.store.list(Some(&src));
+        futures::pin_mut!(listing);
+        while let Some(meta) = listing.next().await {
+            let meta = meta.map_err(|e| map_store_err(e, path))?;
+            let relative = meta
+                .location
+                .as_ref()
+                .strip_prefix(src.as_ref())
+                .unwrap_or_default()
+                .trim_start_matches('/');
+            if relative.is_empty() {
+                continue;
+            }
+            let target = ObjectPath::from(format!("{}/{}", dest, relative));
+            moves.push((meta.location, target));
+        }
+        if moves.is_empty() {
+            return Err(BackendError::NotFound {
+                path: path.to_path_buf(),
+                backend: "s3".to_string(),
+            });
+        }
+
+        for (from, to) in &moves {
+            self.store
+                .copy(from, to)
+                .await
+                .map_err(|e| map_store_err(e, path))?;
+        }
+        for (from, _) in &moves {
+            self.store
+                .delete(from)
+                .await
+                .map_err(|e| map_store_err(e, path))?;
+        }
+        tracing::info!(
+            "Moved {} ({} objects) to {}",
+            path.display(),
+            moves.len(),
+            trashed.display()
+        );
+        Ok(trashed)
+    }
+
+    fn supports(&self, operation: &str) -> bool {
+        matches!(
             operation,
             "stat" | "list" | "read" | "write" | "delete" | "mkdir" | "rename" | "exists"
         )
@@ -541,10 +1606,8 @@ mod tests {
     use super::*;
 
     fn make_backend(prefix: Option<&str>) -> S3Backend {
-        S3Backend {
-            store: Arc::new(object_store::memory::InMemory::new()),
-            prefix: prefix.map(|s| s.to_string()),
-        }
+        let store = Arc::new(object_store::memory::InMemory::new());
+        S3Backend::from_stores(store.clone(), store, prefix.map(|s| s.to_string()))
     }
 
     #[test]
@@ -608,7 +1671,7 @@ mod tests {
             .with_content_type("application/json".to_string())
             .with_metadata(metadata);
 
-        let attrs = build_attributes(&options);
+        let attrs = build_attributes(Path::new("data.bin"), &options);
         assert_eq!(
             attrs.get(&Attribute::ContentType).map(|v| v.as_ref()),
             Some("application/json")
@@ -629,19 +1692,34 @@ mod tests {
 
     #[test]
     fn test_build_attributes_empty_when_unset() {
-        let attrs = build_attributes(&WriteOptions::new());
+        let attrs = build_attributes(Path::new("data.bin"), &WriteOptions::new());
         assert!(attrs.is_empty());
     }
 
+    #[test]
+    fn test_build_attributes_guesses_content_type() {
+        let attrs = build_attributes(Path::new("site/index.html"), &WriteOptions::new());
+        assert_eq!(
+            attrs.get(&Attribute::ContentType).map(|v| v.as_ref()),
+            Some("text/html")
+        );
+
+        // An explicit content type wins over the extension
+        let options = WriteOptions::new().with_content_type("text/plain".to_string());
+        let attrs = build_attributes(Path::new("site/index.html"), &options);
+        assert_eq!(
+            attrs.get(&Attribute::ContentType).map(|v| v.as_ref()),
+            Some("text/plain")
+        );
+    }
+
     // === Integration tests against an in-memory ObjectStore ===
     // These exercise the prefix-boundary fix and streaming write logic
     // end-to-end without touching the network.
 
     fn in_memory_backend(prefix: Option<&str>) -> S3Backend {
-        S3Backend {
-            store: Arc::new(object_store::memory::InMemory::new()),
-            prefix: prefix.map(|s| s.to_string()),
-        }
+        let store = Arc::new(object_store::memory::InMemory::new());
+        S3Backend::from_stores(store.clone(), store, prefix.map(|s| s.to_string()))
     }
 
     async fn put_bytes(backend: &S3Backend, path: &str, data: &[u8]) {
@@ -772,4 +1850,1211 @@ mod tests {
             .expect_err("should reject overwrite");
         assert!(matches!(err, BackendError::AlreadyExists { .. }));
     }
+
+    async fn write_with(
+        backend: &S3Backend,
+        path: &str,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> BackendResult<u64> {
+        let reader: Box<dyn AsyncRead + Unpin + Send> =
+            Box::new(std::io::Cursor::new(data.to_vec()));
+        backend
+            .write(Path::new(path), reader, Some(data.len() as u64), options)
+            .await
+    }
+
+    #[tokio::test]
+    async fn write_if_none_match_fails_when_object_exists() {
+        let backend = in_memory_backend(None);
+
+        write_with(
+            &backend,
+            "new.txt",
+            b"v1",
+            WriteOptions::new().if_none_match_any(),
+        )
+        .await
+        .expect("create-only write of a new key should succeed");
+
+        let err = write_with(
+            &backend,
+            "new.txt",
+            b"v2",
+            WriteOptions::new().if_none_match_any(),
+        )
+        .await
+        .expect_err("create-only write must not replace an existing object");
+        assert!(matches!(err, BackendError::PreconditionFailed { .. }));
+    }
+
+    #[tokio::test]
+    async fn write_if_match_succeeds_with_current_etag_and_fails_when_stale() {
+        let backend = in_memory_backend(None);
+        put_bytes(&backend, "doc.txt", b"v1").await;
+
+        let stale = backend
+            .stat(Path::new("doc.txt"))
+            .await
+            .unwrap()
+            .etag
+            .expect("in-memory store reports ETags");
+
+        write_with(
+            &backend,
+            "doc.txt",
+            b"v2",
+            WriteOptions::new().with_if_match(stale.clone()),
+        )
+        .await
+        .expect("write with the current ETag should succeed");
+
+        // `stale` no longer matches after the v2 write
+        let err = write_with(
+            &backend,
+            "doc.txt",
+            b"v3",
+            WriteOptions::new().with_if_match(stale),
+        )
+        .await
+        .expect_err("write with a stale ETag should fail");
+        assert!(matches!(err, BackendError::PreconditionFailed { .. }));
+
+        let mut stream = backend.read(Path::new("doc.txt")).await.unwrap();
+        let mut buf = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            buf.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(buf, b"v2");
+    }
+
+    #[tokio::test]
+    async fn conditional_write_above_threshold_uses_single_put() {
+        let size: usize = 16 * 1024 * 1024;
+        let payload = vec![7u8; size];
+        let backend = in_memory_backend(None);
+        put_bytes(&backend, "big.bin", b"old").await;
+
+        // The multipart path has no conditional mode; a create-only write of
+        // a large object must still be rejected atomically.
+        let err = write_with(
+            &backend,
+            "big.bin",
+            &payload,
+            WriteOptions::new().if_none_match_any(),
+        )
+        .await
+        .expect_err("create-only write must fail for an existing key");
+        assert!(matches!(err, BackendError::PreconditionFailed { .. }));
+    }
+
+    #[tokio::test]
+    async fn conditional_write_rejects_objects_over_the_buffered_cap() {
+        let backend = in_memory_backend(None);
+        let err = backend
+            .write(
+                Path::new("huge.bin"),
+                Box::new(&b"never read"[..]),
+                Some(MAX_CONDITIONAL_PUT_SIZE + 1),
+                WriteOptions::new().if_none_match_any(),
+            )
+            .await
+            .expect_err("a conditional write over the cap must not be buffered");
+        assert!(
+            matches!(err, BackendError::Other { ref message, .. } if message.contains("huge.bin"))
+        );
+        assert!(all_keys(&backend).await.is_empty());
+
+        // A body longer than its hint is cut off at the limit, not buffered
+        let mut reader: &[u8] = &[1u8; 64];
+        let err = read_single_put_body(&mut reader, Some(8), 32, Path::new("big.bin"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BackendError::Other { .. }));
+        let mut reader: &[u8] = &[1u8; 32];
+        let body = read_single_put_body(&mut reader, None, 32, Path::new("ok.bin"))
+            .await
+            .unwrap();
+        assert_eq!(body.len(), 32);
+    }
+
+    async fn all_keys(backend: &S3Backend) -> Vec<String> {
+        let mut keys: Vec<String> = backend
+            .store
+            .list(None)
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn move_to_trash_moves_objects_and_prefixes_without_markers() {
+        let backend = make_backend(Some("root"));
+        for key in ["docs/a.txt", "docs/sub/b.txt", "docs2/c.txt", "notes.txt"] {
+            put_bytes(&backend, key, key.as_bytes()).await;
+        }
+
+        let trashed_dir = backend.move_to_trash(Path::new("docs")).await.unwrap();
+        let trashed_file = backend.move_to_trash(Path::new("notes.txt")).await.unwrap();
+        assert_ne!(trashed_dir.parent(), trashed_file.parent());
+
+        let dir_key = format!("root/{}", trashed_dir.display());
+        let file_key = format!("root/{}", trashed_file.display());
+        let mut expected = vec![
+            "root/docs2/c.txt".to_string(),
+            format!("{}/a.txt", dir_key),
+            format!("{}/sub/b.txt", dir_key),
+            file_key,
+        ];
+        expected.sort();
+        assert_eq!(all_keys(&backend).await, expected);
+
+        // Trashing the same key twice in a row uses two groups
+        put_bytes(&backend, "notes.txt", b"again").await;
+        let again = backend.move_to_trash(Path::new("notes.txt")).await.unwrap();
+        assert_ne!(again, trashed_file);
+
+        assert!(matches!(
+            backend.move_to_trash(Path::new("missing")).await,
+            Err(BackendError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn purge_trash_removes_aged_groups_on_s3() {
+        let backend = make_backend(Some("root"));
+        let week_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(7 * 86400);
+        let old = trash_entry_path(backend.trash_dir(), Path::new("old.txt"), week_ago);
+        put_bytes(&backend, &old.to_string_lossy(), b"old").await;
+        put_bytes(&backend, "new.txt", b"new").await;
+        let fresh = backend.move_to_trash(Path::new("new.txt")).await.unwrap();
+
+        let purged = backend
+            .purge_trash(std::time::Duration::from_secs(86400))
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 1);
+        assert_eq!(
+            all_keys(&backend).await,
+            vec![format!("root/{}", fresh.display())]
+        );
+    }
+
+    #[test]
+    fn coalesce_ranges_merges_overlapping_adjacent_and_near_ranges() {
+        let ranges = [50..60, 0..10, 10..20, 5..8, 15..30, 100..100, 1000..1010];
+        assert_eq!(coalesce_ranges(&ranges, 0), vec![0..30, 50..60, 1000..1010]);
+        assert_eq!(coalesce_ranges(&ranges, 20), vec![0..60, 1000..1010]);
+        assert!(coalesce_ranges(&[7..7, 9..9], 0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn read_ranges_matches_individually_fetched_ranges() {
+        let backend = in_memory_backend(Some("root"));
+        let data: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+        put_bytes(&backend, "blob.bin", &data).await;
+
+        let ranges = vec![
+            900_000..900_100,
+            0..4096,
+            4096..8192,
+            2_000..6_000,
+            10_000..10_000,
+            500_000..700_000,
+            999_990..1_000_000,
+        ];
+        let buffers = backend
+            .read_ranges(Path::new("blob.bin"), &ranges)
+            .await
+            .unwrap();
+        assert_eq!(buffers.len(), ranges.len());
+
+        let key = backend.path_to_key(Path::new("blob.bin"));
+        for (range, buf) in ranges.iter().zip(&buffers) {
+            assert_eq!(&buf[..], &data[range.start as usize..range.end as usize]);
+            if range.is_empty() {
+                continue;
+            }
+            let single = backend
+                .store
+                .get_range(&key, range.start as usize..range.end as usize)
+                .await
+                .unwrap();
+            assert_eq!(buf, &single);
+        }
+
+        // Ranges past EOF are truncated, not a panic
+        let buffers = backend
+            .read_ranges(Path::new("blob.bin"), &[999_000..1_000_100, 0..10])
+            .await
+            .unwrap();
+        assert_eq!(&buffers[0][..], &data[999_000..]);
+        assert_eq!(&buffers[1][..], &data[..10]);
+
+        assert!(matches!(
+            backend
+                .read_ranges(Path::new("missing.bin"), &[0..10, 20..30])
+                .await,
+            Err(BackendError::NotFound { .. })
+        ));
+    }
+
+    fn fixed_manifest(size: u64, chunk_kib: u32, window_starts: &[u32]) -> CargoManifest {
+        let chunking = orbit_core_manifest::cargo::Chunking {
+            chunking_type: "fixed".to_string(),
+            avg_kib: None,
+            algo: None,
+            fixed_kib: Some(chunk_kib),
+        };
+        let mut cargo = CargoManifest::new("f.bin", size, chunking);
+        for (id, first_chunk) in window_starts.iter().enumerate() {
+            cargo.windows.push(orbit_core_manifest::cargo::WindowMeta {
+                id: id as u32,
+                first_chunk: *first_chunk,
+                count: 64,
+                merkle_root: String::new(),
+                overlap: Some(4),
+            });
+        }
+        cargo
+    }
+
+    #[test]
+    fn plan_window_parts_groups_whole_windows_up_to_min_size() {
+        // 1 KiB chunks, windows own 60 chunks each; the last owns the tail
+        let cargo = fixed_manifest(250 * 1024, 1, &[0, 60, 120, 180, 240]);
+        let parts = plan_window_parts(&cargo, 100 * 1024).unwrap();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].range, 0..120 * 1024);
+        assert_eq!(parts[0].windows, vec![0, 1]);
+        assert_eq!(parts[1].range, 120 * 1024..240 * 1024);
+        assert_eq!(parts[1].windows, vec![2, 3]);
+        // Last part may be short
+        assert_eq!(parts[2].range, 240 * 1024..250 * 1024);
+        assert_eq!(parts[2].windows, vec![4]);
+        assert_eq!(parts.iter().map(|p| p.index).collect::<Vec<_>>(), [0, 1, 2]);
+
+        let no_size = CargoManifest::new(
+            "f.bin",
+            10,
+            orbit_core_manifest::cargo::Chunking {
+                chunking_type: "cdc".to_string(),
+                avg_kib: None,
+                algo: None,
+                fixed_kib: None,
+            },
+        );
+        assert!(plan_window_parts(&no_size, MIN_PART_SIZE).is_err());
+    }
+
+    /// Check a plan covers the file with whole windows in S3's part limits
+    fn assert_valid_plan(cargo: &CargoManifest, parts: &[WindowPart]) {
+        assert!(parts.len() as u64 <= MAX_PARTS);
+        assert_eq!(parts.first().unwrap().range.start, 0);
+        assert_eq!(parts.last().unwrap().range.end, cargo.size);
+        for pair in parts.windows(2) {
+            assert_eq!(pair[0].range.end, pair[1].range.start);
+        }
+        for part in parts {
+            assert!(part.range.end - part.range.start <= MAX_PART_SIZE);
+        }
+        for part in &parts[..parts.len() - 1] {
+            assert!(part.range.end - part.range.start >= MIN_PART_SIZE);
+        }
+        let windows: Vec<u32> = parts.iter().flat_map(|p| p.windows.clone()).collect();
+        assert_eq!(windows, (0..cargo.windows.len() as u32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn plan_window_parts_stays_within_s3_part_count() {
+        const MIB: u64 = 1024 * 1024;
+
+        // 9,999 windows of 8 MiB: one window per part still fits
+        let starts: Vec<u32> = (0..9_999).collect();
+        let cargo = fixed_manifest(9_999 * 8 * MIB, 8 * 1024, &starts);
+        let parts = plan_window_parts(&cargo, MIN_PART_SIZE).unwrap();
+        assert_eq!(parts.len(), 9_999);
+        assert_valid_plan(&cargo, &parts);
+
+        // 25,000 windows would need 25,000 parts at the minimum size; the
+        // part size grows so the plan fits in 10,000
+        let starts: Vec<u32> = (0..25_000).collect();
+        let cargo = fixed_manifest(25_000 * 8 * MIB, 8 * 1024, &starts);
+        let parts = plan_window_parts(&cargo, MIN_PART_SIZE).unwrap();
+        assert!(parts.len() <= 10_000);
+        assert_valid_plan(&cargo, &parts);
+    }
+
+    #[test]
+    fn plan_window_parts_keeps_parts_under_five_gib() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        // 3 GiB windows with a 4 GiB target: two windows would make a 6 GiB
+        // part, so each window is a part of its own
+        let cargo = fixed_manifest(12 * GIB, 1024 * 1024, &[0, 3, 6, 9]);
+        let parts = plan_window_parts(&cargo, 4 * GIB).unwrap();
+        assert_eq!(parts.len(), 4);
+        assert_valid_plan(&cargo, &parts);
+
+        // A single window over 5 GiB cannot be sent as one part
+        let cargo = fixed_manifest(12 * GIB, 1024 * 1024, &[0, 6]);
+        assert!(matches!(
+            plan_window_parts(&cargo, MIN_PART_SIZE),
+            Err(BackendError::InvalidConfig { .. })
+        ));
+    }
+
+    /// Multipart store that fails one part index until told otherwise and
+    /// counts the parts it accepts. Parts are held here and written to the
+    /// inner store on completion, because `InMemory`'s own multipart support
+    /// only accepts parts in index order.
+    #[derive(Default)]
+    struct FlakyMultipart {
+        inner: Arc<object_store::memory::InMemory>,
+        fail_part: std::sync::Mutex<Option<usize>>,
+        parts: std::sync::Mutex<std::collections::BTreeMap<(String, usize), Bytes>>,
+        parts_sent: std::sync::atomic::AtomicUsize,
+        next_id: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MultipartStore for FlakyMultipart {
+        async fn create_multipart(
+            &self,
+            _path: &ObjectPath,
+        ) -> object_store::Result<object_store::MultipartId> {
+            let id = self
+                .next_id
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(id.to_string())
+        }
+
+        async fn put_part(
+            &self,
+            _path: &ObjectPath,
+            id: &object_store::MultipartId,
+            part_idx: usize,
+            data: PutPayload,
+        ) -> object_store::Result<PartId> {
+            if *self.fail_part.lock().unwrap() == Some(part_idx) {
+                return Err(object_store::Error::Generic {
+                    store: "flaky",
+                    source: "connection reset".into(),
+                });
+            }
+            self.parts_sent
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let bytes: Bytes = data.into_iter().flatten().collect::<Vec<u8>>().into();
+            self.parts
+                .lock()
+                .unwrap()
+                .insert((id.clone(), part_idx), bytes);
+            Ok(PartId {
+                content_id: part_idx.to_string(),
+            })
+        }
+
+        async fn complete_multipart(
+            &self,
+            path: &ObjectPath,
+            id: &object_store::MultipartId,
+            parts: Vec<PartId>,
+        ) -> object_store::Result<object_store::PutResult> {
+            let mut body = Vec::new();
+            {
+                let stored = self.parts.lock().unwrap();
+                for (idx, part) in parts.iter().enumerate() {
+                    assert_eq!(part.content_id, idx.to_string());
+                    body.extend_from_slice(&stored[&(id.clone(), idx)]);
+                }
+            }
+            self.inner.put(path, body.into()).await
+        }
+
+        async fn abort_multipart(
+            &self,
+            _path: &ObjectPath,
+            id: &object_store::MultipartId,
+        ) -> object_store::Result<()> {
+            self.parts
+                .lock()
+                .unwrap()
+                .retain(|(upload, _), _| upload != id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn windowed_upload_resumes_only_unfinished_windows() {
+        use crate::config::{ChunkingStrategy, CopyConfig};
+        use crate::manifest_integration::{verify_file_windows, ManifestGenerator};
+        use std::sync::atomic::Ordering;
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("big.bin");
+        let data: Vec<u8> = (0..24 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let manifests = dir.path().join("manifests");
+        let config = CopyConfig {
+            manifest_output_dir: Some(manifests.clone()),
+            chunking_strategy: ChunkingStrategy::Fixed { size_kib: 16 },
+            ..Default::default()
+        };
+        let mut generator = ManifestGenerator::new(&source, &source, &config).unwrap();
+        generator
+            .generate_file_manifest(&source, "big.bin")
+            .unwrap();
+        let cargo = CargoManifest::load(manifests.join("big.bin.cargo.json")).unwrap();
+
+        let inner = Arc::new(object_store::memory::InMemory::new());
+        let flaky = Arc::new(FlakyMultipart {
+            inner: inner.clone(),
+            fail_part: std::sync::Mutex::new(Some(2)),
+            ..Default::default()
+        });
+        let backend = S3Backend::from_stores(inner, flaky.clone(), None);
+        let sidecar = dir.path().join("big.bin.orbit_s3_upload");
+        let dest = Path::new("uploads/big.bin");
+
+        let plan = plan_window_parts(&cargo, MIN_PART_SIZE).unwrap();
+        assert!(
+            plan.len() >= 4,
+            "expected a multi-part plan, got {:?}",
+            plan
+        );
+
+        // First attempt dies on part 2; parts already in flight are recorded
+        assert!(backend
+            .upload_windowed(&source, dest, &cargo, &sidecar)
+            .await
+            .is_err());
+        assert!(!backend.exists(dest).await.unwrap());
+        let recorded = WindowedUploadState::load(&sidecar).unwrap();
+        let recorded: std::collections::HashSet<usize> =
+            recorded.parts.iter().map(|p| p.index).collect();
+        assert!(recorded.contains(&0) && recorded.contains(&1));
+        assert!(!recorded.contains(&2));
+
+        // Resume sends exactly the parts that were not recorded
+        *flaky.fail_part.lock().unwrap() = None;
+        flaky.parts_sent.store(0, Ordering::SeqCst);
+        let report = backend
+            .upload_windowed(&source, dest, &cargo, &sidecar)
+            .await
+            .unwrap();
+        assert_eq!(report.parts, plan);
+        assert_eq!(report.parts_skipped, recorded.len());
+        assert_eq!(report.parts_uploaded, plan.len() - recorded.len());
+        assert_eq!(
+            flaky.parts_sent.load(Ordering::SeqCst),
+            report.parts_uploaded
+        );
+        let mut skipped_windows = report.windows_skipped.clone();
+        skipped_windows.sort_unstable();
+        let mut expected_windows: Vec<u32> = plan
+            .iter()
+            .filter(|p| recorded.contains(&p.index))
+            .flat_map(|p| p.windows.iter().copied())
+            .collect();
+        expected_windows.sort_unstable();
+        assert_eq!(skipped_windows, expected_windows);
+        assert!(!sidecar.exists());
+
+        // Every window is carried by exactly one part, and the object verifies
+        let mut all_windows: Vec<u32> = plan
+            .iter()
+            .flat_map(|p| p.windows.iter().copied())
+            .collect();
+        all_windows.sort_unstable();
+        assert_eq!(
+            all_windows,
+            cargo.windows.iter().map(|w| w.id).collect::<Vec<_>>()
+        );
+
+        let downloaded = dir.path().join("downloaded.bin");
+        let mut stream = backend.read(dest).await.unwrap();
+        let mut out = Vec::with_capacity(data.len());
+        while let Some(chunk) = stream.next().await {
+            out.extend_from_slice(&chunk.unwrap());
+        }
+        std::fs::write(&downloaded, &out).unwrap();
+        assert!(verify_file_windows(&downloaded, &cargo, 2).unwrap().is_ok());
+    }
+
+    async fn write_with_attributes(backend: &S3Backend, path: &str) {
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("owner".to_string(), "alice".to_string());
+        let options = WriteOptions::new()
+            .with_content_type("application/x-ndjson".to_string())
+            .with_metadata(metadata);
+        let reader: Box<dyn AsyncRead + Unpin + Send> = Box::new(&b"{}\n"[..]);
+        backend
+            .write(Path::new(path), reader, Some(3), options)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn stat_returns_written_content_type_and_metadata() {
+        let backend = in_memory_backend(Some("attrs"));
+        write_with_attributes(&backend, "events.log").await;
+
+        let meta = backend.stat(Path::new("events.log")).await.unwrap();
+        assert_eq!(meta.content_type.as_deref(), Some("application/x-ndjson"));
+        let custom = meta.custom_metadata.unwrap();
+        assert_eq!(custom.get("owner").map(String::as_str), Some("alice"));
+
+        // Without an explicit type, the extension decides
+        put_bytes(&backend, "page.html", b"<p>").await;
+        let meta = backend.stat(Path::new("page.html")).await.unwrap();
+        assert_eq!(meta.content_type.as_deref(), Some("text/html"));
+        assert!(meta.custom_metadata.is_none());
+    }
+
+    /// Requires an S3 service such as MinIO, configured like the versioning
+    /// test below: `S3_TESTS_ENABLED=1`, `S3_TEST_BUCKET`, `S3_TEST_ENDPOINT`,
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`.
+    #[tokio::test]
+    #[ignore]
+    async fn head_object_returns_content_type_and_metadata() {
+        if std::env::var("S3_TESTS_ENABLED").unwrap_or_default() != "1" {
+            return;
+        }
+
+        let mut config = S3BackendConfig::new(
+            std::env::var("S3_TEST_BUCKET").unwrap_or_else(|_| "orbit-test-bucket".to_string()),
+        );
+        config.region = std::env::var("S3_TEST_REGION").ok();
+        config.endpoint = std::env::var("S3_TEST_ENDPOINT").ok();
+        config.access_key = std::env::var("AWS_ACCESS_KEY_ID").ok();
+        config.secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").ok();
+        config.force_path_style = config.endpoint.is_some();
+        let prefix = format!("orbit-attrs-{}", std::process::id());
+        let backend = S3Backend::with_prefix(config, prefix).await.unwrap();
+
+        write_with_attributes(&backend, "events.log").await;
+        let meta = backend.stat(Path::new("events.log")).await.unwrap();
+        backend
+            .delete(Path::new("events.log"), false)
+            .await
+            .unwrap();
+
+        assert_eq!(meta.content_type.as_deref(), Some("application/x-ndjson"));
+        let custom = meta.custom_metadata.unwrap();
+        assert_eq!(custom.get("owner").map(String::as_str), Some("alice"));
+    }
+
+    /// Object store whose next GET stream is cut off with an error after
+    /// `cut_after` bytes, as if the connection dropped mid-transfer.
+    #[derive(Debug, Default)]
+    struct InterruptingStore {
+        inner: object_store::memory::InMemory,
+        cut_after: std::sync::Mutex<Option<usize>>,
+        ranges: std::sync::Mutex<Vec<Option<GetRange>>>,
+        if_matches: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    impl std::fmt::Display for InterruptingStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "InterruptingStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for InterruptingStore {
+        async fn put_opts(
+            &self,
+            location: &ObjectPath,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<object_store::PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &ObjectPath,
+            opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn object_store::MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &ObjectPath,
+            options: GetOptions,
+        ) -> object_store::Result<object_store::GetResult> {
+            self.ranges.lock().unwrap().push(options.range.clone());
+            self.if_matches
+                .lock()
+                .unwrap()
+                .push(options.if_match.clone());
+            let mut result = self.inner.get_opts(location, options).await?;
+            let cut_after = self.cut_after.lock().unwrap().take();
+            if let Some(limit) = cut_after {
+                let meta = result.meta.clone();
+                let range = result.range.clone();
+                let attributes = result.attributes.clone();
+                let body = result.bytes().await?;
+                let items = vec![
+                    Ok(body.slice(..limit)),
+                    Err(object_store::Error::Generic {
+                        store: "interrupting",
+                        source: "connection reset".into(),
+                    }),
+                ];
+                result = object_store::GetResult {
+                    payload: object_store::GetResultPayload::Stream(
+                        futures::stream::iter(items).boxed(),
+                    ),
+                    meta,
+                    range,
+                    attributes,
+                };
+            }
+            Ok(result)
+        }
+
+        async fn head(&self, location: &ObjectPath) -> object_store::Result<ObjectMeta> {
+            self.inner.head(location).await
+        }
+
+        async fn delete(&self, location: &ObjectPath) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&ObjectPath>,
+        ) -> futures::stream::BoxStream<'_, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&ObjectPath>,
+        ) -> object_store::Result<object_store::ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &ObjectPath, to: &ObjectPath) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            from: &ObjectPath,
+            to: &ObjectPath,
+        ) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    #[tokio::test]
+    async fn resumable_download_continues_after_interruption() {
+        let data: Vec<u8> = (0..300_000u32).map(|i| (i % 241) as u8).collect();
+        let checksum = blake3::hash(&data).to_hex().to_string();
+
+        let store = Arc::new(InterruptingStore::default());
+        let multipart = Arc::new(object_store::memory::InMemory::new());
+        let backend = S3Backend::from_stores(store.clone(), multipart, None);
+        let key = Path::new("objects/big.bin");
+        store
+            .inner
+            .put(&ObjectPath::from("objects/big.bin"), data.clone().into())
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("big.bin");
+        let partial = partial_download_path(&dest);
+
+        // The connection drops after 100 000 bytes; they are kept
+        *store.cut_after.lock().unwrap() = Some(100_000);
+        let err = backend
+            .download_resumable_verified(key, &dest, &checksum)
+            .await
+            .unwrap_err();
+        assert!(!matches!(err, BackendError::ChecksumMismatch { .. }));
+        assert_eq!(std::fs::metadata(&partial).unwrap().len(), 100_000);
+        assert!(!dest.exists());
+
+        let report = backend
+            .download_resumable_verified(key, &dest, &checksum)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            VerifiedDownloadReport {
+                resumed_from: 100_000,
+                bytes_fetched: 200_000,
+                size: 300_000,
+            }
+        );
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+        assert!(!partial.exists());
+        assert!(matches!(
+            store.ranges.lock().unwrap().as_slice(),
+            [Some(GetRange::Offset(0)), Some(GetRange::Offset(100_000))]
+        ));
+        // Both GETs were pinned to the version the download started from
+        let if_matches = store.if_matches.lock().unwrap();
+        assert_eq!(if_matches.len(), 2);
+        assert!(if_matches[0].is_some());
+        assert_eq!(if_matches[0], if_matches[1]);
+        assert!(!partial_etag_path(&partial).exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_restarts_when_object_changed() {
+        let store = Arc::new(InterruptingStore::default());
+        let multipart = Arc::new(object_store::memory::InMemory::new());
+        let backend = S3Backend::from_stores(store.clone(), multipart, None);
+        let key = Path::new("doc.txt");
+        let object = ObjectPath::from("doc.txt");
+        store
+            .inner
+            .put(&object, Bytes::from_static(b"old version").into())
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("doc.txt");
+        let partial = partial_download_path(&dest);
+
+        *store.cut_after.lock().unwrap() = Some(4);
+        let old = blake3::hash(b"old version").to_hex().to_string();
+        backend
+            .download_resumable_verified(key, &dest, &old)
+            .await
+            .unwrap_err();
+        assert_eq!(std::fs::read(&partial).unwrap(), b"old ");
+
+        // The object is overwritten before the download is resumed
+        store
+            .inner
+            .put(&object, Bytes::from_static(b"new version").into())
+            .await
+            .unwrap();
+        let new = blake3::hash(b"new version").to_hex().to_string();
+        let report = backend
+            .download_resumable_verified(key, &dest, &new)
+            .await
+            .unwrap();
+        assert_eq!(report.resumed_from, 0);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"new version");
+    }
+
+    #[tokio::test]
+    async fn resumable_download_handles_empty_object() {
+        let backend = make_backend(None);
+        let key = Path::new("empty.txt");
+        backend
+            .store
+            .put(&ObjectPath::from("empty.txt"), Bytes::new().into())
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("empty.txt");
+        let checksum = blake3::hash(b"").to_hex().to_string();
+        let report = backend
+            .download_resumable_verified(key, &dest, &checksum)
+            .await
+            .unwrap();
+        assert_eq!(report.size, 0);
+        assert_eq!(report.bytes_fetched, 0);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"");
+        assert!(!partial_download_path(&dest).exists());
+    }
+
+    #[tokio::test]
+    async fn resumable_download_rejects_checksum_mismatch() {
+        let backend = make_backend(None);
+        let key = Path::new("doc.txt");
+        backend
+            .store
+            .put(
+                &ObjectPath::from("doc.txt"),
+                Bytes::from_static(b"current").into(),
+            )
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("doc.txt");
+        let partial = partial_download_path(&dest);
+
+        // A corrupt partial recorded against the current version
+        std::fs::write(&partial, b"stale").unwrap();
+        let etag = backend
+            .store
+            .head(&ObjectPath::from("doc.txt"))
+            .await
+            .unwrap()
+            .e_tag
+            .unwrap();
+        std::fs::write(partial_etag_path(&partial), etag).unwrap();
+        let expected = blake3::hash(b"current").to_hex().to_string();
+        let err = backend
+            .download_resumable_verified(key, &dest, &expected)
+            .await
+            .unwrap_err();
+        match err {
+            BackendError::ChecksumMismatch {
+                path,
+                expected: e,
+                actual,
+            } => {
+                assert_eq!(path, key);
+                assert_eq!(e, expected);
+                assert_eq!(actual, blake3::hash(b"stalent").to_hex().to_string());
+            }
+            other => panic!("expected ChecksumMismatch, got {other:?}"),
+        }
+        assert!(!partial.exists());
+        assert!(!partial_etag_path(&partial).exists());
+        assert!(!dest.exists());
+
+        // With the bad partial gone, a retry downloads and verifies cleanly
+        let report = backend
+            .download_resumable_verified(key, &dest, &expected)
+            .await
+            .unwrap();
+        assert_eq!(report.resumed_from, 0);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"current");
+    }
+
+    /// Store whose every request fails as if the endpoint were down
+    #[derive(Debug, Default)]
+    struct UnreachableStore {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl UnreachableStore {
+        fn fail<T>(&self) -> object_store::Result<T> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(object_store::Error::Generic {
+                store: "unreachable",
+                source: "connection refused".into(),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl std::fmt::Display for UnreachableStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "UnreachableStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for UnreachableStore {
+        async fn put_opts(
+            &self,
+            _location: &ObjectPath,
+            _payload: PutPayload,
+            _opts: PutOptions,
+        ) -> object_store::Result<object_store::PutResult> {
+            self.fail()
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            _location: &ObjectPath,
+            _opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn object_store::MultipartUpload>> {
+            self.fail()
+        }
+
+        async fn get_opts(
+            &self,
+            _location: &ObjectPath,
+            _options: GetOptions,
+        ) -> object_store::Result<object_store::GetResult> {
+            self.fail()
+        }
+
+        async fn delete(&self, _location: &ObjectPath) -> object_store::Result<()> {
+            self.fail()
+        }
+
+        fn list(
+            &self,
+            _prefix: Option<&ObjectPath>,
+        ) -> futures::stream::BoxStream<'_, object_store::Result<ObjectMeta>> {
+            futures::stream::iter([self.fail()]).boxed()
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            _prefix: Option<&ObjectPath>,
+        ) -> object_store::Result<object_store::ListResult> {
+            self.fail()
+        }
+
+        async fn copy(&self, _from: &ObjectPath, _to: &ObjectPath) -> object_store::Result<()> {
+            self.fail()
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            _from: &ObjectPath,
+            _to: &ObjectPath,
+        ) -> object_store::Result<()> {
+            self.fail()
+        }
+    }
+
+    #[async_trait]
+    impl MultipartStore for UnreachableStore {
+        async fn create_multipart(
+            &self,
+            _path: &ObjectPath,
+        ) -> object_store::Result<object_store::MultipartId> {
+            self.fail()
+        }
+
+        async fn put_part(
+            &self,
+            _path: &ObjectPath,
+            _id: &object_store::MultipartId,
+            _part_idx: usize,
+            _data: PutPayload,
+        ) -> object_store::Result<PartId> {
+            self.fail()
+        }
+
+        async fn complete_multipart(
+            &self,
+            _path: &ObjectPath,
+            _id: &object_store::MultipartId,
+            _parts: Vec<PartId>,
+        ) -> object_store::Result<object_store::PutResult> {
+            self.fail()
+        }
+
+        async fn abort_multipart(
+            &self,
+            _path: &ObjectPath,
+            _id: &object_store::MultipartId,
+        ) -> object_store::Result<()> {
+            self.fail()
+        }
+    }
+
+    #[tokio::test]
+    async fn failover_routes_around_unhealthy_endpoint_and_opens_its_breaker() {
+        use crate::backend::CircuitState;
+
+        let primary = Arc::new(UnreachableStore::default());
+        let replica = Arc::new(object_store::memory::InMemory::new());
+        let failover = Arc::new(FailoverStore::new(
+            vec![
+                (
+                    "primary".to_string(),
+                    primary.clone() as Arc<dyn EndpointStore>,
+                ),
+                (
+                    "replica".to_string(),
+                    replica.clone() as Arc<dyn EndpointStore>,
+                ),
+            ],
+            3,
+            std::time::Duration::from_secs(60),
+        ));
+        let backend = S3Backend::from_stores(failover.clone(), failover.clone(), None);
+
+        for i in 0..5 {
+            put_bytes(&backend, &format!("file{}.txt", i), b"replicated").await;
+        }
+        for i in 0..5 {
+            let meta = backend
+                .stat(Path::new(&format!("file{}.txt", i)))
+                .await
+                .unwrap();
+            assert_eq!(meta.size, 10);
+        }
+
+        // The primary was tried until its breaker opened, then skipped
+        assert_eq!(primary.calls(), 3);
+        assert_eq!(
+            failover.endpoint_states().await,
+            vec![
+                ("primary".to_string(), CircuitState::Open),
+                ("replica".to_string(), CircuitState::Closed),
+            ]
+        );
+        assert!(replica.head(&ObjectPath::from("file4.txt")).await.is_ok());
+
+        // A missing object is an answer from a healthy endpoint
+        assert!(matches!(
+            backend.stat(Path::new("missing.txt")).await,
+            Err(BackendError::NotFound { .. })
+        ));
+        let entries: Vec<_> = backend
+            .list(Path::new(""), ListOptions::recursive())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 5);
+        assert_eq!(primary.calls(), 3);
+
+        // Multipart uploads stay on the endpoint that created them
+        let path = ObjectPath::from("parts.bin");
+        let id = failover.create_multipart(&path).await.unwrap();
+        assert!(id.starts_with("1:"));
+        let part = failover
+            .put_part(&path, &id, 0, Bytes::from_static(b"part").into())
+            .await
+            .unwrap();
+        failover
+            .complete_multipart(&path, &id, vec![part])
+            .await
+            .unwrap();
+        assert!(replica.head(&path).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn failover_fails_fast_when_every_breaker_is_open() {
+        let stores: Vec<Arc<UnreachableStore>> = (0..2)
+            .map(|_| Arc::new(UnreachableStore::default()))
+            .collect();
+        let failover = Arc::new(FailoverStore::new(
+            stores
+                .iter()
+                .enumerate()
+                .map(|(i, store)| (format!("ep{}", i), store.clone() as Arc<dyn EndpointStore>))
+                .collect(),
+            2,
+            std::time::Duration::from_secs(60),
+        ));
+        let backend = S3Backend::from_stores(failover.clone(), failover, None);
+
+        for _ in 0..2 {
+            let err = backend.stat(Path::new("a.txt")).await.unwrap_err();
+            assert!(err.to_string().contains("connection refused"), "{}", err);
+        }
+        assert!(stores.iter().all(|store| store.calls() == 2));
+
+        let err = backend.stat(Path::new("a.txt")).await.unwrap_err();
+        assert!(err.to_string().contains("circuit breakers open"), "{}", err);
+        assert!(stores.iter().all(|store| store.calls() == 2));
+    }
+
+    #[cfg(feature = "s3-cli")]
+    #[test]
+    fn versioning_disabled_bucket_is_rejected() {
+        use crate::protocol::s3::versioning::VersioningStatus;
+
+        let err = require_versioning(VersioningStatus::Disabled, "plain-bucket", "list versions")
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("versioning is not enabled"), "{}", message);
+        assert!(message.contains("plain-bucket"), "{}", message);
+
+        assert!(require_versioning(VersioningStatus::Enabled, "b", "x").is_ok());
+        assert!(require_versioning(VersioningStatus::Suspended, "b", "x").is_ok());
+    }
+
+    #[cfg(feature = "s3-cli")]
+    #[tokio::test]
+    async fn version_api_needs_s3_configuration() {
+        let backend = in_memory_backend(None);
+        let err = backend
+            .list_object_versions(Path::new("any"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BackendError::Unsupported { .. }));
+    }
+
+    /// Requires a versioning-capable S3 service (e.g. MinIO). Uses the same
+    /// environment as the `protocol::s3` integration tests: `S3_TESTS_ENABLED=1`,
+    /// `S3_TEST_BUCKET`, `S3_TEST_ENDPOINT`, `AWS_ACCESS_KEY_ID`,
+    /// `AWS_SECRET_ACCESS_KEY`. Versioning is enabled on the bucket.
+    #[cfg(feature = "s3-cli")]
+    #[tokio::test]
+    #[ignore]
+    async fn restore_version_makes_older_version_current() {
+        use crate::protocol::s3::{S3Client, S3Config, VersioningOperations};
+
+        if std::env::var("S3_TESTS_ENABLED").unwrap_or_default() != "1" {
+            return;
+        }
+
+        let mut config = S3BackendConfig::new(
+            std::env::var("S3_TEST_BUCKET").unwrap_or_else(|_| "orbit-test-bucket".to_string()),
+        );
+        config.region = std::env::var("S3_TEST_REGION").ok();
+        config.endpoint = std::env::var("S3_TEST_ENDPOINT").ok();
+        config.access_key = std::env::var("AWS_ACCESS_KEY_ID").ok();
+        config.secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").ok();
+        config.force_path_style = config.endpoint.is_some();
+
+        let mut admin_config = S3Config::new(config.bucket.clone());
+        admin_config.region = config.region.clone();
+        admin_config.endpoint = config.endpoint.clone();
+        admin_config.access_key = config.access_key.clone();
+        admin_config.secret_key = config.secret_key.clone();
+        admin_config.force_path_style = config.force_path_style;
+        let admin = S3Client::new(admin_config).await.unwrap();
+        admin.enable_versioning().await.unwrap();
+
+        let prefix = format!("orbit-versions-{}", std::process::id());
+        let backend = S3Backend::with_prefix(config, prefix).await.unwrap();
+
+        put_bytes(&backend, "doc.txt", b"first").await;
+        put_bytes(&backend, "doc.txt", b"second").await;
+
+        let versions = backend
+            .list_object_versions(Path::new("doc.txt"))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(versions[0].is_latest);
+        assert_eq!(versions[0].size, 6);
+        assert!(!versions[1].is_latest);
+        assert_eq!(versions[1].size, 5);
+
+        backend
+            .restore_version(Path::new("doc.txt"), &versions[1].version_id)
+            .await
+            .unwrap();
+
+        let mut stream = backend.read(Path::new("doc.txt")).await.unwrap();
+        let mut current = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            current.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(current, b"first");
+
+        let versions = backend
+            .list_object_versions(Path::new("doc.txt"))
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 3);
+        assert!(versions[0].is_latest);
+        assert_eq!(versions[0].size, 5);
+
+        for version in versions {
+            admin
+                .delete_version(
+                    &format!("{}/doc.txt", backend.prefix.as_deref().unwrap()),
+                    &version.version_id,
+                )
+                .await
+                .unwrap();
+        }
+    }
 }
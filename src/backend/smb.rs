@@ -13,7 +13,9 @@ use std::sync::Arc;
 use tokio::io::AsyncRead;
 use tokio::sync::RwLock;
 
-use crate::protocols::smb::{client_for, SmbAuth, SmbClient, SmbMetadata, SmbSecurity, SmbTarget};
+use crate::protocols::smb::{
+    client_for, SmbAuth, SmbClient, SmbConnectionInfo, SmbMetadata, SmbSecurity, SmbTarget,
+};
 
 /// SMB backend configuration
 ///
@@ -174,7 +176,10 @@ impl SmbBackend {
                 },
                 SmbError::EncryptionRequired => BackendError::InvalidConfig {
                     backend: "smb".to_string(),
-                    message: "Encryption required but not available".to_string(),
+                    message: format!(
+                        "{}\\{} does not offer SMB3 encryption, which the security policy requires",
+                        config.host, config.share
+                    ),
                 },
                 other => BackendError::Other {
                     backend: "smb".to_string(),
@@ -224,6 +229,11 @@ impl SmbBackend {
         }
     }
 
+    /// Dialect, encryption and signing negotiated with the server
+    pub async fn connection_info(&self) -> Option<SmbConnectionInfo> {
+        self.client.read().await.connection_info()
+    }
+
     /// Map SMB errors to backend errors
     fn map_error(&self, e: crate::protocols::smb::SmbError, path: &Path) -> BackendError {
         use crate::protocols::smb::SmbError;
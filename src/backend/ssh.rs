@@ -164,6 +164,7 @@ impl SshConfig {
 ///     Ok(())
 /// }
 /// ```
+#[derive(Clone)]
 pub struct SshBackend {
     #[allow(dead_code)]
     config: SshConfig,
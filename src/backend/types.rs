@@ -1,9 +1,11 @@
 //! Common types for backend abstraction
 
 use bytes::Bytes;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+pub use crate::core::trash::{trash_entry_path, trash_entry_time, trash_group_name, TRASH_DIR};
+
 #[cfg(feature = "backend-abstraction")]
 use futures::Stream;
 #[cfg(feature = "backend-abstraction")]
@@ -261,6 +263,14 @@ pub struct WriteOptions {
 
     /// File permissions (Unix-style, e.g., 0o644)
     pub permissions: Option<u32>,
+
+    /// Only write if the existing object's ETag matches (cloud storage).
+    /// A mismatch fails with `BackendError::PreconditionFailed`.
+    pub if_match: Option<String>,
+
+    /// Only write if no existing object matches (cloud storage). S3 only
+    /// accepts `"*"`, i.e. create-only.
+    pub if_none_match: Option<String>,
 }
 
 impl Default for WriteOptions {
@@ -271,6 +281,8 @@ impl Default for WriteOptions {
             content_type: None,
             metadata: None,
             permissions: None,
+            if_match: None,
+            if_none_match: None,
         }
     }
 }
@@ -304,12 +316,111 @@ impl WriteOptions {
         self.overwrite = false;
         self
     }
+
+    /// Only overwrite an object whose current ETag is `etag`
+    pub fn with_if_match(mut self, etag: String) -> Self {
+        self.if_match = Some(etag);
+        self
+    }
+
+    /// Only write if the object does not exist yet (`If-None-Match: *`)
+    pub fn if_none_match_any(mut self) -> Self {
+        self.if_none_match = Some("*".to_string());
+        self
+    }
+
+    /// Whether an ETag precondition is attached to this write
+    pub fn is_conditional(&self) -> bool {
+        self.if_match.is_some() || self.if_none_match.is_some()
+    }
+}
+
+/// Options for delete operations
+#[derive(Debug, Clone, Default)]
+pub struct DeleteOptions {
+    /// Delete directories and their contents
+    pub recursive: bool,
+
+    /// Move the entry into the backend's trash instead of deleting it
+    pub trash: bool,
+}
+
+impl DeleteOptions {
+    /// Create options for a recursive delete
+    pub fn recursive() -> Self {
+        Self {
+            recursive: true,
+            ..Default::default()
+        }
+    }
+
+    /// Move to the trash rather than deleting
+    pub fn with_trash(mut self) -> Self {
+        self.trash = true;
+        self
+    }
+}
+
+/// Guess a MIME type from a path's extension
+///
+/// Covers common document, image, web and archive formats; anything else
+/// returns `None` and is left to the backend's default.
+pub fn guess_content_type(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        _ => return None,
+    };
+    Some(mime.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_write_options_conditionals() {
+        let opts = WriteOptions::new();
+        assert!(!opts.is_conditional());
+
+        let opts = WriteOptions::new().with_if_match("\"abc\"".to_string());
+        assert_eq!(opts.if_match.as_deref(), Some("\"abc\""));
+        assert!(opts.is_conditional());
+
+        let opts = WriteOptions::new().if_none_match_any();
+        assert_eq!(opts.if_none_match.as_deref(), Some("*"));
+        assert!(opts.is_conditional());
+    }
+
+    #[test]
+    fn test_guess_content_type() {
+        assert_eq!(
+            guess_content_type(Path::new("site/index.HTML")).as_deref(),
+            Some("text/html")
+        );
+        assert_eq!(
+            guess_content_type(Path::new("logo.svg")).as_deref(),
+            Some("image/svg+xml")
+        );
+        assert_eq!(guess_content_type(Path::new("data.bin")), None);
+        assert_eq!(guess_content_type(Path::new("Makefile")), None);
+    }
+
     #[test]
     fn test_metadata_file() {
         let meta = Metadata::file(1024);
@@ -3,11 +3,16 @@
  *
  * Subscribes to progress events and renders them as formatted console output
  * with progress bars, transfer rates, and ETAs.
+ *
+ * For programmatic monitoring, [`JsonProgressRenderer`] renders the same
+ * events as newline-delimited JSON (`--progress json`).
  */
 
 use crate::core::progress::{ProgressEvent, ProgressSubscriber};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Instant;
 
@@ -297,6 +302,119 @@ impl CliProgressRenderer {
     }
 }
 
+/// A single line of the JSON progress stream
+#[derive(Debug, Serialize)]
+struct JsonProgressLine<'a> {
+    event: &'static str,
+    path: &'a str,
+    bytes: u64,
+    total: u64,
+}
+
+/// JSON progress renderer for supervising processes
+///
+/// Emits one JSON object per line for every file-level event:
+/// `{"event":"file_start"|"file_progress"|"file_done","path":...,"bytes":...,"total":...}`.
+/// Non file-level events (scans, resume decisions, chunk verification) are not emitted.
+pub struct JsonProgressRenderer<W: Write> {
+    subscriber: ProgressSubscriber,
+    writer: W,
+    active_transfers: HashMap<String, (PathBuf, u64)>,
+}
+
+impl<W: Write> JsonProgressRenderer<W> {
+    /// Create a new JSON renderer writing to `writer`
+    pub fn new(subscriber: ProgressSubscriber, writer: W) -> Self {
+        Self {
+            subscriber,
+            writer,
+            active_transfers: HashMap::new(),
+        }
+    }
+
+    /// Run until all publishers are dropped, returning the writer
+    pub fn run(mut self) -> io::Result<W> {
+        while let Some(event) = self.subscriber.recv() {
+            self.handle_event(event)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Spawn the renderer in a background thread
+    pub fn spawn(self) -> thread::JoinHandle<io::Result<W>>
+    where
+        W: Send + 'static,
+    {
+        thread::spawn(move || self.run())
+    }
+
+    fn handle_event(&mut self, event: ProgressEvent) -> io::Result<()> {
+        match event {
+            ProgressEvent::TransferStart {
+                file_id,
+                source,
+                total_bytes,
+                ..
+            } => {
+                self.write_line("file_start", &source, 0, total_bytes)?;
+                self.active_transfers
+                    .insert(file_id.as_str().to_string(), (source, total_bytes));
+            }
+            ProgressEvent::TransferProgress {
+                file_id,
+                bytes_transferred,
+                total_bytes,
+                ..
+            } => {
+                if let Some((path, _)) = self.active_transfers.get(file_id.as_str()) {
+                    let path = path.clone();
+                    self.write_line("file_progress", &path, bytes_transferred, total_bytes)?;
+                }
+            }
+            ProgressEvent::TransferComplete {
+                file_id,
+                total_bytes,
+                ..
+            } => {
+                if let Some((path, total)) = self.active_transfers.remove(file_id.as_str()) {
+                    self.write_line("file_done", &path, total_bytes, total)?;
+                }
+            }
+            ProgressEvent::TransferFailed {
+                file_id,
+                bytes_transferred,
+                ..
+            } => {
+                // A failed transfer still closes the file's event sequence
+                if let Some((path, total)) = self.active_transfers.remove(file_id.as_str()) {
+                    self.write_line("file_done", &path, bytes_transferred, total)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn write_line(
+        &mut self,
+        event: &'static str,
+        path: &Path,
+        bytes: u64,
+        total: u64,
+    ) -> io::Result<()> {
+        let path = path.to_string_lossy();
+        let line = JsonProgressLine {
+            event,
+            path: &path,
+            bytes,
+            total,
+        };
+        serde_json::to_writer(&mut self.writer, &line).map_err(io::Error::other)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
 /// Print a text-based progress bar
 fn print_progress_bar(percentage: f64, width: usize) {
     let filled = ((percentage / 100.0) * width as f64) as usize;
@@ -0,0 +1,345 @@
+/*!
+ * Audit log command handlers for Orbit CLI
+ *
+ * `orbit audit verify` checks the HMAC chain of an audit log and exits with
+ * a code per failure kind, so CI can tell tampering from a truncated or
+ * garbled log.
+ */
+
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use orbit_observability::{audit_segments, validate_audit_file, AuditSigner, ChainError};
+
+use crate::cli_style::{print_error, print_info, section_header, Icons, Theme};
+use crate::error::{OrbitError, Result};
+
+/// Exit code: the log could not be verified (unreadable, no secret)
+pub const AUDIT_VERIFY_ERROR: i32 = 1;
+/// Exit code: events are missing, reordered or unsigned
+pub const AUDIT_BROKEN_CHAIN: i32 = 2;
+/// Exit code: an event's HMAC does not match its contents
+pub const AUDIT_BAD_HMAC: i32 = 3;
+/// Exit code: a line is not a valid audit event
+pub const AUDIT_MALFORMED: i32 = 4;
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// Verify an audit log's HMAC chain (secret from ORBIT_AUDIT_SECRET)
+    ///
+    /// Accepts a log file or a directory of rotated segments
+    /// (`audit.jsonl`, `audit.jsonl.1`, `audit.jsonl.2.zst`, ...), which are
+    /// verified oldest first as one chain. Exits 0 when valid, 2 for a broken
+    /// chain, 3 for a bad HMAC and 4 for malformed JSON.
+    Verify {
+        /// Audit log file or directory of segments
+        path: PathBuf,
+    },
+}
+
+/// Result of verifying an audit log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// Every event is present, in order and correctly signed
+    Valid { events: usize, segments: usize },
+    /// Sequence gap or missing integrity hash
+    BrokenChain(String),
+    /// Integrity hash does not match the event
+    BadHmac(String),
+    /// A line could not be parsed as an event
+    Malformed(String),
+}
+
+impl VerifyOutcome {
+    /// Process exit code for this outcome
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            VerifyOutcome::Valid { .. } => 0,
+            VerifyOutcome::BrokenChain(_) => AUDIT_BROKEN_CHAIN,
+            VerifyOutcome::BadHmac(_) => AUDIT_BAD_HMAC,
+            VerifyOutcome::Malformed(_) => AUDIT_MALFORMED,
+        }
+    }
+}
+
+pub fn handle_audit_command(command: AuditCommands) -> Result<()> {
+    match command {
+        AuditCommands::Verify { path } => {
+            let signer = AuditSigner::from_env()
+                .map_err(|e| OrbitError::Config(format!("Cannot verify audit log: {}", e)))?;
+            let code = handle_audit_verify(&path, &signer);
+            if code != 0 {
+                std::process::exit(code);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Verify `path` and print a summary, returning the exit code
+pub fn handle_audit_verify(path: &Path, signer: &AuditSigner) -> i32 {
+    section_header(&format!("{} Verifying Audit Log", Icons::SHIELD));
+    println!();
+    println!(
+        "  {} {}",
+        Theme::muted("Path:"),
+        Theme::value(path.display())
+    );
+    println!();
+
+    let outcome = match verify_audit_log(path, signer) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            print_error(&format!("Could not verify audit log: {}", e), None::<&str>);
+            return AUDIT_VERIFY_ERROR;
+        }
+    };
+
+    match &outcome {
+        VerifyOutcome::Valid { events, segments } => {
+            print_info(&format!(
+                "{} events in {} segment(s), chain intact",
+                events, segments
+            ));
+            crate::cli_style::print_success("Audit log verified");
+        }
+        VerifyOutcome::BrokenChain(detail) => {
+            print_error(&format!("Broken chain: {}", detail), None::<&str>)
+        }
+        VerifyOutcome::BadHmac(detail) => {
+            print_error(&format!("Bad HMAC: {}", detail), None::<&str>)
+        }
+        VerifyOutcome::Malformed(detail) => {
+            print_error(&format!("Malformed audit log: {}", detail), None::<&str>)
+        }
+    }
+
+    outcome.exit_code()
+}
+
+/// Verify the audit log at `path` (a file or a directory of segments)
+///
+/// The chain walk is [`validate_audit_file`]'s; this maps its errors onto
+/// [`VerifyOutcome`]. Errors are reserved for logs that cannot be read at
+/// all.
+pub fn verify_audit_log(path: &Path, signer: &AuditSigner) -> Result<VerifyOutcome> {
+    let segments = audit_segments(path)?;
+    if segments.is_empty() {
+        return Err(OrbitError::Config(format!(
+            "No audit log segments found in {}",
+            path.display()
+        )));
+    }
+
+    Ok(match validate_audit_file(path, signer) {
+        Ok(report) => VerifyOutcome::Valid {
+            events: report.valid_events,
+            segments: segments.len(),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+            match e
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<ChainError>())
+            {
+                Some(
+                    ChainError::IntegrityFailure { .. } | ChainError::ResumeIntegrityFailure(_),
+                ) => VerifyOutcome::BadHmac(e.to_string()),
+                Some(ChainError::Serialization(_)) | None => {
+                    VerifyOutcome::Malformed(e.to_string())
+                }
+                Some(_) => VerifyOutcome::BrokenChain(e.to_string()),
+            }
+        }
+        Err(e) => return Err(e.into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orbit_observability::{
+        ChainHead, EventCapture, EventPayload, OrbitEvent, TraceContext, UnifiedLogger,
+    };
+    use tempfile::TempDir;
+
+    const SECRET: &[u8] = b"audit-verify-test-secret";
+
+    /// Signed audit log lines for `count` events
+    fn signed_lines(count: usize) -> Vec<String> {
+        let capture = EventCapture::with_secret(SECRET);
+        let logger = capture.logger();
+        let ctx = TraceContext::new_root();
+        for i in 0..count {
+            logger
+                .emit_with_context(
+                    &ctx,
+                    EventPayload::Custom {
+                        event_type: format!("event_{}", i),
+                        data: serde_json::json!({ "value": i }),
+                    },
+                )
+                .unwrap();
+        }
+        capture
+            .events()
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect()
+    }
+
+    fn write_log(dir: &Path, name: &str, lines: &[String]) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn valid_log_exits_zero() {
+        let temp = TempDir::new().unwrap();
+        let path = write_log(temp.path(), "audit.jsonl", &signed_lines(5));
+        let signer = AuditSigner::from_bytes(SECRET);
+
+        assert_eq!(
+            verify_audit_log(&path, &signer).unwrap(),
+            VerifyOutcome::Valid {
+                events: 5,
+                segments: 1
+            }
+        );
+        assert_eq!(handle_audit_verify(&path, &signer), 0);
+    }
+
+    #[test]
+    fn tampered_logs_exit_with_failure_kind() {
+        let temp = TempDir::new().unwrap();
+        let signer = AuditSigner::from_bytes(SECRET);
+        let lines = signed_lines(5);
+
+        // Deleted event
+        let mut removed = lines.clone();
+        removed.remove(2);
+        let path = write_log(temp.path(), "removed.jsonl", &removed);
+        assert_eq!(handle_audit_verify(&path, &signer), AUDIT_BROKEN_CHAIN);
+
+        // Edited event body
+        let mut edited = lines.clone();
+        edited[1] = edited[1].replace("event_1", "event_X");
+        let path = write_log(temp.path(), "edited.jsonl", &edited);
+        assert_eq!(handle_audit_verify(&path, &signer), AUDIT_BAD_HMAC);
+
+        // Wrong secret looks like tampering too
+        let path = write_log(temp.path(), "valid.jsonl", &lines);
+        let wrong = AuditSigner::from_bytes(b"some-other-secret");
+        assert_eq!(handle_audit_verify(&path, &wrong), AUDIT_BAD_HMAC);
+
+        // Garbled line
+        let mut garbled = lines.clone();
+        garbled[3] = "{\"not\": json".to_string();
+        let path = write_log(temp.path(), "garbled.jsonl", &garbled);
+        assert_eq!(handle_audit_verify(&path, &signer), AUDIT_MALFORMED);
+
+        let missing = temp.path().join("missing.jsonl");
+        assert_eq!(handle_audit_verify(&missing, &signer), AUDIT_VERIFY_ERROR);
+    }
+
+    #[test]
+    fn rotated_and_compressed_segments_form_one_chain() {
+        let temp = TempDir::new().unwrap();
+        let signer = AuditSigner::from_bytes(SECRET);
+        let lines = signed_lines(6);
+
+        let oldest = lines[0..2].join("\n") + "\n";
+        std::fs::write(
+            temp.path().join("audit.jsonl.2.zst"),
+            zstd::encode_all(oldest.as_bytes(), 3).unwrap(),
+        )
+        .unwrap();
+        write_log(temp.path(), "audit.jsonl.1", &lines[2..4]);
+        write_log(temp.path(), "audit.jsonl", &lines[4..6]);
+
+        assert_eq!(
+            verify_audit_log(temp.path(), &signer).unwrap(),
+            VerifyOutcome::Valid {
+                events: 6,
+                segments: 3
+            }
+        );
+
+        // Losing a middle segment breaks the chain
+        std::fs::remove_file(temp.path().join("audit.jsonl.1")).unwrap();
+        assert_eq!(
+            handle_audit_verify(temp.path(), &signer),
+            AUDIT_BROKEN_CHAIN
+        );
+    }
+
+    #[test]
+    fn resumed_segment_verifies_alone_and_with_its_predecessor() {
+        let temp = TempDir::new().unwrap();
+        let signer = AuditSigner::from_bytes(SECRET);
+        let live = temp.path().join("audit.jsonl");
+        let ctx = TraceContext::new_root();
+
+        let logger = UnifiedLogger::new(Some(&live), signer.clone()).unwrap();
+        logger.emit_job_start(&ctx, 1, 100, "s3").unwrap();
+        logger.emit_file_start(&ctx, "/a", "/b", 100).unwrap();
+        drop(logger);
+        std::fs::rename(&live, temp.path().join("audit.jsonl.1")).unwrap();
+
+        // The new segment continues from audit.jsonl.head
+        let logger = UnifiedLogger::new(Some(&live), signer.clone()).unwrap();
+        logger.emit_job_complete(&ctx, 10, "digest").unwrap();
+        logger.flush().unwrap();
+        drop(logger);
+
+        assert_eq!(
+            verify_audit_log(&live, &signer).unwrap(),
+            VerifyOutcome::Valid {
+                events: 1,
+                segments: 1
+            }
+        );
+        assert_eq!(
+            verify_audit_log(temp.path(), &signer).unwrap(),
+            VerifyOutcome::Valid {
+                events: 3,
+                segments: 2
+            }
+        );
+    }
+
+    #[test]
+    fn unsigned_resume_line_breaks_the_chain() {
+        let temp = TempDir::new().unwrap();
+        let signer = AuditSigner::from_bytes(SECRET);
+        let lines = signed_lines(3);
+        let events: Vec<OrbitEvent> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let head = ChainHead {
+            sequence: 2,
+            hash: hex::decode(events[1].integrity_hash.as_ref().unwrap()).unwrap(),
+        };
+
+        let signed = vec![head.to_resume_line(&signer), lines[2].clone()];
+        let path = write_log(temp.path(), "signed.jsonl", &signed);
+        assert_eq!(handle_audit_verify(&path, &signer), 0);
+
+        // Anyone can write an unsigned resume line that skips ahead
+        let unsigned = vec![
+            format!(
+                r#"{{"chain_resume":{{"sequence":2,"hash":"{}"}}}}"#,
+                hex::encode(&head.hash)
+            ),
+            lines[2].clone(),
+        ];
+        let path = write_log(temp.path(), "unsigned.jsonl", &unsigned);
+        assert_eq!(handle_audit_verify(&path, &signer), AUDIT_BROKEN_CHAIN);
+
+        let other = AuditSigner::from_bytes(b"some-other-secret");
+        let forged = vec![head.to_resume_line(&other), lines[2].clone()];
+        let path = write_log(temp.path(), "forged.jsonl", &forged);
+        assert_eq!(handle_audit_verify(&path, &signer), AUDIT_BAD_HMAC);
+    }
+}
@@ -0,0 +1,347 @@
+/*!
+ * Orbit Chunk Command
+ *
+ * Runs content-defined chunking over a single file and prints every chunk's
+ * offset, length and BLAKE3 hash, followed by size statistics. Useful for
+ * checking chunk boundaries and dedup behaviour without running a transfer.
+ */
+
+use crate::cli_style::{format_bytes, Icons, Theme};
+use crate::error::{OrbitError, Result};
+use orbit_core_cdc::{ChunkConfig, ChunkStream};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Default average chunk size (matches the transfer default)
+pub const DEFAULT_AVG_SIZE: usize = 64 * 1024;
+
+/// Chunk size settings for `orbit chunk`
+#[derive(Debug, Clone)]
+pub struct ChunkOptions {
+    /// Target average chunk size (must be a power of two)
+    pub avg_size: usize,
+    /// Minimum chunk size (default: avg / 8)
+    pub min_size: Option<usize>,
+    /// Maximum chunk size (default: avg * 4)
+    pub max_size: Option<usize>,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            avg_size: DEFAULT_AVG_SIZE,
+            min_size: None,
+            max_size: None,
+        }
+    }
+}
+
+impl ChunkOptions {
+    /// Build the CDC configuration, filling in min and max from the average
+    pub fn chunk_config(&self) -> Result<ChunkConfig> {
+        let min = self.min_size.unwrap_or(self.avg_size / 8);
+        let max = self.max_size.unwrap_or(self.avg_size * 4);
+        ChunkConfig::new(min, self.avg_size, max).map_err(|e| OrbitError::Config(e.to_string()))
+    }
+}
+
+/// Size statistics over the chunks of one file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkSummary {
+    pub count: u64,
+    pub total_bytes: u64,
+    pub min_size: usize,
+    pub max_size: usize,
+    pub mean_size: f64,
+    /// Population standard deviation of the chunk sizes
+    pub stddev: f64,
+    /// Running sum of squared differences from the mean (Welford)
+    m2: f64,
+}
+
+impl ChunkSummary {
+    fn add(&mut self, length: usize) {
+        if self.count == 0 {
+            self.min_size = length;
+            self.max_size = length;
+        } else {
+            self.min_size = self.min_size.min(length);
+            self.max_size = self.max_size.max(length);
+        }
+        self.count += 1;
+        self.total_bytes += length as u64;
+
+        let value = length as f64;
+        let delta = value - self.mean_size;
+        self.mean_size += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean_size);
+        self.stddev = (self.m2 / self.count as f64).sqrt();
+    }
+}
+
+/// Parse a byte size such as `4096`, `64K`, `64KiB`, `1MiB` or `2MB`
+///
+/// `K`/`KiB`, `M`/`MiB` and `G`/`GiB` are binary multiples; `KB`, `MB` and
+/// `GB` are decimal. Suffixes are case-insensitive.
+pub fn parse_byte_size(value: &str) -> std::result::Result<usize, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+    let number: usize = number.parse().map_err(|_| {
+        format!(
+            "invalid size '{}': expected a number with an optional unit",
+            value
+        )
+    })?;
+    let multiplier: usize = match suffix.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        other => return Err(format!("unknown size unit '{}' in '{}'", other, value)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size '{}' is too large", value))
+}
+
+/// Chunk `path` and print the chunks and summary to stdout
+pub fn run_chunk(path: &Path, options: &ChunkOptions, json_output: bool) -> Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    write_chunks(path, options, json_output, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Chunk `path`, writing one line per chunk and then the summary to `out`
+///
+/// The text form is `offset,length,hash` lines under a header; with
+/// `json_output` every chunk and the summary are NDJSON objects tagged by
+/// `"type"`. Chunks are streamed, so memory stays at the chunker's buffer.
+pub fn write_chunks<W: Write>(
+    path: &Path,
+    options: &ChunkOptions,
+    json_output: bool,
+    out: &mut W,
+) -> Result<ChunkSummary> {
+    let config = options.chunk_config()?;
+    let file = File::open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => OrbitError::SourceNotFound(path.to_path_buf()),
+        _ => OrbitError::Io(e),
+    })?;
+
+    if !json_output {
+        writeln!(out, "offset,length,hash")?;
+    }
+
+    let mut summary = ChunkSummary::default();
+    for chunk in ChunkStream::new(file, config.clone()) {
+        let chunk = chunk.map_err(io::Error::other)?;
+        summary.add(chunk.length);
+        let hash = hex::encode(chunk.hash);
+        if json_output {
+            let line = serde_json::json!({
+                "type": "chunk",
+                "offset": chunk.offset,
+                "length": chunk.length,
+                "hash": hash,
+            });
+            writeln!(out, "{}", line)?;
+        } else {
+            writeln!(out, "{},{},{}", chunk.offset, chunk.length, hash)?;
+        }
+    }
+
+    if json_output {
+        let line = serde_json::json!({
+            "type": "summary",
+            "file": path.display().to_string(),
+            "chunks": summary.count,
+            "total_bytes": summary.total_bytes,
+            "min_size": summary.min_size,
+            "max_size": summary.max_size,
+            "mean_size": summary.mean_size,
+            "stddev": summary.stddev,
+            "config": {
+                "min_size": config.min_size,
+                "avg_size": config.avg_size,
+                "max_size": config.max_size,
+            },
+        });
+        writeln!(out, "{}", line)?;
+    } else {
+        print_summary(out, path, &summary, &config)?;
+    }
+
+    Ok(summary)
+}
+
+fn print_summary<W: Write>(
+    out: &mut W,
+    path: &Path,
+    summary: &ChunkSummary,
+    config: &ChunkConfig,
+) -> io::Result<()> {
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{} {}",
+        Icons::STATS,
+        Theme::header(format!("Chunks of {}", path.display()))
+    )?;
+    writeln!(
+        out,
+        "  {} {} ({}, {} bytes)",
+        Theme::muted("Chunks:"),
+        summary.count,
+        format_bytes(summary.total_bytes),
+        summary.total_bytes
+    )?;
+    writeln!(
+        out,
+        "  {} min {}  avg {:.0}  max {}  stddev {:.0}",
+        Theme::muted("Sizes:"),
+        summary.min_size,
+        summary.mean_size,
+        summary.max_size,
+        summary.stddev
+    )?;
+    writeln!(
+        out,
+        "  {} min {}  avg {}  max {}",
+        Theme::muted("Config:"),
+        config.min_size,
+        config.avg_size,
+        config.max_size
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orbit_core_cdc::testing::xorshift_bytes;
+    use tempfile::tempdir;
+
+    /// 300 KiB of pseudo-random bytes, so CDC finds real boundaries
+    fn write_fixture(path: &Path) -> Vec<u8> {
+        let data = xorshift_bytes(300 * 1024, 0x2545_F491_4F6C_DD1D);
+        std::fs::write(path, &data).unwrap();
+        data
+    }
+
+    fn small_chunks() -> ChunkOptions {
+        ChunkOptions {
+            avg_size: 8 * 1024,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_text_output_covers_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fixture.bin");
+        let data = write_fixture(&path);
+
+        let mut out = Vec::new();
+        let summary = write_chunks(&path, &small_chunks(), false, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let rows: Vec<(u64, u64, &str)> = text
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                (
+                    fields[0].parse().unwrap(),
+                    fields[1].parse().unwrap(),
+                    fields[2],
+                )
+            })
+            .collect();
+
+        assert!(rows.len() > 10, "expected many chunks, got {}", rows.len());
+        assert_eq!(rows.len() as u64, summary.count);
+        assert_eq!(rows.iter().map(|r| r.1).sum::<u64>(), data.len() as u64);
+        assert_eq!(summary.total_bytes, data.len() as u64);
+
+        // Chunks are contiguous and each hash is the BLAKE3 of its bytes
+        let mut expected_offset = 0;
+        for (offset, length, hash) in &rows {
+            assert_eq!(*offset, expected_offset);
+            let bytes = &data[*offset as usize..(*offset + *length) as usize];
+            assert_eq!(*hash, blake3::hash(bytes).to_hex().as_str());
+            expected_offset += length;
+        }
+        assert!(text.contains(&format!("{} bytes", data.len())));
+    }
+
+    #[test]
+    fn test_json_output_is_ndjson() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fixture.bin");
+        let data = write_fixture(&path);
+
+        let mut out = Vec::new();
+        write_chunks(&path, &small_chunks(), true, &mut out).unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let (summary, chunks) = lines.split_last().unwrap();
+        assert!(chunks.iter().all(|c| c["type"] == "chunk"));
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["chunks"], chunks.len() as u64);
+        assert_eq!(summary["total_bytes"], data.len() as u64);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|c| c["length"].as_u64().unwrap())
+                .sum::<u64>(),
+            data.len() as u64
+        );
+        assert_eq!(summary["config"]["avg_size"], 8 * 1024);
+    }
+
+    #[test]
+    fn test_summary_statistics() {
+        let mut summary = ChunkSummary::default();
+        for length in [2, 4, 4, 4, 5, 5, 7, 9] {
+            summary.add(length);
+        }
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.min_size, 2);
+        assert_eq!(summary.max_size, 9);
+        assert!((summary.mean_size - 5.0).abs() < 1e-9);
+        assert!((summary.stddev - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("4096"), Ok(4096));
+        assert_eq!(parse_byte_size("64KiB"), Ok(64 * 1024));
+        assert_eq!(parse_byte_size("64k"), Ok(64 * 1024));
+        assert_eq!(parse_byte_size("1MiB"), Ok(1024 * 1024));
+        assert_eq!(parse_byte_size("2MB"), Ok(2_000_000));
+        assert!(parse_byte_size("KiB").is_err());
+        assert!(parse_byte_size("64XB").is_err());
+    }
+
+    #[test]
+    fn test_invalid_average_is_config_error() {
+        let options = ChunkOptions {
+            avg_size: 60_000,
+            ..Default::default()
+        };
+        assert!(matches!(options.chunk_config(), Err(OrbitError::Config(_))));
+    }
+}
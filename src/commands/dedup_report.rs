@@ -0,0 +1,282 @@
+/*!
+ * Orbit Dedup Report Command
+ *
+ * Chunks every file under a directory with CDC and reports how much of the
+ * tree is duplicate content: total bytes, unique bytes, the dedup ratio and
+ * the most referenced chunks. Useful for estimating dedup savings before a
+ * migration.
+ *
+ * Like the Universe Map, chunks are keyed by their BLAKE3 content ID, but
+ * only a reference count is kept per chunk (no locations), so memory grows
+ * with the number of distinct chunks rather than the size of the tree.
+ */
+
+use crate::cli_style::{format_bytes, section_header, stats_table, Icons, Theme};
+use crate::error::{OrbitError, Result};
+use orbit_core_cdc::{ChunkConfig, ChunkStream};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Number of duplicated chunks listed in the report
+pub const DEFAULT_TOP_CHUNKS: usize = 10;
+
+/// In-memory index of chunk reference counts
+#[derive(Debug, Default)]
+pub struct DedupIndex {
+    counts: HashMap<[u8; 32], u64>,
+    total_bytes: u64,
+    unique_bytes: u64,
+    total_chunks: u64,
+}
+
+impl DedupIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one occurrence of a chunk
+    ///
+    /// The chunk's length only counts towards unique bytes the first time
+    /// its content ID is seen.
+    pub fn add_chunk(&mut self, content_id: [u8; 32], length: usize) {
+        self.total_chunks += 1;
+        self.total_bytes += length as u64;
+        let count = self.counts.entry(content_id).or_insert(0);
+        if *count == 0 {
+            self.unique_bytes += length as u64;
+        }
+        *count += 1;
+    }
+
+    /// Number of distinct chunks
+    pub fn unique_chunks(&self) -> u64 {
+        self.counts.len() as u64
+    }
+
+    /// Chunks referenced more than once, most referenced first
+    pub fn top_duplicates(&self, limit: usize) -> Vec<DuplicateChunk> {
+        let mut duplicates: Vec<(&[u8; 32], u64)> = self
+            .counts
+            .iter()
+            .filter(|(_, &count)| count > 1)
+            .map(|(id, &count)| (id, count))
+            .collect();
+        duplicates.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        duplicates
+            .into_iter()
+            .take(limit)
+            .map(|(id, references)| DuplicateChunk {
+                content_id: hex::encode(id),
+                references,
+            })
+            .collect()
+    }
+}
+
+/// A chunk that appears more than once in the tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateChunk {
+    /// BLAKE3 content ID (hex)
+    pub content_id: String,
+    /// Number of times the chunk occurs
+    pub references: u64,
+}
+
+/// Duplicate-content summary for a directory tree
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DedupReport {
+    pub files: u64,
+    pub total_bytes: u64,
+    pub unique_bytes: u64,
+    pub total_chunks: u64,
+    pub unique_chunks: u64,
+    /// Total bytes divided by unique bytes (1.0 when nothing repeats)
+    pub dedup_ratio: f64,
+    pub top_duplicates: Vec<DuplicateChunk>,
+}
+
+impl DedupReport {
+    /// Bytes that would not need storing or sending with deduplication
+    pub fn savings_bytes(&self) -> u64 {
+        self.total_bytes - self.unique_bytes
+    }
+}
+
+/// Chunk every regular file under `dir` and summarise the duplication
+///
+/// Symlinks are not followed.
+pub fn build_report(dir: &Path, config: &ChunkConfig, top: usize) -> Result<DedupReport> {
+    if !dir.is_dir() {
+        return Err(OrbitError::SourceNotFound(dir.to_path_buf()));
+    }
+
+    let mut index = DedupIndex::new();
+    let mut files = 0;
+    for entry in WalkDir::new(dir).follow_links(false) {
+        let entry = entry.map_err(io::Error::from)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        files += 1;
+        let file = File::open(entry.path())?;
+        for chunk in ChunkStream::new(file, config.clone()) {
+            let chunk = chunk.map_err(io::Error::other)?;
+            index.add_chunk(chunk.hash, chunk.length);
+        }
+    }
+
+    let dedup_ratio = if index.unique_bytes == 0 {
+        1.0
+    } else {
+        index.total_bytes as f64 / index.unique_bytes as f64
+    };
+
+    Ok(DedupReport {
+        files,
+        total_bytes: index.total_bytes,
+        unique_bytes: index.unique_bytes,
+        total_chunks: index.total_chunks,
+        unique_chunks: index.unique_chunks(),
+        dedup_ratio,
+        top_duplicates: index.top_duplicates(top),
+    })
+}
+
+/// Build the report for `dir` with the default chunk sizes and print it
+pub fn run_dedup_report(dir: &Path, json_output: bool) -> Result<()> {
+    let report = build_report(dir, &ChunkConfig::default(), DEFAULT_TOP_CHUNKS)?;
+
+    if json_output {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| OrbitError::Other(format!("Failed to serialize report: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    println!();
+    section_header(&format!("{} Dedup Report: {}", Icons::STATS, dir.display()));
+    println!();
+    let savings_percent = if report.total_bytes == 0 {
+        0.0
+    } else {
+        report.savings_bytes() as f64 * 100.0 / report.total_bytes as f64
+    };
+    let table = stats_table(&[
+        ("Files", report.files.to_string()),
+        ("Total bytes", format_bytes(report.total_bytes)),
+        ("Unique bytes", format_bytes(report.unique_bytes)),
+        (
+            "Savings",
+            format!(
+                "{} ({:.1}%)",
+                format_bytes(report.savings_bytes()),
+                savings_percent
+            ),
+        ),
+        ("Dedup ratio", format!("{:.2}x", report.dedup_ratio)),
+        (
+            "Chunks",
+            format!("{} ({} unique)", report.total_chunks, report.unique_chunks),
+        ),
+    ]);
+    println!("{}", table);
+
+    if !report.top_duplicates.is_empty() {
+        println!();
+        println!("  {}", Theme::header("Most duplicated chunks"));
+        for chunk in &report.top_duplicates {
+            println!(
+                "  {} {}  {}",
+                Theme::muted(Icons::BULLET),
+                &chunk.content_id[..16],
+                Theme::value(format!("x{}", chunk.references))
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orbit_core_cdc::testing::xorshift_bytes;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn small_chunks() -> ChunkConfig {
+        ChunkConfig::new(2 * 1024, 8 * 1024, 32 * 1024).unwrap()
+    }
+
+    #[test]
+    fn test_report_counts_duplicated_files_once() {
+        let dir = tempdir().unwrap();
+        let a = xorshift_bytes(200 * 1024, 0x9E37_79B9_7F4A_7C15);
+        let b = xorshift_bytes(100 * 1024, 0x2545_F491_4F6C_DD1D);
+        fs::write(dir.path().join("a.bin"), &a).unwrap();
+        fs::write(dir.path().join("a-copy.bin"), &a).unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("nested/a-again.bin"), &a).unwrap();
+        fs::write(dir.path().join("nested/b.bin"), &b).unwrap();
+        fs::write(dir.path().join("empty.txt"), b"").unwrap();
+
+        let report = build_report(dir.path(), &small_chunks(), 5).unwrap();
+
+        assert_eq!(report.files, 5);
+        assert_eq!(report.total_bytes, (3 * a.len() + b.len()) as u64);
+        assert_eq!(report.unique_bytes, (a.len() + b.len()) as u64);
+        assert_eq!(report.savings_bytes(), 2 * a.len() as u64);
+        assert!((report.dedup_ratio - 700.0 / 300.0).abs() < 1e-9);
+
+        // Every chunk of `a` is referenced three times
+        assert!(!report.top_duplicates.is_empty());
+        assert!(report.top_duplicates.iter().all(|c| c.references == 3));
+        assert!(report.unique_chunks < report.total_chunks);
+    }
+
+    #[test]
+    fn test_report_without_duplicates() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("one"), xorshift_bytes(50 * 1024, 1)).unwrap();
+        fs::write(dir.path().join("two"), xorshift_bytes(50 * 1024, 2)).unwrap();
+
+        let report = build_report(dir.path(), &small_chunks(), 5).unwrap();
+
+        assert_eq!(report.unique_bytes, report.total_bytes);
+        assert_eq!(report.dedup_ratio, 1.0);
+        assert!(report.top_duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_top_duplicates_ordered_by_references() {
+        let mut index = DedupIndex::new();
+        for _ in 0..2 {
+            index.add_chunk([1; 32], 10);
+        }
+        for _ in 0..4 {
+            index.add_chunk([2; 32], 10);
+        }
+        index.add_chunk([3; 32], 10);
+
+        let top = index.top_duplicates(10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].references, 4);
+        assert_eq!(top[0].content_id, hex::encode([2; 32]));
+        assert_eq!(top[1].references, 2);
+        assert_eq!(index.top_duplicates(1).len(), 1);
+        assert_eq!(index.unique_chunks(), 3);
+    }
+
+    #[test]
+    fn test_missing_directory_is_an_error() {
+        let dir = tempdir().unwrap();
+        let err = build_report(&dir.path().join("missing"), &small_chunks(), 5).unwrap_err();
+        assert!(matches!(err, OrbitError::SourceNotFound(_)));
+    }
+}
@@ -1,15 +1,103 @@
 //! Doctor command — diagnose configuration and probe backend connectivity.
 //!
-//! Doctor is informational. It never mutates state and currently always exits
-//! with code 0 regardless of probe failures; a `--strict` mode is tracked in
-//! [ROADMAP-v0.7.md].
+//! Doctor never mutates state. Alongside the informational sections it runs a
+//! set of pass/warn/fail checks (secrets for enabled features, audit log
+//! writability, compiled protocol support, zero-copy for the planned
+//! source/destination, backend reachability) and collects them into a
+//! [`DoctorReport`]; the CLI exits non-zero when any check fails.
+
+use std::path::{Path, PathBuf};
 
 use crate::cli_style::{self, section_header, Icons, Theme};
 use crate::config::CopyConfig;
+use crate::error::{OrbitError, Result};
 use crate::get_zero_copy_capabilities;
 
-/// Entry point for `orbit doctor [--target <uri>...]`.
-pub fn run_doctor(targets: &[String]) {
+/// Inputs for `orbit doctor`
+#[derive(Debug, Clone, Default)]
+pub struct DoctorOptions {
+    /// Backend URIs to probe (`--target`, repeatable)
+    pub targets: Vec<String>,
+    /// Planned transfer source (`--source`)
+    pub source: Option<String>,
+    /// Planned transfer destination (`--dest`)
+    pub dest: Option<String>,
+    /// Audit log a transfer would write (`--audit-log` or the config file)
+    pub audit_log: Option<PathBuf>,
+}
+
+/// Outcome of a single doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A single doctor check result
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// What was checked (env var, path or URI)
+    pub name: String,
+    pub status: CheckStatus,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// All checks from one doctor run
+#[derive(Debug, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// First check recorded under `name`
+    pub fn find(&self, name: &str) -> Option<&Check> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    /// Number of checks with the given status
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Whether any check failed
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+
+    /// `Err` when any check failed, for the CLI exit code
+    pub fn into_result(self) -> Result<()> {
+        match self.count(CheckStatus::Fail) {
+            0 => Ok(()),
+            n => Err(OrbitError::Config(format!(
+                "orbit doctor found {} failing check{}",
+                n,
+                if n == 1 { "" } else { "s" }
+            ))),
+        }
+    }
+}
+
+/// Entry point for `orbit doctor [--target <uri>...] [--source <path>] [--dest <path>]`.
+pub fn run_doctor(options: &DoctorOptions) -> DoctorReport {
+    run_doctor_with_env(options, &|key| std::env::var(key).ok())
+}
+
+fn run_doctor_with_env(
+    options: &DoctorOptions,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> DoctorReport {
     cli_style::print_banner();
     section_header(&format!("{} Orbit Doctor", Icons::WRENCH));
     println!();
@@ -19,9 +107,261 @@ pub fn run_doctor(targets: &[String]) {
     print_hardware_section();
     print_features_section();
     print_env_section();
-    print_backend_probes_section(targets);
 
-    println!("  {}", Theme::success("Doctor check complete."));
+    let mut report = DoctorReport::default();
+    report
+        .checks
+        .extend(check_secrets(options.audit_log.as_deref(), env));
+    if let Some(path) = &options.audit_log {
+        report.checks.push(check_audit_log(path));
+    }
+    let uris = options
+        .targets
+        .iter()
+        .chain(options.source.iter())
+        .chain(options.dest.iter());
+    let protocol_checks: Vec<Check> = uris.filter_map(|uri| check_protocol(uri)).collect();
+    if let (Some(source), Some(dest)) = (&options.source, &options.dest) {
+        if let Some(check) = check_zero_copy(source, dest) {
+            report.checks.push(check);
+        }
+    }
+    // Probing a URI whose backend isn't compiled in would only repeat the failure
+    let probe_targets: Vec<String> = options
+        .targets
+        .iter()
+        .filter(|t| {
+            !protocol_checks
+                .iter()
+                .any(|c| &c.name == *t && c.status == CheckStatus::Fail)
+        })
+        .cloned()
+        .collect();
+    report.checks.extend(protocol_checks);
+    print_checks_section(&report);
+
+    print_backend_probes_section(&probe_targets, &mut report);
+
+    let failures = report.count(CheckStatus::Fail);
+    let warnings = report.count(CheckStatus::Warn);
+    if failures > 0 {
+        println!(
+            "  {} {}",
+            Icons::ERROR,
+            Theme::error(format!(
+                "Doctor found {} failure(s) and {} warning(s).",
+                failures, warnings
+            ))
+        );
+    } else if warnings > 0 {
+        println!(
+            "  {} {}",
+            Icons::WARNING,
+            Theme::warning(format!(
+                "Doctor check complete with {} warning(s).",
+                warnings
+            ))
+        );
+    } else {
+        println!("  {}", Theme::success("Doctor check complete."));
+    }
+    println!();
+
+    report
+}
+
+// ---------------------------------------------------------------------------
+// Pass/warn/fail checks
+// ---------------------------------------------------------------------------
+
+/// Secrets required by the features this run would use
+///
+/// Audit logging needs `ORBIT_AUDIT_SECRET` for HMAC chaining; without it
+/// the logger silently disables itself.
+fn check_secrets(audit_log: Option<&Path>, env: &dyn Fn(&str) -> Option<String>) -> Vec<Check> {
+    let mut checks = Vec::new();
+    if let Some(path) = audit_log {
+        let set = env("ORBIT_AUDIT_SECRET").is_some_and(|v| !v.is_empty());
+        checks.push(if set {
+            Check::new("ORBIT_AUDIT_SECRET", CheckStatus::Pass, "set")
+        } else {
+            Check::new(
+                "ORBIT_AUDIT_SECRET",
+                CheckStatus::Fail,
+                format!(
+                    "not set, so audit logging to {} would be disabled; export ORBIT_AUDIT_SECRET",
+                    path.display()
+                ),
+            )
+        });
+    }
+    checks
+}
+
+/// Whether the audit log can be appended to (or created)
+///
+/// The logger creates missing parent directories, so a missing path is
+/// judged by its nearest existing ancestor.
+fn check_audit_log(path: &Path) -> Check {
+    let name = path.display().to_string();
+    if path.is_dir() {
+        return Check::new(name, CheckStatus::Fail, "audit log path is a directory");
+    }
+    if path.exists() {
+        return match std::fs::OpenOptions::new().append(true).open(path) {
+            Ok(_) => Check::new(name, CheckStatus::Pass, "audit log is writable"),
+            Err(e) => Check::new(
+                name,
+                CheckStatus::Fail,
+                format!("audit log is not writable: {}", e),
+            ),
+        };
+    }
+
+    let Some(ancestor) = path.ancestors().skip(1).find(|p| {
+        if p.as_os_str().is_empty() {
+            Path::new(".").is_dir()
+        } else {
+            p.is_dir()
+        }
+    }) else {
+        return Check::new(name, CheckStatus::Fail, "no existing parent directory");
+    };
+    let ancestor = if ancestor.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        ancestor
+    };
+    // An unnamed temp file is removed on drop, leaving nothing behind
+    match tempfile::tempfile_in(ancestor) {
+        Ok(_) => Check::new(
+            name,
+            CheckStatus::Pass,
+            format!("will be created under {}", ancestor.display()),
+        ),
+        Err(e) => Check::new(
+            name,
+            CheckStatus::Fail,
+            format!("cannot create files in {}: {}", ancestor.display(), e),
+        ),
+    }
+}
+
+/// Cargo feature a URI scheme needs, and whether this build has it
+fn scheme_feature(uri: &str) -> Option<(&'static str, bool)> {
+    let (scheme, _) = uri.split_once("://")?;
+    match scheme.to_ascii_lowercase().as_str() {
+        "s3" => Some(("s3-native", cfg!(feature = "s3-native"))),
+        "smb" | "cifs" => Some(("smb-native", cfg!(feature = "smb-native"))),
+        "ssh" | "sftp" => Some(("ssh-backend", cfg!(feature = "ssh-backend"))),
+        "azblob" | "azure" => Some(("azure-native", cfg!(feature = "azure-native"))),
+        "gs" | "gcs" => Some(("gcs-native", cfg!(feature = "gcs-native"))),
+        _ => None,
+    }
+}
+
+/// Whether the backend a URI asks for was compiled in
+fn check_protocol(uri: &str) -> Option<Check> {
+    let (feature, enabled) = scheme_feature(uri)?;
+    Some(if enabled {
+        Check::new(
+            uri,
+            CheckStatus::Pass,
+            format!("{} backend compiled in", feature),
+        )
+    } else {
+        Check::new(
+            uri,
+            CheckStatus::Fail,
+            format!(
+                "this build lacks the backend; rebuild with --features {}",
+                feature
+            ),
+        )
+    })
+}
+
+/// Zero-copy suitability for a local source/destination pair
+///
+/// Remote pairs are skipped. The destination may not exist yet, so its
+/// nearest existing ancestor stands in for the filesystem comparison.
+fn check_zero_copy(source: &str, dest: &str) -> Option<Check> {
+    if source.contains("://") || dest.contains("://") {
+        return None;
+    }
+    let name = format!("zero-copy {} -> {}", source, dest);
+    let source = Path::new(source);
+    if !source.exists() {
+        return Some(Check::new(name, CheckStatus::Fail, "source does not exist"));
+    }
+
+    let caps = get_zero_copy_capabilities();
+    if !caps.available {
+        return Some(Check::new(
+            name,
+            CheckStatus::Warn,
+            "zero-copy unavailable on this platform; buffered copy will be used",
+        ));
+    }
+    if caps.cross_filesystem {
+        return Some(Check::new(name, CheckStatus::Pass, caps.method));
+    }
+
+    let dest_probe = Path::new(dest)
+        .ancestors()
+        .find(|p| !p.as_os_str().is_empty() && p.exists())
+        .unwrap_or(Path::new("."));
+    Some(
+        match crate::core::zero_copy::same_filesystem(source, dest_probe) {
+            Ok(true) => Check::new(
+                name,
+                CheckStatus::Pass,
+                format!("{}, same filesystem", caps.method),
+            ),
+            Ok(false) => Check::new(
+                name,
+                CheckStatus::Warn,
+                format!(
+                    "different filesystems; {} needs one, so buffered copy will be used",
+                    caps.method
+                ),
+            ),
+            Err(e) => Check::new(
+                name,
+                CheckStatus::Warn,
+                format!("could not compare filesystems: {}", e),
+            ),
+        },
+    )
+}
+
+fn print_checks_section(report: &DoctorReport) {
+    section_header(&format!("{} Checks", Icons::SHIELD));
+    if report.checks.is_empty() {
+        println!(
+            "  {} {}",
+            Icons::BULLET,
+            Theme::muted("Nothing to check. Pass --source/--dest, --target or --audit-log.")
+        );
+    }
+    for check in &report.checks {
+        let icon = match check.status {
+            CheckStatus::Pass => Icons::SUCCESS,
+            CheckStatus::Warn => Icons::WARNING,
+            CheckStatus::Fail => Icons::ERROR,
+        };
+        let detail = match check.status {
+            CheckStatus::Pass => Theme::success(&check.detail).to_string(),
+            CheckStatus::Warn => Theme::warning(&check.detail).to_string(),
+            CheckStatus::Fail => Theme::error(&check.detail).to_string(),
+        };
+        println!(
+            "  {} {} {}",
+            icon,
+            Theme::muted(format!("{}:", check.name)),
+            detail
+        );
+    }
     println!();
 }
 
@@ -187,7 +527,7 @@ fn print_env_section() {
 // ---------------------------------------------------------------------------
 
 #[cfg(not(feature = "backend-abstraction"))]
-fn print_backend_probes_section(targets: &[String]) {
+fn print_backend_probes_section(targets: &[String], _report: &mut DoctorReport) {
     section_header(&format!("{} Backend Connectivity", Icons::SATELLITE));
     if targets.is_empty() {
         println!(
@@ -210,7 +550,7 @@ fn print_backend_probes_section(targets: &[String]) {
 }
 
 #[cfg(feature = "backend-abstraction")]
-fn print_backend_probes_section(targets: &[String]) {
+fn print_backend_probes_section(targets: &[String], report: &mut DoctorReport) {
     use crate::backend::{from_env, BackendRegistry};
 
     let env_type = std::env::var("ORBIT_BACKEND_TYPE")
@@ -246,6 +586,11 @@ fn print_backend_probes_section(targets: &[String]) {
                 e
             );
             println!();
+            report.checks.push(Check::new(
+                "backend probes",
+                CheckStatus::Fail,
+                format!("could not start runtime: {}", e),
+            ));
             return;
         }
     };
@@ -254,16 +599,27 @@ fn print_backend_probes_section(targets: &[String]) {
     let registry = BackendRegistry::new();
 
     for target in targets {
-        runtime.block_on(probe_uri(&registry, target));
+        let status = runtime.block_on(probe_uri(&registry, target));
+        report
+            .checks
+            .push(Check::new(target.as_str(), status, "connectivity probe"));
     }
 
     if env_probe_enabled {
         match from_env() {
             Ok(config) => {
                 let label = format!("env:{}", env_type.as_deref().unwrap_or("?"));
-                runtime.block_on(probe_config(&registry, &label, &config, None));
+                let status = runtime.block_on(probe_config(&registry, &label, &config, None));
+                report
+                    .checks
+                    .push(Check::new(label, status, "connectivity probe"));
             }
             Err(e) => {
+                report.checks.push(Check::new(
+                    format!("env:{}", env_type.as_deref().unwrap_or("?")),
+                    CheckStatus::Fail,
+                    format!("env config error: {}", e),
+                ));
                 println!(
                     "  {} {} {}",
                     Icons::ERROR,
@@ -285,7 +641,7 @@ fn print_backend_probes_section(targets: &[String]) {
 }
 
 #[cfg(feature = "backend-abstraction")]
-async fn probe_uri(registry: &crate::backend::BackendRegistry, uri: &str) {
+async fn probe_uri(registry: &crate::backend::BackendRegistry, uri: &str) -> CheckStatus {
     use crate::backend::parse_uri;
 
     let (config, path) = match parse_uri(uri) {
@@ -303,10 +659,10 @@ async fn probe_uri(registry: &crate::backend::BackendRegistry, uri: &str) {
                 Icons::ARROW_RIGHT,
                 Theme::muted("check the URI scheme and query parameters")
             );
-            return;
+            return CheckStatus::Fail;
         }
     };
-    probe_config(registry, uri, &config, Some(&path)).await;
+    probe_config(registry, uri, &config, Some(&path)).await
 }
 
 /// Choose what path to pass to `Backend::list` when probing.
@@ -357,7 +713,7 @@ async fn probe_config(
     label: &str,
     config: &crate::backend::BackendConfig,
     list_path: Option<&std::path::Path>,
-) {
+) -> CheckStatus {
     use crate::backend::types::ListOptions;
     use futures::StreamExt;
     use std::time::Instant;
@@ -374,7 +730,7 @@ async fn probe_config(
                 e
             );
             print_suggestion(&e);
-            return;
+            return CheckStatus::Fail;
         }
     };
 
@@ -397,6 +753,7 @@ async fn probe_config(
                         Theme::muted("→ connected in"),
                         elapsed.as_millis()
                     );
+                    CheckStatus::Pass
                 }
                 Some(Err(e)) => {
                     println!(
@@ -407,6 +764,7 @@ async fn probe_config(
                         e
                     );
                     print_suggestion(&e);
+                    CheckStatus::Fail
                 }
             }
         }
@@ -419,6 +777,7 @@ async fn probe_config(
                 e
             );
             print_suggestion(&e);
+            CheckStatus::Fail
         }
     }
 }
@@ -477,12 +836,91 @@ mod tests {
         // Smoke test: running doctor against a local temp dir should not panic.
         let tmp = tempfile::tempdir().unwrap();
         let uri = tmp.path().to_string_lossy().to_string();
-        run_doctor(&[uri]);
+        let report = run_doctor(&DoctorOptions {
+            targets: vec![uri.clone()],
+            ..Default::default()
+        });
+        assert_eq!(report.find(&uri).unwrap().status, CheckStatus::Pass);
     }
 
     #[test]
     fn run_doctor_with_no_targets_does_not_panic() {
-        run_doctor(&[]);
+        run_doctor(&DoctorOptions::default());
+    }
+
+    #[test]
+    fn missing_audit_secret_fails_doctor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = DoctorOptions {
+            audit_log: Some(tmp.path().join("audit.jsonl")),
+            ..Default::default()
+        };
+
+        let report = run_doctor_with_env(&options, &|_| None);
+        let check = report.find("ORBIT_AUDIT_SECRET").unwrap();
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("audit logging"), "{}", check.detail);
+        assert!(report.has_failures());
+        assert!(report.into_result().is_err());
+
+        let report = run_doctor_with_env(&options, &|key| {
+            (key == "ORBIT_AUDIT_SECRET").then(|| "s3cret".to_string())
+        });
+        assert_eq!(
+            report.find("ORBIT_AUDIT_SECRET").unwrap().status,
+            CheckStatus::Pass
+        );
+        assert!(!report.has_failures());
+    }
+
+    #[test]
+    fn secrets_not_required_without_audit_log() {
+        assert!(check_secrets(None, &|_| None).is_empty());
+    }
+
+    #[test]
+    fn audit_log_writability() {
+        let tmp = tempfile::tempdir().unwrap();
+        let nested = tmp.path().join("logs/audit.jsonl");
+        assert_eq!(check_audit_log(&nested).status, CheckStatus::Pass);
+        // Nothing is left behind
+        assert!(!tmp.path().join("logs").exists());
+
+        assert_eq!(check_audit_log(tmp.path()).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn protocol_check_follows_compiled_features() {
+        let check = check_protocol("gs://bucket/data").unwrap();
+        let expected = if cfg!(feature = "gcs-native") {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+        assert_eq!(check.status, expected);
+        assert!(check_protocol("/local/path").is_none());
+    }
+
+    #[test]
+    fn zero_copy_check_reports_missing_source() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("missing");
+        let check = check_zero_copy(
+            missing.to_str().unwrap(),
+            tmp.path().join("out").to_str().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(check.status, CheckStatus::Fail);
+
+        let src = tmp.path().join("src.bin");
+        std::fs::write(&src, b"data").unwrap();
+        let check = check_zero_copy(
+            src.to_str().unwrap(),
+            tmp.path().join("out/dst.bin").to_str().unwrap(),
+        )
+        .unwrap();
+        assert_ne!(check.status, CheckStatus::Fail);
+        assert!(check_zero_copy("s3://bucket/a", "/tmp/b").is_none());
     }
 
     #[test]
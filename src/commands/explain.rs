@@ -7,7 +7,7 @@
  */
 
 use crate::cli_style::{section_header, Icons, Theme};
-use crate::config::{CompressionType, CopyConfig, CopyMode, ErrorMode};
+use crate::config::{CompressionType, CopyConfig, CopyMode, ErrorMode, OverwritePolicy};
 
 fn mode_description(copy_mode: CopyMode) -> &'static str {
     match copy_mode {
@@ -29,9 +29,15 @@ fn retry_description(config: &CopyConfig) -> Option<String> {
         ""
     };
 
+    let budget = config
+        .retry_budget
+        .as_ref()
+        .map(|b| format!(", {} shared retry credits/min", b.metrics().capacity))
+        .unwrap_or_default();
+
     Some(format!(
-        "Up to {} attempts, {}s delay{}",
-        config.retry_attempts, config.retry_delay_secs, backoff
+        "Up to {} attempts, {}s delay{}{}",
+        config.retry_attempts, config.retry_delay_secs, backoff, budget
     ))
 }
 
@@ -107,6 +113,14 @@ fn advanced_lines(config: &CopyConfig) -> Vec<String> {
             Theme::muted("Manifests:"),
         ));
     }
+    if let Some(prior) = &config.since_manifest {
+        lines.push(format!(
+            "  {} {} Only files changed since {}",
+            Icons::BULLET,
+            Theme::muted("Incremental:"),
+            prior.display()
+        ));
+    }
     if config.write_batch.is_some() {
         lines.push(format!(
             "  {} {} Recording batch journal for replay",
@@ -120,15 +134,19 @@ fn advanced_lines(config: &CopyConfig) -> Vec<String> {
 
 fn active_conditions(config: &CopyConfig) -> Vec<&'static str> {
     let mut conditions = Vec::new();
-    if config.no_clobber {
+    if config.no_clobber || config.overwrite == OverwritePolicy::Never {
         conditions.push("Won't overwrite existing files");
     }
     if config.if_size_differ {
         conditions.push("Only if sizes differ");
     }
-    if config.if_source_newer {
+    if config.if_source_newer || config.overwrite == OverwritePolicy::IfSourceNewer {
         conditions.push("Only if source is newer");
     }
+    if config.overwrite == OverwritePolicy::Prompt && !config.no_clobber && !config.if_source_newer
+    {
+        conditions.push("Asking before overwriting existing files");
+    }
     if config.ignore_existing {
         conditions.push("Skipping files that already exist");
     }
@@ -174,10 +192,17 @@ pub fn explain_transfer(source: &str, dest: &str, config: &CopyConfig, is_remote
         "  {} {} {}",
         Icons::BULLET,
         Theme::muted("Scope:"),
-        if config.recursive {
-            Theme::value("Recursive (all subdirectories)")
+        if !config.recursive {
+            Theme::value("Single file".to_string())
         } else {
-            Theme::value("Single file")
+            let mut scope = match config.max_depth {
+                Some(depth) => format!("Recursive (up to {} level(s) deep)", depth),
+                None => "Recursive (all subdirectories)".to_string(),
+            };
+            if !config.one_file_system {
+                scope.push_str(", crossing filesystems");
+            }
+            Theme::value(scope)
         }
     );
 
@@ -266,7 +291,14 @@ pub fn explain_transfer(source: &str, dest: &str, config: &CopyConfig, is_remote
         "  {} {} {}",
         Icons::BULLET,
         Theme::muted("Parallelism:"),
-        Theme::value(worker_description(config.parallel, is_remote))
+        Theme::value(if config.adaptive_concurrency {
+            format!(
+                "Adaptive, up to {}",
+                worker_description(config.parallel, is_remote).to_lowercase()
+            )
+        } else {
+            worker_description(config.parallel, is_remote)
+        })
     );
 
     if config.max_bandwidth > 0 {
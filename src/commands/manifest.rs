@@ -11,7 +11,7 @@ use clap::Subcommand;
 use crate::cli_style::{self, format_bytes, print_error, print_info, section_header, Icons, Theme};
 use crate::config::{ChunkingStrategy, CopyConfig};
 use crate::error::{OrbitError, Result, EXIT_FATAL};
-use crate::manifest_integration::ManifestGenerator;
+use crate::manifest_integration::{verify_file_windows, ManifestGenerator};
 
 fn parse_chunking_strategy(chunking: &str, chunk_size: u32) -> Result<ChunkingStrategy> {
     match chunking {
@@ -52,6 +52,10 @@ pub enum ManifestCommands {
         /// Average chunk size in KiB (for CDC) or fixed size (for fixed)
         #[arg(long, default_value = "256")]
         chunk_size: u32,
+
+        /// Files chunked and hashed concurrently (defaults to the CPU count)
+        #[arg(long)]
+        jobs: Option<usize>,
     },
 
     /// Verify a completed transfer using manifests
@@ -59,6 +63,18 @@ pub enum ManifestCommands {
         /// Directory containing manifests
         #[arg(short, long)]
         manifest_dir: PathBuf,
+
+        /// Re-hash every window of the target files and compare Merkle roots
+        #[arg(long)]
+        deep: bool,
+
+        /// Target root to re-hash (defaults to the flight plan's target)
+        #[arg(short, long, requires = "deep")]
+        target: Option<PathBuf>,
+
+        /// Windows hashed concurrently per file (defaults to the CPU count)
+        #[arg(long, requires = "deep")]
+        jobs: Option<usize>,
     },
 
     /// Show differences between manifest and target
@@ -88,8 +104,17 @@ pub fn handle_manifest_command(command: ManifestCommands) -> Result<()> {
             output,
             chunking,
             chunk_size,
-        } => handle_manifest_plan(source, dest, output, chunking, chunk_size),
-        ManifestCommands::Verify { manifest_dir } => handle_manifest_verify(manifest_dir),
+            jobs,
+        } => handle_manifest_plan(source, dest, output, chunking, chunk_size, jobs),
+        ManifestCommands::Verify {
+            manifest_dir,
+            deep,
+            target,
+            jobs,
+        } => {
+            let deep_target = deep.then_some(DeepVerifyOptions { target, jobs });
+            handle_manifest_verify(manifest_dir, deep_target)
+        }
         ManifestCommands::Diff {
             manifest_dir,
             target,
@@ -104,6 +129,7 @@ pub fn handle_manifest_plan(
     output: PathBuf,
     chunking: String,
     chunk_size: u32,
+    jobs: Option<usize>,
 ) -> Result<()> {
     section_header(&format!("{} Creating Flight Plan", Icons::MANIFEST));
     println!();
@@ -158,8 +184,18 @@ pub fn handle_manifest_plan(
     } else if source.is_dir() {
         use walkdir::WalkDir;
 
-        for entry in WalkDir::new(&source).into_iter().filter_map(|e| e.ok()) {
-            if entry.file_type().is_file() {
+        let jobs = jobs
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(4)
+            })
+            .max(1);
+        let files = WalkDir::new(&source)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| {
                 let relative_path = entry
                     .path()
                     .strip_prefix(&source)
@@ -168,9 +204,9 @@ pub fn handle_manifest_plan(
                     .to_string();
 
                 println!("  {} {}", Theme::muted(Icons::ARROW_RIGHT), relative_path);
-                generator.generate_file_manifest(entry.path(), &relative_path)?;
-            }
-        }
+                (entry.into_path(), relative_path)
+            });
+        generator.generate_file_manifests(files, jobs)?;
     } else {
         print_error(
             "Source path does not exist or is not accessible",
@@ -187,7 +223,18 @@ pub fn handle_manifest_plan(
     Ok(())
 }
 
-pub fn handle_manifest_verify(manifest_dir: PathBuf) -> Result<()> {
+/// Options for `manifest verify --deep`
+pub struct DeepVerifyOptions {
+    /// Target root; falls back to the flight plan's target endpoint
+    pub target: Option<PathBuf>,
+    /// Windows hashed concurrently per file
+    pub jobs: Option<usize>,
+}
+
+pub fn handle_manifest_verify(
+    manifest_dir: PathBuf,
+    deep: Option<DeepVerifyOptions>,
+) -> Result<()> {
     use crate::manifests::{CargoManifest, FlightPlan};
 
     section_header(&format!("{} Verifying Manifests", Icons::SHIELD));
@@ -242,6 +289,21 @@ pub fn handle_manifest_verify(manifest_dir: PathBuf) -> Result<()> {
     );
     println!();
 
+    let deep = deep.map(|opts| {
+        let root = opts
+            .target
+            .unwrap_or_else(|| PathBuf::from(&flight_plan.target.root));
+        let jobs = opts
+            .jobs
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(4)
+            })
+            .max(1);
+        (root, jobs)
+    });
+
     let mut verified = 0;
     let mut failed = 0;
 
@@ -260,6 +322,51 @@ pub fn handle_manifest_verify(manifest_dir: PathBuf) -> Result<()> {
         }
 
         match CargoManifest::load(&cargo_path) {
+            Ok(cargo) if deep.is_some() => {
+                let (root, jobs) = deep.as_ref().unwrap();
+                // A single-file plan targets the file itself
+                let file_path = if root.is_file() {
+                    root.clone()
+                } else {
+                    root.join(&file_ref.path)
+                };
+
+                match verify_file_windows(&file_path, &cargo, *jobs) {
+                    Ok(report) if report.is_ok() => {
+                        println!(
+                            "  {} {} {} windows re-hashed, {}",
+                            Theme::success(Icons::SUCCESS),
+                            file_ref.path,
+                            report.windows_checked,
+                            format_bytes(cargo.size)
+                        );
+                        verified += 1;
+                    }
+                    Ok(report) => {
+                        let detail = if report.size_matches {
+                            format!("(windows {:?} differ)", report.mismatched_windows)
+                        } else {
+                            "(size differs)".to_string()
+                        };
+                        println!(
+                            "  {} {} {}",
+                            Theme::error(Icons::ERROR),
+                            file_ref.path,
+                            Theme::error(detail)
+                        );
+                        failed += 1;
+                    }
+                    Err(e) => {
+                        println!(
+                            "  {} {} {}",
+                            Theme::error(Icons::ERROR),
+                            file_ref.path,
+                            Theme::error(format!("({})", e))
+                        );
+                        failed += 1;
+                    }
+                }
+            }
             Ok(cargo) => {
                 println!(
                     "  {} {} {} windows, {}",
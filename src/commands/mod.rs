@@ -4,7 +4,10 @@
  * This module organizes subcommands for better code organization.
  */
 
+pub mod audit;
 pub mod batch;
+pub mod chunk;
+pub mod dedup_report;
 pub mod doctor;
 pub mod explain;
 pub mod history;
@@ -27,11 +27,11 @@ pub fn copy_with_lz4(
     let temp_compressed = dest_path.with_extension("tmp.lz4");
 
     // Setup bandwidth limiter
-    let bandwidth_limiter = BandwidthLimiter::new(config.max_bandwidth);
+    let bandwidth_limiter = BandwidthLimiter::from_config(config);
     if bandwidth_limiter.is_enabled() {
         info!(
             "LZ4 compression with bandwidth limiting: {} bytes/sec",
-            config.max_bandwidth
+            bandwidth_limiter.current_limit()
         );
     }
 
@@ -71,7 +71,7 @@ pub fn copy_with_lz4(
 
         let mut buffer = vec![0u8; config.chunk_size];
         let mut bytes_read = start_offset;
-        let mut last_checkpoint = Instant::now();
+        let mut checkpoints = resume::CheckpointSchedule::from_config(config, start_offset);
 
         while bytes_read < source_size {
             let remaining = (source_size - bytes_read) as usize;
@@ -100,12 +100,12 @@ pub fn copy_with_lz4(
                 }
             }
 
-            if config.resume_enabled && last_checkpoint.elapsed() > Duration::from_secs(5) {
+            if config.resume_enabled && checkpoints.is_due(bytes_read) {
                 let compressed_size = std::fs::metadata(&temp_compressed)
                     .map(|m| m.len())
                     .unwrap_or(0);
                 resume::save_resume_info(dest_path, bytes_read, Some(compressed_size), true)?;
-                last_checkpoint = Instant::now();
+                checkpoints.mark(bytes_read);
             }
         }
 
@@ -175,11 +175,11 @@ pub fn copy_with_zstd(
     let temp_compressed = dest_path.with_extension("tmp.zst");
 
     // Setup bandwidth limiter
-    let bandwidth_limiter = BandwidthLimiter::new(config.max_bandwidth);
+    let bandwidth_limiter = BandwidthLimiter::from_config(config);
     if bandwidth_limiter.is_enabled() {
         info!(
             "Zstd compression with bandwidth limiting: {} bytes/sec",
-            config.max_bandwidth
+            bandwidth_limiter.current_limit()
         );
     }
 
@@ -210,7 +210,7 @@ pub fn copy_with_zstd(
 
         let mut buffer = vec![0u8; config.chunk_size];
         let mut bytes_read = start_offset;
-        let mut last_checkpoint = Instant::now();
+        let mut checkpoints = resume::CheckpointSchedule::from_config(config, start_offset);
 
         while bytes_read < source_size {
             let remaining = (source_size - bytes_read) as usize;
@@ -239,12 +239,12 @@ pub fn copy_with_zstd(
                 }
             }
 
-            if config.resume_enabled && last_checkpoint.elapsed() > Duration::from_secs(5) {
+            if config.resume_enabled && checkpoints.is_due(bytes_read) {
                 let compressed_size = std::fs::metadata(&temp_compressed)
                     .map(|m| m.len())
                     .unwrap_or(0);
                 resume::save_resume_info(dest_path, bytes_read, Some(compressed_size), true)?;
-                last_checkpoint = Instant::now();
+                checkpoints.mark(bytes_read);
             }
         }
 
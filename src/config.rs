@@ -31,10 +31,49 @@ pub struct CopyConfig {
     #[serde(default)]
     pub copy_mode: CopyMode,
 
+    /// What Mirror mode does with destination-only entries
+    #[serde(default)]
+    pub delete_mode: DeleteMode,
+
+    /// Sync/Update treat mtimes this many seconds apart as equal, to absorb
+    /// clock skew between source and destination
+    #[serde(default = "default_mtime_tolerance")]
+    pub mtime_tolerance_secs: u64,
+
+    /// When mtimes differ but fall within the tolerance, compare size and
+    /// checksum instead of assuming the file is unchanged
+    #[serde(default)]
+    pub checksum_ambiguous_mtime: bool,
+
     /// Enable recursive directory copying
     #[serde(default)]
     pub recursive: bool,
 
+    /// Limit recursion to this many levels below the source
+    /// (1 = top-level entries only, None = unlimited)
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Do not descend into directories on a different filesystem than the
+    /// source (mount points are recreated empty). On by default
+    #[serde(default = "default_true")]
+    pub one_file_system: bool,
+
+    /// Write a directory source into a single archive at the destination
+    /// instead of a mirrored tree (None = regular copy)
+    #[serde(default)]
+    pub archive: Option<ArchiveFormat>,
+
+    /// Unpack a `.tar`/`.tar.zst` source into the destination directory
+    /// instead of copying the archive file
+    #[serde(default)]
+    pub extract: bool,
+
+    /// Copy only this byte range of a single file, writing it at the same
+    /// offset in the destination (`--offset` / `--length`)
+    #[serde(default)]
+    pub byte_range: Option<crate::core::range::ByteRange>,
+
     /// Preserve file metadata (timestamps, permissions)
     #[serde(default = "default_true")]
     pub preserve_metadata: bool,
@@ -65,6 +104,24 @@ pub struct CopyConfig {
     #[serde(default = "default_true")]
     pub verify_checksum: bool,
 
+    /// Re-read a random sample of copied files after a directory copy
+    /// (`--verify sample:P`)
+    #[serde(default)]
+    pub verify_sample: Option<crate::core::verify_sample::VerifySample>,
+
+    /// Write a `*sum -c` compatible sidecar listing each copied file's hash
+    #[serde(default)]
+    pub checksum_manifest: bool,
+
+    /// Sidecar location; defaults to `<dest>.sha256` / `<dest>.b3` beside
+    /// the destination directory
+    #[serde(default)]
+    pub checksum_manifest_path: Option<PathBuf>,
+
+    /// Hash algorithm for the checksum manifest
+    #[serde(default)]
+    pub checksum_algorithm: ChecksumAlgorithm,
+
     /// Compression type
     #[serde(default)]
     pub compression: CompressionType,
@@ -73,6 +130,10 @@ pub struct CopyConfig {
     #[serde(default = "default_true")]
     pub show_progress: bool,
 
+    /// Progress output format (interactive bars or a JSON event stream on stderr)
+    #[serde(default)]
+    pub progress_format: ProgressFormat,
+
     /// Chunk size in bytes for buffered I/O
     #[serde(default = "default_chunk_size")]
     pub chunk_size: usize,
@@ -89,15 +150,38 @@ pub struct CopyConfig {
     #[serde(default)]
     pub exponential_backoff: bool,
 
+    /// Retry credits shared by every file in this session; `None` = each
+    /// operation retries independently. Set at runtime (`--retry-budget`),
+    /// and shared by clones of this config.
+    #[serde(skip)]
+    pub retry_budget: Option<std::sync::Arc<crate::core::retry::RetryBudget>>,
+
+    /// Write a resume checkpoint at least this often in seconds (0 = no time trigger)
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval_secs: u64,
+
+    /// Also write a resume checkpoint after this many bytes (0 = no byte trigger)
+    #[serde(default)]
+    pub checkpoint_interval_bytes: u64,
+
     /// Maximum bandwidth in bytes per second (0 = unlimited)
     #[serde(default)]
     pub max_bandwidth: u64,
 
+    /// Time-of-day bandwidth windows; `max_bandwidth` applies outside them
+    #[serde(default)]
+    pub bandwidth_schedule: Vec<crate::core::bandwidth::BandwidthWindow>,
+
     /// Number of parallel file operations / workers (0 = auto-detect)
     /// For network backends, auto = 256; for local, auto = CPU count
     #[serde(default)]
     pub parallel: usize,
 
+    /// Tune directory-copy concurrency from measured throughput and errors
+    /// (`--parallel auto-adaptive`); `parallel` becomes the ceiling
+    #[serde(default)]
+    pub adaptive_concurrency: bool,
+
     /// Per-operation concurrency (e.g., multipart upload/download parts)
     /// Default: 5. Controls how many parts of a single large file are
     /// transferred simultaneously.
@@ -108,6 +192,10 @@ pub struct CopyConfig {
     #[serde(default)]
     pub symlink_mode: SymlinkMode,
 
+    /// Handling of FIFOs, sockets and device nodes in directory copies
+    #[serde(default)]
+    pub special_files: SpecialFileMode,
+
     /// Error handling mode
     #[serde(default)]
     pub error_mode: ErrorMode,
@@ -136,10 +224,20 @@ pub struct CopyConfig {
     #[serde(default)]
     pub filter_from: Option<PathBuf>,
 
+    /// Whether hidden entries (dotfiles, and on Windows the hidden
+    /// attribute) are walked in directory copies
+    #[serde(default)]
+    pub hidden_files: HiddenMode,
+
     /// Dry run mode (don't actually copy)
     #[serde(default)]
     pub dry_run: bool,
 
+    /// Probe the destination for writability and free inodes before a
+    /// directory copy reads any source file
+    #[serde(default = "default_true")]
+    pub preflight: bool,
+
     /// Use zero-copy system calls when available
     #[serde(default = "default_true")]
     pub use_zero_copy: bool,
@@ -152,6 +250,12 @@ pub struct CopyConfig {
     #[serde(default)]
     pub manifest_output_dir: Option<PathBuf>,
 
+    /// Prior manifest directory for incremental copies: files whose size and
+    /// digest match it are skipped, and the updated manifest is written to
+    /// `manifest_output_dir` (or back into this directory)
+    #[serde(default)]
+    pub since_manifest: Option<PathBuf>,
+
     /// Chunking strategy for manifest generation
     #[serde(default)]
     pub chunking_strategy: ChunkingStrategy,
@@ -308,6 +412,11 @@ pub struct CopyConfig {
     #[serde(default)]
     pub if_source_newer: bool,
 
+    /// Whether a file copy may replace an existing destination file
+    /// (`no_clobber` forces `Never`)
+    #[serde(default)]
+    pub overwrite: OverwritePolicy,
+
     /// Flatten directory hierarchy (strip path components)
     #[serde(default)]
     pub flatten: bool,
@@ -320,6 +429,13 @@ pub struct CopyConfig {
     #[serde(default)]
     pub sparse_mode: crate::core::sparse::SparseMode,
 
+    /// Append-only (tailing) transfer mode.
+    /// Auto: tail files classified as append-only (WALs, logs), moving only
+    /// bytes added since the previous pass. Always: tail every file.
+    /// Never: always copy whole files.
+    #[serde(default)]
+    pub tail_mode: crate::core::tail::TailMode,
+
     /// Preserve hardlinks: detect files sharing the same inode during
     /// directory scan and recreate hardlink groups at the destination.
     #[serde(default)]
@@ -370,7 +486,15 @@ impl Default for CopyConfig {
     fn default() -> Self {
         Self {
             copy_mode: CopyMode::Copy,
+            delete_mode: DeleteMode::Delete,
+            mtime_tolerance_secs: default_mtime_tolerance(),
+            checksum_ambiguous_mtime: false,
             recursive: false,
+            max_depth: None,
+            one_file_system: true,
+            archive: None,
+            extract: false,
+            byte_range: None,
             preserve_metadata: true,
             preserve_flags: None,
             transform: None,
@@ -378,16 +502,27 @@ impl Default for CopyConfig {
             verify_metadata: false,
             resume_enabled: false,
             verify_checksum: true,
+            verify_sample: None,
+            checksum_manifest: false,
+            checksum_manifest_path: None,
+            checksum_algorithm: ChecksumAlgorithm::Sha256,
             compression: CompressionType::None,
             show_progress: true,
+            progress_format: ProgressFormat::Bar,
             chunk_size: default_chunk_size(),
             retry_attempts: default_retry_attempts(),
             retry_delay_secs: default_retry_delay(),
             exponential_backoff: false,
+            retry_budget: None,
+            checkpoint_interval_secs: default_checkpoint_interval(),
+            checkpoint_interval_bytes: 0,
             max_bandwidth: 0,
+            bandwidth_schedule: Vec::new(),
             parallel: 0,
+            adaptive_concurrency: false,
             concurrency: default_concurrency(),
             symlink_mode: SymlinkMode::Skip,
+            special_files: SpecialFileMode::Skip,
             error_mode: ErrorMode::Abort,
             log_level: LogLevel::Info,
             log_file: None,
@@ -395,10 +530,13 @@ impl Default for CopyConfig {
             include_patterns: Vec::new(),
             exclude_patterns: Vec::new(),
             filter_from: None,
+            hidden_files: HiddenMode::Include,
             dry_run: false,
+            preflight: true,
             use_zero_copy: true,
             generate_manifest: false,
             manifest_output_dir: None,
+            since_manifest: None,
             chunking_strategy: ChunkingStrategy::default(),
             audit_format: AuditFormat::Json,
             audit_log_path: None,
@@ -439,9 +577,11 @@ impl Default for CopyConfig {
             no_clobber: false,
             if_size_differ: false,
             if_source_newer: false,
+            overwrite: OverwritePolicy::Always,
             flatten: false,
             // rsync-inspired improvements
             sparse_mode: crate::core::sparse::SparseMode::Auto,
+            tail_mode: crate::core::tail::TailMode::Never,
             preserve_hardlinks: false,
             inplace: false,
             inplace_safety: InplaceSafety::Reflink,
@@ -472,6 +612,65 @@ pub enum CopyMode {
     Mirror,
 }
 
+/// How Mirror mode removes destination entries that are not in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DeleteMode {
+    /// Remove them
+    #[default]
+    Delete,
+
+    /// Move them under `.orbit-quarantine/<timestamp>/` in the destination,
+    /// keeping their relative paths, so they can be recovered
+    Quarantine,
+
+    /// Move them into a new group under `.orbit-trash/` in the destination,
+    /// the same layout as a backend trash, so `purge_trash` can expire them
+    Trash,
+}
+
+/// Whether a file copy may replace an existing destination file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    /// Leave existing files alone (`--no-clobber`)
+    Never,
+
+    /// Replace only files whose mtime is older than the source's
+    IfSourceNewer,
+
+    /// Replace unconditionally (`--force`)
+    #[default]
+    Always,
+
+    /// Ask on the terminal for each file, skipping it when there is no
+    /// terminal to ask on (`--interactive`)
+    Prompt,
+}
+
+/// Hash algorithm for checksum manifests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    /// SHA-256, checked with `sha256sum -c`; reuses the copy's verification
+    /// hash when one was computed
+    #[default]
+    Sha256,
+
+    /// BLAKE3, checked with `b3sum -c`
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// File extension for the default sidecar name
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Blake3 => "b3",
+        }
+    }
+}
+
 /// Compression type for file transfers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -488,6 +687,45 @@ pub enum CompressionType {
     Zstd { level: i32 },
 }
 
+/// Progress output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressFormat {
+    /// Interactive progress bars (TTY)
+    #[default]
+    Bar,
+
+    /// Newline-delimited JSON progress events written to stderr
+    Json,
+}
+
+/// Archive container written by `--archive`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    /// Uncompressed POSIX tar
+    #[serde(rename = "tar")]
+    Tar,
+
+    /// Tar stream compressed with zstd
+    #[serde(rename = "tar.zst")]
+    TarZst,
+
+    /// Zip with deflate compression
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Conventional file extension (without the leading dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarZst => "tar.zst",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+}
+
 /// Symbolic link handling mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -503,6 +741,35 @@ pub enum SymlinkMode {
     Preserve,
 }
 
+/// Handling of special files (FIFOs, sockets, character and block devices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecialFileMode {
+    /// Leave special files out of the copy
+    #[default]
+    Skip,
+
+    /// Record each special file as a failed file; the rest of the copy
+    /// continues and the run reports the failures at the end
+    Error,
+
+    /// Create the same node type at the destination (device nodes need
+    /// privileges)
+    Recreate,
+}
+
+/// Handling of hidden files and directories in directory walks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HiddenMode {
+    /// Walk hidden entries like any other
+    #[default]
+    Include,
+
+    /// Leave hidden entries out, unless an include pattern names them
+    Exclude,
+}
+
 /// Error handling mode determines behavior on errors
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -599,6 +866,14 @@ fn default_retry_delay() -> u64 {
     5
 }
 
+fn default_checkpoint_interval() -> u64 {
+    5
+}
+
+fn default_mtime_tolerance() -> u64 {
+    2
+}
+
 fn default_delta_block_size() -> usize {
     1024 * 1024 // 1 MB
 }
@@ -866,6 +1141,7 @@ recursive = false
         assert_eq!(config.concurrency, 5); // default
         assert!(config.show_stats); // default (now true)
         assert!(config.human_readable); // default (now true)
+        assert!(config.one_file_system); // default
     }
 
     #[test]
@@ -932,6 +1208,8 @@ audit_log_path = "/var/log/orbit_audit.log"
         assert!(!config.if_size_differ);
         assert!(!config.if_source_newer);
         assert!(!config.flatten);
+        // Source walks stay on one filesystem unless asked not to
+        assert!(config.one_file_system);
         // S3 upload enhancement fields (Phase 3)
         assert!(config.s3_content_type.is_none());
         assert!(config.s3_content_encoding.is_none());
@@ -0,0 +1,470 @@
+/*!
+ * Archive output mode (`--archive`).
+ *
+ * Instead of recreating the source tree at the destination, the tree is
+ * streamed into a single `.tar`, `.tar.zst`, or `.zip` file. Entries are
+ * written one at a time straight from the source files, so memory use does
+ * not grow with the size of the tree. Relative paths are preserved, and when
+ * metadata preservation is on, permissions and mtimes are stored where the
+ * format allows.
+ *
+ * The archive is written to a `.orbit-part` sibling and renamed into place
+ * once complete, so an interrupted run never leaves a truncated archive
+ * under the final name.
+ */
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+use walkdir::WalkDir;
+
+use super::filter::FilterList;
+use super::validation::matches_exclude_pattern;
+use super::CopyStats;
+use crate::config::{ArchiveFormat, CopyConfig, HiddenMode, SymlinkMode};
+use crate::error::{OrbitError, Result};
+
+/// Suffix for the in-progress archive file
+const PARTIAL_SUFFIX: &str = ".orbit-part";
+
+/// zstd level used for `tar.zst` output
+const TAR_ZSTD_LEVEL: i32 = 3;
+
+/// A filtered source entry ready to be appended to an archive
+struct ArchiveEntry {
+    source_path: PathBuf,
+    /// Archive member name, always `/`-separated
+    name: String,
+    kind: EntryKind,
+    metadata: fs::Metadata,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Directory,
+    File,
+    Symlink,
+}
+
+/// Resolve the archive file path for a destination.
+///
+/// An existing directory receives `<source name>.<ext>`; any other path is
+/// used as the archive file itself.
+pub fn resolve_archive_path(source_dir: &Path, dest: &Path, format: ArchiveFormat) -> PathBuf {
+    if dest.is_dir() {
+        let stem = source_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "archive".to_string());
+        dest.join(format!("{}.{}", stem, format.extension()))
+    } else {
+        dest.to_path_buf()
+    }
+}
+
+/// Stream a directory tree into a single archive at `dest`.
+pub fn archive_directory(
+    source_dir: &Path,
+    dest: &Path,
+    format: ArchiveFormat,
+    config: &CopyConfig,
+) -> Result<CopyStats> {
+    let start_time = Instant::now();
+    let archive_path = resolve_archive_path(source_dir, dest, format);
+
+    let filter_list = FilterList::from_config(
+        &config.include_patterns,
+        &config.exclude_patterns,
+        config.filter_from.as_deref(),
+    )
+    .map_err(|e| OrbitError::Config(format!("Invalid filter configuration: {}", e)))?;
+
+    if config.dry_run {
+        let mut stats = CopyStats::new();
+        for_each_entry(
+            source_dir,
+            &[&archive_path],
+            config,
+            &filter_list,
+            |entry| {
+                println!("Would archive: {}", entry.name);
+                if entry.kind == EntryKind::File {
+                    stats.files_copied += 1;
+                    stats.bytes_copied += entry.metadata.len();
+                }
+                Ok(())
+            },
+        )?;
+        stats.duration = start_time.elapsed();
+        return Ok(stats);
+    }
+
+    if let Some(parent) = archive_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut partial = archive_path.clone().into_os_string();
+    partial.push(PARTIAL_SUFFIX);
+    let partial_path = PathBuf::from(partial);
+
+    let result = write_archive(
+        source_dir,
+        &partial_path,
+        &[&archive_path, &partial_path],
+        format,
+        config,
+        &filter_list,
+    );
+
+    match result {
+        Ok(mut stats) => {
+            fs::rename(&partial_path, &archive_path)?;
+            stats.duration = start_time.elapsed();
+            Ok(stats)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            Err(e)
+        }
+    }
+}
+
+fn write_archive(
+    source_dir: &Path,
+    partial_path: &Path,
+    exclude: &[&Path],
+    format: ArchiveFormat,
+    config: &CopyConfig,
+    filter_list: &FilterList,
+) -> Result<CopyStats> {
+    let file = File::create(partial_path)?;
+    let mut stats = CopyStats::new();
+
+    match format {
+        ArchiveFormat::Tar => {
+            let mut builder = tar_builder(BufWriter::new(file), config);
+            for_each_entry(source_dir, exclude, config, filter_list, |entry| {
+                append_tar_entry(&mut builder, entry, &mut stats)
+            })?;
+            builder.into_inner()?.flush()?;
+        }
+        ArchiveFormat::TarZst => {
+            let encoder = zstd::Encoder::new(BufWriter::new(file), TAR_ZSTD_LEVEL)
+                .map_err(|e| OrbitError::Compression(e.to_string()))?;
+            let mut builder = tar_builder(encoder, config);
+            for_each_entry(source_dir, exclude, config, filter_list, |entry| {
+                append_tar_entry(&mut builder, entry, &mut stats)
+            })?;
+            builder
+                .into_inner()?
+                .finish()
+                .map_err(|e| OrbitError::Compression(e.to_string()))?
+                .flush()?;
+        }
+        ArchiveFormat::Zip => {
+            // ZipWriter needs Seek to patch local headers, so it writes the
+            // file directly rather than through a BufWriter.
+            let mut zip = zip::ZipWriter::new(file);
+            for_each_entry(source_dir, exclude, config, filter_list, |entry| {
+                append_zip_entry(&mut zip, entry, config, &mut stats)
+            })?;
+            zip.finish().map_err(zip_error)?;
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Walk the source tree, applying filters, symlink mode, `--max-depth` and
+/// `--one-file-system`, and hand each entry to `f` in a stable (sorted)
+/// order. Files in `exclude` are skipped.
+fn for_each_entry<F>(
+    source_dir: &Path,
+    exclude: &[&Path],
+    config: &CopyConfig,
+    filter_list: &FilterList,
+    mut f: F,
+) -> Result<()>
+where
+    F: FnMut(&ArchiveEntry) -> Result<()>,
+{
+    // Never archive the archive itself when it is written inside the source
+    let exclude: Vec<PathBuf> = exclude.iter().map(|p| absolute(p)).collect();
+
+    // Same walk options as a plain directory copy
+    let mut walker = WalkDir::new(source_dir)
+        .follow_links(config.symlink_mode == SymlinkMode::Follow)
+        .same_file_system(config.one_file_system)
+        .sort_by_file_name();
+    if let Some(max_depth) = config.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+    let mut walker = walker.into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::warn!("Failed to read entry: {}", e);
+                continue;
+            }
+        };
+
+        let relative_path = match entry.path().strip_prefix(source_dir) {
+            Ok(p) if !p.as_os_str().is_empty() => p,
+            _ => continue,
+        };
+
+        let should_process = if config.hidden_files == HiddenMode::Exclude
+            && filter_list.excludes_hidden(&entry, relative_path)
+        {
+            false
+        } else if !filter_list.is_empty() {
+            filter_list.should_include(relative_path)
+        } else {
+            !matches_exclude_pattern(relative_path, &config.exclude_patterns)
+        };
+        if !should_process {
+            if entry.file_type().is_dir() {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+
+        let file_type = entry.file_type();
+        let kind = if file_type.is_dir() {
+            EntryKind::Directory
+        } else if file_type.is_file() {
+            EntryKind::File
+        } else if file_type.is_symlink() {
+            if config.symlink_mode == SymlinkMode::Skip {
+                continue;
+            }
+            EntryKind::Symlink
+        } else {
+            continue; // Skip special files
+        };
+
+        if kind == EntryKind::File
+            && exclude
+                .iter()
+                .any(|p| p.file_name() == Some(entry.file_name()))
+            && exclude.contains(&absolute(entry.path()))
+        {
+            continue;
+        }
+
+        let source_path = entry.path().to_path_buf();
+        let metadata = if kind == EntryKind::Symlink {
+            fs::symlink_metadata(&source_path)?
+        } else {
+            fs::metadata(&source_path)?
+        };
+
+        f(&ArchiveEntry {
+            source_path,
+            name: member_name(relative_path),
+            kind,
+            metadata,
+        })?;
+    }
+
+    Ok(())
+}
+
+fn tar_builder<W: Write>(writer: W, config: &CopyConfig) -> tar::Builder<W> {
+    let mut builder = tar::Builder::new(writer);
+    // Symlinks reaching the builder are always stored as links; following
+    // is handled by the walker.
+    builder.follow_symlinks(false);
+    builder.mode(if config.preserve_metadata {
+        tar::HeaderMode::Complete
+    } else {
+        tar::HeaderMode::Deterministic
+    });
+    builder
+}
+
+fn append_tar_entry<W: Write>(
+    builder: &mut tar::Builder<W>,
+    entry: &ArchiveEntry,
+    stats: &mut CopyStats,
+) -> Result<()> {
+    match entry.kind {
+        EntryKind::Directory => builder.append_dir(&entry.name, &entry.source_path)?,
+        EntryKind::File => {
+            let mut file = File::open(&entry.source_path)?;
+            builder.append_file(&entry.name, &mut file)?;
+            stats.files_copied += 1;
+            stats.bytes_copied += entry.metadata.len();
+        }
+        EntryKind::Symlink => builder.append_path_with_name(&entry.source_path, &entry.name)?,
+    }
+    Ok(())
+}
+
+fn append_zip_entry<W: Write + io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    entry: &ArchiveEntry,
+    config: &CopyConfig,
+    stats: &mut CopyStats,
+) -> Result<()> {
+    let mut options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    if config.preserve_metadata {
+        if let Some(mode) = unix_mode(&entry.metadata) {
+            options = options.unix_permissions(mode);
+        }
+        if let Some(time) = entry.metadata.modified().ok().and_then(zip_time) {
+            options = options.last_modified_time(time);
+        }
+    }
+
+    match entry.kind {
+        EntryKind::Directory => zip
+            .add_directory(entry.name.as_str(), options)
+            .map_err(zip_error)?,
+        EntryKind::File => {
+            let options = options.large_file(entry.metadata.len() >= u32::MAX as u64);
+            zip.start_file(entry.name.as_str(), options)
+                .map_err(zip_error)?;
+            let mut file = File::open(&entry.source_path)?;
+            io::copy(&mut file, zip)?;
+            stats.files_copied += 1;
+            stats.bytes_copied += entry.metadata.len();
+        }
+        EntryKind::Symlink => {
+            let target = fs::read_link(&entry.source_path)?;
+            zip.add_symlink(entry.name.as_str(), target.to_string_lossy(), options)
+                .map_err(zip_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Archive member name for a relative path, using `/` on every platform
+fn member_name(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Convert an mtime to zip's local-time DOS timestamp (2-second resolution,
+/// 1980..=2107). Out-of-range times are left unset.
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    use chrono::{Datelike, Timelike};
+
+    let local: chrono::DateTime<chrono::Local> = time.into();
+    let year = u16::try_from(local.year()).ok()?;
+    zip::DateTime::from_date_and_time(
+        year,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        (local.second() as u8) & !1,
+    )
+    .ok()
+}
+
+fn zip_error(e: zip::result::ZipError) -> OrbitError {
+    match e {
+        zip::result::ZipError::Io(io_err) => OrbitError::Io(io_err),
+        other => OrbitError::Other(format!("Zip archive error: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_archive_path_into_existing_directory() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("photos");
+        let path = resolve_archive_path(&source, dir.path(), ArchiveFormat::TarZst);
+        assert_eq!(path, dir.path().join("photos.tar.zst"));
+
+        let explicit = dir.path().join("out.zip");
+        let path = resolve_archive_path(&source, &explicit, ArchiveFormat::Zip);
+        assert_eq!(path, explicit);
+    }
+
+    #[test]
+    fn test_member_name_uses_forward_slashes() {
+        let rel: PathBuf = ["a", "b", "c.txt"].iter().collect();
+        assert_eq!(member_name(&rel), "a/b/c.txt");
+    }
+
+    #[test]
+    fn test_archive_skips_itself_when_written_inside_source() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("data.txt"), b"hello").unwrap();
+
+        let archive = dir.path().join("self.tar");
+        let config = CopyConfig {
+            recursive: true,
+            ..Default::default()
+        };
+        let stats = archive_directory(dir.path(), &archive, ArchiveFormat::Tar, &config).unwrap();
+        assert_eq!(stats.files_copied, 1);
+
+        let mut reader = tar::Archive::new(File::open(&archive).unwrap());
+        let names: Vec<String> = reader
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["data.txt".to_string()]);
+    }
+
+    #[test]
+    fn test_archive_honors_max_depth() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("src");
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::write(source.join("top.txt"), b"top").unwrap();
+        fs::write(source.join("sub/deep.txt"), b"deep").unwrap();
+
+        let archive = dir.path().join("out.tar");
+        let config = CopyConfig {
+            recursive: true,
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let stats = archive_directory(&source, &archive, ArchiveFormat::Tar, &config).unwrap();
+        assert_eq!(stats.files_copied, 1);
+
+        let mut reader = tar::Archive::new(File::open(&archive).unwrap());
+        let names: Vec<String> = reader
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert!(names.contains(&"top.txt".to_string()));
+        assert!(!names.iter().any(|name| name.ends_with("deep.txt")));
+    }
+}
@@ -1,22 +1,100 @@
 /*!
  * Bandwidth throttling utilities with token bucket rate limiting
+ *
+ * A limiter either enforces one fixed rate or follows a time-of-day schedule
+ * (`--bwlimit-schedule`). Scheduled limiters re-check the local wall clock on
+ * every call and swap in a fresh token bucket when a window boundary is
+ * crossed, so long transfers pick up the new rate mid-file.
  */
 
+use chrono::{Local, NaiveTime};
 use governor::{
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock},
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter as GovernorRateLimiter,
 };
+use serde::{Deserialize, Serialize};
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// Token bucket refill rate; each token is worth `rate / TOKENS_PER_SEC` bytes
+const TOKENS_PER_SEC: u32 = 1000;
+/// Bucket capacity: 100ms of traffic, so an idle limiter cannot release a
+/// full second's worth of data in one burst
+const BURST_TOKENS: u32 = 100;
+
+/// A local time-of-day window with its own bandwidth limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthWindow {
+    /// Local time the window opens (inclusive)
+    pub start: NaiveTime,
+
+    /// Local time the window closes (exclusive). An end at or before `start`
+    /// wraps past midnight.
+    pub end: NaiveTime,
+
+    /// Bytes per second while the window is open (0 = unlimited)
+    pub limit: u64,
+}
+
+impl BandwidthWindow {
+    /// Whether `time` falls inside this window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Parse a `--bwlimit-schedule` spec such as `09:00-17:00=10,22:00-06:00=0`.
+///
+/// Each entry is `HH:MM-HH:MM=MBPS` with the limit in MB/s, matching
+/// `--max-bandwidth`; 0 means unlimited during that window.
+pub fn parse_bandwidth_schedule(spec: &str) -> Result<Vec<BandwidthWindow>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (times, limit) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("missing '=<MB/s>' in schedule entry '{}'", entry))?;
+            let (start, end) = times
+                .split_once('-')
+                .ok_or_else(|| format!("expected HH:MM-HH:MM in schedule entry '{}'", entry))?;
+            let parse_time = |t: &str| {
+                NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                    .map_err(|_| format!("invalid time '{}' in schedule entry '{}'", t, entry))
+            };
+            let mbps: u64 = limit
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid limit '{}' in schedule entry '{}'", limit, entry))?;
+            Ok(BandwidthWindow {
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+                limit: mbps.saturating_mul(1024 * 1024),
+            })
+        })
+        .collect()
+}
+
+/// Limit in effect at `time`: the first matching window, else `default_limit`
+pub fn effective_limit(schedule: &[BandwidthWindow], default_limit: u64, time: NaiveTime) -> u64 {
+    schedule
+        .iter()
+        .find(|w| w.contains(time))
+        .map_or(default_limit, |w| w.limit)
+}
+
 /// Bandwidth rate limiter using token bucket algorithm
 #[derive(Clone)]
 pub struct BandwidthLimiter {
-    limiter: Option<Arc<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
-    bytes_per_token: u64,
+    bucket: Option<TokenBucket>,
+    schedule: Option<Arc<Schedule>>,
 }
 
 impl BandwidthLimiter {
@@ -25,47 +103,154 @@ impl BandwidthLimiter {
     /// # Arguments
     /// * `max_bytes_per_sec` - Maximum bytes per second (0 = unlimited)
     pub fn new(max_bytes_per_sec: u64) -> Self {
-        if max_bytes_per_sec == 0 {
-            return Self {
-                limiter: None,
-                bytes_per_token: 0,
-            };
+        Self {
+            bucket: TokenBucket::new(max_bytes_per_sec),
+            schedule: None,
         }
+    }
 
-        // Configure token bucket: replenish at rate to achieve target bandwidth
-        // Use 1000 tokens/sec, each token = bytes_per_sec/1000 bytes
-        // Guard against very low bandwidth values where integer division would yield 0
-        let tokens_per_sec = 1000u32;
-        let bytes_per_token = (max_bytes_per_sec / tokens_per_sec as u64).max(1);
+    /// Create a limiter that follows `schedule` in local time and falls back to
+    /// `default_limit` outside every window
+    pub fn with_schedule(default_limit: u64, schedule: Vec<BandwidthWindow>) -> Self {
+        Self::with_schedule_clock(default_limit, schedule, Box::new(|| Local::now().time()))
+    }
 
-        let quota = Quota::per_second(NonZeroU32::new(tokens_per_sec).unwrap());
+    /// Create the limiter described by `config.max_bandwidth` and
+    /// `config.bandwidth_schedule`
+    pub fn from_config(config: &crate::config::CopyConfig) -> Self {
+        Self::with_schedule(config.max_bandwidth, config.bandwidth_schedule.clone())
+    }
 
+    fn with_schedule_clock(
+        default_limit: u64,
+        windows: Vec<BandwidthWindow>,
+        clock: Box<dyn Fn() -> NaiveTime + Send + Sync>,
+    ) -> Self {
+        if windows.is_empty() {
+            return Self::new(default_limit);
+        }
         Self {
-            limiter: Some(Arc::new(GovernorRateLimiter::direct(quota))),
-            bytes_per_token,
+            bucket: None,
+            schedule: Some(Arc::new(Schedule {
+                windows,
+                default_limit,
+                clock,
+                active: Mutex::new(None),
+            })),
         }
     }
 
     /// Wait until we can transfer the given number of bytes
     pub fn wait_for_capacity(&self, bytes: u64) {
-        if let Some(ref limiter) = self.limiter {
-            if let Some(tokens_needed) = bytes
-                .checked_div(self.bytes_per_token)
-                .map(|tokens| tokens.max(1) as u32)
-            {
-                // Wait for tokens to become available
-                if let Some(tokens) = NonZeroU32::new(tokens_needed) {
-                    while limiter.check_n(tokens).is_err() {
-                        thread::sleep(Duration::from_millis(1));
-                    }
-                }
-            }
+        if let Some(bucket) = self.current_bucket() {
+            bucket.wait(bytes);
         }
     }
 
     /// Check if bandwidth limiting is enabled
     pub fn is_enabled(&self) -> bool {
-        self.limiter.is_some()
+        match &self.schedule {
+            Some(schedule) => {
+                schedule.default_limit > 0 || schedule.windows.iter().any(|w| w.limit > 0)
+            }
+            None => self.bucket.is_some(),
+        }
+    }
+
+    /// Limit currently in effect in bytes per second (0 = unlimited)
+    pub fn current_limit(&self) -> u64 {
+        self.current_bucket().map_or(0, |b| b.rate)
+    }
+
+    fn current_bucket(&self) -> Option<TokenBucket> {
+        match &self.schedule {
+            Some(schedule) => schedule.current_bucket(),
+            None => self.bucket.clone(),
+        }
+    }
+}
+
+/// Time-of-day schedule shared by clones of a limiter
+struct Schedule {
+    windows: Vec<BandwidthWindow>,
+    default_limit: u64,
+    clock: Box<dyn Fn() -> NaiveTime + Send + Sync>,
+    /// Limit and bucket for the window currently in effect
+    active: Mutex<Option<(u64, Option<TokenBucket>)>>,
+}
+
+impl Schedule {
+    fn current_bucket(&self) -> Option<TokenBucket> {
+        let limit = effective_limit(&self.windows, self.default_limit, (self.clock)());
+        let mut active = self.active.lock().unwrap();
+        match active.as_ref() {
+            Some((current, bucket)) if *current == limit => bucket.clone(),
+            _ => {
+                tracing::info!("Bandwidth schedule: limit now {} bytes/sec", limit);
+                let bucket = TokenBucket::new(limit);
+                *active = Some((limit, bucket.clone()));
+                bucket
+            }
+        }
+    }
+}
+
+/// Fixed-rate token bucket
+#[derive(Clone)]
+struct TokenBucket {
+    limiter: Arc<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+    bytes_per_token: u64,
+    rate: u64,
+}
+
+impl TokenBucket {
+    /// Bucket for `rate` bytes per second, or None when unlimited
+    fn new(rate: u64) -> Option<Self> {
+        if rate == 0 {
+            return None;
+        }
+
+        // Configure token bucket: replenish at rate to achieve target bandwidth
+        // Guard against very low bandwidth values where integer division would yield 0
+        let bytes_per_token = (rate / TOKENS_PER_SEC as u64).max(1);
+        let quota = Quota::per_second(NonZeroU32::new(TOKENS_PER_SEC).unwrap())
+            .allow_burst(NonZeroU32::new(BURST_TOKENS).unwrap());
+
+        Some(Self {
+            limiter: Arc::new(GovernorRateLimiter::direct(quota)),
+            bytes_per_token,
+            rate,
+        })
+    }
+
+    fn tokens_for(&self, bytes: u64) -> u64 {
+        (bytes / self.bytes_per_token).max(1)
+    }
+
+    /// Block until `bytes` worth of tokens have been taken.
+    ///
+    /// Requests larger than the bucket are taken in bucket-sized installments.
+    fn wait(&self, bytes: u64) {
+        let mut remaining = self.tokens_for(bytes);
+        while remaining > 0 {
+            let n = remaining.min(BURST_TOKENS as u64) as u32;
+            let tokens = NonZeroU32::new(n).unwrap();
+            // n never exceeds the burst size, so capacity errors cannot occur
+            while let Ok(Err(not_until)) = self.limiter.check_n(tokens) {
+                thread::sleep(not_until.wait_time_from(DefaultClock::default().now()));
+            }
+            remaining -= n as u64;
+        }
+    }
+
+    /// Take `bytes` worth of tokens if they are available right now
+    #[cfg(test)]
+    fn try_take(&self, bytes: u64) -> bool {
+        let n = self.tokens_for(bytes).min(BURST_TOKENS as u64) as u32;
+        matches!(
+            self.limiter.check_n(NonZeroU32::new(n).unwrap()),
+            Ok(Ok(()))
+        )
     }
 }
 
@@ -216,4 +401,99 @@ mod tests {
             max_duration
         );
     }
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bandwidth_schedule() {
+        let schedule = parse_bandwidth_schedule("09:00-17:00=10, 22:00-06:00=0").unwrap();
+        assert_eq!(
+            schedule,
+            vec![
+                BandwidthWindow {
+                    start: at(9, 0),
+                    end: at(17, 0),
+                    limit: 10 * 1024 * 1024,
+                },
+                BandwidthWindow {
+                    start: at(22, 0),
+                    end: at(6, 0),
+                    limit: 0,
+                },
+            ]
+        );
+        assert!(parse_bandwidth_schedule("09:00=10").is_err());
+        assert!(parse_bandwidth_schedule("9am-5pm=10").is_err());
+        assert!(parse_bandwidth_schedule("09:00-17:00").is_err());
+    }
+
+    #[test]
+    fn test_effective_limit_windows_and_midnight_wrap() {
+        let schedule = parse_bandwidth_schedule("09:00-17:00=10,22:00-06:00=0").unwrap();
+        let default = 50 * 1024 * 1024;
+        assert_eq!(effective_limit(&schedule, default, at(8, 59)), default);
+        assert_eq!(
+            effective_limit(&schedule, default, at(9, 0)),
+            10 * 1024 * 1024
+        );
+        assert_eq!(effective_limit(&schedule, default, at(17, 0)), default);
+        assert_eq!(effective_limit(&schedule, default, at(23, 30)), 0);
+        assert_eq!(effective_limit(&schedule, default, at(3, 0)), 0);
+    }
+
+    #[test]
+    fn test_scheduled_limit_switches_at_window_boundary() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        // Mocked wall clock in minutes since midnight
+        let minutes = Arc::new(AtomicU32::new(8 * 60 + 59));
+        let clock_minutes = Arc::clone(&minutes);
+        let one_mb = 1024 * 1024;
+        let limiter = BandwidthLimiter::with_schedule_clock(
+            4 * one_mb,
+            vec![BandwidthWindow {
+                start: at(9, 0),
+                end: at(17, 0),
+                limit: one_mb,
+            }],
+            Box::new(move || {
+                let m = clock_minutes.load(Ordering::SeqCst);
+                at(m / 60, m % 60)
+            }),
+        );
+        assert!(limiter.is_enabled());
+        assert_eq!(limiter.current_limit(), 4 * one_mb);
+
+        // Business hours start mid-transfer: the 1 MB/s bucket holds 100ms
+        // of traffic, so 100 KB passes and the next 50 KB must wait
+        minutes.store(9 * 60, Ordering::SeqCst);
+        assert_eq!(limiter.current_limit(), one_mb);
+        let bucket = limiter.current_bucket().unwrap();
+        assert!(bucket.try_take(100 * 1024));
+        assert!(!bucket.try_take(50 * 1024));
+
+        // Clones share the schedule and see the same bucket
+        let clone = limiter.clone();
+        assert!(!clone.current_bucket().unwrap().try_take(50 * 1024));
+
+        // Evening: back to the default rate with a fresh, larger bucket
+        minutes.store(17 * 60, Ordering::SeqCst);
+        assert_eq!(limiter.current_limit(), 4 * one_mb);
+        let bucket = limiter.current_bucket().unwrap();
+        assert!(bucket.try_take(300 * 1024));
+        assert!(!bucket.try_take(200 * 1024));
+    }
+
+    #[test]
+    fn test_wait_splits_requests_larger_than_bucket() {
+        // 2 KB/s bucket holds 200 bytes; 1 KB must wait ~0.4s for refill
+        let limiter = BandwidthLimiter::new(2_000);
+        let start = Instant::now();
+        limiter.wait_for_capacity(1_000);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(3), "{:?}", elapsed);
+    }
 }
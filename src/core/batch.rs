@@ -423,7 +423,7 @@ pub fn record_create_file(
     let mode = file_mode(&metadata);
 
     let file = File::open(source_path)?;
-    let config = orbit_core_cdc::ChunkConfig::default_config();
+    let config = orbit_core_cdc::ChunkConfig::for_file_size(size);
     let stream = orbit_core_cdc::ChunkStream::new(file, config);
 
     let mut chunk_hashes = Vec::new();
@@ -431,6 +431,7 @@ pub fn record_create_file(
 
     for chunk in stream {
         let chunk = chunk.map_err(io::Error::other)?;
+        orbit_observability::metrics::record_chunk(chunk.length);
         chunk_hashes.push(chunk.hash);
         chunk_data.push(chunk.data);
     }
@@ -15,7 +15,7 @@ use super::inplace::InplaceWriter;
 use super::progress::ProgressPublisher;
 use super::resume::{
     cleanup_resume_info, decide_resume_strategy, load_resume_info, record_chunk_digest,
-    save_resume_info_full, validate_chunks, ResumeDecision, ResumeInfo,
+    save_resume_info_full, validate_chunks, CheckpointSchedule, ResumeDecision, ResumeInfo,
 };
 use super::CopyStats;
 use crate::config::CopyConfig;
@@ -55,11 +55,11 @@ pub fn copy_buffered(
     }
 
     // Setup bandwidth limiter with token bucket algorithm
-    let bandwidth_limiter = BandwidthLimiter::new(config.max_bandwidth);
+    let bandwidth_limiter = BandwidthLimiter::from_config(config);
     if bandwidth_limiter.is_enabled() {
         info!(
             "Bandwidth limiting enabled: {} bytes/sec",
-            config.max_bandwidth
+            bandwidth_limiter.current_limit()
         );
     }
 
@@ -193,6 +193,10 @@ pub fn copy_buffered(
 
     let mut sparse_file: Option<File> = None;
     let mut buffered_writer: Option<BufWriter<File>> = None;
+    // Destination length before this run; zero regions below it may hold
+    // data from an interrupted copy and must be punched out, not skipped
+    let mut sparse_existing_len = 0;
+    let mut source_holes = None;
 
     if inplace_writer.is_none() {
         if sparse_enabled {
@@ -203,9 +207,11 @@ pub fn copy_buffered(
                 .truncate(start_offset == 0)
                 .open(dest_path)?;
             if start_offset > 0 {
+                sparse_existing_len = file.metadata()?.len();
                 file.seek(SeekFrom::Start(start_offset))?;
             }
             sparse_file = Some(file);
+            source_holes = super::sparse::SourceHoles::probe(source_path, source_size);
         } else {
             buffered_writer = Some(BufWriter::new(
                 OpenOptions::new()
@@ -276,7 +282,7 @@ pub fn copy_buffered(
     // Copy loop
     let mut buffer = vec![0u8; config.chunk_size];
     let mut bytes_copied = start_offset;
-    let mut last_checkpoint = Instant::now();
+    let mut checkpoints = CheckpointSchedule::from_config(config, start_offset);
     let mut last_progress_event = Instant::now();
     let progress_interval = Duration::from_millis(500); // Emit progress events every 500ms
 
@@ -284,7 +290,17 @@ pub fn copy_buffered(
         let remaining = (source_size - bytes_copied) as usize;
         let to_read = remaining.min(config.chunk_size);
 
-        let n = source_file.read(&mut buffer[..to_read])?;
+        let in_hole = source_holes
+            .as_mut()
+            .is_some_and(|holes| holes.is_hole(bytes_copied, to_read as u64));
+        let n = if in_hole {
+            // The filesystem knows this range is zero: don't read it
+            source_file.seek_relative(to_read as i64)?;
+            buffer[..to_read].fill(0);
+            to_read
+        } else {
+            source_file.read(&mut buffer[..to_read])?
+        };
         if n == 0 {
             break;
         }
@@ -299,7 +315,7 @@ pub fn copy_buffered(
         } else if let Some(ref mut file) = sparse_file {
             let is_zero = sparse_enabled && buffer[..n].iter().all(|&b| b == 0);
             if is_zero {
-                file.seek(SeekFrom::Current(n as i64))?;
+                super::sparse::skip_zero_region(file, bytes_copied, n, sparse_existing_len)?;
             } else {
                 file.write_all(&buffer[..n])?;
             }
@@ -327,7 +343,7 @@ pub fn copy_buffered(
         }
 
         // Checkpoint for resume - sync to disk for durability
-        if config.resume_enabled && last_checkpoint.elapsed() > Duration::from_secs(5) {
+        if config.resume_enabled && checkpoints.is_due(bytes_copied) {
             if let Some(ref mut writer) = buffered_writer {
                 writer.flush()?;
                 writer.get_ref().sync_data()?;
@@ -336,7 +352,7 @@ pub fn copy_buffered(
             }
             resume_info.bytes_copied = bytes_copied;
             save_resume_info_full(dest_path, &resume_info, false)?;
-            last_checkpoint = Instant::now();
+            checkpoints.mark(bytes_copied);
         }
 
         // Bandwidth throttling using token bucket algorithm
@@ -375,6 +391,7 @@ pub fn copy_buffered(
 
     // Verify checksum
     let checksum = if let Some(h) = hasher {
+        let _verify = tracing::info_span!("verify").entered();
         let hash = h.finalize();
         Some(format!("{:x}", hash))
     } else {
@@ -404,3 +421,51 @@ pub fn copy_buffered(
         bytes_skipped: 0,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::resume::resume_writes_on_this_thread;
+    use tempfile::tempdir;
+
+    fn copy_counting_checkpoints(checkpoint_bytes: u64) -> usize {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("dest.bin");
+        let data: Vec<u8> = (0..1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let config = CopyConfig {
+            resume_enabled: true,
+            show_progress: false,
+            chunk_size: 64 * 1024,
+            checkpoint_interval_secs: 0,
+            checkpoint_interval_bytes: checkpoint_bytes,
+            ..Default::default()
+        };
+
+        let before = resume_writes_on_this_thread();
+        copy_buffered(
+            &source,
+            &dest,
+            data.len() as u64,
+            &config,
+            &ProgressPublisher::noop(),
+        )
+        .unwrap();
+        let writes = resume_writes_on_this_thread() - before;
+
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+        writes
+    }
+
+    #[test]
+    fn test_checkpoints_follow_byte_interval() {
+        // 1 MiB in 64 KiB chunks: a checkpoint after every 256 KiB
+        assert_eq!(copy_counting_checkpoints(256 * 1024), 4);
+        // One per chunk
+        assert_eq!(copy_counting_checkpoints(64 * 1024), 16);
+        // Neither trigger enabled
+        assert_eq!(copy_counting_checkpoints(0), 0);
+    }
+}
@@ -2,11 +2,13 @@
  * Streaming checksum calculation for efficient hashing during copy
  */
 
+use crate::config::ChecksumAlgorithm;
 use crate::error::Result;
 use sha2::{Digest, Sha256};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
+use std::sync::Mutex;
 
 /// Streaming hasher that calculates checksum incrementally
 pub struct StreamingHasher {
@@ -55,6 +57,85 @@ pub fn calculate_checksum(path: &Path) -> Result<String> {
     Ok(format!("{:x}", hasher.finalize()))
 }
 
+/// Calculate a file's hash with `algorithm`, as lowercase hex
+pub fn calculate_checksum_with(path: &Path, algorithm: ChecksumAlgorithm) -> Result<String> {
+    match algorithm {
+        ChecksumAlgorithm::Sha256 => calculate_checksum(path),
+        ChecksumAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update_reader(BufReader::new(File::open(path)?))?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+    }
+}
+
+/// Per-file hashes collected during a copy and written as a sidecar in the
+/// `hash  path` format read by `sha256sum -c` and `b3sum -c`
+pub struct ChecksumManifest {
+    algorithm: ChecksumAlgorithm,
+    entries: Mutex<Vec<(String, String)>>,
+}
+
+impl ChecksumManifest {
+    /// Empty manifest for hashes produced by `algorithm`
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            algorithm,
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Algorithm the recorded hashes must use
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// Record `hash` for `relative_path` (relative to the destination root)
+    pub fn record(&self, relative_path: &Path, hash: String) {
+        let mut path = relative_path.to_string_lossy().into_owned();
+        if cfg!(windows) {
+            path = path.replace('\\', "/");
+        }
+        self.entries.lock().unwrap().push((path, hash));
+    }
+
+    /// Number of files recorded
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Render the manifest sorted by path
+    ///
+    /// Paths containing a newline or backslash are escaped the way GNU
+    /// coreutils does: the line starts with `\` and those characters are
+    /// written as `\n` and `\\`.
+    pub fn render(&self) -> String {
+        let mut entries = self.entries.lock().unwrap().clone();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut out = String::new();
+        for (path, hash) in entries {
+            if path.contains(['\n', '\\']) {
+                let escaped = path.replace('\\', "\\\\").replace('\n', "\\n");
+                out.push_str(&format!("\\{}  {}\n", hash, escaped));
+            } else {
+                out.push_str(&format!("{}  {}\n", hash, path));
+            }
+        }
+        out
+    }
+
+    /// Write the sorted manifest to `path`
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +164,17 @@ mod tests {
         let checksum = calculate_checksum(temp.path()).unwrap();
         assert_eq!(checksum.len(), 64); // SHA256 is 64 hex chars
     }
+
+    #[test]
+    fn test_checksum_manifest_sorted_and_escaped() {
+        let manifest = ChecksumManifest::new(ChecksumAlgorithm::Sha256);
+        manifest.record(Path::new("b.txt"), "22".to_string());
+        manifest.record(Path::new("a/one.txt"), "11".to_string());
+        manifest.record(Path::new("odd\nname"), "33".to_string());
+
+        assert_eq!(
+            manifest.render(),
+            "11  a/one.txt\n22  b.txt\n\\33  odd\\nname\n"
+        );
+    }
 }
@@ -2,23 +2,31 @@
  * Concurrency control for managing parallel transfers
  *
  * This module provides semaphore-based concurrency limiting to control
- * the number of simultaneous file operations.
+ * the number of simultaneous file operations. An adaptive limiter can
+ * additionally tune its limit from observed throughput and error rate.
  */
 
 use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
 /// A counting semaphore for controlling concurrent operations
 #[derive(Clone)]
 pub struct ConcurrencyLimiter {
     state: Arc<SemaphoreState>,
+    adaptive: Option<Arc<Mutex<AdaptiveWindow>>>,
 }
 
 struct SemaphoreState {
-    available: Mutex<usize>,
+    permits: Mutex<Permits>,
     condvar: Condvar,
     max: usize,
 }
 
+struct Permits {
+    in_use: usize,
+    limit: usize,
+}
+
 impl ConcurrencyLimiter {
     /// Create a new concurrency limiter
     ///
@@ -38,24 +46,41 @@ impl ConcurrencyLimiter {
             max_concurrent
         };
 
+        Self::with_limit(max, max)
+    }
+
+    /// Create a limiter whose limit is tuned by an [`AdaptiveController`]
+    ///
+    /// Starts at `baseline` permits and moves between 1 and `max` as
+    /// completions are reported through [`record_completion`](Self::record_completion).
+    pub fn adaptive(baseline: usize, max: usize) -> Self {
+        let max = max.max(1);
+        let controller = AdaptiveController::new(baseline, max);
+        let mut limiter = Self::with_limit(controller.current(), max);
+        limiter.adaptive = Some(Arc::new(Mutex::new(AdaptiveWindow::new(controller))));
+        limiter
+    }
+
+    fn with_limit(limit: usize, max: usize) -> Self {
         Self {
             state: Arc::new(SemaphoreState {
-                available: Mutex::new(max),
+                permits: Mutex::new(Permits { in_use: 0, limit }),
                 condvar: Condvar::new(),
                 max,
             }),
+            adaptive: None,
         }
     }
 
     /// Acquire a permit (blocks until available)
     pub fn acquire(&self) -> ConcurrencyPermit {
-        let mut available = self.state.available.lock().unwrap();
+        let mut permits = self.state.permits.lock().unwrap();
 
-        while *available == 0 {
-            available = self.state.condvar.wait(available).unwrap();
+        while permits.in_use >= permits.limit {
+            permits = self.state.condvar.wait(permits).unwrap();
         }
 
-        *available -= 1;
+        permits.in_use += 1;
 
         ConcurrencyPermit {
             state: self.state.clone(),
@@ -64,10 +89,10 @@ impl ConcurrencyLimiter {
 
     /// Try to acquire a permit without blocking
     pub fn try_acquire(&self) -> Option<ConcurrencyPermit> {
-        let mut available = self.state.available.lock().unwrap();
+        let mut permits = self.state.permits.lock().unwrap();
 
-        if *available > 0 {
-            *available -= 1;
+        if permits.in_use < permits.limit {
+            permits.in_use += 1;
             Some(ConcurrencyPermit {
                 state: self.state.clone(),
             })
@@ -81,9 +106,70 @@ impl ConcurrencyLimiter {
         self.state.max
     }
 
+    /// Get the current limit (equal to `max_concurrent` unless adaptive)
+    pub fn limit(&self) -> usize {
+        self.state.permits.lock().unwrap().limit
+    }
+
+    /// Change the limit, clamped to `1..=max_concurrent`
+    ///
+    /// Lowering the limit does not revoke permits already held; new
+    /// acquisitions wait until enough of them are released.
+    pub fn set_limit(&self, limit: usize) {
+        let mut permits = self.state.permits.lock().unwrap();
+        permits.limit = limit.clamp(1, self.state.max);
+        self.state.condvar.notify_all();
+    }
+
     /// Get the current number of available permits
     pub fn available(&self) -> usize {
-        *self.state.available.lock().unwrap()
+        let permits = self.state.permits.lock().unwrap();
+        permits.limit.saturating_sub(permits.in_use)
+    }
+
+    /// Whether this limiter tunes its own limit
+    pub fn is_adaptive(&self) -> bool {
+        self.adaptive.is_some()
+    }
+
+    /// Report a finished operation to the adaptive controller
+    ///
+    /// Completions are grouped into windows of at least
+    /// [`ADAPTIVE_WINDOW`] and one operation per permit; each closed window
+    /// feeds its throughput and error rate to the controller and applies the
+    /// new limit. No-op for fixed limiters.
+    pub fn record_completion(&self, bytes: u64, success: bool) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+        let mut window = adaptive.lock().unwrap();
+        window.bytes += bytes;
+        window.ops += 1;
+        if !success {
+            window.errors += 1;
+        }
+
+        let elapsed = window.started.elapsed();
+        if elapsed < ADAPTIVE_WINDOW || window.ops < window.controller.current() as u64 {
+            return;
+        }
+
+        let throughput = window.bytes as f64 / elapsed.as_secs_f64();
+        let error_rate = window.errors as f64 / window.ops as f64;
+        let previous = window.controller.current();
+        let next = window.controller.adjust(throughput, error_rate);
+        if next != previous {
+            tracing::debug!(
+                "Adaptive concurrency {} -> {} ({:.0} B/s, {:.0}% errors)",
+                previous,
+                next,
+                throughput,
+                error_rate * 100.0
+            );
+        }
+        window.reset();
+        drop(window);
+        self.set_limit(next);
     }
 }
 
@@ -95,12 +181,105 @@ pub struct ConcurrencyPermit {
 
 impl Drop for ConcurrencyPermit {
     fn drop(&mut self) {
-        let mut available = self.state.available.lock().unwrap();
-        *available += 1;
+        let mut permits = self.state.permits.lock().unwrap();
+        permits.in_use -= 1;
         self.state.condvar.notify_one();
     }
 }
 
+/// Minimum length of an adaptive measurement window
+pub const ADAPTIVE_WINDOW: Duration = Duration::from_millis(500);
+
+/// Relative throughput change treated as noise rather than a trend
+const THROUGHPUT_TOLERANCE: f64 = 0.05;
+
+/// Error/timeout rate above which the controller backs off multiplicatively
+const ERROR_RATE_LIMIT: f64 = 0.05;
+
+/// AIMD-style concurrency controller
+///
+/// Each call to [`adjust`](Self::adjust) reports the throughput and error rate
+/// measured at the current concurrency:
+/// - error rate above 5%: halve (multiplicative decrease)
+/// - throughput up more than 5% on the previous window: add one
+/// - throughput down more than 5%: remove one
+/// - otherwise: hold
+///
+/// Storage that degrades past some concurrency K therefore settles around
+/// K instead of climbing to `max`.
+#[derive(Debug, Clone)]
+pub struct AdaptiveController {
+    current: usize,
+    max: usize,
+    last_throughput: Option<f64>,
+}
+
+impl AdaptiveController {
+    /// Start at `baseline`, never exceeding `max`
+    pub fn new(baseline: usize, max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            current: baseline.clamp(1, max),
+            max,
+            last_throughput: None,
+        }
+    }
+
+    /// Current concurrency
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Feed one window's measurements and return the new concurrency
+    pub fn adjust(&mut self, throughput: f64, error_rate: f64) -> usize {
+        if error_rate > ERROR_RATE_LIMIT {
+            self.current = (self.current / 2).max(1);
+            // Re-baseline at the reduced level
+            self.last_throughput = None;
+            return self.current;
+        }
+
+        let next = match self.last_throughput {
+            None => self.current + 1,
+            Some(prev) if throughput > prev * (1.0 + THROUGHPUT_TOLERANCE) => self.current + 1,
+            Some(prev) if throughput < prev * (1.0 - THROUGHPUT_TOLERANCE) => {
+                self.current.saturating_sub(1)
+            }
+            Some(_) => self.current,
+        };
+        self.current = next.clamp(1, self.max);
+        self.last_throughput = Some(throughput);
+        self.current
+    }
+}
+
+struct AdaptiveWindow {
+    controller: AdaptiveController,
+    started: Instant,
+    bytes: u64,
+    ops: u64,
+    errors: u64,
+}
+
+impl AdaptiveWindow {
+    fn new(controller: AdaptiveController) -> Self {
+        Self {
+            controller,
+            started: Instant::now(),
+            bytes: 0,
+            ops: 0,
+            errors: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.started = Instant::now();
+        self.bytes = 0;
+        self.ops = 0;
+        self.errors = 0;
+    }
+}
+
 /// Helper function to detect optimal concurrency level for local operations.
 pub fn detect_optimal_concurrency() -> usize {
     let cpu_count = num_cpus::get();
@@ -404,4 +583,90 @@ mod tests {
         let _permit = limiter.acquire();
         assert_eq!(limiter.available(), 255);
     }
+
+    #[test]
+    fn test_set_limit_resizes_permits() {
+        let limiter = ConcurrencyLimiter::new(4);
+        let p1 = limiter.acquire();
+        let _p2 = limiter.acquire();
+
+        limiter.set_limit(1);
+        assert_eq!(limiter.limit(), 1);
+        assert_eq!(limiter.available(), 0);
+        assert!(limiter.try_acquire().is_none());
+
+        // Clamped to max
+        limiter.set_limit(100);
+        assert_eq!(limiter.limit(), 4);
+        drop(p1);
+        assert_eq!(limiter.available(), 3);
+    }
+
+    /// Storage that serves `per_op` bytes/s per operation up to `knee`
+    /// concurrent operations; beyond that, contention cuts total throughput
+    /// and past twice the knee requests start timing out.
+    struct DegradingBackend {
+        knee: usize,
+        per_op: f64,
+    }
+
+    impl DegradingBackend {
+        fn window(&self, concurrency: usize) -> (f64, f64) {
+            let c = concurrency as f64;
+            let k = self.knee as f64;
+            let throughput = if concurrency <= self.knee {
+                c * self.per_op
+            } else {
+                k * self.per_op * (k / c).powi(2)
+            };
+            let error_rate = if concurrency > 2 * self.knee {
+                0.2
+            } else {
+                0.0
+            };
+            (throughput, error_rate)
+        }
+    }
+
+    #[test]
+    fn test_adaptive_controller_converges_near_knee() {
+        let backend = DegradingBackend {
+            knee: 6,
+            per_op: 10_000_000.0,
+        };
+        let mut controller = AdaptiveController::new(2, 64);
+
+        let mut tail = Vec::new();
+        for round in 0..60 {
+            let (throughput, error_rate) = backend.window(controller.current());
+            let next = controller.adjust(throughput, error_rate);
+            if round >= 40 {
+                tail.push(next);
+            }
+        }
+
+        assert!(
+            tail.iter().all(|c| (5..=7).contains(c)),
+            "expected to settle near 6, got {:?}",
+            tail
+        );
+    }
+
+    #[test]
+    fn test_adaptive_controller_backs_off_on_errors() {
+        let mut controller = AdaptiveController::new(32, 64);
+        assert_eq!(controller.adjust(1_000.0, 0.5), 16);
+        assert_eq!(controller.adjust(1_000.0, 0.5), 8);
+        // Clean window after back-off re-baselines and probes upward
+        assert_eq!(controller.adjust(1_000.0, 0.0), 9);
+    }
+
+    #[test]
+    fn test_adaptive_limiter_starts_at_baseline() {
+        let limiter = ConcurrencyLimiter::adaptive(4, 16);
+        assert!(limiter.is_adaptive());
+        assert_eq!(limiter.max_concurrent(), 16);
+        assert_eq!(limiter.limit(), 4);
+        assert!(!ConcurrencyLimiter::new(4).is_adaptive());
+    }
 }
@@ -0,0 +1,314 @@
+/*!
+ * Deduplicating chunk writer backed by the Universe index.
+ *
+ * Files are split into CDC chunks and stored in `.orbitpak` containers. Before
+ * a chunk is written, the Universe (`universe_v3`) is consulted for its
+ * content ID:
+ * - **Known and packed in this store**: the existing container location is
+ *   recorded in the file's recipe and nothing is written
+ * - **Unknown** (or only known at locations this store cannot reference,
+ *   such as plain files on another Star): the chunk is appended to the
+ *   active container and registered in the Universe
+ *
+ * The resulting [`DedupRecipe`] lists the packed chunk for every byte range of
+ * the file, so the file can be reassembled with [`DedupWriter::read_file`]
+ * regardless of which write originally uploaded each chunk.
+ */
+
+use crate::error::{OrbitError, Result};
+use orbit_core_cdc::{ChunkConfig, ChunkStream};
+use orbit_core_starmap::container::{
+    ContainerPool, ContainerReader, PackedChunkRef, DEFAULT_MAX_CONTAINER_SIZE,
+};
+use orbit_core_starmap::universe_v3::{ChunkLocation, Universe};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Star ID recorded for chunks packed by a local writer
+pub const LOCAL_STAR_ID: &str = "local";
+
+/// Counters for a single deduplicating write
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DedupWriteStats {
+    /// Chunks appended to a container
+    pub chunks_uploaded: u64,
+    /// Chunks satisfied by an existing container location
+    pub chunks_referenced: u64,
+    /// Bytes appended to a container
+    pub bytes_uploaded: u64,
+    /// Bytes satisfied by existing container locations
+    pub bytes_referenced: u64,
+}
+
+impl DedupWriteStats {
+    /// Fraction of the file's bytes that did not have to be written (0.0 - 1.0)
+    pub fn dedup_ratio(&self) -> f64 {
+        let total = self.bytes_uploaded + self.bytes_referenced;
+        if total == 0 {
+            0.0
+        } else {
+            self.bytes_referenced as f64 / total as f64
+        }
+    }
+}
+
+/// Ordered list of packed chunks that make up a written file
+#[derive(Debug, Clone)]
+pub struct DedupRecipe {
+    /// Total file size in bytes
+    pub size: u64,
+    /// Packed chunk for each consecutive byte range of the file
+    pub chunks: Vec<PackedChunkRef>,
+    /// What this write uploaded versus referenced
+    pub stats: DedupWriteStats,
+}
+
+/// Writes files into a container store, uploading only chunks the Universe
+/// does not already know about
+pub struct DedupWriter {
+    universe: Universe,
+    pool: ContainerPool,
+    directory: PathBuf,
+    chunk_config: Option<ChunkConfig>,
+}
+
+impl DedupWriter {
+    /// Open a writer over a container directory, creating it if needed
+    ///
+    /// Containers already in the directory are kept, so chunks registered by
+    /// earlier writers remain referenceable.
+    pub fn open(universe: Universe, container_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(container_dir)?;
+        let pool = ContainerPool::open(container_dir.to_path_buf(), DEFAULT_MAX_CONTAINER_SIZE)?;
+
+        Ok(Self {
+            universe,
+            pool,
+            directory: container_dir.to_path_buf(),
+            chunk_config: None,
+        })
+    }
+
+    /// Use a fixed CDC configuration instead of sizing chunks per file
+    pub fn with_chunk_config(mut self, config: ChunkConfig) -> Self {
+        self.chunk_config = Some(config);
+        self
+    }
+
+    /// The Universe index this writer registers chunks in
+    pub fn universe(&self) -> &Universe {
+        &self.universe
+    }
+
+    /// Chunk and store a file
+    pub fn write_file(&mut self, path: &Path) -> Result<DedupRecipe> {
+        let file = File::open(path)?;
+        let config = match &self.chunk_config {
+            Some(config) => config.clone(),
+            None => ChunkConfig::for_file_size(file.metadata()?.len()),
+        };
+        self.write_stream(file, config)
+    }
+
+    /// Chunk and store everything read from `reader`
+    pub fn write_stream<R: Read>(&mut self, reader: R, config: ChunkConfig) -> Result<DedupRecipe> {
+        let mut recipe = DedupRecipe {
+            size: 0,
+            chunks: Vec::new(),
+            stats: DedupWriteStats::default(),
+        };
+
+        for chunk in ChunkStream::new(reader, config) {
+            let chunk = chunk.map_err(io::Error::other)?;
+            orbit_observability::metrics::record_chunk(chunk.length);
+            let length = chunk.length as u64;
+            recipe.size += length;
+
+            if let Some(existing) = self.find_packed(&chunk.hash)? {
+                recipe.stats.chunks_referenced += 1;
+                recipe.stats.bytes_referenced += length;
+                recipe.chunks.push(existing);
+                continue;
+            }
+
+            let packed = self.pool.pack_chunk(&chunk.data)?;
+            let location = ChunkLocation::new(
+                LOCAL_STAR_ID.to_string(),
+                self.pool.container_path(&packed.container_id),
+                packed.offset,
+                packed.length,
+            );
+            self.universe
+                .insert_chunk(chunk.hash, location)
+                .map_err(universe_error)?;
+
+            recipe.stats.chunks_uploaded += 1;
+            recipe.stats.bytes_uploaded += length;
+            recipe.chunks.push(packed);
+        }
+
+        self.pool.flush()?;
+        Ok(recipe)
+    }
+
+    /// Reassemble a file from its recipe, returning the number of bytes written
+    pub fn read_file<W: Write>(&self, recipe: &DedupRecipe, out: &mut W) -> Result<u64> {
+        let mut readers: HashMap<&str, ContainerReader> = HashMap::new();
+        let mut written = 0u64;
+
+        for chunk_ref in &recipe.chunks {
+            let reader = match readers.entry(chunk_ref.container_id.as_str()) {
+                std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+                std::collections::hash_map::Entry::Vacant(e) => e.insert(ContainerReader::open(
+                    &self.pool.container_path(&chunk_ref.container_id),
+                )?),
+            };
+            let data = reader.read_chunk(chunk_ref)?;
+            out.write_all(&data)?;
+            written += data.len() as u64;
+        }
+
+        Ok(written)
+    }
+
+    /// Find a location for `hash` that lives in one of this store's containers
+    fn find_packed(&self, hash: &[u8; 32]) -> Result<Option<PackedChunkRef>> {
+        let mut found = None;
+        self.universe
+            .scan_chunk(hash, |location| {
+                found = self.as_packed_ref(&location);
+                found.is_none()
+            })
+            .map_err(universe_error)?;
+        Ok(found)
+    }
+
+    fn as_packed_ref(&self, location: &ChunkLocation) -> Option<PackedChunkRef> {
+        if location.star_id != LOCAL_STAR_ID
+            || location.path.parent() != Some(self.directory.as_path())
+            || location.path.extension().and_then(|e| e.to_str()) != Some("orbitpak")
+        {
+            return None;
+        }
+        let container_id = location.path.file_stem()?.to_str()?;
+        Some(PackedChunkRef::new(
+            container_id.to_string(),
+            location.offset,
+            location.length,
+        ))
+    }
+}
+
+fn universe_error(e: orbit_core_starmap::Error) -> OrbitError {
+    OrbitError::Other(format!("Universe index error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orbit_core_cdc::testing::xorshift_bytes;
+    use std::collections::HashSet;
+    use tempfile::tempdir;
+
+    fn chunk_hashes(data: &[u8], config: &ChunkConfig) -> Vec<[u8; 32]> {
+        ChunkStream::new(data, config.clone())
+            .map(|c| c.unwrap().hash)
+            .collect()
+    }
+
+    #[test]
+    fn test_second_write_only_uploads_unique_chunks() {
+        let dir = tempdir().unwrap();
+        let config = ChunkConfig::new(2048, 8192, 32768).unwrap();
+
+        let shared = xorshift_bytes(256 * 1024, 0x9E37_79B9_7F4A_7C15);
+        let mut first = xorshift_bytes(64 * 1024, 1);
+        first.extend_from_slice(&shared);
+        let mut second = shared.clone();
+        second.extend_from_slice(&xorshift_bytes(64 * 1024, 2));
+
+        std::fs::write(dir.path().join("first.bin"), &first).unwrap();
+        std::fs::write(dir.path().join("second.bin"), &second).unwrap();
+
+        let universe = Universe::open(dir.path().join("universe.db")).unwrap();
+        let mut writer = DedupWriter::open(universe, &dir.path().join("packs"))
+            .unwrap()
+            .with_chunk_config(config.clone());
+
+        let first_recipe = writer.write_file(&dir.path().join("first.bin")).unwrap();
+        assert_eq!(first_recipe.size, first.len() as u64);
+
+        let known: HashSet<_> = chunk_hashes(&first, &config).into_iter().collect();
+        let mut expected_unique = HashSet::new();
+        for hash in chunk_hashes(&second, &config) {
+            if !known.contains(&hash) {
+                expected_unique.insert(hash);
+            }
+        }
+        assert!(!expected_unique.is_empty());
+
+        let second_recipe = writer.write_file(&dir.path().join("second.bin")).unwrap();
+        let stats = &second_recipe.stats;
+        assert_eq!(stats.chunks_uploaded, expected_unique.len() as u64);
+        assert!(stats.chunks_referenced > 0);
+        assert_eq!(
+            stats.bytes_uploaded + stats.bytes_referenced,
+            second.len() as u64
+        );
+
+        // Both files reassemble from the shared containers
+        let mut out = Vec::new();
+        writer.read_file(&second_recipe, &mut out).unwrap();
+        assert_eq!(out, second);
+        out.clear();
+        writer.read_file(&first_recipe, &mut out).unwrap();
+        assert_eq!(out, first);
+
+        // Rewriting identical content uploads nothing
+        let again = writer.write_file(&dir.path().join("second.bin")).unwrap();
+        assert_eq!(again.stats.chunks_uploaded, 0);
+        assert_eq!(again.stats.dedup_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_locations_outside_store_are_not_referenced() {
+        let dir = tempdir().unwrap();
+        let config = ChunkConfig::new(2048, 8192, 32768).unwrap();
+        let data = xorshift_bytes(16 * 1024, 7);
+
+        let universe = Universe::open(dir.path().join("universe.db")).unwrap();
+        for hash in chunk_hashes(&data, &config) {
+            universe
+                .insert_chunk(
+                    hash,
+                    ChunkLocation::new(
+                        "star-2".to_string(),
+                        PathBuf::from("/remote/file.bin"),
+                        0,
+                        1,
+                    ),
+                )
+                .unwrap();
+        }
+
+        let mut writer = DedupWriter::open(universe, &dir.path().join("packs")).unwrap();
+        let recipe = writer
+            .write_stream(data.as_slice(), config.clone())
+            .unwrap();
+        assert_eq!(recipe.stats.chunks_referenced, 0);
+        assert_eq!(recipe.stats.bytes_uploaded, data.len() as u64);
+
+        // A writer reopened over the same store references what was packed
+        drop(writer);
+        let universe = Universe::open(dir.path().join("universe.db")).unwrap();
+        let mut writer = DedupWriter::open(universe, &dir.path().join("packs")).unwrap();
+        let again = writer.write_stream(data.as_slice(), config).unwrap();
+        assert_eq!(again.stats.chunks_uploaded, 0);
+
+        let mut out = Vec::new();
+        writer.read_file(&recipe, &mut out).unwrap();
+        assert_eq!(out, data);
+    }
+}
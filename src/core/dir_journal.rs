@@ -0,0 +1,287 @@
+/*!
+ * Resume journal for directory copies.
+ *
+ * Single-file resume picks up inside a file, but an interrupted directory
+ * copy would otherwise re-walk and re-copy every file. With resume enabled,
+ * each file that finishes is appended to a journal beside the destination
+ * directory, keyed on its relative path, size and mtime. A resumed run skips
+ * journaled files whose source is unchanged without opening them, and the
+ * journal is removed once a run completes without failures.
+ *
+ * Mirror deletions go through the same journal. The whole deletion plan is
+ * written before anything is removed, and each entry is marked once it is
+ * gone. A resumed mirror works through the rest of the plan instead of
+ * scanning the destination again.
+ *
+ * The journal is JSON lines, written one record per `write` so a crash can
+ * at worst leave a torn final line, which is ignored on load.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+/// Suffix appended to the destination directory name for its journal
+pub const JOURNAL_SUFFIX: &str = ".orbit_dir_journal";
+
+/// Source identity recorded for a completed file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub size: u64,
+    pub mtime_secs: i64,
+    pub mtime_nanos: u32,
+}
+
+impl FileStamp {
+    /// Stamp from a source file's metadata
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> io::Result<Self> {
+        let modified = metadata.modified()?;
+        let (mtime_secs, mtime_nanos) = match modified.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                (-(d.as_secs() as i64), d.subsec_nanos())
+            }
+        };
+        Ok(Self {
+            size: metadata.len(),
+            mtime_secs,
+            mtime_nanos,
+        })
+    }
+
+    /// Stamp for the file at `path`
+    pub fn from_path(path: &Path) -> io::Result<Self> {
+        Self::from_metadata(&std::fs::metadata(path)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JournalRecord {
+    path: PathBuf,
+    #[serde(flatten)]
+    stamp: FileStamp,
+}
+
+/// One line of the journal
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JournalLine {
+    Copied(JournalRecord),
+    DeletePlanned { delete_planned: PathBuf },
+    Deleted { deleted: PathBuf },
+}
+
+/// Journal of files a directory copy has finished
+#[derive(Debug)]
+pub struct DirectoryJournal {
+    path: PathBuf,
+    done: HashMap<PathBuf, FileStamp>,
+    /// Mirror deletions planned by an earlier run, in plan order
+    planned_deletions: Vec<PathBuf>,
+    deleted: HashSet<PathBuf>,
+    file: Mutex<File>,
+}
+
+impl DirectoryJournal {
+    /// Journal location for a destination directory: a sibling named
+    /// `<dir>.orbit_dir_journal`, so it never appears inside the copied tree
+    ///
+    /// Returns `None` for destinations without a final path component
+    /// (e.g. `/`).
+    pub fn path_for(dest_dir: &Path) -> Option<PathBuf> {
+        let name = dest_dir.file_name()?;
+        let mut journal_name = name.to_os_string();
+        journal_name.push(JOURNAL_SUFFIX);
+        Some(dest_dir.with_file_name(journal_name))
+    }
+
+    /// Open (or create) the journal at `path`, loading any completed entries
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut done = HashMap::new();
+        let mut planned_deletions = Vec::new();
+        let mut deleted = HashSet::new();
+        match File::open(path) {
+            Ok(existing) => {
+                for line in BufReader::new(existing).lines() {
+                    let line = line?;
+                    // A torn last line from a crash is simply not done yet
+                    match serde_json::from_str::<JournalLine>(&line) {
+                        Ok(JournalLine::Copied(record)) => {
+                            done.insert(record.path, record.stamp);
+                        }
+                        Ok(JournalLine::DeletePlanned { delete_planned }) => {
+                            planned_deletions.push(delete_planned);
+                        }
+                        Ok(JournalLine::Deleted { deleted: path }) => {
+                            deleted.insert(path);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            done,
+            planned_deletions,
+            deleted,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of files recorded as done when the journal was opened
+    pub fn resumed_entries(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Whether `relative_path` was completed by an earlier run from an
+    /// identical source file
+    pub fn is_done(&self, relative_path: &Path, stamp: &FileStamp) -> bool {
+        self.done.get(relative_path) == Some(stamp)
+    }
+
+    /// Record `relative_path` as completed
+    pub fn record(&self, relative_path: &Path, stamp: FileStamp) -> io::Result<()> {
+        self.append(&[JournalLine::Copied(JournalRecord {
+            path: relative_path.to_path_buf(),
+            stamp,
+        })])
+    }
+
+    /// Mirror deletions an earlier run planned but did not finish, in plan
+    /// order, or `None` if no deletion pass was started
+    pub fn pending_deletions(&self) -> Option<Vec<&Path>> {
+        if self.planned_deletions.is_empty() {
+            return None;
+        }
+        Some(
+            self.planned_deletions
+                .iter()
+                .filter(|path| !self.deleted.contains(*path))
+                .map(PathBuf::as_path)
+                .collect(),
+        )
+    }
+
+    /// Record the full deletion plan before any entry is removed
+    pub fn plan_deletions(&self, relative_paths: &[&Path]) -> io::Result<()> {
+        let lines: Vec<JournalLine> = relative_paths
+            .iter()
+            .map(|path| JournalLine::DeletePlanned {
+                delete_planned: path.to_path_buf(),
+            })
+            .collect();
+        self.append(&lines)
+    }
+
+    /// Record a planned deletion as done
+    pub fn record_deleted(&self, relative_path: &Path) -> io::Result<()> {
+        self.append(&[JournalLine::Deleted {
+            deleted: relative_path.to_path_buf(),
+        }])
+    }
+
+    /// Append `lines` in a single write
+    fn append(&self, lines: &[JournalLine]) -> io::Result<()> {
+        let mut buf = Vec::new();
+        for line in lines {
+            serde_json::to_writer(&mut buf, line)?;
+            buf.push(b'\n');
+        }
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&buf)?;
+        file.flush()
+    }
+
+    /// Delete the journal once the copy has fully completed
+    pub fn remove(self) -> io::Result<()> {
+        drop(self.file);
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn journal_path_is_sibling_of_destination() {
+        assert_eq!(
+            DirectoryJournal::path_for(Path::new("/backup/photos.2024")),
+            Some(PathBuf::from("/backup/photos.2024.orbit_dir_journal"))
+        );
+        assert_eq!(DirectoryJournal::path_for(Path::new("/")), None);
+    }
+
+    #[test]
+    fn records_survive_reopen_and_torn_lines() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dst.orbit_dir_journal");
+        let stamp = FileStamp {
+            size: 5,
+            mtime_secs: 1_700_000_000,
+            mtime_nanos: 42,
+        };
+
+        let journal = DirectoryJournal::open(&path).unwrap();
+        journal.record(Path::new("a/b.txt"), stamp).unwrap();
+        drop(journal);
+
+        // Simulate a crash mid-append
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"path\":\"c.txt\",\"si").unwrap();
+        drop(file);
+
+        let journal = DirectoryJournal::open(&path).unwrap();
+        assert_eq!(journal.resumed_entries(), 1);
+        assert!(journal.is_done(Path::new("a/b.txt"), &stamp));
+        assert!(!journal.is_done(Path::new("c.txt"), &stamp));
+
+        // A changed source no longer matches
+        let changed = FileStamp { size: 6, ..stamp };
+        assert!(!journal.is_done(Path::new("a/b.txt"), &changed));
+
+        journal.remove().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn deletion_plan_survives_reopen() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dst.orbit_dir_journal");
+
+        let journal = DirectoryJournal::open(&path).unwrap();
+        assert!(journal.pending_deletions().is_none());
+        journal
+            .plan_deletions(&[Path::new("a.txt"), Path::new("dir/b.txt"), Path::new("dir")])
+            .unwrap();
+        journal.record_deleted(Path::new("a.txt")).unwrap();
+        drop(journal);
+
+        let journal = DirectoryJournal::open(&path).unwrap();
+        assert_eq!(
+            journal.pending_deletions().unwrap(),
+            vec![Path::new("dir/b.txt"), Path::new("dir")]
+        );
+        assert_eq!(journal.resumed_entries(), 0);
+    }
+}
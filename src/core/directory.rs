@@ -14,18 +14,121 @@ use tracing::info;
 use walkdir::WalkDir;
 
 use super::batch::{record_create_file, JournalEntry, TransferJournal};
-use super::concurrency::ConcurrencyLimiter;
+use super::concurrency::{detect_optimal_concurrency, ConcurrencyLimiter};
+use super::dir_journal::{DirectoryJournal, FileStamp};
 use super::disk_guardian::{self, GuardianConfig};
+use super::file_metadata::{FileMetadata, PreserveFlags};
 use super::filter::FilterList;
 use super::hardlink::{create_hardlink, HardlinkTracker};
-use super::metadata_ops::preserve_metadata;
 use super::progress::ProgressPublisher;
-use super::validation::matches_exclude_pattern;
+use super::validation::{matches_exclude_pattern, probe_clock_skew};
+use super::verify_sample::SampleVerifier;
 use super::CopyStats;
 use crate::audit::AuditLogger;
-use crate::config::{CopyConfig, CopyMode, SymlinkMode};
-use crate::core::checksum::calculate_checksum;
+use crate::config::{
+    ChecksumAlgorithm, CopyConfig, CopyMode, DeleteMode, ErrorMode, HiddenMode, SpecialFileMode,
+    SymlinkMode,
+};
+use crate::core::checksum::{calculate_checksum, calculate_checksum_with, ChecksumManifest};
+use crate::core::trash::{trash_group_name, TRASH_DIR};
 use crate::error::{OrbitError, Result};
+use crate::manifest_integration::{ManifestGenerator, PriorManifest};
+
+/// Maximum number of failed paths listed in the directory copy summary
+const FAILED_PATHS_SHOWN: usize = 10;
+
+/// Directory at the destination root that `--delete-mode quarantine` moves
+/// extraneous entries into. Mirror scans never treat it as extraneous.
+pub const QUARANTINE_DIR: &str = ".orbit-quarantine";
+
+/// Starting concurrency for `--parallel auto-adaptive`
+const ADAPTIVE_BASELINE: usize = 4;
+
+/// State for a `--since` incremental copy
+struct SinceScan {
+    prior: PriorManifest,
+    /// Builds the updated manifest; `None` for dry runs
+    generator: Option<Mutex<ManifestGenerator>>,
+}
+
+impl SinceScan {
+    fn open(
+        prior_dir: &Path,
+        source_dir: &Path,
+        dest_dir: &Path,
+        config: &CopyConfig,
+    ) -> Result<Self> {
+        let prior = PriorManifest::load(prior_dir)?;
+        info!(
+            "Incremental copy since {:?}: {} files in prior manifest",
+            prior_dir,
+            prior.len()
+        );
+        let generator = if config.dry_run {
+            None
+        } else {
+            Some(Mutex::new(ManifestGenerator::new(
+                source_dir, dest_dir, config,
+            )?))
+        };
+        Ok(Self { prior, generator })
+    }
+
+    /// Whether the file at `path` matches the prior manifest. Files that
+    /// cannot be checked are treated as changed.
+    fn is_unchanged(&self, path: &Path, relative_path: &str) -> bool {
+        self.prior
+            .is_unchanged(path, relative_path)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Could not compare {:?} with prior manifest: {}", path, e);
+                false
+            })
+    }
+
+    /// Add a file to the updated manifest, hashing it unless `unchanged`
+    fn record(&self, path: &Path, relative_path: &str, unchanged: bool) {
+        let Some(generator) = &self.generator else {
+            return;
+        };
+        let mut generator = generator.lock().unwrap();
+        let result = if unchanged {
+            generator.carry_forward(&self.prior, relative_path)
+        } else {
+            generator.generate_file_manifest(path, relative_path)
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to add {:?} to updated manifest: {}", path, e);
+        }
+    }
+
+    /// Write the updated flight plan
+    fn finish(self) -> Result<()> {
+        match self.generator {
+            Some(generator) => generator.into_inner().unwrap().finalize("sha256:pending"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Probe the destination's clock once and log how far it is from ours
+///
+/// Skew beyond `--mtime-tolerance` makes Sync and Update misjudge which
+/// side is newer, so that case is a warning.
+fn log_clock_skew(dest_dir: &Path, config: &CopyConfig) {
+    match probe_clock_skew(dest_dir) {
+        Ok(skew_ms) if skew_ms.unsigned_abs() > config.mtime_tolerance_secs * 1000 => {
+            tracing::warn!(
+                "Destination clock is {:.1}s {} the local clock, beyond the {}s mtime tolerance; \
+                 consider raising --mtime-tolerance",
+                skew_ms.unsigned_abs() as f64 / 1000.0,
+                if skew_ms > 0 { "ahead of" } else { "behind" },
+                config.mtime_tolerance_secs
+            )
+        }
+        Ok(skew_ms) => info!("Destination clock skew: {}ms", skew_ms),
+        Err(e) => tracing::debug!("Could not probe destination clock: {}", e),
+    }
+}
 
 /// Work item for parallel processing
 #[derive(Clone)]
@@ -41,7 +144,11 @@ enum EntryType {
     Directory,
     File,
     Symlink,
-    Hardlink { target: PathBuf },
+    Hardlink {
+        target: PathBuf,
+    },
+    /// FIFO, socket or device node
+    Special,
 }
 
 /// Copy a directory recursively with streaming iteration to reduce memory usage
@@ -89,6 +196,28 @@ pub fn copy_directory_impl(
         ));
     }
 
+    if config.byte_range.is_some() {
+        return Err(OrbitError::Config(
+            "--offset and --length apply to single-file copies".to_string(),
+        ));
+    }
+
+    if config.extract {
+        return Err(OrbitError::Config(
+            "--extract needs a .tar or .tar.zst file as the source, not a directory".to_string(),
+        ));
+    }
+
+    if let Some(format) = config.archive {
+        if config.copy_mode != CopyMode::Copy || config.write_batch.is_some() {
+            return Err(OrbitError::Config(
+                "--archive is only supported with --mode copy and without --write-batch"
+                    .to_string(),
+            ));
+        }
+        return super::archive::archive_directory(source_dir, dest_dir, format, config);
+    }
+
     if config.write_batch.is_some() && config.copy_mode != CopyMode::Copy {
         return Err(OrbitError::Config(
             "--write-batch is only supported with --mode copy".to_string(),
@@ -98,6 +227,15 @@ pub fn copy_directory_impl(
     let start_time = Instant::now();
     let job_id = generate_job_id();
 
+    // Each file copy opens its own copy_file span under this one
+    let _copy_span = tracing::info_span!(
+        "copy_directory",
+        job_id = job_id.as_str(),
+        source = %source_dir.display(),
+        dest = %dest_dir.display(),
+    )
+    .entered();
+
     // Initialize audit logger if audit log path is configured
     let audit_logger: Option<Arc<Mutex<AuditLogger>>> =
         if config.audit_log_path.is_some() || config.verbose {
@@ -113,8 +251,17 @@ pub fn copy_directory_impl(
         };
 
     // Setup concurrency limiter for controlling parallel transfers
-    let concurrency_limiter = if config.parallel > 0 {
-        let limiter = ConcurrencyLimiter::new(config.parallel);
+    let workers = worker_count(config);
+    let concurrency_limiter = if config.adaptive_concurrency {
+        let limiter = ConcurrencyLimiter::adaptive(ADAPTIVE_BASELINE.min(workers), workers);
+        info!(
+            "Adaptive concurrency enabled: starting at {}, max {} parallel transfers",
+            limiter.limit(),
+            limiter.max_concurrent()
+        );
+        Some(Arc::new(limiter))
+    } else if workers > 0 {
+        let limiter = ConcurrencyLimiter::new(workers);
         info!(
             "Concurrency control enabled: max {} parallel transfers",
             limiter.max_concurrent()
@@ -129,7 +276,18 @@ pub fn copy_directory_impl(
         println!("Performing pre-flight checks...");
     }
 
-    let guardian_config = GuardianConfig::default();
+    if config.preflight {
+        let _preflight_span = tracing::info_span!("preflight").entered();
+        disk_guardian::preflight_destination(dest_dir)?;
+    }
+
+    // The preflight covers the guardian's writability probe; without it
+    // the guardian runs its own
+    let disk_check_span = tracing::info_span!("disk_check").entered();
+    let guardian_config = GuardianConfig {
+        check_integrity: !config.preflight,
+        ..GuardianConfig::default()
+    };
     let estimated_size = disk_guardian::estimate_directory_size(source_dir)?;
 
     if config.show_progress {
@@ -137,6 +295,7 @@ pub fn copy_directory_impl(
     }
 
     disk_guardian::ensure_transfer_safety(dest_dir, estimated_size, &guardian_config)?;
+    drop(disk_check_span);
 
     // Emit start audit event
     if let Some(ref logger) = audit_logger {
@@ -165,12 +324,48 @@ pub fn copy_directory_impl(
         std::fs::create_dir_all(dest_dir)?;
     }
 
+    if matches!(config.copy_mode, CopyMode::Sync | CopyMode::Update) && !config.dry_run {
+        log_clock_skew(dest_dir, config);
+    }
+
     let rename_index = if config.detect_renames {
         Some(Arc::new(build_hash_index(dest_dir)?))
     } else {
         None
     };
 
+    let dir_journal = if config.resume_enabled && !config.dry_run {
+        open_dir_journal(dest_dir)
+    } else {
+        None
+    };
+
+    let since_scan = match &config.since_manifest {
+        Some(prior_dir) => Some(SinceScan::open(prior_dir, source_dir, dest_dir, config)?),
+        None => None,
+    };
+
+    let checksum_manifest = if config.checksum_manifest && !config.dry_run {
+        Some(ChecksumManifest::new(config.checksum_algorithm))
+    } else {
+        None
+    };
+
+    // Only `--since` refreshes cargo manifests during the copy, so other
+    // manifest directories may describe an older source
+    let sample_verifier = match config.verify_sample {
+        Some(sample) if !config.dry_run => Some(SampleVerifier::new(
+            sample,
+            config.since_manifest.as_ref().map(|prior| {
+                config
+                    .manifest_output_dir
+                    .clone()
+                    .unwrap_or_else(|| prior.clone())
+            }),
+        )),
+        _ => None,
+    };
+
     let batch_journal = if config.write_batch.is_some() {
         Some(Arc::new(Mutex::new(TransferJournal::new(
             source_dir.to_path_buf(),
@@ -197,8 +392,8 @@ pub fn copy_directory_impl(
 
     // Bounded channel prevents scanner from overwhelming copiers
     // Buffer size: use parallel threads as baseline, bounded between 16-1000
-    let buffer_size = if config.parallel > 0 {
-        config.parallel.clamp(16, 1000)
+    let buffer_size = if workers > 0 {
+        workers.clamp(16, 1000)
     } else {
         100
     };
@@ -208,6 +403,7 @@ pub fn copy_directory_impl(
     let source_dir = source_dir.to_path_buf();
     let dest_dir = dest_dir.to_path_buf();
     let total_stats = Arc::new(Mutex::new(CopyStats::new()));
+    let failed_paths = Mutex::new(Vec::new());
     let expected_entries = Arc::new(Mutex::new(HashSet::new()));
 
     println!("Scanning and copying directory tree...");
@@ -220,7 +416,7 @@ pub fn copy_directory_impl(
         let expected_entries = expected_entries.clone();
         let filter_list = filter_list.clone();
 
-        thread::spawn(move || -> Result<()> {
+        thread::spawn(move || -> Result<HashSet<PathBuf>> {
             produce_work_items(
                 &source_dir,
                 &dest_dir,
@@ -228,6 +424,7 @@ pub fn copy_directory_impl(
                 tx,
                 expected_entries,
                 &filter_list,
+                path_device,
             )
         })
     };
@@ -239,16 +436,20 @@ pub fn copy_directory_impl(
         config,
         rx,
         total_stats.clone(),
+        &failed_paths,
         concurrency_limiter.as_ref(),
         rename_index.as_ref(),
         batch_journal.as_ref(),
+        dir_journal.as_ref(),
+        since_scan.as_ref(),
+        checksum_manifest.as_ref(),
+        sample_verifier.as_ref(),
+        pub_ref,
     )?;
 
     // Wait for producer to finish and check for errors
-    match producer_handle.join() {
-        Ok(Ok(())) => {
-            // Producer finished successfully
-        }
+    let pruned_dirs = match producer_handle.join() {
+        Ok(Ok(pruned_dirs)) => pruned_dirs,
         Ok(Err(e)) => {
             tracing::error!("Producer thread error: {}", e);
             return Err(e);
@@ -259,7 +460,7 @@ pub fn copy_directory_impl(
                 e
             )));
         }
-    }
+    };
 
     let mut final_stats = match Arc::try_unwrap(total_stats) {
         Ok(mutex) => mutex.into_inner().unwrap(),
@@ -268,11 +469,47 @@ pub fn copy_directory_impl(
 
     let mut deleted_count = 0;
     if config.copy_mode == CopyMode::Mirror {
-        match collect_deletion_candidates(&dest_dir, &expected_entries, config, &filter_list) {
+        let journal = dir_journal.as_deref();
+        let resumed = journal.and_then(|j| Some((j, j.pending_deletions()?)));
+        let deletions = match resumed {
+            Some((journal, pending)) => {
+                info!("Resuming mirror deletions: {} entries left", pending.len());
+                Ok(resume_deletions(
+                    journal,
+                    &pending,
+                    &dest_dir,
+                    &expected_entries,
+                ))
+            }
+            None => collect_deletion_candidates(
+                &dest_dir,
+                &expected_entries,
+                &pruned_dirs,
+                config,
+                &filter_list,
+            )
+            .inspect(|deletions| {
+                if let Some(journal) = journal {
+                    plan_deletions(journal, deletions, &dest_dir);
+                }
+            }),
+        };
+        match deletions {
             Ok(deletions) => {
-                let summary = apply_deletions(&deletions, config);
+                let summary = apply_deletions(&deletions, &dest_dir, config, journal);
                 deleted_count = summary.deleted as u64;
-                final_stats.files_failed += summary.failed as u64;
+                if let Some(dir) = &summary.quarantine_dir {
+                    info!("Moved {} extraneous entries to {:?}", summary.deleted, dir);
+                }
+                final_stats.files_failed += summary.failed.len() as u64;
+                failed_paths
+                    .lock()
+                    .unwrap()
+                    .extend(summary.failed.into_iter().map(|p| {
+                        p.strip_prefix(&dest_dir)
+                            .map(Path::to_path_buf)
+                            .unwrap_or(p)
+                    }));
             }
             Err(e) => {
                 tracing::error!("Failed to scan destination for deletions: {}", e);
@@ -281,12 +518,58 @@ pub fn copy_directory_impl(
         }
     }
 
+    // Runs after mirror deletions, which also touch directory mtimes
+    if config.preserve_metadata && !config.dry_run {
+        let failed = restore_directory_metadata(&source_dir, &dest_dir, &expected_entries, config)?;
+        final_stats.files_failed += failed.len() as u64;
+        failed_paths.lock().unwrap().extend(failed);
+    }
+
     if let (Some(batch_path), Some(journal)) = (config.write_batch.as_ref(), batch_journal.as_ref())
     {
         let journal = journal.lock().unwrap();
         journal.save(batch_path).map_err(OrbitError::Io)?;
     }
 
+    if let Some(scan) = since_scan {
+        scan.finish()?;
+    }
+
+    if let Some(sampler) = &sample_verifier {
+        let report = sampler.report();
+        info!("{}", report);
+        println!("{}", report);
+    }
+
+    if let Some(checksums) = &checksum_manifest {
+        let path = checksum_manifest_path(&dest_dir, config);
+        checksums.write(&path)?;
+        info!("Wrote {} checksums to {:?}", checksums.len(), path);
+    }
+
+    if let Some(journal) = dir_journal {
+        match Arc::try_unwrap(journal) {
+            Ok(journal) if final_stats.files_failed == 0 => {
+                if let Err(e) = journal.remove() {
+                    tracing::warn!("Failed to remove resume journal: {}", e);
+                }
+            }
+            Ok(journal) => println!(
+                "Resume journal kept at {:?}; rerun with --resume to skip completed files",
+                journal.path()
+            ),
+            Err(_) => tracing::warn!("Resume journal still in use; leaving it in place"),
+        }
+    }
+
+    if let Some(budget) = &config.retry_budget {
+        let metrics = budget.metrics();
+        info!(
+            "Retry budget: {} retries granted, {} denied, {:.1}/{} credits left",
+            metrics.granted, metrics.denied, metrics.available, metrics.capacity
+        );
+    }
+
     final_stats.duration = start_time.elapsed();
 
     // Emit directory scan complete event
@@ -320,7 +603,11 @@ pub fn copy_directory_impl(
     println!("  Total bytes: {}", final_stats.bytes_copied);
     println!("  Duration: {:?}", final_stats.duration);
     if config.copy_mode == CopyMode::Mirror {
-        println!("  Files deleted: {}", deleted_count);
+        match config.delete_mode {
+            DeleteMode::Delete => println!("  Files deleted: {}", deleted_count),
+            DeleteMode::Quarantine => println!("  Files quarantined: {}", deleted_count),
+            DeleteMode::Trash => println!("  Files moved to trash: {}", deleted_count),
+        }
     }
 
     // Emit completion audit event
@@ -345,6 +632,23 @@ pub fn copy_directory_impl(
     }
 
     if final_stats.files_failed > 0 {
+        // Skip/Partial runs carry on past failures, so spell out what was
+        // left behind; Abort keeps the bare count. The list goes to stderr
+        // so it never mixes into --json / NDJSON output on stdout.
+        if config.error_mode != ErrorMode::Abort {
+            let mut failed_paths = failed_paths.into_inner().unwrap();
+            failed_paths.sort();
+            eprint!(
+                "\n{}",
+                format_failure_summary(final_stats.files_failed, &failed_paths)
+            );
+            return Err(OrbitError::Parallel(format!(
+                "Completed with {} {}",
+                final_stats.files_failed,
+                failure_noun(final_stats.files_failed)
+            )));
+        }
+
         return Err(OrbitError::Parallel(format!(
             "{} files failed to copy",
             final_stats.files_failed
@@ -354,7 +658,52 @@ pub fn copy_directory_impl(
     Ok(final_stats)
 }
 
+fn failure_noun(count: u64) -> &'static str {
+    if count == 1 {
+        "failure"
+    } else {
+        "failures"
+    }
+}
+
+/// Render the "Completed with N failures" section of the directory summary
+///
+/// At most [`FAILED_PATHS_SHOWN`] paths are listed; the rest (including
+/// failures that were not tied to a path) are folded into "+M more".
+fn format_failure_summary(files_failed: u64, failed_paths: &[PathBuf]) -> String {
+    let mut out = format!(
+        "Completed with {} {}:\n",
+        files_failed,
+        failure_noun(files_failed)
+    );
+    for path in failed_paths.iter().take(FAILED_PATHS_SHOWN) {
+        out.push_str(&format!("  {}\n", path.display()));
+    }
+    let shown = failed_paths.len().min(FAILED_PATHS_SHOWN) as u64;
+    if files_failed > shown {
+        out.push_str(&format!("  +{} more\n", files_failed - shown));
+    }
+    out
+}
+
+/// Device ID of `path` (`st_dev`), used by `--one-file-system`
+#[cfg(unix)]
+fn path_device(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::symlink_metadata(path).ok().map(|m| m.dev())
+}
+
+/// Device IDs are not compared off Unix; `--one-file-system` is a no-op
+#[cfg(not(unix))]
+fn path_device(_path: &Path) -> Option<u64> {
+    None
+}
+
 /// Producer: walks directory tree and sends work items via bounded channel
+///
+/// Returns the relative paths of directories whose contents were not walked
+/// because they sit on another device (`--one-file-system`), so mirror
+/// deletions leave their destination contents alone.
 #[allow(clippy::while_let_on_iterator)]
 fn produce_work_items(
     source_dir: &Path,
@@ -363,11 +712,20 @@ fn produce_work_items(
     tx: crossbeam_channel::Sender<WorkItem>,
     expected_entries: Arc<Mutex<HashSet<PathBuf>>>,
     filter_list: &FilterList,
-) -> Result<()> {
-    let mut walker = WalkDir::new(source_dir)
-        .follow_links(false)
-        .same_file_system(true)
-        .into_iter();
+    device_of: fn(&Path) -> Option<u64>,
+) -> Result<HashSet<PathBuf>> {
+    let mut walker = WalkDir::new(source_dir).follow_links(false);
+    if let Some(max_depth) = config.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+    let mut walker = walker.into_iter();
+
+    let root_device = if config.one_file_system {
+        device_of(source_dir)
+    } else {
+        None
+    };
+    let mut pruned_dirs = HashSet::new();
 
     // Process in batches for better cache locality and reduced syscalls
     let mut dir_batch = Vec::with_capacity(100);
@@ -399,8 +757,13 @@ fn produce_work_items(
             continue;
         }
 
-        // Apply filter rules (first-match-wins with include/exclude)
-        let should_process = if !filter_list.is_empty() {
+        // Hidden entries are dropped before the pattern filters, unless an
+        // include rule names them
+        let should_process = if config.hidden_files == HiddenMode::Exclude
+            && filter_list.excludes_hidden(&entry, relative_path)
+        {
+            false
+        } else if !filter_list.is_empty() {
             // If we have filters configured, use them exclusively
             filter_list.should_include(relative_path)
         } else {
@@ -420,6 +783,18 @@ fn produce_work_items(
             continue;
         }
 
+        // Mount points are recreated empty, like rsync -x
+        if let (Some(root), true) = (root_device, entry.file_type().is_dir()) {
+            if device_of(entry.path()).is_some_and(|dev| dev != root) {
+                info!(
+                    "Not crossing into {} (different filesystem)",
+                    relative_path.display()
+                );
+                walker.skip_current_dir();
+                pruned_dirs.insert(relative_path.to_path_buf());
+            }
+        }
+
         let dest_path = dest_dir.join(relative_path);
         let source_path = entry.path().to_path_buf();
 
@@ -429,8 +804,12 @@ fn produce_work_items(
             EntryType::Symlink
         } else if entry.file_type().is_file() {
             EntryType::File
+        } else if config.special_files == SpecialFileMode::Skip {
+            // Opening a FIFO would block, so never treat these as files
+            info!("Skipping special file: {}", relative_path.display());
+            continue;
         } else {
-            continue; // Skip special files
+            EntryType::Special
         };
 
         if matches!(entry_type, EntryType::File) {
@@ -464,10 +843,13 @@ fn produce_work_items(
             EntryType::Directory => {
                 dir_batch.push(work_item);
                 if dir_batch.len() >= 100 {
-                    flush_directory_batch(&mut dir_batch, config)?;
+                    flush_directory_batch(&mut dir_batch)?;
                 }
             }
-            EntryType::File | EntryType::Symlink | EntryType::Hardlink { .. } => {
+            EntryType::File
+            | EntryType::Symlink
+            | EntryType::Hardlink { .. }
+            | EntryType::Special => {
                 file_batch.push(work_item);
                 if file_batch.len() >= 100 {
                     flush_file_batch(&mut file_batch, &tx)?;
@@ -477,11 +859,11 @@ fn produce_work_items(
     }
 
     // Flush remaining batches
-    flush_directory_batch(&mut dir_batch, config)?;
+    flush_directory_batch(&mut dir_batch)?;
     flush_file_batch(&mut file_batch, &tx)?;
 
     // Channel will be dropped here, signaling consumers to finish
-    Ok(())
+    Ok(pruned_dirs)
 }
 
 #[derive(Debug)]
@@ -493,23 +875,31 @@ struct DeletionItem {
 #[derive(Default)]
 struct DeletionSummary {
     deleted: usize,
-    failed: usize,
+    failed: Vec<PathBuf>,
+    /// Where this run's quarantined or trashed entries went, if any were
+    /// moved
+    quarantine_dir: Option<PathBuf>,
 }
 
 #[allow(clippy::while_let_on_iterator)]
 fn collect_deletion_candidates(
     dest_dir: &Path,
     expected_entries: &Arc<Mutex<HashSet<PathBuf>>>,
+    pruned_dirs: &HashSet<PathBuf>,
     config: &CopyConfig,
     filter_list: &FilterList,
 ) -> Result<Vec<DeletionItem>> {
     let expected: HashSet<PathBuf> = expected_entries.lock().unwrap().iter().cloned().collect();
     let mut deletions = Vec::new();
+    // Only entries the source walk could have seen are candidates
     let mut walker = WalkDir::new(dest_dir)
         .follow_links(false)
         .same_file_system(true)
-        .contents_first(true)
-        .into_iter();
+        .contents_first(true);
+    if let Some(max_depth) = config.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+    let mut walker = walker.into_iter();
 
     while let Some(entry) = walker.next() {
         let entry = match entry {
@@ -535,8 +925,26 @@ fn collect_deletion_candidates(
             }
         };
 
+        // Entries are yielded contents-first, so the quarantine and trash
+        // directories cannot be pruned when reached; match on the path instead
+        if relative_path.starts_with(QUARANTINE_DIR) || relative_path.starts_with(TRASH_DIR) {
+            continue;
+        }
+
+        // Contents of source mount points skipped by --one-file-system
+        if pruned_dirs
+            .iter()
+            .any(|dir| relative_path != dir && relative_path.starts_with(dir))
+        {
+            continue;
+        }
+
         // Apply filter rules (same logic as in produce_work_items)
-        let should_process = if !filter_list.is_empty() {
+        let should_process = if config.hidden_files == HiddenMode::Exclude
+            && filter_list.excludes_hidden(&entry, relative_path)
+        {
+            false
+        } else if !filter_list.is_empty() {
             filter_list.should_include(relative_path)
         } else {
             !matches_exclude_pattern(relative_path, &config.exclude_patterns)
@@ -576,30 +984,107 @@ fn collect_deletion_candidates(
     Ok(deletions)
 }
 
-fn apply_deletions(deletions: &[DeletionItem], config: &CopyConfig) -> DeletionSummary {
-    let mut summary = DeletionSummary::default();
+/// Journal the deletion plan so an interrupted pass can be resumed
+fn plan_deletions(journal: &DirectoryJournal, deletions: &[DeletionItem], dest_dir: &Path) {
+    let relative: Vec<&Path> = deletions
+        .iter()
+        .filter_map(|item| item.path.strip_prefix(dest_dir).ok())
+        .collect();
+    if let Err(e) = journal.plan_deletions(&relative) {
+        tracing::warn!("Failed to journal planned deletions: {}", e);
+    }
+}
+
+/// Deletion items for the rest of a journaled plan
+///
+/// Entries the source now has are dropped from the plan, and entries that
+/// are already gone (removed just before an interruption) are marked done.
+fn resume_deletions(
+    journal: &DirectoryJournal,
+    pending: &[&Path],
+    dest_dir: &Path,
+    expected_entries: &Arc<Mutex<HashSet<PathBuf>>>,
+) -> Vec<DeletionItem> {
+    let expected = expected_entries.lock().unwrap();
+    let mut deletions = Vec::with_capacity(pending.len());
+    for relative in pending {
+        if expected.contains(*relative) {
+            continue;
+        }
+        let path = dest_dir.join(relative);
+        let file_type = match std::fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata.file_type(),
+            Err(e) => {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    if let Err(e) = journal.record_deleted(relative) {
+                        tracing::warn!("Failed to journal deletion of {:?}: {}", relative, e);
+                    }
+                } else {
+                    tracing::warn!("Failed to stat planned deletion {:?}: {}", path, e);
+                }
+                continue;
+            }
+        };
+        let entry_type = if file_type.is_dir() {
+            EntryType::Directory
+        } else if file_type.is_symlink() {
+            EntryType::Symlink
+        } else {
+            EntryType::File
+        };
+        deletions.push(DeletionItem { path, entry_type });
+    }
+    deletions
+}
 
+fn apply_deletions(
+    deletions: &[DeletionItem],
+    dest_dir: &Path,
+    config: &CopyConfig,
+    journal: Option<&DirectoryJournal>,
+) -> DeletionSummary {
+    let mut summary = DeletionSummary::default();
     for item in deletions {
         if config.dry_run {
-            println!("Would delete: {:?}", item.path);
+            let action = match config.delete_mode {
+                DeleteMode::Delete => "delete",
+                DeleteMode::Quarantine => "quarantine",
+                DeleteMode::Trash => "move to trash",
+            };
+            println!("Would {}: {:?}", action, item.path);
             summary.deleted += 1;
             continue;
         }
 
-        let result = match item.entry_type {
-            EntryType::Directory => std::fs::remove_dir_all(&item.path),
-            EntryType::File | EntryType::Symlink | EntryType::Hardlink { .. } => {
-                std::fs::remove_file(&item.path)
+        let result = if config.delete_mode != DeleteMode::Delete {
+            quarantine_entry(
+                item,
+                dest_dir,
+                config.delete_mode,
+                &mut summary.quarantine_dir,
+            )
+        } else {
+            match item.entry_type {
+                EntryType::Directory => std::fs::remove_dir_all(&item.path),
+                EntryType::File
+                | EntryType::Symlink
+                | EntryType::Hardlink { .. }
+                | EntryType::Special => std::fs::remove_file(&item.path),
             }
         };
 
         match result {
             Ok(_) => {
                 summary.deleted += 1;
+                if let (Some(journal), Ok(relative)) = (journal, item.path.strip_prefix(dest_dir)) {
+                    if let Err(e) = journal.record_deleted(relative) {
+                        tracing::warn!("Failed to journal deletion of {:?}: {}", relative, e);
+                    }
+                }
             }
             Err(e) => {
                 tracing::error!("Failed to delete {:?}: {}", item.path, e);
-                summary.failed += 1;
+                summary.failed.push(item.path.clone());
             }
         }
     }
@@ -607,6 +1092,78 @@ fn apply_deletions(deletions: &[DeletionItem], config: &CopyConfig) -> DeletionS
     summary
 }
 
+/// Move one extraneous entry into this run's quarantine directory or trash
+/// group, creating it on first use
+fn quarantine_entry(
+    item: &DeletionItem,
+    dest_dir: &Path,
+    mode: DeleteMode,
+    run_dir: &mut Option<PathBuf>,
+) -> std::io::Result<()> {
+    let relative = item
+        .path
+        .strip_prefix(dest_dir)
+        .map_err(|_| std::io::Error::other("entry is outside the destination"))?;
+
+    let run_dir = match run_dir {
+        Some(dir) => dir,
+        None if mode == DeleteMode::Trash => run_dir.insert(create_trash_group(dest_dir)?),
+        None => run_dir.insert(create_quarantine_run_dir(dest_dir)?),
+    };
+    let target = run_dir.join(relative);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    move_merging(&item.path, &target)
+}
+
+/// Create `.orbit-quarantine/<UTC timestamp>` under `dest_dir`, adding a
+/// suffix if a run in the same second already used the name
+fn create_quarantine_run_dir(dest_dir: &Path) -> std::io::Result<PathBuf> {
+    let root = dest_dir.join(QUARANTINE_DIR);
+    std::fs::create_dir_all(&root)?;
+
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut candidate = root.join(&stamp);
+    let mut n = 1;
+    loop {
+        match std::fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                candidate = root.join(format!("{}-{}", stamp, n));
+                n += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Create a new group under `.orbit-trash/` in `dest_dir`, laid out like a
+/// backend trash so `purge_trash` ages it the same way
+fn create_trash_group(dest_dir: &Path) -> std::io::Result<PathBuf> {
+    let group = dest_dir
+        .join(TRASH_DIR)
+        .join(trash_group_name(std::time::SystemTime::now()));
+    std::fs::create_dir_all(&group)?;
+    Ok(group)
+}
+
+/// Rename `source` to `target`. Directories are visited after their contents,
+/// so a directory's target may already exist; anything left in it is moved
+/// across and the emptied source removed.
+fn move_merging(source: &Path, target: &Path) -> std::io::Result<()> {
+    let is_dir = std::fs::symlink_metadata(source)?.is_dir();
+    if !is_dir || !target.exists() {
+        return std::fs::rename(source, target);
+    }
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        move_merging(&entry.path(), &target.join(entry.file_name()))?;
+    }
+    std::fs::remove_dir(source)
+}
+
 fn build_hash_index(dest_dir: &Path) -> Result<HashMap<String, PathBuf>> {
     let mut index = HashMap::new();
     let walker = WalkDir::new(dest_dir)
@@ -650,23 +1207,65 @@ fn build_hash_index(dest_dir: &Path) -> Result<HashMap<String, PathBuf>> {
 }
 
 /// Flush directory batch - create directories sequentially before files
-fn flush_directory_batch(batch: &mut Vec<WorkItem>, config: &CopyConfig) -> Result<()> {
+///
+/// Directory metadata is applied later by [`restore_directory_metadata`],
+/// once no more children will be written into them.
+fn flush_directory_batch(batch: &mut Vec<WorkItem>) -> Result<()> {
     for item in batch.drain(..) {
         if !item.dest_path.exists() {
             std::fs::create_dir_all(&item.dest_path)?;
         }
+    }
+    Ok(())
+}
 
-        if config.preserve_metadata {
-            if let Err(e) = preserve_metadata(&item.source_path, &item.dest_path) {
-                tracing::warn!(
-                    "Failed to preserve directory metadata for {:?}: {}",
-                    item.dest_path,
-                    e
-                );
+/// Re-apply source directory metadata once the whole tree has been written
+///
+/// Creating files inside a directory bumps its mtime, so timestamps and modes
+/// are restored bottom-up (deepest directories first, the root last) after
+/// every child is in place. Uses `preserve_flags` when set, otherwise times
+/// and permissions. Returns the relative paths that could not be restored;
+/// those only count as failures with `strict_metadata`, otherwise they are
+/// logged and skipped.
+fn restore_directory_metadata(
+    source_dir: &Path,
+    dest_dir: &Path,
+    expected_entries: &Mutex<HashSet<PathBuf>>,
+    config: &CopyConfig,
+) -> Result<Vec<PathBuf>> {
+    let flags = match config.preserve_flags.as_deref() {
+        Some(spec) => PreserveFlags::from_str(spec)?,
+        None => PreserveFlags::default(),
+    };
+
+    let mut dirs: Vec<PathBuf> = expected_entries
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|rel| {
+            std::fs::symlink_metadata(source_dir.join(rel)).is_ok_and(|m| m.is_dir())
+                && std::fs::symlink_metadata(dest_dir.join(rel)).is_ok_and(|m| m.is_dir())
+        })
+        .cloned()
+        .collect();
+    dirs.push(PathBuf::new());
+    dirs.sort_by_key(|rel| std::cmp::Reverse(rel.components().count()));
+
+    let mut failed = Vec::new();
+    for rel in dirs {
+        let result = FileMetadata::from_path(&source_dir.join(&rel))
+            .and_then(|meta| meta.apply_to(&dest_dir.join(&rel), flags));
+        if let Err(e) = result {
+            if config.strict_metadata {
+                tracing::error!("Failed to restore directory metadata for {:?}: {}", rel, e);
+                failed.push(rel);
+            } else {
+                tracing::warn!("Failed to restore directory metadata for {:?}: {}", rel, e);
             }
         }
     }
-    Ok(())
+
+    Ok(failed)
 }
 
 /// Flush file batch - send to workers via channel (blocks if channel full = backpressure)
@@ -684,6 +1283,87 @@ fn flush_file_batch(
     Ok(())
 }
 
+/// Open the resume journal for `dest_dir`, logging rather than failing the
+/// copy when it cannot be used
+fn open_dir_journal(dest_dir: &Path) -> Option<Arc<DirectoryJournal>> {
+    let Some(path) = DirectoryJournal::path_for(dest_dir) else {
+        tracing::warn!("No resume journal location for {:?}", dest_dir);
+        return None;
+    };
+    match DirectoryJournal::open(&path) {
+        Ok(journal) => {
+            if journal.resumed_entries() > 0 {
+                info!(
+                    "Resuming directory copy: {} files already completed",
+                    journal.resumed_entries()
+                );
+            }
+            Some(Arc::new(journal))
+        }
+        Err(e) => {
+            tracing::warn!("Failed to open resume journal {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Sidecar location for `--checksum-manifest`: the configured path, or
+/// `<dest>.<ext>` beside the destination so it is not part of the tree
+fn checksum_manifest_path(dest_dir: &Path, config: &CopyConfig) -> PathBuf {
+    if let Some(path) = &config.checksum_manifest_path {
+        return path.clone();
+    }
+    let ext = config.checksum_algorithm.extension();
+    match dest_dir.file_name() {
+        Some(name) => {
+            let mut sidecar = name.to_os_string();
+            sidecar.push(format!(".{}", ext));
+            dest_dir.with_file_name(sidecar)
+        }
+        None => dest_dir.join("checksums.txt"),
+    }
+}
+
+/// Add a copied file to the checksum manifest, reusing the copy's SHA-256
+/// verification hash when there is one and hashing the destination otherwise
+fn record_checksum(checksums: &ChecksumManifest, item: &WorkItem, verified: Option<&str>) {
+    let hash = match (checksums.algorithm(), verified) {
+        (ChecksumAlgorithm::Sha256, Some(hash)) => Ok(hash.to_string()),
+        (algorithm, _) => calculate_checksum_with(&item.dest_path, algorithm),
+    };
+    match hash {
+        Ok(hash) => checksums.record(&item.relative_path, hash),
+        Err(e) => tracing::warn!(
+            "Failed to hash {:?} for checksum manifest: {}",
+            item.dest_path,
+            e
+        ),
+    }
+}
+
+/// The SHA-256 of the whole file, if the copy computed one
+///
+/// Delta transfers report the delta engine's hash (BLAKE3 by default), so
+/// their checksum is not reused as a SHA-256.
+fn copied_sha256(stats: &CopyStats) -> Option<&str> {
+    if stats.delta_stats.is_some() {
+        return None;
+    }
+    stats.checksum.as_deref()
+}
+
+/// Worker threads for the copy phase (0 = sequential)
+///
+/// Adaptive mode always runs in parallel; with `parallel` unset its ceiling
+/// is [`detect_optimal_concurrency`].
+fn worker_count(config: &CopyConfig) -> usize {
+    if config.adaptive_concurrency && config.parallel == 0 {
+        detect_optimal_concurrency()
+    } else {
+        config.parallel
+    }
+}
+
 /// Consumer: process work items in parallel or sequentially
 #[allow(clippy::too_many_arguments)]
 fn consume_work_items(
@@ -692,34 +1372,59 @@ fn consume_work_items(
     config: &CopyConfig,
     rx: crossbeam_channel::Receiver<WorkItem>,
     total_stats: Arc<Mutex<CopyStats>>,
+    failed_paths: &Mutex<Vec<PathBuf>>,
     concurrency_limiter: Option<&Arc<ConcurrencyLimiter>>,
     rename_index: Option<&Arc<HashMap<String, PathBuf>>>,
     batch_journal: Option<&Arc<Mutex<TransferJournal>>>,
+    dir_journal: Option<&Arc<DirectoryJournal>>,
+    since_scan: Option<&SinceScan>,
+    checksums: Option<&ChecksumManifest>,
+    sampler: Option<&SampleVerifier>,
+    publisher: &ProgressPublisher,
 ) -> Result<()> {
-    if config.parallel > 0 {
+    let workers = worker_count(config);
+    if workers > 0 {
         // Parallel processing with thread pool and concurrency control
         let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(config.parallel)
+            .num_threads(workers)
             .build()
             .map_err(|e| OrbitError::Parallel(e.to_string()))?;
 
+        // Pool threads do not inherit the caller's span; re-enter it so
+        // each file's span stays under the directory job
+        let parent_span = tracing::Span::current();
         pool.install(|| {
             rx.into_iter().par_bridge().for_each(|item| {
-                if let Err(e) = process_work_item(
+                let _parent = parent_span.enter();
+                let result = process_work_item(
                     &item,
                     source_dir,
                     dest_dir,
                     config,
                     &total_stats,
+                    failed_paths,
                     concurrency_limiter,
                     rename_index,
                     batch_journal,
-                ) {
-                    tracing::error!("Error copying {:?}: {}", item.source_path, e);
-                    if let Ok(mut stats) = total_stats.lock() {
-                        stats.files_failed += 1;
+                    dir_journal,
+                    since_scan,
+                    checksums,
+                    sampler,
+                    publisher,
+                );
+                // Feeds the adaptive controller; no-op for fixed limits
+                if let Some(limiter) = concurrency_limiter {
+                    match &result {
+                        Ok(stats) => {
+                            limiter.record_completion(stats.bytes_copied, stats.files_failed == 0)
+                        }
+                        Err(_) => limiter.record_completion(0, false),
                     }
                 }
+                if let Err(e) = result {
+                    tracing::error!("Error copying {:?}: {}", item.source_path, e);
+                    record_failure(&item, &total_stats, failed_paths);
+                }
             });
         });
     } else {
@@ -731,14 +1436,18 @@ fn consume_work_items(
                 dest_dir,
                 config,
                 &total_stats,
+                failed_paths,
                 None,
                 rename_index,
                 batch_journal,
+                dir_journal,
+                since_scan,
+                checksums,
+                sampler,
+                publisher,
             ) {
                 tracing::error!("Error copying {:?}: {}", item.source_path, e);
-                if let Ok(mut stats) = total_stats.lock() {
-                    stats.files_failed += 1;
-                }
+                record_failure(&item, &total_stats, failed_paths);
             }
         }
     }
@@ -746,7 +1455,20 @@ fn consume_work_items(
     Ok(())
 }
 
-/// Process a single work item (file or symlink)
+fn record_failure(
+    item: &WorkItem,
+    total_stats: &Mutex<CopyStats>,
+    failed_paths: &Mutex<Vec<PathBuf>>,
+) {
+    if let Ok(mut stats) = total_stats.lock() {
+        stats.files_failed += 1;
+    }
+    if let Ok(mut failed) = failed_paths.lock() {
+        failed.push(item.relative_path.clone());
+    }
+}
+
+/// Process a single work item (file or symlink), returning its stats
 #[allow(clippy::too_many_arguments)]
 fn process_work_item(
     item: &WorkItem,
@@ -754,10 +1476,16 @@ fn process_work_item(
     dest_dir: &Path,
     config: &CopyConfig,
     stats_mutex: &Arc<Mutex<CopyStats>>,
+    failed_paths: &Mutex<Vec<PathBuf>>,
     concurrency_limiter: Option<&Arc<ConcurrencyLimiter>>,
     rename_index: Option<&Arc<HashMap<String, PathBuf>>>,
     batch_journal: Option<&Arc<Mutex<TransferJournal>>>,
-) -> Result<()> {
+    dir_journal: Option<&Arc<DirectoryJournal>>,
+    since_scan: Option<&SinceScan>,
+    checksums: Option<&ChecksumManifest>,
+    sampler: Option<&SampleVerifier>,
+    publisher: &ProgressPublisher,
+) -> Result<CopyStats> {
     // Acquire concurrency permit if limiter is provided
     // Permit is automatically released when dropped (RAII pattern)
     let _permit = concurrency_limiter.map(|limiter| limiter.acquire());
@@ -770,7 +1498,7 @@ fn process_work_item(
     let stats = match &item.entry_type {
         EntryType::Directory => {
             // Directories already handled in producer
-            return Ok(());
+            return Ok(CopyStats::new());
         }
         EntryType::Symlink => {
             handle_symlink(
@@ -792,6 +1520,18 @@ fn process_work_item(
                 bytes_skipped: 0,
             }
         }
+        EntryType::Special => {
+            handle_special_file(
+                &item.source_path,
+                &item.dest_path,
+                config.special_files,
+                config.dry_run,
+            )?;
+            CopyStats {
+                files_copied: 1,
+                ..CopyStats::new()
+            }
+        }
         EntryType::Hardlink { target } => {
             if let Some(parent) = item.dest_path.parent() {
                 if !parent.exists() {
@@ -844,7 +1584,12 @@ fn process_work_item(
                     bytes_skipped: 0,
                 }
             } else {
-                let stats = super::copy_file(&item.source_path, &item.dest_path, &file_config)?;
+                let stats = super::copy_file_impl(
+                    &item.source_path,
+                    &item.dest_path,
+                    &file_config,
+                    Some(publisher),
+                )?;
                 if let Some(journal) = batch_journal {
                     let mut journal = journal.lock().unwrap();
                     record_create_file(&mut journal, &item.source_path, &item.relative_path)?;
@@ -853,14 +1598,48 @@ fn process_work_item(
             }
         }
         EntryType::File => {
-            // Ensure parent directory exists before copying file
-            if let Some(parent) = item.dest_path.parent() {
-                if !parent.exists() {
-                    std::fs::create_dir_all(parent)?;
+            let relative = item.relative_path.to_string_lossy();
+            if let Some(scan) = since_scan {
+                if scan.is_unchanged(&item.source_path, &relative) {
+                    scan.record(&item.source_path, &relative, true);
+                    // The file may never have been copied to this destination
+                    if let (Some(checksums), true) = (checksums, item.dest_path.exists()) {
+                        record_checksum(checksums, item, None);
+                    }
+                    if let Ok(mut total_stats) = stats_mutex.lock() {
+                        total_stats.files_skipped += 1;
+                    }
+                    return Ok(CopyStats {
+                        files_skipped: 1,
+                        ..CopyStats::new()
+                    });
                 }
             }
 
-            let mut source_hash: Option<String> = None;
+            let stamp = dir_journal.and_then(|_| FileStamp::from_path(&item.source_path).ok());
+            if let (Some(journal), Some(stamp)) = (dir_journal, stamp.as_ref()) {
+                if journal.is_done(&item.relative_path, stamp) && item.dest_path.exists() {
+                    if let Some(checksums) = checksums {
+                        record_checksum(checksums, item, None);
+                    }
+                    if let Ok(mut total_stats) = stats_mutex.lock() {
+                        total_stats.files_skipped += 1;
+                    }
+                    return Ok(CopyStats {
+                        files_skipped: 1,
+                        ..CopyStats::new()
+                    });
+                }
+            }
+
+            // Ensure parent directory exists before copying file
+            if let Some(parent) = item.dest_path.parent() {
+                if !parent.exists() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+
+            let mut source_hash: Option<String> = None;
             let mut hardlink_stats: Option<CopyStats> = None;
 
             if !config.link_dest.is_empty() {
@@ -948,17 +1727,50 @@ fn process_work_item(
             let stats = if let Some(stats) = hardlink_stats {
                 stats
             } else {
-                let stats = super::copy_file(&item.source_path, &item.dest_path, &file_config)?;
+                let stats = super::copy_file_impl(
+                    &item.source_path,
+                    &item.dest_path,
+                    &file_config,
+                    Some(publisher),
+                )?;
                 if let Some(journal) = batch_journal {
                     let mut journal = journal.lock().unwrap();
                     record_create_file(&mut journal, &item.source_path, &item.relative_path)?;
                 }
                 stats
             };
+            if let (Some(scan), 0) = (since_scan, stats.files_failed) {
+                scan.record(&item.source_path, &relative, false);
+            }
+            if let (Some(sampler), 0) = (sampler, stats.files_failed) {
+                sampler.verify(
+                    &item.source_path,
+                    &item.dest_path,
+                    &item.relative_path,
+                    copied_sha256(&stats),
+                )?;
+            }
+            if let (Some(journal), Some(stamp)) = (dir_journal, stamp) {
+                if stats.files_failed == 0 {
+                    if let Err(e) = journal.record(&item.relative_path, stamp) {
+                        tracing::warn!("Failed to update resume journal: {}", e);
+                    }
+                }
+            }
             stats
         }
     };
 
+    // Unchanged files are listed too, so the manifest covers the whole tree
+    if let Some(checksums) = checksums {
+        if stats.files_failed == 0
+            && (stats.files_copied == 1 || stats.files_skipped == 1)
+            && !matches!(item.entry_type, EntryType::Symlink | EntryType::Special)
+        {
+            record_checksum(checksums, item, copied_sha256(&stats));
+        }
+    }
+
     // Update total stats atomically
     if let Ok(mut total_stats) = stats_mutex.lock() {
         total_stats.bytes_copied += stats.bytes_copied;
@@ -968,8 +1780,14 @@ fn process_work_item(
         total_stats.bytes_skipped += stats.bytes_skipped;
         total_stats.chunks_resumed += stats.chunks_resumed;
     }
+    // ErrorMode::Skip reports a skipped failure as a successful result
+    if stats.files_failed > 0 {
+        if let Ok(mut failed) = failed_paths.lock() {
+            failed.push(item.relative_path.clone());
+        }
+    }
 
-    Ok(())
+    Ok(stats)
 }
 
 /// Handle symbolic link based on mode
@@ -1007,6 +1825,69 @@ fn handle_symlink(
     }
 }
 
+/// Handle a FIFO, socket or device node based on mode
+fn handle_special_file(
+    source_path: &Path,
+    dest_path: &Path,
+    mode: SpecialFileMode,
+    dry_run: bool,
+) -> Result<()> {
+    match mode {
+        SpecialFileMode::Skip => Ok(()),
+        SpecialFileMode::Error => Err(OrbitError::Other(format!(
+            "{} is a special file (FIFO, socket or device); use --special skip or --special recreate",
+            source_path.display()
+        ))),
+        SpecialFileMode::Recreate if dry_run => {
+            println!("Would recreate special file: {}", dest_path.display());
+            Ok(())
+        }
+        SpecialFileMode::Recreate => {
+            if let Some(parent) = dest_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            if std::fs::symlink_metadata(dest_path).is_ok() {
+                std::fs::remove_file(dest_path)?;
+            }
+            recreate_special_file(source_path, dest_path)
+        }
+    }
+}
+
+/// Create a node of the source's type and mode with mknod(2)
+///
+/// Device nodes usually need root; the error is returned as-is.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn recreate_special_file(source_path: &Path, dest_path: &Path) -> Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::symlink_metadata(source_path)?;
+    let path = CString::new(dest_path.as_os_str().as_bytes())
+        .map_err(|_| OrbitError::InvalidPath(dest_path.to_path_buf()))?;
+    let ret = unsafe {
+        libc::mknod(
+            path.as_ptr(),
+            metadata.mode() as libc::mode_t,
+            metadata.rdev() as libc::dev_t,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().into())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn recreate_special_file(source_path: &Path, _dest_path: &Path) -> Result<()> {
+    Err(OrbitError::Other(format!(
+        "Cannot recreate special file {} on this platform",
+        source_path.display()
+    )))
+}
+
 /// Create a symbolic link (cross-platform)
 #[cfg(unix)]
 fn create_symlink(target: &Path, link_path: &Path) -> Result<()> {
@@ -1052,14 +1933,127 @@ mod tests {
 
         let config = mirror_config();
         let filter_list = FilterList::new();
-        let deletions =
-            collect_deletion_candidates(dest_dir, &expected, &config, &filter_list).unwrap();
+        let deletions = collect_deletion_candidates(
+            dest_dir,
+            &expected,
+            &HashSet::new(),
+            &config,
+            &filter_list,
+        )
+        .unwrap();
 
         assert_eq!(deletions.len(), 1);
         assert_eq!(deletions[0].path, dest_dir.join("extra.txt"));
         assert!(matches!(deletions[0].entry_type, EntryType::File));
     }
 
+    #[test]
+    fn sampled_verification_passes_for_delta_transfers() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&dest).unwrap();
+        for i in 0..4 {
+            let name = format!("file{}.bin", i);
+            std::fs::write(source.join(&name), vec![i as u8; 64 * 1024]).unwrap();
+            std::fs::write(dest.join(&name), vec![0xFF; 32 * 1024]).unwrap();
+        }
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            check_mode: crate::core::delta::CheckMode::Delta,
+            // Sparse handling takes the buffered path and bypasses delta
+            sparse_mode: crate::core::sparse::SparseMode::Never,
+            verify_sample: Some(crate::core::verify_sample::VerifySample {
+                percent: 100.0,
+                seed: Some(1),
+            }),
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_failed, 0);
+        assert_eq!(stats.files_copied, 4);
+        for i in 0..4 {
+            let name = format!("file{}.bin", i);
+            assert_eq!(
+                std::fs::read(dest.join(&name)).unwrap(),
+                std::fs::read(source.join(&name)).unwrap()
+            );
+        }
+    }
+
+    fn hidden_tree(temp: &TempDir) -> PathBuf {
+        let source = temp.path().join("src");
+        std::fs::create_dir_all(source.join(".cache")).unwrap();
+        std::fs::write(source.join("visible.txt"), b"visible").unwrap();
+        std::fs::write(source.join(".env"), b"secret").unwrap();
+        std::fs::write(source.join(".cache/blob"), b"blob").unwrap();
+        source
+    }
+
+    #[test]
+    fn hidden_entries_excluded() {
+        let temp = TempDir::new().unwrap();
+        let source = hidden_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            hidden_files: HiddenMode::Exclude,
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 1);
+        assert!(dest.join("visible.txt").exists());
+        assert!(!dest.join(".env").exists());
+        assert!(!dest.join(".cache").exists());
+    }
+
+    #[test]
+    fn hidden_entries_included_by_default() {
+        let temp = TempDir::new().unwrap();
+        let source = hidden_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 3);
+        assert_eq!(std::fs::read(dest.join(".env")).unwrap(), b"secret");
+        assert_eq!(std::fs::read(dest.join(".cache/blob")).unwrap(), b"blob");
+    }
+
+    #[test]
+    fn include_pattern_overrides_hidden_exclude() {
+        let temp = TempDir::new().unwrap();
+        let source = hidden_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            hidden_files: HiddenMode::Exclude,
+            include_patterns: vec![".env".to_string()],
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 2);
+        assert!(dest.join("visible.txt").exists());
+        assert_eq!(std::fs::read(dest.join(".env")).unwrap(), b"secret");
+        assert!(!dest.join(".cache").exists());
+    }
+
     #[test]
     fn collect_deletion_candidates_respects_excludes() {
         let temp = TempDir::new().unwrap();
@@ -1074,8 +2068,14 @@ mod tests {
         config.exclude_patterns = vec!["*.log".to_string()];
 
         let filter_list = FilterList::new();
-        let deletions =
-            collect_deletion_candidates(dest_dir, &expected, &config, &filter_list).unwrap();
+        let deletions = collect_deletion_candidates(
+            dest_dir,
+            &expected,
+            &HashSet::new(),
+            &config,
+            &filter_list,
+        )
+        .unwrap();
 
         assert!(deletions.is_empty());
     }
@@ -1094,13 +2094,98 @@ mod tests {
             entry_type: EntryType::File,
         }];
 
-        let summary = apply_deletions(&deletions, &mirror_config());
+        let summary = apply_deletions(&deletions, dest_dir, &mirror_config(), None);
 
         assert_eq!(summary.deleted, 1);
-        assert!(summary.failed == 0);
+        assert!(summary.failed.is_empty());
         assert!(!victim.exists());
     }
 
+    #[test]
+    fn quarantine_mode_moves_extraneous_entries() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(dest.join("old/nested")).unwrap();
+        std::fs::write(source.join("keep.txt"), b"keep").unwrap();
+        std::fs::write(dest.join("stale.txt"), b"stale").unwrap();
+        std::fs::write(dest.join("old/nested/deep.txt"), b"deep").unwrap();
+
+        let config = CopyConfig {
+            delete_mode: DeleteMode::Quarantine,
+            ..mirror_config()
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+
+        assert!(dest.join("keep.txt").exists());
+        assert!(!dest.join("stale.txt").exists());
+        assert!(!dest.join("old").exists());
+
+        let runs: Vec<_> = std::fs::read_dir(dest.join(QUARANTINE_DIR))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+        assert_eq!(std::fs::read(run.join("stale.txt")).unwrap(), b"stale");
+        assert_eq!(
+            std::fs::read(run.join("old/nested/deep.txt")).unwrap(),
+            b"deep"
+        );
+
+        // The quarantine directory is not itself extraneous on the next run
+        copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(
+            std::fs::read_dir(dest.join(QUARANTINE_DIR))
+                .unwrap()
+                .count(),
+            1
+        );
+        assert!(run.join("stale.txt").exists());
+
+        // ...nor does plain delete mode remove it
+        copy_directory(&source, &dest, &mirror_config()).unwrap();
+        assert!(run.join("stale.txt").exists());
+    }
+
+    #[test]
+    fn mirror_trash_mode_moves_extraneous_entries_to_trash_groups() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(dest.join("old")).unwrap();
+        std::fs::write(source.join("keep.txt"), b"keep").unwrap();
+        std::fs::write(dest.join("stale.txt"), b"stale").unwrap();
+        std::fs::write(dest.join("old/deep.txt"), b"deep").unwrap();
+
+        let config = CopyConfig {
+            delete_mode: DeleteMode::Trash,
+            ..mirror_config()
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+        assert!(!dest.join("stale.txt").exists());
+        assert!(!dest.join("old").exists());
+
+        let groups: Vec<_> = std::fs::read_dir(dest.join(TRASH_DIR))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        let name = group.file_name().unwrap().to_str().unwrap();
+        assert!(crate::core::trash::trash_entry_time(name).is_some());
+        assert_eq!(std::fs::read(group.join("stale.txt")).unwrap(), b"stale");
+        assert_eq!(std::fs::read(group.join("old/deep.txt")).unwrap(), b"deep");
+
+        // A second pass gets its own group and leaves the trash alone
+        std::fs::write(dest.join("stale.txt"), b"stale again").unwrap();
+        copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(std::fs::read_dir(dest.join(TRASH_DIR)).unwrap().count(), 2);
+        assert!(group.join("stale.txt").exists());
+    }
+
     #[cfg(unix)]
     #[test]
     fn collect_deletion_candidates_honors_symlink_skip() {
@@ -1118,9 +2203,712 @@ mod tests {
         config.symlink_mode = SymlinkMode::Skip;
 
         let filter_list = FilterList::new();
-        let deletions =
-            collect_deletion_candidates(dest_dir, &expected, &config, &filter_list).unwrap();
+        let deletions = collect_deletion_candidates(
+            dest_dir,
+            &expected,
+            &HashSet::new(),
+            &config,
+            &filter_list,
+        )
+        .unwrap();
 
         assert!(deletions.is_empty());
     }
+
+    #[test]
+    fn failure_summary_caps_listed_paths() {
+        let paths: Vec<PathBuf> = (0..12)
+            .map(|i| PathBuf::from(format!("f{:02}", i)))
+            .collect();
+
+        // One failure was not tied to a path (e.g. a destination scan error)
+        let summary = format_failure_summary(13, &paths);
+        assert!(summary.starts_with("Completed with 13 failures:\n"));
+        assert!(summary.contains("  f09\n"));
+        assert!(!summary.contains("f10"));
+        assert!(summary.ends_with("  +3 more\n"));
+
+        let summary = format_failure_summary(1, &paths[..1]);
+        assert_eq!(summary, "Completed with 1 failure:\n  f00\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skip_mode_reports_partial_failure_distinctly() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::write(source.join("a.txt"), b"alpha").unwrap();
+        std::fs::write(source.join("sub/b.txt"), b"beta").unwrap();
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            error_mode: ErrorMode::Skip,
+            symlink_mode: SymlinkMode::Preserve,
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_copied, 2);
+        assert_eq!(stats.files_failed, 0);
+
+        // Preserving a symlink over an existing regular file fails, even as root
+        std::os::unix::fs::symlink("a.txt", source.join("link")).unwrap();
+        std::fs::write(dest.join("link"), b"in the way").unwrap();
+
+        let err = copy_directory(&source, &dest, &config).unwrap_err();
+        assert_eq!(err.exit_code(), crate::error::EXIT_PARTIAL);
+        assert_eq!(
+            err.to_string(),
+            "Parallel processing error: Completed with 1 failure"
+        );
+
+        // Abort keeps the plain count
+        let config = CopyConfig {
+            error_mode: ErrorMode::Abort,
+            ..config
+        };
+        let err = copy_directory(&source, &dest, &config).unwrap_err();
+        assert!(err.to_string().contains("1 files failed to copy"));
+    }
+
+    #[test]
+    fn checksum_manifest_lists_copied_files_in_sum_format() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::write(source.join("a.txt"), b"alpha").unwrap();
+        std::fs::write(source.join("sub/b.txt"), b"beta").unwrap();
+        std::fs::write(source.join("sub/c.txt"), b"gamma").unwrap();
+
+        for algorithm in [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Blake3] {
+            let dest = temp.path().join(format!("dst-{}", algorithm.extension()));
+            let config = CopyConfig {
+                recursive: true,
+                show_progress: false,
+                checksum_manifest: true,
+                checksum_algorithm: algorithm,
+                ..CopyConfig::default()
+            };
+            copy_directory(&source, &dest, &config).unwrap();
+
+            // `<hash>  <path>` per line, sorted, as `sha256sum -c` / `b3sum -c` read it
+            let sidecar = temp.path().join(format!(
+                "dst-{}.{}",
+                algorithm.extension(),
+                algorithm.extension()
+            ));
+            let manifest = std::fs::read_to_string(&sidecar).unwrap();
+            let lines: Vec<(&str, &str)> = manifest
+                .lines()
+                .map(|line| line.split_once("  ").unwrap())
+                .collect();
+            let paths: Vec<&str> = lines.iter().map(|(_, path)| *path).collect();
+            assert_eq!(paths, ["a.txt", "sub/b.txt", "sub/c.txt"]);
+            for (hash, path) in lines {
+                assert_eq!(hash.len(), 64);
+                assert_eq!(
+                    hash,
+                    calculate_checksum_with(&dest.join(path), algorithm).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn checksum_manifest_covers_delta_and_unchanged_files() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&dest).unwrap();
+        // Delta-transferred: the destination exists with different content
+        std::fs::write(source.join("delta.bin"), vec![7u8; 96 * 1024]).unwrap();
+        std::fs::write(dest.join("delta.bin"), vec![9u8; 64 * 1024]).unwrap();
+        std::fs::write(source.join("new.txt"), b"new").unwrap();
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            check_mode: crate::core::delta::CheckMode::Delta,
+            sparse_mode: crate::core::sparse::SparseMode::Never,
+            checksum_manifest: true,
+            ..CopyConfig::default()
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+
+        // Second run: both files are up to date and skipped, but still listed
+        let config = CopyConfig {
+            copy_mode: CopyMode::Update,
+            ..config
+        };
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_skipped, 2);
+
+        let sidecar = temp.path().join("dst.sha256");
+        let manifest = std::fs::read_to_string(&sidecar).unwrap();
+        let paths: Vec<&str> = manifest
+            .lines()
+            .map(|line| line.split_once("  ").unwrap().1)
+            .collect();
+        assert_eq!(paths, ["delta.bin", "new.txt"]);
+
+        for line in manifest.lines() {
+            let (hash, path) = line.split_once("  ").unwrap();
+            assert_eq!(hash, calculate_checksum(&dest.join(path)).unwrap());
+        }
+
+        // Round trip through coreutils where it is installed
+        if let Ok(output) = std::process::Command::new("sha256sum")
+            .arg("-c")
+            .arg(&sidecar)
+            .current_dir(&dest)
+            .output()
+        {
+            assert!(
+                output.status.success(),
+                "sha256sum -c failed: {}",
+                String::from_utf8_lossy(&output.stdout)
+            );
+        }
+    }
+
+    #[test]
+    fn since_manifest_transfers_only_changed_files() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::write(source.join("a.txt"), b"alpha").unwrap();
+        std::fs::write(source.join("sub/b.txt"), b"beta").unwrap();
+        std::fs::write(source.join("sub/c.txt"), b"gamma").unwrap();
+
+        // Baseline scan, as `orbit manifest plan` writes it
+        let baseline = temp.path().join("baseline");
+        let plan_config = CopyConfig {
+            manifest_output_dir: Some(baseline.clone()),
+            ..CopyConfig::default()
+        };
+        let mut generator = ManifestGenerator::new(&source, &source, &plan_config).unwrap();
+        for rel in ["a.txt", "sub/b.txt", "sub/c.txt"] {
+            generator
+                .generate_file_manifest(&source.join(rel), rel)
+                .unwrap();
+        }
+        generator.finalize("sha256:pending").unwrap();
+
+        // Same size, different content: only the digest can tell
+        std::fs::write(source.join("sub/b.txt"), b"BETA").unwrap();
+
+        let updated = temp.path().join("updated");
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            since_manifest: Some(baseline),
+            manifest_output_dir: Some(updated.clone()),
+            ..CopyConfig::default()
+        };
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_copied, 1);
+        assert_eq!(stats.files_skipped, 2);
+        assert_eq!(std::fs::read(dest.join("sub/b.txt")).unwrap(), b"BETA");
+        assert!(!dest.join("a.txt").exists());
+        assert!(!dest.join("sub/c.txt").exists());
+
+        // The updated manifest covers every file and becomes the next baseline
+        let plan =
+            orbit_core_manifest::FlightPlan::load(updated.join("job.flightplan.json")).unwrap();
+        assert_eq!(plan.files.len(), 3);
+
+        let dest2 = temp.path().join("dst2");
+        let config = CopyConfig {
+            since_manifest: Some(updated),
+            manifest_output_dir: None,
+            ..config
+        };
+        let stats = copy_directory(&source, &dest2, &config).unwrap();
+        assert_eq!(stats.files_copied, 0);
+        assert_eq!(stats.files_skipped, 3);
+
+        // Skipped files already at the destination still get a checksum line
+        let config = CopyConfig {
+            checksum_manifest: true,
+            ..config
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+        let manifest = std::fs::read_to_string(temp.path().join("dst.sha256")).unwrap();
+        assert_eq!(
+            manifest,
+            format!(
+                "{}  sub/b.txt\n",
+                calculate_checksum(&dest.join("sub/b.txt")).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn resumed_mirror_finishes_journaled_deletion_pass() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("keep.txt"), b"keep").unwrap();
+        std::fs::create_dir_all(dest.join("old")).unwrap();
+        std::fs::write(dest.join("keep.txt"), b"keep").unwrap();
+        for name in ["a.txt", "b.txt", "old/c.txt"] {
+            std::fs::write(dest.join(name), b"extra").unwrap();
+        }
+
+        // Plan the pass, then stop after the first deletion
+        let journal_path = DirectoryJournal::path_for(&dest).unwrap();
+        let journal = DirectoryJournal::open(&journal_path).unwrap();
+        let expected = Arc::new(Mutex::new(HashSet::from([PathBuf::from("keep.txt")])));
+        let mut deletions = collect_deletion_candidates(
+            &dest,
+            &expected,
+            &HashSet::new(),
+            &mirror_config(),
+            &FilterList::new(),
+        )
+        .unwrap();
+        assert_eq!(deletions.len(), 4);
+        // Keep the walk's contents-first order, with a.txt deleted first
+        let first = deletions
+            .iter()
+            .position(|item| item.path == dest.join("a.txt"))
+            .unwrap();
+        let first = deletions.remove(first);
+        deletions.insert(0, first);
+        plan_deletions(&journal, &deletions, &dest);
+        let summary = apply_deletions(&deletions[..1], &dest, &mirror_config(), Some(&journal));
+        assert_eq!(summary.deleted, 1);
+        drop(journal);
+
+        // An entry the interrupted pass already deleted is not processed
+        // again, even if something with that name appears in the meantime
+        std::fs::write(dest.join("a.txt"), b"new").unwrap();
+
+        let config = CopyConfig {
+            resume_enabled: true,
+            ..mirror_config()
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(std::fs::read(dest.join("a.txt")).unwrap(), b"new");
+        assert!(!dest.join("b.txt").exists());
+        assert!(!dest.join("old").exists());
+        assert_eq!(std::fs::read(dest.join("keep.txt")).unwrap(), b"keep");
+        assert!(!journal_path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resumed_copy_skips_journaled_files() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::write(source.join("a.txt"), b"alpha").unwrap();
+        std::fs::write(source.join("sub/b.txt"), b"beta").unwrap();
+        std::fs::write(source.join("sub/c.txt"), b"gamma").unwrap();
+        std::os::unix::fs::symlink("a.txt", source.join("link")).unwrap();
+
+        // Preserving a symlink over a regular file fails, interrupting the run
+        std::fs::create_dir_all(&dest).unwrap();
+        std::fs::write(dest.join("link"), b"in the way").unwrap();
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            resume_enabled: true,
+            error_mode: ErrorMode::Skip,
+            symlink_mode: SymlinkMode::Preserve,
+            ..CopyConfig::default()
+        };
+        assert!(copy_directory(&source, &dest, &config).is_err());
+        let journal_path = DirectoryJournal::path_for(&dest).unwrap();
+        assert!(journal_path.exists());
+
+        // Completed files must not be transferred again: tag their copies,
+        // and change one source so it no longer matches the journal
+        std::fs::write(dest.join("a.txt"), b"tagged").unwrap();
+        std::fs::write(dest.join("sub/b.txt"), b"tagged").unwrap();
+        std::fs::write(source.join("sub/c.txt"), b"gamma, edited").unwrap();
+        std::fs::remove_file(dest.join("link")).unwrap();
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_skipped, 2);
+        // The changed file and the symlink
+        assert_eq!(stats.files_copied, 2);
+        assert_eq!(std::fs::read(dest.join("a.txt")).unwrap(), b"tagged");
+        assert_eq!(std::fs::read(dest.join("sub/b.txt")).unwrap(), b"tagged");
+        assert_eq!(
+            std::fs::read(dest.join("sub/c.txt")).unwrap(),
+            b"gamma, edited"
+        );
+        assert!(dest.join("link").is_symlink());
+
+        // A clean finish removes the journal
+        assert!(!journal_path.exists());
+    }
+
+    #[test]
+    fn adaptive_concurrency_copies_tree() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        for i in 0..20 {
+            std::fs::write(source.join("sub").join(format!("f{i}.txt")), b"data").unwrap();
+        }
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            adaptive_concurrency: true,
+            ..CopyConfig::default()
+        };
+        assert!(worker_count(&config) > 0);
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_copied, 20);
+        assert!(dest.join("sub/f19.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_metadata_restored_after_children_are_copied() {
+        use filetime::{set_file_mtime, FileTime};
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("outer/inner")).unwrap();
+        std::fs::write(source.join("outer/a.txt"), b"alpha").unwrap();
+        std::fs::write(source.join("outer/inner/b.txt"), b"beta").unwrap();
+
+        let dirs = [
+            ("", 0o755, 1_500_000_000),
+            ("outer", 0o750, 1_600_000_000),
+            ("outer/inner", 0o700, 1_700_000_000),
+        ];
+        for (rel, mode, mtime) in dirs {
+            let dir = source.join(rel);
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(mode)).unwrap();
+            set_file_mtime(&dir, FileTime::from_unix_time(mtime, 0)).unwrap();
+        }
+
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            preserve_metadata: true,
+            strict_metadata: true,
+            ..CopyConfig::default()
+        };
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+        assert_eq!(stats.files_copied, 2);
+
+        for (rel, mode, mtime) in dirs {
+            let meta = std::fs::metadata(dest.join(rel)).unwrap();
+            assert_eq!(
+                meta.permissions().mode() & 0o7777,
+                mode,
+                "mode of {:?}",
+                rel
+            );
+            assert_eq!(
+                FileTime::from_last_modification_time(&meta).unix_seconds(),
+                mtime,
+                "mtime of {:?}",
+                rel
+            );
+        }
+    }
+
+    #[test]
+    fn max_depth_one_copies_only_top_level_entries() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        std::fs::create_dir_all(source.join("sub/deeper")).unwrap();
+        std::fs::write(source.join("top.txt"), b"top").unwrap();
+        std::fs::write(source.join("sub/nested.txt"), b"nested").unwrap();
+        std::fs::write(source.join("sub/deeper/deep.txt"), b"deep").unwrap();
+
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            max_depth: Some(1),
+            ..CopyConfig::default()
+        };
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 1);
+        assert_eq!(std::fs::read(dest.join("top.txt")).unwrap(), b"top");
+        assert!(dest.join("sub").is_dir());
+        assert!(!dest.join("sub/nested.txt").exists());
+        assert!(!dest.join("sub/deeper").exists());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn make_fifo(path: &Path) {
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o640) }, 0);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn special_tree(temp: &TempDir) -> PathBuf {
+        let source = temp.path().join("src");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::write(source.join("data.txt"), b"data").unwrap();
+        std::fs::write(source.join("empty.txt"), b"").unwrap();
+        make_fifo(&source.join("sub/pipe"));
+        source
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn special_files_skipped_by_default() {
+        let temp = TempDir::new().unwrap();
+        let source = special_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 2);
+        assert!(std::fs::symlink_metadata(dest.join("sub/pipe")).is_err());
+        assert_eq!(std::fs::read(dest.join("data.txt")).unwrap(), b"data");
+        // Zero-byte files are copied, not skipped
+        let empty = std::fs::symlink_metadata(dest.join("empty.txt")).unwrap();
+        assert!(empty.is_file());
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn special_files_recreated() {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        let temp = TempDir::new().unwrap();
+        let source = special_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            special_files: SpecialFileMode::Recreate,
+            ..CopyConfig::default()
+        };
+
+        let stats = copy_directory(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 3);
+        let pipe = std::fs::symlink_metadata(dest.join("sub/pipe")).unwrap();
+        assert!(pipe.file_type().is_fifo());
+        let source_mode = std::fs::symlink_metadata(source.join("sub/pipe"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(pipe.permissions().mode(), source_mode);
+        assert_eq!(std::fs::metadata(dest.join("empty.txt")).unwrap().len(), 0);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn special_files_error_mode_fails_without_blocking() {
+        let temp = TempDir::new().unwrap();
+        let source = special_tree(&temp);
+        let dest = temp.path().join("dst");
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            special_files: SpecialFileMode::Error,
+            ..CopyConfig::default()
+        };
+
+        let err = copy_directory(&source, &dest, &config).unwrap_err();
+
+        assert!(err.to_string().contains("1 files failed to copy"));
+        assert_eq!(std::fs::read(dest.join("data.txt")).unwrap(), b"data");
+        assert!(std::fs::symlink_metadata(dest.join("sub/pipe")).is_err());
+    }
+
+    #[test]
+    fn max_depth_mirror_keeps_deeper_destination_entries() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("sub")).unwrap();
+        std::fs::create_dir_all(dest.join("sub")).unwrap();
+        std::fs::write(source.join("top.txt"), b"top").unwrap();
+        std::fs::write(dest.join("stale.txt"), b"stale").unwrap();
+        std::fs::write(dest.join("sub/below.txt"), b"below").unwrap();
+
+        let config = CopyConfig {
+            max_depth: Some(1),
+            ..mirror_config()
+        };
+        copy_directory(&source, &dest, &config).unwrap();
+
+        // Top-level extras go; anything below the limit was never compared
+        assert!(!dest.join("stale.txt").exists());
+        assert!(dest.join("sub/below.txt").exists());
+    }
+
+    /// Pretends every directory named `mnt` is another filesystem
+    fn mock_device(path: &Path) -> Option<u64> {
+        if path.file_name().is_some_and(|name| name == "mnt") {
+            Some(2)
+        } else {
+            Some(1)
+        }
+    }
+
+    #[test]
+    fn one_file_system_skips_other_device_contents() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        let dest = temp.path().join("dst");
+        std::fs::create_dir_all(source.join("data")).unwrap();
+        std::fs::create_dir_all(source.join("mnt/share")).unwrap();
+        std::fs::write(source.join("data/local.txt"), b"local").unwrap();
+        std::fs::write(source.join("mnt/remote.txt"), b"remote").unwrap();
+
+        let walk = |one_file_system: bool| {
+            let config = CopyConfig {
+                recursive: true,
+                one_file_system,
+                ..CopyConfig::default()
+            };
+            let (tx, rx) = crossbeam_channel::unbounded();
+            let expected = Arc::new(Mutex::new(HashSet::new()));
+            let pruned = produce_work_items(
+                &source,
+                &dest,
+                &config,
+                tx,
+                expected.clone(),
+                &FilterList::new(),
+                mock_device,
+            )
+            .unwrap();
+            let files: HashSet<PathBuf> = rx.iter().map(|item| item.relative_path).collect();
+            let expected = expected.lock().unwrap().clone();
+            (files, expected, pruned)
+        };
+
+        let (files, expected, pruned) = walk(true);
+        assert!(files.contains(Path::new("data/local.txt")));
+        assert!(!files.contains(Path::new("mnt/remote.txt")));
+        // The mount point itself is still created, but nothing under it
+        assert!(expected.contains(Path::new("mnt")));
+        assert!(!expected.contains(Path::new("mnt/share")));
+        assert_eq!(pruned, HashSet::from([PathBuf::from("mnt")]));
+
+        let (files, _, pruned) = walk(false);
+        assert!(files.contains(Path::new("mnt/remote.txt")));
+        assert!(pruned.is_empty());
+    }
+
+    #[test]
+    fn deletion_candidates_skip_pruned_mount_contents() {
+        let temp = TempDir::new().unwrap();
+        let dest_dir = temp.path();
+        std::fs::create_dir_all(dest_dir.join("mnt")).unwrap();
+        std::fs::write(dest_dir.join("mnt/kept.txt"), b"kept").unwrap();
+        std::fs::write(dest_dir.join("extra.txt"), b"extra").unwrap();
+
+        let expected = Arc::new(Mutex::new(HashSet::from([PathBuf::from("mnt")])));
+        let pruned = HashSet::from([PathBuf::from("mnt")]);
+        let deletions = collect_deletion_candidates(
+            dest_dir,
+            &expected,
+            &pruned,
+            &mirror_config(),
+            &FilterList::new(),
+        )
+        .unwrap();
+
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].path, dest_dir.join("extra.txt"));
+    }
+
+    #[test]
+    fn preflight_fails_when_destination_cannot_be_created() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("data.txt"), b"data").unwrap();
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            ..CopyConfig::default()
+        };
+
+        // A regular file where the destination's parent should be
+        std::fs::write(temp.path().join("blocker"), b"x").unwrap();
+        let err = copy_directory(&source, &temp.path().join("blocker/dest"), &config).unwrap_err();
+        assert!(
+            matches!(err, OrbitError::DestinationNotWritable { .. }),
+            "{}",
+            err
+        );
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn preflight_fails_on_read_only_destination_before_reading_source() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Root ignores permission bits, so a read-only directory stays writable
+        if unsafe { libc::geteuid() } == 0 {
+            eprintln!("Skipping: test requires non-root user");
+            return;
+        }
+
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("src");
+        std::fs::create_dir(&source).unwrap();
+        let file = source.join("data.txt");
+        std::fs::write(&file, b"data").unwrap();
+        // Reading this would fail with a different error
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o000)).unwrap();
+        let config = CopyConfig {
+            recursive: true,
+            show_progress: false,
+            ..CopyConfig::default()
+        };
+
+        let dest = temp.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::set_permissions(&dest, std::fs::Permissions::from_mode(0o555)).unwrap();
+        let err = copy_directory(&source, &dest, &config).unwrap_err();
+        assert!(
+            matches!(err, OrbitError::DestinationNotWritable { .. }),
+            "{}",
+            err
+        );
+        assert_eq!(std::fs::read_dir(&dest).unwrap().count(), 0);
+
+        // Skipping the preflight leaves the check to the disk guardian
+        let skipped = CopyConfig {
+            preflight: false,
+            ..config.clone()
+        };
+        let err = copy_directory(&source, &dest, &skipped).unwrap_err();
+        assert!(!matches!(err, OrbitError::DestinationNotWritable { .. }));
+        assert_eq!(std::fs::read_dir(&dest).unwrap().count(), 0);
+
+        std::fs::set_permissions(&dest, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o644)).unwrap();
+    }
 }
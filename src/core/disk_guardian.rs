@@ -4,6 +4,7 @@
  * Prevents mid-transfer OOM/disk-full scenarios with:
  * - Enhanced disk space validation with safety margins
  * - Filesystem integrity checks (type, permissions, writability)
+ * - Destination preflight: probe write/delete and free inode check
  * - Optional live filesystem watching
  * - Staging area support using tempfile
  */
@@ -185,6 +186,61 @@ fn check_not_readonly(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Prefix of the probe file written by `preflight_destination`
+const PREFLIGHT_PROBE_PREFIX: &str = ".orbit-preflight";
+
+/// Confirm the destination root can take a transfer before anything is read
+///
+/// Creates the directory if needed, checks for free inodes where the
+/// filesystem reports them, then writes and deletes a small probe file.
+/// Any failure is a `DestinationNotWritable` naming the cause, so a
+/// read-only or exhausted destination fails at the start of a long copy
+/// rather than part way through.
+pub fn preflight_destination(dest_dir: &Path) -> Result<()> {
+    let not_writable = |reason: String| OrbitError::DestinationNotWritable {
+        path: dest_dir.to_path_buf(),
+        reason,
+    };
+
+    std::fs::create_dir_all(dest_dir)
+        .map_err(|e| not_writable(format!("cannot create the directory: {}", e)))?;
+
+    if free_inodes(dest_dir) == Some(0) {
+        return Err(not_writable(
+            "the filesystem has no free inodes".to_string(),
+        ));
+    }
+
+    let probe = dest_dir.join(format!("{}-{}", PREFLIGHT_PROBE_PREFIX, std::process::id()));
+    std::fs::write(&probe, b"orbit preflight")
+        .map_err(|e| not_writable(format!("cannot create a file: {}", e)))?;
+    std::fs::remove_file(&probe)
+        .map_err(|e| not_writable(format!("cannot delete a file: {}", e)))?;
+
+    Ok(())
+}
+
+/// Inodes available to unprivileged users, or `None` when the filesystem
+/// does not report a fixed inode count (btrfs, many network filesystems)
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[allow(clippy::useless_conversion)] // `fsfilcnt_t` is only 32 bits on macOS
+fn free_inodes(dir: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(dir.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `path` is NUL-terminated and `stat` is a valid out-parameter
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 || stat.f_files == 0 {
+        return None;
+    }
+    Some(u64::from(stat.f_favail))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn free_inodes(_dir: &Path) -> Option<u64> {
+    None
+}
+
 /// Create a staging area for safe transfers
 pub fn create_staging_area(base_path: &Path) -> Result<TempDir> {
     let parent = base_path.parent().unwrap_or_else(|| Path::new("."));
@@ -292,6 +348,23 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_preflight_creates_destination_and_leaves_no_probe() {
+        let temp = tempdir().unwrap();
+        let dest = temp.path().join("new/dest");
+        preflight_destination(&dest).unwrap();
+        assert!(dest.is_dir());
+        assert_eq!(std::fs::read_dir(&dest).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_preflight_rejects_destination_under_a_file() {
+        let temp = tempdir().unwrap();
+        std::fs::write(temp.path().join("file"), b"x").unwrap();
+        let err = preflight_destination(&temp.path().join("file/dest")).unwrap_err();
+        assert!(matches!(err, OrbitError::DestinationNotWritable { .. }));
+    }
+
     #[test]
     fn test_check_integrity() {
         let temp = tempdir().unwrap();
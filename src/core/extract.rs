@@ -0,0 +1,286 @@
+/*!
+ * Archive extraction mode (`--extract`).
+ *
+ * The inverse of `--archive`: a `.tar` or `.tar.zst` source is unpacked
+ * into the destination directory instead of being copied as one file.
+ * The format is detected from the file's magic bytes. Entries are streamed
+ * out one at a time, so a compressed archive is never staged on disk.
+ *
+ * Member names that are absolute or contain `..` are rejected before
+ * anything is written for them, and unpacking through a symlink that points
+ * outside the destination fails as well. Each regular file's size on disk is
+ * checked against its header once it has been written.
+ */
+
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+
+use super::CopyStats;
+use crate::config::{ArchiveFormat, CopyConfig};
+use crate::error::{OrbitError, Result};
+
+/// zstd frame magic number
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Zip local file header signature
+const ZIP_MAGIC: [u8; 4] = *b"PK\x03\x04";
+
+/// Offset of the `ustar` magic in a tar header
+const USTAR_MAGIC_OFFSET: usize = 257;
+
+/// Work out an archive's format from its first bytes
+///
+/// Pre-POSIX tar files have no magic, so a `.tar` extension is accepted
+/// when nothing else matches.
+pub fn detect_archive_format(path: &Path) -> Result<ArchiveFormat> {
+    let mut header = Vec::with_capacity(512);
+    File::open(path)?.take(512).read_to_end(&mut header)?;
+
+    if header.starts_with(&ZSTD_MAGIC) {
+        Ok(ArchiveFormat::TarZst)
+    } else if header.starts_with(&ZIP_MAGIC) {
+        Ok(ArchiveFormat::Zip)
+    } else if header.get(USTAR_MAGIC_OFFSET..USTAR_MAGIC_OFFSET + 5) == Some(b"ustar")
+        || path.extension().is_some_and(|ext| ext == "tar")
+    {
+        Ok(ArchiveFormat::Tar)
+    } else {
+        Err(OrbitError::Config(format!(
+            "--extract: {} is not a tar or tar.zst archive",
+            path.display()
+        )))
+    }
+}
+
+/// Stream the archive at `source` into the directory `dest_dir`.
+pub fn extract_archive(source: &Path, dest_dir: &Path, config: &CopyConfig) -> Result<CopyStats> {
+    let start_time = Instant::now();
+
+    let file = File::open(source)?;
+    let reader: Box<dyn Read> = match detect_archive_format(source)? {
+        ArchiveFormat::Tar => Box::new(BufReader::new(file)),
+        ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(file)?),
+        ArchiveFormat::Zip => {
+            return Err(OrbitError::Config(
+                "--extract supports tar and tar.zst archives, not zip".to_string(),
+            ))
+        }
+    };
+
+    if dest_dir.exists() && !dest_dir.is_dir() {
+        return Err(OrbitError::Config(format!(
+            "--extract needs a directory destination, but {} is a file",
+            dest_dir.display()
+        )));
+    }
+    if !config.dry_run {
+        fs::create_dir_all(dest_dir)?;
+    }
+
+    let mut archive = tar::Archive::new(reader);
+    archive.set_preserve_permissions(config.preserve_metadata);
+    archive.set_preserve_mtime(config.preserve_metadata);
+    archive.set_overwrite(true);
+
+    let mut stats = CopyStats::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let member = entry.path()?.into_owned();
+        let relative = contained_path(&member)?;
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let is_file = entry.header().entry_type().is_file();
+        let size = entry.header().size()?;
+
+        if config.dry_run {
+            println!("Would extract: {}", relative.display());
+        } else {
+            // unpack_in also refuses to write through a symlink that leaves
+            // the destination
+            if !entry.unpack_in(dest_dir)? {
+                return Err(OrbitError::InvalidPath(member));
+            }
+            if is_file {
+                let written = fs::symlink_metadata(dest_dir.join(&relative))?.len();
+                if written != size {
+                    return Err(OrbitError::Other(format!(
+                        "Extracted {} is {} bytes, but the archive header says {}",
+                        relative.display(),
+                        written,
+                        size
+                    )));
+                }
+            }
+        }
+
+        if is_file {
+            stats.files_copied += 1;
+            stats.bytes_copied += size;
+        }
+    }
+
+    stats.duration = start_time.elapsed();
+    Ok(stats)
+}
+
+/// Check that a member name stays inside the destination
+///
+/// Returns the name with any `./` components removed.
+fn contained_path(member: &Path) -> Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in member.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                tracing::warn!(
+                    "Rejecting archive entry outside the destination: {}",
+                    member.display()
+                );
+                return Err(OrbitError::InvalidPath(member.to_path_buf()));
+            }
+        }
+    }
+    Ok(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Tar bytes holding `(name, contents)` files with mode 0o640
+    fn tar_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o640);
+            header.set_mtime(1_600_000_000);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *contents).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn extract_config() -> CopyConfig {
+        CopyConfig {
+            extract: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_extract_tar_recreates_tree() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("bundle.tar");
+        fs::write(
+            &source,
+            tar_bytes(&[("top.txt", b"top"), ("nested/deep/file.bin", b"deep data")]),
+        )
+        .unwrap();
+        let dest = dir.path().join("out");
+
+        let stats = extract_archive(&source, &dest, &extract_config()).unwrap();
+
+        assert_eq!(stats.files_copied, 2);
+        assert_eq!(stats.bytes_copied, 12);
+        assert_eq!(fs::read(dest.join("top.txt")).unwrap(), b"top");
+        assert_eq!(
+            fs::read(dest.join("nested/deep/file.bin")).unwrap(),
+            b"deep data"
+        );
+
+        let mtime = fs::metadata(dest.join("top.txt"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(
+            mtime,
+            std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000)
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dest.join("top.txt"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o640);
+        }
+    }
+
+    #[test]
+    fn test_extract_tar_zst_detected_by_magic() {
+        let dir = tempdir().unwrap();
+        // No telling extension: the format comes from the zstd magic
+        let source = dir.path().join("bundle.bin");
+        let tar = tar_bytes(&[("a/b.txt", b"compressed entry")]);
+        fs::write(&source, zstd::encode_all(tar.as_slice(), 3).unwrap()).unwrap();
+        assert_eq!(
+            detect_archive_format(&source).unwrap(),
+            ArchiveFormat::TarZst
+        );
+
+        let dest = dir.path().join("out");
+        let stats = extract_archive(&source, &dest, &extract_config()).unwrap();
+
+        assert_eq!(stats.files_copied, 1);
+        assert_eq!(fs::read(dest.join("a/b.txt")).unwrap(), b"compressed entry");
+    }
+
+    #[test]
+    fn test_extract_rejects_parent_dir_entry() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("evil.tar");
+
+        // tar::Header::set_path refuses `..`, so write the name directly
+        let mut header = tar::Header::new_old();
+        let name = b"../escape";
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_cksum();
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, &b"evil"[..]).unwrap();
+        fs::write(&source, builder.into_inner().unwrap()).unwrap();
+
+        let dest = dir.path().join("out");
+        let err = extract_archive(&source, &dest, &extract_config()).unwrap_err();
+
+        assert!(matches!(err, OrbitError::InvalidPath(ref p) if p == Path::new("../escape")));
+        assert!(!dir.path().join("escape").exists());
+    }
+
+    #[test]
+    fn test_extract_dry_run_writes_nothing() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("bundle.tar");
+        fs::write(&source, tar_bytes(&[("one.txt", b"1")])).unwrap();
+        let dest = dir.path().join("out");
+
+        let config = CopyConfig {
+            dry_run: true,
+            ..extract_config()
+        };
+        let stats = extract_archive(&source, &dest, &config).unwrap();
+
+        assert_eq!(stats.files_copied, 1);
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_contained_path_strips_cur_dir() {
+        assert_eq!(
+            contained_path(Path::new("./a/./b")).unwrap(),
+            PathBuf::from("a/b")
+        );
+        assert!(contained_path(Path::new("/etc/passwd")).is_err());
+        assert!(contained_path(Path::new("a/../../b")).is_err());
+    }
+}
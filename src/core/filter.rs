@@ -282,6 +282,15 @@ impl FilterList {
         !self.should_include(path)
     }
 
+    /// Check if a walked entry is dropped by `--hidden exclude`
+    ///
+    /// Hidden entries are those whose name starts with `.` and, on Windows,
+    /// those with the hidden attribute. An include rule matching the path
+    /// keeps the entry.
+    pub fn excludes_hidden(&self, entry: &walkdir::DirEntry, relative_path: &Path) -> bool {
+        is_hidden(entry) && self.evaluate(relative_path) != FilterDecision::Include
+    }
+
     /// Load filter rules from a file
     ///
     /// File format supports:
@@ -444,6 +453,25 @@ impl FilterList {
     }
 }
 
+/// Whether a walked entry is hidden by name or, on Windows, by attribute
+fn is_hidden(entry: &walkdir::DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+        if let Ok(metadata) = entry.metadata() {
+            return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+        }
+    }
+
+    false
+}
+
 /// Helper function to parse and add a pattern to filter list
 fn add_pattern_to_filter(
     filter_list: &mut FilterList,
@@ -228,7 +228,9 @@ impl ConfigOptimizer {
         // RULE 8: The Speed Limit (macOS Bandwidth)
         // =================================================================================
         #[cfg(target_os = "macos")]
-        if config.use_zero_copy && config.max_bandwidth > 0 {
+        if config.use_zero_copy
+            && (config.max_bandwidth > 0 || !config.bandwidth_schedule.is_empty())
+        {
             notices.push(Notice {
                 level: NoticeLevel::Warning,
                 code: "ZEROCOPY_DISABLED_BANDWIDTH".to_string(),
@@ -3,15 +3,19 @@
  */
 
 // Submodules
+pub mod archive;
 pub mod bandwidth;
 pub mod batch;
 pub mod buffered;
 pub mod checksum;
 pub mod concurrency;
+pub mod dedup;
 pub mod delta;
+pub mod dir_journal;
 pub mod directory;
 pub mod disk_guardian;
 pub mod dry_run;
+pub mod extract;
 pub mod file_metadata;
 pub mod filter;
 pub mod guidance;
@@ -21,12 +25,16 @@ pub mod link_dest;
 pub mod metadata_ops;
 pub mod probe;
 pub mod progress;
+pub mod range;
 pub mod resume;
 pub mod retry;
 pub mod sparse;
+pub mod tail;
 pub mod transfer;
 pub mod transform;
+pub mod trash;
 pub mod validation;
+pub mod verify_sample;
 pub mod zero_copy;
 
 use std::path::{Path, PathBuf};
@@ -181,6 +189,13 @@ pub fn copy_file_impl_with_stats(
     publisher: Option<&progress::ProgressPublisher>,
     stats: Option<&OperationStats>,
 ) -> Result<CopyStats> {
+    if config.extract {
+        return extract::extract_archive(source_path, dest_path, config);
+    }
+    if let Some(range) = config.byte_range {
+        return range::copy_range(source_path, dest_path, range, config);
+    }
+
     // Create default stats tracker if none provided
     let default_stats = OperationStats::new();
     let (stats_ref, use_default) = match stats {
@@ -211,6 +226,19 @@ fn copy_file_impl_inner(
     // Generate a unique job ID for audit correlation
     let job_id = generate_job_id();
 
+    // Root of this transfer's span tree. The phases below (validate,
+    // disk_check, transfer, verify, metadata) are its children, and the
+    // job_id field carries into the audit bridge and OTel exports. Span
+    // fields are only evaluated when a subscriber is interested.
+    let _copy_span = tracing::info_span!(
+        "copy_file",
+        job_id = job_id.as_str(),
+        source = %source_path.display(),
+        dest = %dest_path.display(),
+    )
+    .entered();
+    let validate_span = tracing::info_span!("validate").entered();
+
     // Initialize audit logger if audit log path is configured
     let mut audit_logger = if config.audit_log_path.is_some() || config.verbose {
         match AuditLogger::new(config.audit_log_path.as_deref(), config.audit_format) {
@@ -254,8 +282,10 @@ fn copy_file_impl_inner(
         }
     }
 
-    // Check if we should copy based on mode
-    if !should_copy_file(source_path, dest_path, config.copy_mode)? {
+    // Check if we should copy based on mode and overwrite policy
+    if !should_copy_file(source_path, dest_path, config)?
+        || !validation::overwrite_allowed(source_path, dest_path, config)?
+    {
         let stats = CopyStats {
             bytes_copied: 0,
             duration: start_time.elapsed(),
@@ -285,6 +315,7 @@ fn copy_file_impl_inner(
 
         return Ok(stats);
     }
+    drop(validate_span);
 
     // Dry run mode
     if config.dry_run {
@@ -366,6 +397,7 @@ fn copy_file_impl_inner(
         source_size
     };
 
+    let disk_check_span = tracing::info_span!("disk_check", required_space).entered();
     if let Err(e) = validation::validate_disk_space(dest_path, required_space) {
         if let Some(ref mut logger) = audit_logger {
             let _ = logger.emit_failure(
@@ -381,6 +413,7 @@ fn copy_file_impl_inner(
         }
         return Err(e);
     }
+    drop(disk_check_span);
 
     // Use provided publisher or create a no-op one
     let noop_publisher = progress::ProgressPublisher::noop();
@@ -389,6 +422,7 @@ fn copy_file_impl_inner(
     // Perform copy with retry logic, metadata preservation, and statistics tracking
     let mut result =
         retry::with_retry_and_metadata_stats(source_path, dest_path, config, Some(stats), || {
+            let _transfer = tracing::info_span!("transfer", bytes = source_size).entered();
             transfer::perform_copy(source_path, dest_path, source_size, config, pub_ref)
         });
 
@@ -589,4 +623,66 @@ mod tests {
         // Small files should skip zero-copy
         assert!(!use_zc);
     }
+
+    #[test]
+    fn test_copy_file_records_phase_spans() {
+        use orbit_observability::{
+            load_events_from_file, AuditBridgeLayer, AuditSigner, EventPayload, UnifiedLogger,
+        };
+        use tracing_subscriber::prelude::*;
+
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.txt");
+        let dest = dir.path().join("dest.txt");
+        std::fs::write(&source, b"traced data").unwrap();
+
+        let audit_path = dir.path().join("spans.jsonl");
+        let logger =
+            UnifiedLogger::new(Some(&audit_path), AuditSigner::from_bytes(b"span-test")).unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(AuditBridgeLayer::new(logger).with_span_events(true));
+
+        let config = CopyConfig {
+            verify_checksum: true,
+            preserve_metadata: true,
+            ..Default::default()
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            copy_file(&source, &dest, &config).unwrap();
+        });
+
+        let spans: Vec<_> = load_events_from_file(&audit_path)
+            .unwrap()
+            .into_iter()
+            .filter_map(|event| match event.payload {
+                EventPayload::SpanStart { name, .. } => Some((name, event.job_id, event.trace_id)),
+                _ => None,
+            })
+            .collect();
+        // Retry spans sit between disk_check and transfer; only the phases
+        // are checked here
+        let phases = [
+            "copy_file",
+            "validate",
+            "disk_check",
+            "transfer",
+            "verify",
+            "metadata",
+        ];
+        let names: Vec<&str> = spans
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .filter(|name| phases.contains(name))
+            .collect();
+        assert_eq!(names, phases);
+
+        // Every phase carries the copy's job ID and belongs to one trace
+        let (_, job_id, trace_id) = &spans[0];
+        let job_id = job_id.as_deref().unwrap();
+        assert!(job_id.starts_with("orbit-"), "{}", job_id);
+        for (name, job, trace) in &spans {
+            assert_eq!(job.as_deref(), Some(job_id), "span {}", name);
+            assert_eq!(trace, trace_id, "span {}", name);
+        }
+    }
 }
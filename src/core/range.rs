@@ -0,0 +1,306 @@
+/*!
+ * Byte-range transfers for single files (`--offset` / `--length`).
+ *
+ * Copies only part of a file: the source is read from `offset` and the
+ * bytes are written at the same offset in the destination, leaving the rest
+ * of the destination untouched. This covers manually restarting a large
+ * copy after an external partial transfer, and splitting one file across
+ * several transfers.
+ *
+ * With checksum verification on, the copied range is re-read and compared
+ * with the source range. A range that reaches the end of the source also
+ * finishes the file, so the destination is truncated to the source size
+ * and the whole file is compared, which catches a range that did not match
+ * what was already at the destination. Split transfers should therefore
+ * copy the final range last.
+ */
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::checksum::{calculate_checksum, StreamingHasher};
+use super::CopyStats;
+use crate::config::CopyConfig;
+use crate::error::{OrbitError, Result};
+
+/// The part of a file to transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    /// First byte to copy
+    pub offset: u64,
+
+    /// Number of bytes to copy (None = to the end of the source)
+    #[serde(default)]
+    pub length: Option<u64>,
+}
+
+impl ByteRange {
+    /// Resolve against the source size, rejecting ranges past its end
+    pub fn resolve(&self, source_size: u64) -> Result<Range<u64>> {
+        let end = match self.length {
+            Some(length) => self.offset.checked_add(length),
+            None => Some(source_size),
+        };
+        match end {
+            Some(end) if self.offset <= source_size && end <= source_size => Ok(self.offset..end),
+            _ => Err(OrbitError::Config(format!(
+                "Byte range {}+{} is outside the {}-byte source",
+                self.offset,
+                self.length
+                    .map_or_else(|| "end".to_string(), |l| l.to_string()),
+                source_size
+            ))),
+        }
+    }
+}
+
+/// Copy `range` of `source_path` to the same offsets in `dest_path`
+pub fn copy_range(
+    source_path: &Path,
+    dest_path: &Path,
+    range: ByteRange,
+    config: &CopyConfig,
+) -> Result<CopyStats> {
+    let start_time = Instant::now();
+
+    let source_metadata = match std::fs::metadata(source_path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(OrbitError::SourceNotFound(source_path.to_path_buf()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if !source_metadata.is_file() {
+        return Err(OrbitError::Config(
+            "--offset and --length apply to single-file copies".to_string(),
+        ));
+    }
+    let source_size = source_metadata.len();
+    let span = range.resolve(source_size)?;
+    let length = span.end - span.start;
+    let completes_file = span.end == source_size;
+
+    let mut stats = CopyStats {
+        bytes_copied: length,
+        files_copied: 1,
+        bytes_skipped: source_size - length,
+        ..CopyStats::new()
+    };
+
+    if config.dry_run {
+        println!(
+            "Would copy bytes {}..{}: {:?} -> {:?}",
+            span.start, span.end, source_path, dest_path
+        );
+        stats.duration = start_time.elapsed();
+        return Ok(stats);
+    }
+
+    if let Some(parent) = dest_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut source = BufReader::new(File::open(source_path)?);
+    source.seek(SeekFrom::Start(span.start))?;
+    let mut dest = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dest_path)?;
+    dest.seek(SeekFrom::Start(span.start))?;
+
+    let mut hasher = config.verify_checksum.then(StreamingHasher::new);
+    let mut buffer = vec![0u8; config.chunk_size.max(4096)];
+    let mut remaining = length;
+    while remaining > 0 {
+        let to_read = remaining.min(buffer.len() as u64) as usize;
+        source.read_exact(&mut buffer[..to_read])?;
+        if let Some(ref mut h) = hasher {
+            h.update(&buffer[..to_read]);
+        }
+        dest.write_all(&buffer[..to_read])?;
+        remaining -= to_read as u64;
+    }
+
+    if completes_file {
+        dest.set_len(source_size)?;
+    }
+    dest.sync_all()?;
+
+    if let Some(h) = hasher {
+        let expected = format!("{:x}", h.finalize());
+        let actual = hash_range(&mut dest, &span, buffer.len())?;
+        if actual != expected {
+            return Err(OrbitError::ChecksumMismatch { expected, actual });
+        }
+        stats.checksum = Some(expected);
+
+        if completes_file {
+            let expected = calculate_checksum(source_path)?;
+            let actual = calculate_checksum(dest_path)?;
+            if actual != expected {
+                return Err(OrbitError::ChecksumMismatch { expected, actual });
+            }
+            stats.checksum = Some(expected);
+        }
+    }
+
+    if completes_file && config.preserve_metadata {
+        if let Err(e) = super::metadata_ops::preserve_metadata(source_path, dest_path) {
+            tracing::warn!("Failed to preserve metadata: {}", e);
+        }
+    }
+
+    stats.duration = start_time.elapsed();
+    Ok(stats)
+}
+
+/// SHA-256 of `span` in `file`, as lowercase hex
+fn hash_range(file: &mut File, span: &Range<u64>, buffer_size: usize) -> Result<String> {
+    file.seek(SeekFrom::Start(span.start))?;
+    let mut reader = BufReader::new(file).take(span.end - span.start);
+    let mut hasher = StreamingHasher::new();
+    let mut buffer = vec![0u8; buffer_size];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    fn range_config() -> CopyConfig {
+        CopyConfig {
+            show_progress: false,
+            chunk_size: 1024,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mid_file_range_writes_only_that_range() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("dest.bin");
+        let data = pattern(64 * 1024);
+        std::fs::write(&source, &data).unwrap();
+        std::fs::write(&dest, vec![0xEEu8; data.len()]).unwrap();
+
+        let range = ByteRange {
+            offset: 10_000,
+            length: Some(20_000),
+        };
+        let stats = copy_range(&source, &dest, range, &range_config()).unwrap();
+
+        assert_eq!(stats.bytes_copied, 20_000);
+        assert!(stats.checksum.is_some());
+        let written = std::fs::read(&dest).unwrap();
+        assert_eq!(written.len(), data.len());
+        assert_eq!(&written[10_000..30_000], &data[10_000..30_000]);
+        assert!(written[..10_000].iter().all(|&b| b == 0xEE));
+        assert!(written[30_000..].iter().all(|&b| b == 0xEE));
+    }
+
+    #[test]
+    fn test_split_transfer_reassembles_file() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("dest.bin");
+        let data = pattern(50_000);
+        std::fs::write(&source, &data).unwrap();
+
+        // Middle first: the destination is created with a gap before it
+        for (offset, length) in [(20_000, Some(10_000)), (0, Some(20_000)), (30_000, None)] {
+            copy_range(
+                &source,
+                &dest,
+                ByteRange { offset, length },
+                &range_config(),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+    }
+
+    #[test]
+    fn test_resume_from_wrong_offset_fails_full_verify() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("dest.bin");
+        let data = pattern(40_000);
+        std::fs::write(&source, &data).unwrap();
+        // An external copy got 15,000 bytes across, but we restart at 20,000
+        std::fs::write(&dest, &data[..15_000]).unwrap();
+
+        let range = ByteRange {
+            offset: 20_000,
+            length: None,
+        };
+        let err = copy_range(&source, &dest, range, &range_config()).unwrap_err();
+        assert!(matches!(err, OrbitError::ChecksumMismatch { .. }));
+
+        // The right offset completes the file and verifies
+        std::fs::write(&dest, &data[..15_000]).unwrap();
+        let range = ByteRange {
+            offset: 15_000,
+            length: None,
+        };
+        let stats = copy_range(&source, &dest, range, &range_config()).unwrap();
+        assert_eq!(stats.bytes_skipped, 15_000);
+        assert_eq!(
+            stats.checksum.unwrap(),
+            calculate_checksum(&source).unwrap()
+        );
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+    }
+
+    #[test]
+    fn test_range_past_end_is_rejected() {
+        let range = ByteRange {
+            offset: 90,
+            length: Some(20),
+        };
+        assert!(matches!(range.resolve(100), Err(OrbitError::Config(_))));
+        assert_eq!(
+            ByteRange {
+                offset: 40,
+                length: None
+            }
+            .resolve(100)
+            .unwrap(),
+            40..100
+        );
+        assert!(ByteRange {
+            offset: 101,
+            length: None
+        }
+        .resolve(100)
+        .is_err());
+        assert!(ByteRange {
+            offset: 1,
+            length: Some(u64::MAX)
+        }
+        .resolve(100)
+        .is_err());
+    }
+}
@@ -2,13 +2,25 @@
  * Resume functionality for interrupted transfers with chunk-level verification
  */
 
+use crate::config::CopyConfig;
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+thread_local! {
+    /// Resume files written by this thread, so tests can count checkpoints
+    static RESUME_WRITES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[cfg(test)]
+pub(crate) fn resume_writes_on_this_thread() -> usize {
+    RESUME_WRITES.with(|w| w.get())
+}
 
 /// Resume information for interrupted transfers with chunk-level tracking
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -36,6 +48,47 @@ pub struct ResumeInfo {
     pub file_size: Option<u64>,
 }
 
+/// Decides when a copy loop writes its next resume checkpoint
+///
+/// A checkpoint is due once either `checkpoint_interval_secs` have passed or
+/// `checkpoint_interval_bytes` have been copied since the last one, whichever
+/// comes first. A value of 0 disables that trigger.
+#[derive(Debug, Clone)]
+pub struct CheckpointSchedule {
+    interval: Option<Duration>,
+    bytes: Option<u64>,
+    last_at: Instant,
+    last_bytes: u64,
+}
+
+impl CheckpointSchedule {
+    /// Schedule for a transfer starting at `start_offset`
+    pub fn from_config(config: &CopyConfig, start_offset: u64) -> Self {
+        Self {
+            interval: (config.checkpoint_interval_secs > 0)
+                .then(|| Duration::from_secs(config.checkpoint_interval_secs)),
+            bytes: (config.checkpoint_interval_bytes > 0)
+                .then_some(config.checkpoint_interval_bytes),
+            last_at: Instant::now(),
+            last_bytes: start_offset,
+        }
+    }
+
+    /// Whether a checkpoint should be written at `bytes_done`
+    pub fn is_due(&self, bytes_done: u64) -> bool {
+        self.interval.is_some_and(|i| self.last_at.elapsed() >= i)
+            || self
+                .bytes
+                .is_some_and(|b| bytes_done.saturating_sub(self.last_bytes) >= b)
+    }
+
+    /// Record that a checkpoint was written at `bytes_done`
+    pub fn mark(&mut self, bytes_done: u64) {
+        self.last_at = Instant::now();
+        self.last_bytes = bytes_done;
+    }
+}
+
 /// Decision on how to handle an interrupted transfer
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResumeDecision {
@@ -147,6 +200,9 @@ pub fn save_resume_info_full(
         let _ = std::fs::remove_file(&temp_path);
         std::io::Error::other(format!("Failed to atomically rename resume file: {}", e))
     })?;
+
+    #[cfg(test)]
+    RESUME_WRITES.with(|w| w.set(w.get() + 1));
     Ok(())
 }
 
@@ -552,4 +608,31 @@ mod tests {
         assert_eq!(loaded.verified_chunks.len(), 2);
         assert_eq!(loaded.verified_windows.len(), 1);
     }
+
+    #[test]
+    fn test_checkpoint_schedule_byte_interval() {
+        let config = CopyConfig {
+            checkpoint_interval_secs: 0,
+            checkpoint_interval_bytes: 1000,
+            ..Default::default()
+        };
+        let mut schedule = CheckpointSchedule::from_config(&config, 500);
+
+        assert!(!schedule.is_due(1499));
+        assert!(schedule.is_due(1500));
+        schedule.mark(1500);
+        assert!(!schedule.is_due(2000));
+        assert!(schedule.is_due(2600));
+    }
+
+    #[test]
+    fn test_checkpoint_schedule_disabled_triggers() {
+        let config = CopyConfig {
+            checkpoint_interval_secs: 0,
+            checkpoint_interval_bytes: 0,
+            ..Default::default()
+        };
+        let schedule = CheckpointSchedule::from_config(&config, 0);
+        assert!(!schedule.is_due(u64::MAX));
+    }
 }
@@ -1,10 +1,18 @@
 /*!
  * Retry logic with exponential backoff
+ *
+ * Each operation retries up to `retry_attempts` times, but during a broad
+ * outage many operations retrying at once can keep a degraded backend down.
+ * A [`RetryBudget`] shared across a session caps the combined retry rate:
+ * every retry spends a credit, credits refill slowly, and once the budget is
+ * empty failing operations give up immediately instead of retrying.
  */
 
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tracing::{debug, error as log_error, info, instrument, warn};
 
@@ -13,6 +21,94 @@ use crate::config::{CopyConfig, ErrorMode};
 use crate::error::{OrbitError, Result};
 use crate::instrumentation::OperationStats;
 
+/// Token bucket of retry credits shared by every operation in a session
+#[derive(Debug)]
+pub struct RetryBudget {
+    capacity: f64,
+    refill_per_sec: f64,
+    bucket: Mutex<Bucket>,
+    granted: AtomicU64,
+    denied: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    credits: f64,
+    refilled_at: Instant,
+}
+
+/// Snapshot of a [`RetryBudget`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryBudgetMetrics {
+    /// Maximum credits the bucket holds
+    pub capacity: u32,
+    /// Credits available right now
+    pub available: f64,
+    /// Retries allowed so far
+    pub granted: u64,
+    /// Retries refused because the budget was empty
+    pub denied: u64,
+}
+
+impl RetryBudget {
+    /// A full budget of `capacity` retries, refilling at `refill_per_sec`
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_per_sec: refill_per_sec.max(0.0),
+            bucket: Mutex::new(Bucket {
+                credits: capacity as f64,
+                refilled_at: Instant::now(),
+            }),
+            granted: AtomicU64::new(0),
+            denied: AtomicU64::new(0),
+        }
+    }
+
+    /// Budget for `--retry-budget`: `capacity` credits that fully refill
+    /// over a minute
+    pub fn per_minute(capacity: u32) -> Self {
+        Self::new(capacity, capacity as f64 / 60.0)
+    }
+
+    /// Spend one credit for a retry; `false` means fail fast instead
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        let mut bucket = self.bucket.lock().unwrap();
+        self.refill(&mut bucket, now);
+        if bucket.credits >= 1.0 {
+            bucket.credits -= 1.0;
+            self.granted.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            self.denied.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.credits =
+            (bucket.credits + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+        bucket.refilled_at = now;
+    }
+
+    /// Current credit level and counters
+    pub fn metrics(&self) -> RetryBudgetMetrics {
+        let mut bucket = self.bucket.lock().unwrap();
+        self.refill(&mut bucket, Instant::now());
+        RetryBudgetMetrics {
+            capacity: self.capacity as u32,
+            available: bucket.credits,
+            granted: self.granted.load(Ordering::Relaxed),
+            denied: self.denied.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// Execute a copy operation with retry logic and exponential backoff
 pub fn with_retry<F>(config: &CopyConfig, operation: F) -> Result<CopyStats>
 where
@@ -33,6 +129,7 @@ where
 {
     let mut attempt = 0;
     let mut last_error: Option<OrbitError> = None;
+    let mut budget_exhausted = false;
 
     while attempt <= config.retry_attempts {
         let attempt_number = attempt + 1;
@@ -44,6 +141,13 @@ where
         let _attempt_guard = attempt_span.enter();
 
         if attempt > 0 {
+            if let Some(budget) = &config.retry_budget {
+                if !budget.try_acquire() {
+                    budget_exhausted = true;
+                    break;
+                }
+            }
+
             let delay = calculate_backoff_delay(config, attempt);
 
             if let Some(ref err) = last_error {
@@ -149,14 +253,25 @@ where
         }
     }
 
-    // All retries exhausted
-    log_error!(
-        "All {} retry attempts exhausted. Last error: {}",
-        config.retry_attempts,
-        last_error
-            .as_ref()
-            .map_or("none".to_string(), |e| e.to_string())
-    );
+    // All retries exhausted, or the retry budget ran out first
+    let last_error_text = last_error
+        .as_ref()
+        .map_or("none".to_string(), |e| e.to_string());
+    if budget_exhausted {
+        log_error!(
+            "Retry budget exhausted after {} attempt(s) ({} of {} retries used). Last error: {}",
+            attempt,
+            attempt - 1,
+            config.retry_attempts,
+            last_error_text
+        );
+    } else {
+        log_error!(
+            "All {} retry attempts exhausted. Last error: {}",
+            config.retry_attempts,
+            last_error_text
+        );
+    }
 
     // Record the actual error that caused the failure, not the RetriesExhausted wrapper
     if let Some(stats_tracker) = stats {
@@ -229,6 +344,7 @@ where
 
     // Preserve metadata if requested
     if config.preserve_metadata {
+        let _metadata = tracing::info_span!("metadata").entered();
         if let Err(e) = super::metadata_ops::preserve_metadata(source_path, dest_path) {
             if config.strict_metadata {
                 log_error!("Failed to preserve metadata (strict mode): {}", e);
@@ -250,6 +366,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
 
     #[test]
     fn test_calculate_backoff_delay() {
@@ -303,6 +420,107 @@ mod tests {
         assert_eq!(attempts, 1);
     }
 
+    #[test]
+    fn test_retry_budget_shared_across_operations() {
+        let budget = Arc::new(RetryBudget::new(4, 0.0));
+        let config = CopyConfig {
+            error_mode: ErrorMode::Partial,
+            retry_attempts: 3,
+            retry_delay_secs: 0,
+            exponential_backoff: false,
+            retry_budget: Some(budget.clone()),
+            ..Default::default()
+        };
+
+        // Each operation fails with a transient error on every attempt
+        let mut attempts = Vec::new();
+        for _ in 0..4 {
+            let mut count = 0;
+            let result = with_retry(&config, || {
+                count += 1;
+                Err(OrbitError::Protocol("backend unavailable".to_string()))
+            });
+            assert!(matches!(result, Err(OrbitError::Protocol(_))));
+            attempts.push(count);
+        }
+
+        // 3 retries, then the last credit, then none left: fail fast
+        assert_eq!(attempts, [4, 2, 1, 1]);
+        let metrics = budget.metrics();
+        assert_eq!(metrics.granted, 4);
+        assert_eq!(metrics.denied, 3);
+        assert!(metrics.available < 1.0);
+    }
+
+    /// Log output written to a shared buffer
+    #[derive(Clone, Default)]
+    struct CapturedLog(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLog {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_budget_denial_logs_attempts_made() {
+        let config = CopyConfig {
+            error_mode: ErrorMode::Partial,
+            retry_attempts: 5,
+            retry_delay_secs: 0,
+            exponential_backoff: false,
+            retry_budget: Some(Arc::new(RetryBudget::new(1, 0.0))),
+            ..Default::default()
+        };
+        let log = CapturedLog::default();
+        let writer = log.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let result = with_retry(&config, || {
+                Err(OrbitError::Protocol("backend unavailable".to_string()))
+            });
+            assert!(result.is_err());
+        });
+
+        let output = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        assert!(
+            output.contains("Retry budget exhausted after 2 attempt(s) (1 of 5 retries used)"),
+            "{}",
+            output
+        );
+        assert!(
+            !output.contains("All 5 retry attempts exhausted"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_retry_budget_refills() {
+        let budget = RetryBudget::new(2, 1.0);
+        let start = Instant::now();
+        assert!(budget.try_acquire_at(start));
+        assert!(budget.try_acquire_at(start));
+        assert!(!budget.try_acquire_at(start));
+
+        // One credit per second, capped at capacity
+        assert!(budget.try_acquire_at(start + Duration::from_millis(1100)));
+        assert!(!budget.try_acquire_at(start + Duration::from_millis(1200)));
+        let later = start + Duration::from_secs(60);
+        assert!(budget.try_acquire_at(later));
+        assert!(budget.try_acquire_at(later));
+        assert!(!budget.try_acquire_at(later));
+    }
+
     #[test]
     fn test_error_mode_skip() {
         let config = CopyConfig {
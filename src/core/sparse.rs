@@ -9,10 +9,16 @@
  * Unlike rsync, which cannot combine --sparse with --inplace, Orbit's
  * CDC chunks are independently addressable, so sparse + in-place works
  * naturally.
+ *
+ * On Linux the source's holes are found with SEEK_DATA/SEEK_HOLE, so they
+ * are never read, and zero regions that overwrite old destination data are
+ * released with fallocate(FALLOC_FL_PUNCH_HOLE). Elsewhere, or on
+ * filesystems without hole support, zeros are detected by content alone.
  */
 
 use std::fs::File;
 use std::io::{self, Seek, SeekFrom, Write};
+use std::ops::Range;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
@@ -120,6 +126,160 @@ where
     Ok(stats)
 }
 
+/// Holes in a source file, found with SEEK_DATA/SEEK_HOLE
+///
+/// Lets a copy loop skip reading regions the filesystem knows are zero.
+/// Queries must move forward through the file.
+#[derive(Debug, Clone)]
+pub struct SourceHoles {
+    /// Data extents in ascending order
+    extents: Vec<Range<u64>>,
+    /// First extent that may still end after the last query
+    next: usize,
+}
+
+impl SourceHoles {
+    /// Map the holes of the file at `path`
+    ///
+    /// Returns None when the file has no holes or the platform or
+    /// filesystem cannot report them.
+    pub fn probe(path: &Path, size: u64) -> Option<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                tracing::debug!("Cannot open {} to map holes: {}", path.display(), e);
+                return None;
+            }
+        };
+        match data_extents(&file, size) {
+            Ok(Some(extents)) => {
+                let data_bytes: u64 = extents.iter().map(|e| e.end - e.start).sum();
+                (data_bytes < size).then(|| Self::from_extents(extents))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::debug!("Hole detection failed for {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Build from known data extents (ascending, non-overlapping)
+    pub fn from_extents(extents: Vec<Range<u64>>) -> Self {
+        Self { extents, next: 0 }
+    }
+
+    /// Whether `offset..offset + len` lies entirely inside a hole
+    pub fn is_hole(&mut self, offset: u64, len: u64) -> bool {
+        while self
+            .extents
+            .get(self.next)
+            .is_some_and(|extent| extent.end <= offset)
+        {
+            self.next += 1;
+        }
+        match self.extents.get(self.next) {
+            Some(extent) => extent.start >= offset + len,
+            None => true,
+        }
+    }
+}
+
+/// Data extents of the first `size` bytes of `file`
+///
+/// Moves the file's offset. Returns None if SEEK_DATA/SEEK_HOLE are not
+/// supported.
+#[cfg(target_os = "linux")]
+pub fn data_extents(file: &File, size: u64) -> io::Result<Option<Vec<Range<u64>>>> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = file.as_raw_fd();
+    let mut extents = Vec::new();
+    let mut offset = 0u64;
+    while offset < size {
+        let data = unsafe { libc::lseek(fd, offset as libc::off_t, libc::SEEK_DATA) };
+        if data == -1 {
+            let err = io::Error::last_os_error();
+            return match err.raw_os_error() {
+                // No data after `offset`: the rest of the file is a hole
+                Some(libc::ENXIO) => Ok(Some(extents)),
+                Some(libc::EINVAL) | Some(libc::EOPNOTSUPP) | Some(libc::ENOSYS) => Ok(None),
+                _ => Err(err),
+            };
+        }
+        let hole = unsafe { libc::lseek(fd, data, libc::SEEK_HOLE) };
+        if hole == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let (start, end) = (data as u64, (hole as u64).min(size));
+        if start >= size {
+            break;
+        }
+        extents.push(start..end);
+        offset = end;
+    }
+    Ok(Some(extents))
+}
+
+/// Data extents of the first `size` bytes of `file`
+///
+/// Hole detection is only implemented on Linux; this always returns None.
+#[cfg(not(target_os = "linux"))]
+pub fn data_extents(_file: &File, _size: u64) -> io::Result<Option<Vec<Range<u64>>>> {
+    Ok(None)
+}
+
+/// Leave `len` zero bytes at `offset` in `file` without writing them
+///
+/// Past `existing_len` (the destination's length before the copy started)
+/// seeking is enough to create a hole. Below it, a resumed copy may find old
+/// data, so the range is punched out, or written as zeros where punching
+/// is unsupported. The file's offset ends at `offset + len`.
+pub fn skip_zero_region(
+    file: &mut File,
+    offset: u64,
+    len: usize,
+    existing_len: u64,
+) -> io::Result<()> {
+    if offset < existing_len {
+        if let Err(e) = punch_hole(file, offset, len as u64) {
+            tracing::debug!("Hole punching unsupported ({}), writing zeros", e);
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&vec![0u8; len])?;
+            return Ok(());
+        }
+    }
+    file.seek(SeekFrom::Start(offset + len as u64))?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn punch_hole(file: &File, offset: u64, len: u64) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    let ret = unsafe {
+        libc::fallocate(
+            file.as_raw_fd(),
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+            offset as libc::off_t,
+            len as libc::off_t,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn punch_hole(_file: &File, _offset: u64, _len: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "hole punching is only implemented on Linux",
+    ))
+}
+
 /// A chunk of data ready for sparse-aware writing.
 pub struct SparseChunk {
     pub offset: u64,
@@ -374,6 +534,65 @@ mod tests {
         assert!(data[1024..].iter().all(|&b| b == 0));
     }
 
+    #[test]
+    fn test_source_holes_is_hole() {
+        let mut holes = SourceHoles::from_extents(vec![4096..8192, 16384..20480]);
+
+        assert!(holes.is_hole(0, 4096));
+        assert!(!holes.is_hole(0, 4097));
+        assert!(!holes.is_hole(4096, 4096));
+        assert!(holes.is_hole(8192, 8192));
+        assert!(!holes.is_hole(12288, 8192));
+        assert!(holes.is_hole(20480, 1 << 20));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_data_extents_finds_holes() {
+        use std::os::unix::fs::FileExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("holey.bin");
+        let size = 8 * 1024 * 1024;
+        let file = File::create(&path).unwrap();
+        file.set_len(size).unwrap();
+        file.write_all_at(&[0xAB; 4096], 4 * 1024 * 1024).unwrap();
+        file.sync_all().unwrap();
+
+        let Some(extents) = data_extents(&File::open(&path).unwrap(), size).unwrap() else {
+            eprintln!("SEEK_DATA unsupported here, skipping");
+            return;
+        };
+        // Filesystems may report holes as data, but never data as a hole
+        assert!(extents
+            .iter()
+            .any(|e| e.start <= 4 * 1024 * 1024 && e.end >= 4 * 1024 * 1024 + 4096));
+        assert!(extents.windows(2).all(|w| w[0].end <= w[1].start));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_skip_zero_region_clears_old_data() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("resumed.bin");
+        std::fs::write(&path, vec![0xFFu8; 64 * 1024]).unwrap();
+
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        skip_zero_region(&mut file, 16 * 1024, 32 * 1024, 64 * 1024).unwrap();
+        assert_eq!(file.stream_position().unwrap(), 48 * 1024);
+        drop(file);
+
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(data.len(), 64 * 1024);
+        assert!(data[..16 * 1024].iter().all(|&b| b == 0xFF));
+        assert!(data[16 * 1024..48 * 1024].iter().all(|&b| b == 0));
+        assert!(data[48 * 1024..].iter().all(|&b| b == 0xFF));
+    }
+
     #[test]
     fn test_sparse_chunk_from_cdc_chunk() {
         let cdc_chunk = orbit_core_cdc::Chunk {
@@ -0,0 +1,352 @@
+/*!
+ * Append-only (tailing) transfer for logs and write-ahead logs.
+ *
+ * Files classified as `SyncStrategy::AppendOnly` only ever grow, so each pass
+ * only needs to move the bytes appended since the previous one. The offset
+ * reached by the last pass is kept in the destination's resume state; the
+ * next pass reads the source from that offset up to its current size and
+ * appends to the destination.
+ *
+ * Tailing falls back to a full re-sync from offset 0 when the recorded
+ * offset can no longer be trusted:
+ * - the source shrank below the offset (truncation or rotation),
+ * - the destination size no longer matches the recorded offset, or
+ * - the bytes just before the offset differ between source and destination
+ *   (the file was replaced by a new one that is already larger).
+ */
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use orbit_core_semantic::{SemanticRegistry, SyncStrategy};
+use serde::{Deserialize, Serialize};
+
+use super::progress::ProgressPublisher;
+use super::resume::{load_resume_info, save_resume_info_full, ResumeInfo};
+use super::CopyStats;
+use crate::config::CopyConfig;
+use crate::error::Result;
+
+/// Bytes before the recorded offset compared to detect a replaced file
+const ROTATION_PROBE_LEN: u64 = 4096;
+
+/// Bytes of the source read for semantic classification in `Auto` mode
+const HEAD_LEN: usize = 4096;
+
+/// Append-only transfer mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TailMode {
+    /// Never tail; every file is copied in full
+    #[default]
+    Never,
+
+    /// Tail files the semantic registry classifies as append-only (WALs, logs)
+    Auto,
+
+    /// Tail every file
+    Always,
+}
+
+/// Where the next tailing pass starts and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TailDecision {
+    /// Append from this offset
+    Append { from_offset: u64 },
+
+    /// Rewrite the destination from offset 0
+    Resync { reason: String },
+}
+
+/// Whether `source_path` should use the append-only transfer
+pub fn should_tail(source_path: &Path, config: &CopyConfig) -> Result<bool> {
+    match config.tail_mode {
+        TailMode::Never => Ok(false),
+        TailMode::Always => Ok(true),
+        TailMode::Auto => {
+            let mut head = Vec::with_capacity(HEAD_LEN);
+            File::open(source_path)?
+                .take(HEAD_LEN as u64)
+                .read_to_end(&mut head)?;
+            let intent = SemanticRegistry::default().determine_intent(source_path, &head);
+            Ok(intent.strategy == SyncStrategy::AppendOnly)
+        }
+    }
+}
+
+/// Decide where to resume appending from the saved state
+pub fn decide_tail_offset(
+    source_path: &Path,
+    dest_path: &Path,
+    source_size: u64,
+    state: &ResumeInfo,
+) -> Result<TailDecision> {
+    let dest_size = match std::fs::metadata(dest_path) {
+        Ok(meta) => meta.len(),
+        Err(_) => {
+            return Ok(TailDecision::Resync {
+                reason: "destination does not exist".to_string(),
+            })
+        }
+    };
+
+    let offset = state.bytes_copied;
+    if offset == 0 {
+        return Ok(TailDecision::Resync {
+            reason: "no previous tail offset".to_string(),
+        });
+    }
+    if dest_size != offset {
+        return Ok(TailDecision::Resync {
+            reason: format!(
+                "destination size {} does not match tail offset {}",
+                dest_size, offset
+            ),
+        });
+    }
+    if source_size < offset {
+        return Ok(TailDecision::Resync {
+            reason: format!(
+                "source shrank from {} to {} bytes (truncated or rotated)",
+                offset, source_size
+            ),
+        });
+    }
+
+    let probe_len = offset.min(ROTATION_PROBE_LEN);
+    let probe_start = offset - probe_len;
+    if read_range(source_path, probe_start, probe_len)?
+        != read_range(dest_path, probe_start, probe_len)?
+    {
+        return Ok(TailDecision::Resync {
+            reason: "source content before tail offset changed (rotated)".to_string(),
+        });
+    }
+
+    Ok(TailDecision::Append {
+        from_offset: offset,
+    })
+}
+
+/// Transfer only the bytes appended to `source_path` since the last pass.
+///
+/// Reads up to the source size observed at the start of the pass, so data
+/// appended while the pass runs is picked up by the next one.
+pub fn copy_append_only(
+    source_path: &Path,
+    dest_path: &Path,
+    source_size: u64,
+    config: &CopyConfig,
+    publisher: &ProgressPublisher,
+) -> Result<CopyStats> {
+    let start_time = Instant::now();
+    let file_id = publisher.start_transfer(
+        source_path.to_path_buf(),
+        dest_path.to_path_buf(),
+        source_size,
+    );
+
+    let mut state = load_resume_info(dest_path, false)?;
+    let start_offset = match decide_tail_offset(source_path, dest_path, source_size, &state)? {
+        TailDecision::Append { from_offset } => from_offset,
+        TailDecision::Resync { reason } => {
+            tracing::info!("Tail re-sync of {:?} from 0: {}", source_path, reason);
+            0
+        }
+    };
+
+    if let Some(parent) = dest_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut dest_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dest_path)?;
+    dest_file.set_len(start_offset)?;
+    dest_file.seek(SeekFrom::Start(start_offset))?;
+
+    let mut source_file = File::open(source_path)?;
+    source_file.seek(SeekFrom::Start(start_offset))?;
+
+    let mut buffer = vec![0u8; config.chunk_size.max(1)];
+    let mut position = start_offset;
+    let mut last_progress_event = Instant::now();
+    let progress_interval = Duration::from_millis(500);
+
+    while position < source_size {
+        let to_read = ((source_size - position) as usize).min(buffer.len());
+        let n = source_file.read(&mut buffer[..to_read])?;
+        if n == 0 {
+            break;
+        }
+        dest_file.write_all(&buffer[..n])?;
+        position += n as u64;
+
+        if last_progress_event.elapsed() >= progress_interval {
+            publisher.update_progress(&file_id, position - start_offset, source_size);
+            last_progress_event = Instant::now();
+        }
+    }
+    dest_file.sync_data()?;
+
+    // Persist the new offset only once the appended bytes are durable
+    state.bytes_copied = position;
+    state.file_size = Some(position);
+    state.file_mtime = std::fs::metadata(source_path)?
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+    state.verified_chunks.clear();
+    save_resume_info_full(dest_path, &state, false)?;
+
+    let bytes_copied = position - start_offset;
+    let duration = start_time.elapsed();
+    publisher.complete_transfer(file_id, bytes_copied, duration.as_millis() as u64, None);
+
+    Ok(CopyStats {
+        bytes_copied,
+        duration,
+        checksum: None,
+        compression_ratio: None,
+        files_copied: 1,
+        files_skipped: 0,
+        files_failed: 0,
+        delta_stats: None,
+        chunks_resumed: 0,
+        bytes_skipped: start_offset,
+    })
+}
+
+fn read_range(path: &Path, offset: u64, len: u64) -> Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::with_capacity(len as usize);
+    file.take(len).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn tail_config() -> CopyConfig {
+        CopyConfig {
+            tail_mode: TailMode::Always,
+            chunk_size: 1024,
+            ..Default::default()
+        }
+    }
+
+    fn append(path: &Path, data: &[u8]) {
+        let mut f = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        f.write_all(data).unwrap();
+    }
+
+    fn pass(source: &Path, dest: &Path) -> CopyStats {
+        let size = std::fs::metadata(source).unwrap().len();
+        copy_append_only(
+            source,
+            dest,
+            size,
+            &tail_config(),
+            &ProgressPublisher::noop(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_second_pass_moves_only_appended_bytes() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("db.wal");
+        let dest = dir.path().join("out.wal");
+
+        let first: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        append(&source, &first);
+        let stats = pass(&source, &dest);
+        assert_eq!(stats.bytes_copied, first.len() as u64);
+        assert_eq!(stats.bytes_skipped, 0);
+
+        append(&source, b"new records");
+        let stats = pass(&source, &dest);
+        assert_eq!(stats.bytes_copied, 11);
+        assert_eq!(stats.bytes_skipped, first.len() as u64);
+        assert_eq!(
+            std::fs::read(&dest).unwrap(),
+            std::fs::read(&source).unwrap()
+        );
+
+        // Nothing new: nothing moves
+        let stats = pass(&source, &dest);
+        assert_eq!(stats.bytes_copied, 0);
+    }
+
+    #[test]
+    fn test_truncated_source_resyncs_from_zero() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("app.log");
+        let dest = dir.path().join("copy.log");
+
+        append(&source, &[b'a'; 5000]);
+        pass(&source, &dest);
+
+        // Rotation: file replaced by a shorter one
+        std::fs::write(&source, b"fresh log").unwrap();
+        let stats = pass(&source, &dest);
+        assert_eq!(stats.bytes_copied, 9);
+        assert_eq!(stats.bytes_skipped, 0);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"fresh log");
+    }
+
+    #[test]
+    fn test_replaced_larger_source_resyncs_from_zero() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("app.log");
+        let dest = dir.path().join("copy.log");
+
+        append(&source, &[b'a'; 100]);
+        pass(&source, &dest);
+
+        // Replaced by different content that is already longer
+        std::fs::write(&source, [b'b'; 300]).unwrap();
+        let decision = decide_tail_offset(
+            &source,
+            &dest,
+            300,
+            &load_resume_info(&dest, false).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(decision, TailDecision::Resync { .. }));
+
+        pass(&source, &dest);
+        assert_eq!(std::fs::read(&dest).unwrap(), vec![b'b'; 300]);
+    }
+
+    #[test]
+    fn test_should_tail_auto_uses_semantic_registry() {
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("000001.wal");
+        let doc = dir.path().join("notes.txt");
+        std::fs::write(&wal, b"\x00\x01").unwrap();
+        std::fs::write(&doc, b"hello").unwrap();
+
+        let config = CopyConfig {
+            tail_mode: TailMode::Auto,
+            ..Default::default()
+        };
+        assert!(should_tail(&wal, &config).unwrap());
+        assert!(!should_tail(&doc, &config).unwrap());
+        assert!(!should_tail(&wal, &CopyConfig::default()).unwrap());
+    }
+}
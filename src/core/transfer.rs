@@ -8,6 +8,7 @@ use std::time::Instant;
 use super::buffered;
 use super::delta::{self, DeltaConfig};
 use super::progress::ProgressPublisher;
+use super::tail;
 use super::validation;
 use super::zero_copy;
 use super::CopyStats;
@@ -62,6 +63,7 @@ pub fn perform_copy(
 /// Direct copy without compression (with optional zero-copy optimization)
 ///
 /// Decision tree:
+/// 0. Append-only files (per `tail_mode`) only move newly appended bytes
 /// 1. Check if zero-copy heuristics are favorable
 /// 2. If yes, attempt zero-copy (fall back to buffered on unsupported)
 /// 3. If no, use buffered copy directly
@@ -72,6 +74,9 @@ fn copy_direct(
     config: &CopyConfig,
     publisher: &ProgressPublisher,
 ) -> Result<CopyStats> {
+    if tail::should_tail(source_path, config)? {
+        return tail::copy_append_only(source_path, dest_path, source_size, config, publisher);
+    }
     if config.inplace || config.sparse_mode != crate::core::sparse::SparseMode::Never {
         return buffered::copy_buffered(source_path, dest_path, source_size, config, publisher);
     }
@@ -0,0 +1,83 @@
+/*!
+ * Trash layout for recoverable deletes
+ *
+ * Backend `move_to_trash` calls and `--delete-mode trash` mirror passes put
+ * entries under `<trash dir>/<group>/<original relative path>`, one group per
+ * delete or per mirror pass. A group name starts with the deletion time in
+ * milliseconds since the Unix epoch, so `purge_trash` can age groups without
+ * reading any metadata.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// Directory (or key prefix) that recoverable deletes move entries into
+pub const TRASH_DIR: &str = ".orbit-trash";
+
+static NEXT_GROUP: AtomicU64 = AtomicU64::new(0);
+
+/// Name for a new trash group created at `deleted_at`
+///
+/// `<millis>-<pid>-<n>`: the process ID and a per-process counter keep
+/// groups created in the same millisecond apart.
+pub fn trash_group_name(deleted_at: SystemTime) -> String {
+    let millis = deleted_at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let n = NEXT_GROUP.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{}-{}", millis, std::process::id(), n)
+}
+
+/// Where `path` goes when trashed at `deleted_at`, in a new group
+pub fn trash_entry_path(trash_dir: &Path, path: &Path, deleted_at: SystemTime) -> PathBuf {
+    let relative = path.strip_prefix("/").unwrap_or(path);
+    trash_dir.join(trash_group_name(deleted_at)).join(relative)
+}
+
+/// Deletion time of a trash group, from its name
+///
+/// Also accepts the bare `<millis>` names of older trash groups.
+pub fn trash_entry_time(name: &str) -> Option<SystemTime> {
+    let millis = name.split('-').next()?;
+    let millis: u64 = millis.parse().ok()?;
+    Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(millis))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn groups_created_in_the_same_millisecond_differ() {
+        let at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let first = trash_entry_path(Path::new(TRASH_DIR), Path::new("/a.txt"), at);
+        let second = trash_entry_path(Path::new(TRASH_DIR), Path::new("/a.txt"), at);
+
+        assert_ne!(first, second);
+        assert!(first.starts_with(TRASH_DIR));
+        assert!(first.ends_with("a.txt"));
+        for path in [&first, &second] {
+            let group = path
+                .parent()
+                .unwrap()
+                .file_name()
+                .unwrap()
+                .to_str()
+                .unwrap();
+            assert_eq!(trash_entry_time(group), Some(at));
+        }
+    }
+
+    #[test]
+    fn trash_entry_time_reads_legacy_and_rejects_other_names() {
+        assert_eq!(
+            trash_entry_time("1000"),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1))
+        );
+        assert_eq!(trash_entry_time("notes"), None);
+        assert_eq!(trash_entry_time(""), None);
+    }
+}
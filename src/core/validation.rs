@@ -5,11 +5,13 @@
 use super::checksum::calculate_checksum;
 use super::delta::{self, checksum as delta_checksum, CheckMode, HashAlgorithm};
 use super::disk_guardian::{self, GuardianConfig};
-use crate::config::{CopyConfig, CopyMode};
+use crate::config::{CopyConfig, CopyMode, OverwritePolicy};
 use crate::error::{OrbitError, Result};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 use sysinfo::Disks;
 
 /// Validate that sufficient disk space is available (basic check)
@@ -56,31 +58,142 @@ pub fn validate_disk_space_enhanced(
 }
 
 /// Determine if a file should be copied based on the copy mode
-pub fn should_copy_file(source_path: &Path, dest_path: &Path, mode: CopyMode) -> Result<bool> {
+///
+/// Sync and Update allow `mtime_tolerance_secs` of slack when comparing
+/// mtimes, so clock skew between source and destination does not make an
+/// unchanged file look newer. Mtimes that differ by no more than the
+/// tolerance are ambiguous: they count as equal unless
+/// `checksum_ambiguous_mtime` is set, in which case size and content decide.
+pub fn should_copy_file(source_path: &Path, dest_path: &Path, config: &CopyConfig) -> Result<bool> {
     // Always copy if destination doesn't exist
     if !dest_path.exists() {
         return Ok(true);
     }
 
-    match mode {
-        CopyMode::Copy => Ok(true),
-        CopyMode::Update => {
-            // Update mode: only copy if source is newer
+    match config.copy_mode {
+        CopyMode::Copy | CopyMode::Mirror => Ok(true),
+        CopyMode::Update | CopyMode::Sync => {
             let source_meta = std::fs::metadata(source_path)?;
             let dest_meta = std::fs::metadata(dest_path)?;
-            Ok(source_meta.modified()? > dest_meta.modified()?)
-        }
-        CopyMode::Sync => {
-            // Sync mode: copy if source is newer OR different size
-            let source_meta = std::fs::metadata(source_path)?;
-            let dest_meta = std::fs::metadata(dest_path)?;
-            Ok(source_meta.modified()? > dest_meta.modified()?
-                || source_meta.len() != dest_meta.len())
+            // Sync also copies on a size change; Update only on a newer source
+            if config.copy_mode == CopyMode::Sync && source_meta.len() != dest_meta.len() {
+                return Ok(true);
+            }
+
+            let source_mtime = source_meta.modified()?;
+            let dest_mtime = dest_meta.modified()?;
+            let tolerance = Duration::from_secs(config.mtime_tolerance_secs);
+            let skew = match source_mtime.duration_since(dest_mtime) {
+                Ok(ahead) => ahead,
+                Err(behind) => behind.duration(),
+            };
+            if skew.is_zero() {
+                Ok(false)
+            } else if skew > tolerance {
+                Ok(source_mtime > dest_mtime)
+            } else if config.checksum_ambiguous_mtime {
+                Ok(source_meta.len() != dest_meta.len()
+                    || calculate_checksum(source_path)? != calculate_checksum(dest_path)?)
+            } else {
+                Ok(false)
+            }
         }
-        CopyMode::Mirror => Ok(true),
     }
 }
 
+/// Check the overwrite policy before a copy replaces `dest_path`
+///
+/// Missing destinations are always writable. `no_clobber` acts as
+/// `OverwritePolicy::Never` and `if_source_newer` as
+/// `OverwritePolicy::IfSourceNewer`, both taking precedence over
+/// `overwrite` (`no_clobber` first). Dry runs never stop to prompt.
+pub fn overwrite_allowed(
+    source_path: &Path,
+    dest_path: &Path,
+    config: &CopyConfig,
+) -> Result<bool> {
+    let policy = if config.no_clobber {
+        OverwritePolicy::Never
+    } else if config.if_source_newer {
+        OverwritePolicy::IfSourceNewer
+    } else {
+        config.overwrite
+    };
+    if policy == OverwritePolicy::Always || !dest_path.exists() {
+        return Ok(true);
+    }
+    if policy == OverwritePolicy::Prompt && config.dry_run {
+        return Ok(true);
+    }
+
+    let source_mtime = std::fs::metadata(source_path)?.modified()?;
+    let dest_mtime = std::fs::metadata(dest_path)?.modified()?;
+    Ok(decide_overwrite(policy, source_mtime, dest_mtime, || {
+        prompt_overwrite(dest_path)
+    }))
+}
+
+/// Apply `policy` to a destination with the given mtimes; `ask` returns
+/// `None` when there is nobody to ask
+fn decide_overwrite(
+    policy: OverwritePolicy,
+    source_mtime: SystemTime,
+    dest_mtime: SystemTime,
+    ask: impl FnOnce() -> Option<bool>,
+) -> bool {
+    match policy {
+        OverwritePolicy::Never => false,
+        OverwritePolicy::IfSourceNewer => source_mtime > dest_mtime,
+        OverwritePolicy::Always => true,
+        OverwritePolicy::Prompt => ask().unwrap_or(false),
+    }
+}
+
+/// Serializes prompts from parallel workers
+static PROMPT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Ask on the terminal whether to replace `dest_path`
+fn prompt_overwrite(dest_path: &Path) -> Option<bool> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        tracing::info!(
+            "Skipping existing {:?}: no terminal to confirm the overwrite",
+            dest_path
+        );
+        return None;
+    }
+
+    let _guard = PROMPT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    eprint!("Overwrite {}? [y/N] ", dest_path.display());
+    std::io::stderr().flush().ok()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer).ok()?;
+    Some(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// Estimate how far the clock stamping mtimes in `dir` is from ours, in
+/// milliseconds (positive = destination ahead)
+///
+/// Writes a scratch file and compares its mtime with the local time at the
+/// write. Network filesystems often stamp mtimes with the server's clock,
+/// which is what Sync and Update end up comparing against.
+pub fn probe_clock_skew(dir: &Path) -> Result<i64> {
+    let probe = dir.join(format!(".orbit-clock-probe-{}", std::process::id()));
+    let before = SystemTime::now();
+    let written = std::fs::write(&probe, b"probe").and_then(|()| std::fs::metadata(&probe));
+    let after = SystemTime::now();
+    let _ = std::fs::remove_file(&probe);
+    let mtime = written?.modified()?;
+
+    let local = before + after.duration_since(before).unwrap_or_default() / 2;
+    Ok(match mtime.duration_since(local) {
+        Ok(ahead) => ahead.as_millis() as i64,
+        Err(behind) => -(behind.duration().as_millis() as i64),
+    })
+}
+
 /// Check if a path matches any exclude patterns
 pub fn matches_exclude_pattern(path: &Path, patterns: &[String]) -> bool {
     use glob::Pattern;
@@ -240,7 +353,11 @@ mod tests {
         let source = NamedTempFile::new().unwrap();
         let dest = NamedTempFile::new().unwrap();
 
-        assert!(should_copy_file(source.path(), dest.path(), CopyMode::Copy).unwrap());
+        let config = CopyConfig {
+            copy_mode: CopyMode::Copy,
+            ..Default::default()
+        };
+        assert!(should_copy_file(source.path(), dest.path(), &config).unwrap());
     }
 
     #[test]
@@ -256,7 +373,188 @@ mod tests {
         source.write_all(b"new").unwrap();
         source.flush().unwrap();
 
-        assert!(should_copy_file(source.path(), dest.path(), CopyMode::Sync).unwrap());
+        let config = CopyConfig {
+            copy_mode: CopyMode::Sync,
+            mtime_tolerance_secs: 0,
+            ..Default::default()
+        };
+        assert!(should_copy_file(source.path(), dest.path(), &config).unwrap());
+    }
+
+    /// Source and destination with `source_data`/`dest_data`, the source's
+    /// mtime `skew_secs` after the destination's
+    fn skewed_pair(
+        source_data: &[u8],
+        dest_data: &[u8],
+        skew_secs: i64,
+    ) -> (tempfile::TempDir, std::path::PathBuf, std::path::PathBuf) {
+        use filetime::{set_file_mtime, FileTime};
+
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source.txt");
+        let dest = dir.path().join("dest.txt");
+        std::fs::write(&source, source_data).unwrap();
+        std::fs::write(&dest, dest_data).unwrap();
+        set_file_mtime(&dest, FileTime::from_unix_time(1_700_000_000, 0)).unwrap();
+        set_file_mtime(
+            &source,
+            FileTime::from_unix_time(1_700_000_000 + skew_secs, 0),
+        )
+        .unwrap();
+        (dir, source, dest)
+    }
+
+    #[test]
+    fn test_mtime_within_tolerance_is_not_retransferred() {
+        for mode in [CopyMode::Sync, CopyMode::Update] {
+            let config = CopyConfig {
+                copy_mode: mode,
+                mtime_tolerance_secs: 2,
+                ..Default::default()
+            };
+            for skew in [-2, -1, 1, 2] {
+                let (_dir, source, dest) = skewed_pair(b"same", b"same", skew);
+                assert!(
+                    !should_copy_file(&source, &dest, &config).unwrap(),
+                    "{:?} with {}s skew",
+                    mode,
+                    skew
+                );
+            }
+
+            // Without tolerance the same skew looks like a newer source
+            let strict = CopyConfig {
+                mtime_tolerance_secs: 0,
+                ..config
+            };
+            let (_dir, source, dest) = skewed_pair(b"same", b"same", 1);
+            assert!(should_copy_file(&source, &dest, &strict).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_genuine_change_detected_despite_tolerance() {
+        let config = CopyConfig {
+            copy_mode: CopyMode::Sync,
+            mtime_tolerance_secs: 2,
+            ..Default::default()
+        };
+
+        // Newer than the tolerance allows
+        let (_dir, source, dest) = skewed_pair(b"edited", b"before", 30);
+        assert!(should_copy_file(&source, &dest, &config).unwrap());
+
+        // Size change inside the tolerance
+        let (_dir, source, dest) = skewed_pair(b"longer text", b"short", 1);
+        assert!(should_copy_file(&source, &dest, &config).unwrap());
+
+        // Same size inside the tolerance: only the checksum can tell
+        let (_dir, source, dest) = skewed_pair(b"edited", b"before", 1);
+        assert!(!should_copy_file(&source, &dest, &config).unwrap());
+        let checked = CopyConfig {
+            checksum_ambiguous_mtime: true,
+            ..config.clone()
+        };
+        assert!(should_copy_file(&source, &dest, &checked).unwrap());
+        let (_dir, source, dest) = skewed_pair(b"same", b"same", 1);
+        assert!(!should_copy_file(&source, &dest, &checked).unwrap());
+    }
+
+    #[test]
+    fn test_probe_clock_skew_on_local_disk() {
+        let dir = tempdir().unwrap();
+        let skew = probe_clock_skew(dir.path()).unwrap();
+        assert!(skew.abs() < 5_000, "local skew of {}ms", skew);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_overwrite_policy_decisions() {
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let older = base - Duration::from_secs(10);
+        let newer = base + Duration::from_secs(10);
+        // (policy, [dest newer, dest older, equal])
+        let cases = [
+            (OverwritePolicy::Never, [false, false, false]),
+            (OverwritePolicy::IfSourceNewer, [false, true, false]),
+            (OverwritePolicy::Always, [true, true, true]),
+            (OverwritePolicy::Prompt, [false, false, false]),
+        ];
+        for (policy, expected) in cases {
+            for (dest_mtime, want) in [newer, older, base].into_iter().zip(expected) {
+                assert_eq!(
+                    decide_overwrite(policy, base, dest_mtime, || None),
+                    want,
+                    "{:?} with dest mtime {:?}",
+                    policy,
+                    dest_mtime
+                );
+            }
+        }
+
+        // An answer on the terminal decides a prompt
+        assert!(decide_overwrite(
+            OverwritePolicy::Prompt,
+            base,
+            newer,
+            || Some(true)
+        ));
+        assert!(!decide_overwrite(
+            OverwritePolicy::Prompt,
+            base,
+            older,
+            || Some(false)
+        ));
+    }
+
+    #[test]
+    fn test_overwrite_allowed_on_files() {
+        let config = |overwrite| CopyConfig {
+            overwrite,
+            ..Default::default()
+        };
+
+        // Destination newer than the source
+        let (_dir, source, dest) = skewed_pair(b"new", b"old", -10);
+        assert!(
+            !overwrite_allowed(&source, &dest, &config(OverwritePolicy::IfSourceNewer)).unwrap()
+        );
+        assert!(!overwrite_allowed(&source, &dest, &config(OverwritePolicy::Never)).unwrap());
+        assert!(overwrite_allowed(&source, &dest, &config(OverwritePolicy::Always)).unwrap());
+        let no_clobber = CopyConfig {
+            no_clobber: true,
+            ..Default::default()
+        };
+        assert!(!overwrite_allowed(&source, &dest, &no_clobber).unwrap());
+
+        // Destination older than the source
+        let (_dir, source, dest) = skewed_pair(b"new", b"old", 10);
+        assert!(
+            overwrite_allowed(&source, &dest, &config(OverwritePolicy::IfSourceNewer)).unwrap()
+        );
+        if !std::io::stdin().is_terminal() {
+            assert!(!overwrite_allowed(&source, &dest, &config(OverwritePolicy::Prompt)).unwrap());
+        }
+
+        // Nothing to overwrite
+        std::fs::remove_file(&dest).unwrap();
+        assert!(overwrite_allowed(&source, &dest, &config(OverwritePolicy::Never)).unwrap());
+    }
+
+    #[test]
+    fn test_if_source_newer_from_config_file() {
+        let config: CopyConfig = toml::from_str("if_source_newer = true").unwrap();
+        assert_eq!(config.overwrite, OverwritePolicy::Always);
+
+        let (_dir, source, dest) = skewed_pair(b"new", b"old", -10);
+        assert!(!overwrite_allowed(&source, &dest, &config).unwrap());
+        let (_dir, source, dest) = skewed_pair(b"new", b"old", 10);
+        assert!(overwrite_allowed(&source, &dest, &config).unwrap());
+
+        // no_clobber still wins
+        let config: CopyConfig =
+            toml::from_str("if_source_newer = true\nno_clobber = true").unwrap();
+        assert!(!overwrite_allowed(&source, &dest, &config).unwrap());
     }
 
     #[test]
@@ -0,0 +1,431 @@
+/*!
+ * Sampled post-copy verification (`--verify sample:P`).
+ *
+ * Re-reads a random P% of the copied files from the destination and checks
+ * them against the source. Very large files whose cargo manifest was just
+ * refreshed by `--since` only have a random P% of their windows re-hashed;
+ * other sampled files are hashed in full.
+ *
+ * Whether a file is sampled depends only on the seed and its relative path,
+ * so the same seed picks the same files on every run regardless of the
+ * order they are copied in. Any mismatch fails that file. At the end the
+ * sample size gives a confidence bound on how much of the unsampled tree
+ * could be corrupt.
+ */
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use orbit_core_manifest::CargoManifest;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::checksum::calculate_checksum;
+use crate::error::{OrbitError, Result};
+use crate::manifest_integration::verify_window_subset;
+
+/// Files at least this large are verified by window when a manifest exists
+pub const WINDOW_SAMPLE_THRESHOLD: u64 = 1024 * 1024 * 1024;
+
+/// Confidence level of the reported corruption bound
+const CONFIDENCE: f64 = 0.95;
+
+/// Settings for `--verify sample:P`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VerifySample {
+    /// Percentage of files (and windows) to verify, in (0, 100]
+    pub percent: f64,
+
+    /// RNG seed (None = pick one and log it)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl VerifySample {
+    /// Parse a `--verify` mode such as `sample:5` or `sample:0.5%`
+    pub fn parse(value: &str) -> std::result::Result<Self, String> {
+        let percent = value
+            .trim()
+            .strip_prefix("sample:")
+            .ok_or_else(|| format!("unknown verify mode '{}': expected sample:P", value))?;
+        let percent: f64 = percent
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| format!("invalid sample percentage in '{}'", value))?;
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(format!(
+                "sample percentage must be above 0 and at most 100, got {}",
+                percent
+            ));
+        }
+        Ok(Self {
+            percent,
+            seed: None,
+        })
+    }
+}
+
+/// Picks and verifies the sampled files of one directory copy
+#[derive(Debug)]
+pub struct SampleVerifier {
+    percent: f64,
+    seed: u64,
+    /// Where cargo manifests for window sampling are looked up
+    manifest_dir: Option<PathBuf>,
+    window_threshold: u64,
+    files_seen: AtomicU64,
+    files_sampled: AtomicU64,
+    windows_checked: AtomicU64,
+    mismatches: Mutex<Vec<PathBuf>>,
+}
+
+impl SampleVerifier {
+    /// Create a verifier, choosing a random seed if none was given
+    pub fn new(sample: VerifySample, manifest_dir: Option<PathBuf>) -> Self {
+        let seed = sample.seed.unwrap_or_else(|| {
+            let seed = rand::random();
+            tracing::info!("Sampled verification seed: {}", seed);
+            seed
+        });
+        Self {
+            percent: sample.percent,
+            seed,
+            manifest_dir,
+            window_threshold: WINDOW_SAMPLE_THRESHOLD,
+            files_seen: AtomicU64::new(0),
+            files_sampled: AtomicU64::new(0),
+            windows_checked: AtomicU64::new(0),
+            mismatches: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Whether the file at `relative_path` is in the sample
+    pub fn selects(&self, relative_path: &Path) -> bool {
+        self.rng_for(relative_path).random::<f64>() * 100.0 < self.percent
+    }
+
+    /// Count a copied file and, if it is sampled, verify `dest` against
+    /// `source`
+    ///
+    /// `source_hash` is the SHA-256 the copy already computed, if any, so
+    /// the source is not read a second time.
+    pub fn verify(
+        &self,
+        source: &Path,
+        dest: &Path,
+        relative_path: &Path,
+        source_hash: Option<&str>,
+    ) -> Result<()> {
+        self.files_seen.fetch_add(1, Ordering::Relaxed);
+        let mut rng = self.rng_for(relative_path);
+        if rng.random::<f64>() * 100.0 >= self.percent {
+            return Ok(());
+        }
+        self.files_sampled.fetch_add(1, Ordering::Relaxed);
+
+        let size = std::fs::metadata(dest)?.len();
+        let cargo = (size >= self.window_threshold)
+            .then(|| self.cargo_for(relative_path))
+            .flatten();
+
+        let result = match cargo {
+            Some(cargo) => self.verify_windows(dest, &cargo, &mut rng),
+            None => {
+                let expected = match source_hash {
+                    Some(hash) => hash.to_string(),
+                    None => calculate_checksum(source)?,
+                };
+                let actual = calculate_checksum(dest)?;
+                if actual == expected {
+                    Ok(())
+                } else {
+                    Err(OrbitError::ChecksumMismatch { expected, actual })
+                }
+            }
+        };
+
+        if let Err(OrbitError::ChecksumMismatch { .. }) = &result {
+            tracing::error!("Sampled verification failed for {:?}", relative_path);
+            self.mismatches
+                .lock()
+                .unwrap()
+                .push(relative_path.to_path_buf());
+        }
+        result
+    }
+
+    /// Re-hash a random subset of the windows of `dest`
+    fn verify_windows(&self, dest: &Path, cargo: &CargoManifest, rng: &mut StdRng) -> Result<()> {
+        let mut ids: Vec<u32> = cargo
+            .windows
+            .iter()
+            .filter(|_| rng.random::<f64>() * 100.0 < self.percent)
+            .map(|w| w.id)
+            .collect();
+        if ids.is_empty() && !cargo.windows.is_empty() {
+            ids.push(cargo.windows[rng.random_range(0..cargo.windows.len())].id);
+        }
+
+        let report = verify_window_subset(dest, cargo, &ids)?;
+        self.windows_checked
+            .fetch_add(report.windows_checked as u64, Ordering::Relaxed);
+        if report.is_ok() {
+            Ok(())
+        } else {
+            Err(OrbitError::ChecksumMismatch {
+                expected: format!("{} bytes, windows {:?} intact", cargo.size, ids),
+                actual: format!(
+                    "size matches: {}, mismatched windows {:?}",
+                    report.size_matches, report.mismatched_windows
+                ),
+            })
+        }
+    }
+
+    /// Load the cargo manifest written for `relative_path`, if there is one
+    fn cargo_for(&self, relative_path: &Path) -> Option<CargoManifest> {
+        let dir = self.manifest_dir.as_ref()?;
+        let safe_name = relative_path.to_string_lossy().replace(['/', '\\'], "_");
+        CargoManifest::load(dir.join(format!("{}.cargo.json", safe_name))).ok()
+    }
+
+    /// RNG for one file, seeded from the run seed and its path
+    fn rng_for(&self, relative_path: &Path) -> StdRng {
+        let hash = blake3::hash(relative_path.to_string_lossy().as_bytes());
+        let mut key = [0u8; 8];
+        key.copy_from_slice(&hash.as_bytes()[..8]);
+        StdRng::seed_from_u64(self.seed ^ u64::from_le_bytes(key))
+    }
+
+    /// Summary of what was verified so far
+    pub fn report(&self) -> SampleReport {
+        SampleReport {
+            percent: self.percent,
+            seed: self.seed,
+            files_seen: self.files_seen.load(Ordering::Relaxed),
+            files_sampled: self.files_sampled.load(Ordering::Relaxed),
+            windows_checked: self.windows_checked.load(Ordering::Relaxed),
+            mismatches: self.mismatches.lock().unwrap().clone(),
+        }
+    }
+}
+
+/// Outcome of sampled verification
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SampleReport {
+    pub percent: f64,
+    pub seed: u64,
+    /// Copied files considered for the sample
+    pub files_seen: u64,
+    pub files_sampled: u64,
+    /// Windows re-hashed in window-sampled files
+    pub windows_checked: u64,
+    /// Sampled files that did not match
+    pub mismatches: Vec<PathBuf>,
+}
+
+impl SampleReport {
+    /// Upper bound on the fraction of corrupt files, at 95% confidence
+    ///
+    /// With `n` clean samples, a corrupt fraction `p` would have let all of
+    /// them through with probability `(1 - p)^n`; the bound is the `p` where
+    /// that falls to 5%. None when nothing was sampled or a mismatch was
+    /// found.
+    pub fn corruption_bound(&self) -> Option<f64> {
+        if self.files_sampled == 0 || !self.mismatches.is_empty() {
+            return None;
+        }
+        Some(1.0 - (1.0 - CONFIDENCE).powf(1.0 / self.files_sampled as f64))
+    }
+}
+
+impl fmt::Display for SampleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sampled verification ({}%, seed {}): {} of {} files checked",
+            self.percent, self.seed, self.files_sampled, self.files_seen
+        )?;
+        if self.windows_checked > 0 {
+            write!(f, ", {} windows", self.windows_checked)?;
+        }
+        if !self.mismatches.is_empty() {
+            return write!(f, "; {} mismatched", self.mismatches.len());
+        }
+        match self.corruption_bound() {
+            Some(bound) => write!(
+                f,
+                "; no mismatches, {:.0}% confidence that under {:.2}% of files are corrupt",
+                CONFIDENCE * 100.0,
+                bound * 100.0
+            ),
+            None => write!(f, "; no files sampled"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CopyConfig;
+    use crate::manifest_integration::ManifestGenerator;
+    use tempfile::tempdir;
+
+    fn verifier(percent: f64, seed: u64) -> SampleVerifier {
+        SampleVerifier::new(
+            VerifySample {
+                percent,
+                seed: Some(seed),
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_sample_is_about_p_percent_and_reproducible() {
+        let paths: Vec<PathBuf> = (0..10_000)
+            .map(|i| PathBuf::from(format!("dir{}/file{}.dat", i % 37, i)))
+            .collect();
+
+        let first: Vec<bool> = paths
+            .iter()
+            .map(|p| verifier(10.0, 42).selects(p))
+            .collect();
+        let selected = first.iter().filter(|&&s| s).count();
+        assert!(
+            (800..=1200).contains(&selected),
+            "selected {} of 10000",
+            selected
+        );
+
+        // Same seed, reverse order: same files
+        let second = verifier(10.0, 42);
+        for (path, &chosen) in paths.iter().zip(&first).rev() {
+            assert_eq!(second.selects(path), chosen);
+        }
+
+        let other: Vec<bool> = paths
+            .iter()
+            .map(|p| verifier(10.0, 43).selects(p))
+            .collect();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_sampled_corruption_is_flagged() {
+        let dir = tempdir().unwrap();
+        let sampler = verifier(50.0, 7);
+        let (mut sampled, mut unsampled) = (None, None);
+        for i in 0.. {
+            let name = PathBuf::from(format!("file{}.txt", i));
+            match sampler.selects(&name) {
+                true if sampled.is_none() => sampled = Some(name),
+                false if unsampled.is_none() => unsampled = Some(name),
+                _ => {}
+            }
+            if sampled.is_some() && unsampled.is_some() {
+                break;
+            }
+        }
+
+        for name in [sampled.as_ref().unwrap(), unsampled.as_ref().unwrap()] {
+            std::fs::write(
+                dir.path().join(format!("src-{}", name.display())),
+                b"original",
+            )
+            .unwrap();
+            std::fs::write(
+                dir.path().join(format!("dst-{}", name.display())),
+                b"corrupt!",
+            )
+            .unwrap();
+        }
+        let check = |name: &PathBuf| {
+            sampler.verify(
+                &dir.path().join(format!("src-{}", name.display())),
+                &dir.path().join(format!("dst-{}", name.display())),
+                name,
+                None,
+            )
+        };
+
+        assert!(check(unsampled.as_ref().unwrap()).is_ok());
+        let err = check(sampled.as_ref().unwrap()).unwrap_err();
+        assert!(matches!(err, OrbitError::ChecksumMismatch { .. }));
+
+        let report = sampler.report();
+        assert_eq!(report.files_seen, 2);
+        assert_eq!(report.files_sampled, 1);
+        assert_eq!(report.mismatches, vec![sampled.unwrap()]);
+        assert_eq!(report.corruption_bound(), None);
+    }
+
+    #[test]
+    fn test_large_file_windows_are_checked_against_manifest() {
+        let dir = tempdir().unwrap();
+        let source_dir = dir.path().join("src");
+        let manifests = dir.path().join("manifests");
+        std::fs::create_dir(&source_dir).unwrap();
+        let source = source_dir.join("big.bin");
+        let data: Vec<u8> = (0..600 * 1024).map(|i| (i * 7 % 253) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let config = CopyConfig {
+            manifest_output_dir: Some(manifests.clone()),
+            ..Default::default()
+        };
+        let mut generator = ManifestGenerator::new(&source_dir, dir.path(), &config).unwrap();
+        generator
+            .generate_file_manifest(&source, "big.bin")
+            .unwrap();
+
+        let mut sampler = SampleVerifier::new(
+            VerifySample {
+                percent: 100.0,
+                seed: Some(1),
+            },
+            Some(manifests),
+        );
+        sampler.window_threshold = 0;
+
+        let dest = dir.path().join("big.copy");
+        std::fs::write(&dest, &data).unwrap();
+        sampler
+            .verify(&source, &dest, Path::new("big.bin"), None)
+            .unwrap();
+        assert!(sampler.report().windows_checked > 0);
+
+        let mut corrupt = data.clone();
+        corrupt[data.len() - 10] ^= 0xFF;
+        std::fs::write(&dest, &corrupt).unwrap();
+        let err = sampler
+            .verify(&source, &dest, Path::new("big.bin"), None)
+            .unwrap_err();
+        assert!(matches!(err, OrbitError::ChecksumMismatch { .. }));
+    }
+
+    #[test]
+    fn test_parse_and_confidence_bound() {
+        assert_eq!(VerifySample::parse("sample:5").unwrap().percent, 5.0);
+        assert_eq!(VerifySample::parse("sample:0.5%").unwrap().percent, 0.5);
+        assert!(VerifySample::parse("sample:0").is_err());
+        assert!(VerifySample::parse("sample:101").is_err());
+        assert!(VerifySample::parse("full").is_err());
+
+        let report = SampleReport {
+            percent: 1.0,
+            seed: 0,
+            files_seen: 30_000,
+            files_sampled: 300,
+            windows_checked: 0,
+            mismatches: Vec::new(),
+        };
+        // The rule of three: roughly 3 / n
+        let bound = report.corruption_bound().unwrap();
+        assert!((bound - 3.0 / 300.0).abs() < 0.001, "bound {}", bound);
+        assert!(report.to_string().contains("no mismatches"));
+    }
+}
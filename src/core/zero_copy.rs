@@ -459,6 +459,7 @@ pub fn try_zero_copy_direct(
             if config.show_progress {
                 println!("Calculating checksum...");
             }
+            let _verify = tracing::info_span!("verify").entered();
             let source_checksum = checksum::calculate_checksum(source_path)?;
             let dest_checksum = checksum::calculate_checksum(dest_path)?;
 
@@ -496,11 +497,11 @@ pub fn try_zero_copy_direct(
     }
 
     // Setup bandwidth limiter
-    let bandwidth_limiter = BandwidthLimiter::new(config.max_bandwidth);
+    let bandwidth_limiter = BandwidthLimiter::from_config(config);
     if bandwidth_limiter.is_enabled() {
         info!(
             "Zero-copy with bandwidth limiting: {} bytes/sec",
-            config.max_bandwidth
+            bandwidth_limiter.current_limit()
         );
     }
 
@@ -571,6 +572,7 @@ pub fn try_zero_copy_direct(
         if config.show_progress {
             println!("Calculating checksum...");
         }
+        let _verify = tracing::info_span!("verify").entered();
         let source_checksum = checksum::calculate_checksum(source_path)?;
         let dest_checksum = checksum::calculate_checksum(dest_path)?;
 
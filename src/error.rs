@@ -28,6 +28,9 @@ pub enum OrbitError {
     /// Insufficient disk space
     InsufficientDiskSpace { required: u64, available: u64 },
 
+    /// Destination failed the preflight write check
+    DestinationNotWritable { path: PathBuf, reason: String },
+
     /// Configuration error
     Config(String),
 
@@ -80,7 +83,8 @@ impl OrbitError {
             | OrbitError::InvalidPath(_)
             | OrbitError::Config(_)
             | OrbitError::Authentication(_)
-            | OrbitError::InsufficientDiskSpace { .. } => EXIT_FATAL,
+            | OrbitError::InsufficientDiskSpace { .. }
+            | OrbitError::DestinationNotWritable { .. } => EXIT_FATAL,
             // Integrity errors: checksum mismatch
             OrbitError::ChecksumMismatch { .. } => EXIT_INTEGRITY,
             // Partial failures: retries exhausted, parallel errors
@@ -99,6 +103,7 @@ impl OrbitError {
             OrbitError::Config(_) => true,
             OrbitError::ChecksumMismatch { .. } => true,
             OrbitError::InsufficientDiskSpace { .. } => true,
+            OrbitError::DestinationNotWritable { .. } => true,
             OrbitError::Authentication(_) => true,
             OrbitError::RetriesExhausted { .. } => true,
 
@@ -187,6 +192,10 @@ impl OrbitError {
             OrbitError::InsufficientDiskSpace { .. } => Some(
                 "Free up disk space or use --compress zstd:3 to reduce transfer size".to_string(),
             ),
+            OrbitError::DestinationNotWritable { .. } => Some(
+                "Check the destination's permissions, mount options and free inodes, or use --no-preflight to skip the check"
+                    .to_string(),
+            ),
             OrbitError::Config(msg) if msg.contains("path required") => {
                 Some("Usage: orbit <SOURCE> <DEST> or orbit -s <SOURCE> -d <DEST>".to_string())
             }
@@ -287,6 +296,7 @@ impl OrbitError {
             OrbitError::SourceNotFound(_) | OrbitError::InvalidPath(_) => ErrorCategory::Validation,
             OrbitError::Io(_) => ErrorCategory::IoError,
             OrbitError::InsufficientDiskSpace { .. } => ErrorCategory::Resource,
+            OrbitError::DestinationNotWritable { .. } => ErrorCategory::Filesystem,
             OrbitError::Config(_) => ErrorCategory::Configuration,
             OrbitError::Compression(_) | OrbitError::Decompression(_) => ErrorCategory::Codec,
             OrbitError::Resume(_) => ErrorCategory::Resume,
@@ -409,6 +419,14 @@ impl fmt::Display for OrbitError {
                     required, available
                 )
             }
+            OrbitError::DestinationNotWritable { path, reason } => {
+                write!(
+                    f,
+                    "Destination {} is not writable: {}",
+                    path.display(),
+                    reason
+                )
+            }
             OrbitError::Config(msg) => {
                 write!(f, "Configuration error: {}", msg)
             }
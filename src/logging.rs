@@ -17,7 +17,7 @@ use crate::config::CopyConfig;
 use crate::error::{OrbitError, Result};
 
 // V3 Observability imports
-use orbit_observability::{AuditBridgeLayer, AuditSigner, UnifiedLogger};
+use orbit_observability::{AuditBridgeLayer, AuditSigner, Redactor, UnifiedLogger};
 
 // ============================================================================
 // User-Facing Output Macros (Phase 3: Terminology Abstraction)
@@ -77,9 +77,11 @@ pub fn init_logging(config: &CopyConfig) -> Result<()> {
                     "🔒 Initializing cryptographic audit logging to {:?}",
                     audit_path
                 );
-                UnifiedLogger::new(Some(audit_path), signer).map_err(|e| {
-                    OrbitError::Config(format!("Failed to create audit logger: {}", e))
-                })?
+                UnifiedLogger::new(Some(audit_path), signer)
+                    .map_err(|e| {
+                        OrbitError::Config(format!("Failed to create audit logger: {}", e))
+                    })?
+                    .with_redactor(audit_redactor())
             }
             Err(_) => {
                 eprintln!("⚠️  ORBIT_AUDIT_SECRET not set - audit logging disabled");
@@ -103,6 +105,13 @@ pub fn init_logging(config: &CopyConfig) -> Result<()> {
     Ok(())
 }
 
+/// Audit redactor: the default denylist plus any comma-separated field names
+/// in `ORBIT_AUDIT_REDACT_FIELDS`
+fn audit_redactor() -> Redactor {
+    let extra = env::var("ORBIT_AUDIT_REDACT_FIELDS").unwrap_or_default();
+    Redactor::default().deny_fields(extra.split(',').filter(|f| !f.trim().is_empty()))
+}
+
 fn init_llm_debug_logging(env_filter: EnvFilter, log_path: Option<&Path>) -> Result<()> {
     let fmt_layer = fmt::layer()
         .json()
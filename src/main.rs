@@ -2,18 +2,24 @@
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use orbit::{
+    cli_progress::JsonProgressRenderer,
     cli_style::{
         self, capability_table, format_bytes, format_duration, guidance_box, header_box,
         preset_table, print_error, section_header, transfer_summary_table, Icons, PresetInfo,
         Theme, TransferSummary,
     },
-    commands::manifest::ManifestCommands,
+    commands::{audit::AuditCommands, manifest::ManifestCommands},
     config::{
-        AuditFormat, CompressionType, CopyConfig, CopyMode, ErrorMode, LogLevel, SymlinkMode,
+        ArchiveFormat, AuditFormat, ChecksumAlgorithm, CompressionType, CopyConfig, CopyMode,
+        DeleteMode, ErrorMode, HiddenMode, LogLevel, OverwritePolicy, ProgressFormat,
+        SpecialFileMode, SymlinkMode,
     },
-    copy_directory, copy_file,
+    copy_directory, copy_directory_impl, copy_file, copy_file_impl,
+    core::bandwidth::parse_bandwidth_schedule,
     core::batch::TransferJournal,
     core::guidance::ConfigOptimizer,
+    core::progress::ProgressPublisher,
+    core::retry::RetryBudget,
     error::{OrbitError, Result, EXIT_SUCCESS},
     get_zero_copy_capabilities, is_zero_copy_available, logging,
     protocol::Protocol,
@@ -120,6 +126,27 @@ struct TransferArgs {
     )]
     mode: Option<CopyModeArg>,
 
+    /// How --mode mirror removes destination-only files: delete them, move
+    /// them under .orbit-quarantine/<timestamp>/, or move them into a
+    /// .orbit-trash/ group in the destination
+    #[arg(
+        long = "delete-mode",
+        value_enum,
+        global = true,
+        help_heading = "Transfer"
+    )]
+    delete_mode: Option<DeleteModeArg>,
+
+    /// Sync/update treat mtimes up to SECS apart as equal, absorbing clock
+    /// skew between source and destination (default 2)
+    #[arg(long, value_name = "SECS", global = true, help_heading = "Transfer")]
+    mtime_tolerance: Option<u64>,
+
+    /// Compare size and checksum when mtimes differ within the tolerance,
+    /// instead of treating the file as unchanged
+    #[arg(long, global = true, help_heading = "Transfer")]
+    checksum_ambiguous_mtime: bool,
+
     /// Recursive copy (auto-detected for directory sources)
     #[arg(
         short = 'R',
@@ -133,6 +160,31 @@ struct TransferArgs {
     #[arg(long, global = true, help_heading = "Transfer")]
     no_auto_recursive: bool,
 
+    /// Descend at most N levels below the source (1 = top-level entries only)
+    #[arg(
+        long = "max-depth",
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        global = true,
+        help_heading = "Transfer"
+    )]
+    max_depth: Option<u32>,
+
+    /// Stay on the source's filesystem; mount points are created empty
+    /// [default: true]
+    #[arg(short = 'x', long, global = true, help_heading = "Transfer")]
+    one_file_system: bool,
+
+    /// Let recursive copies descend into other filesystems mounted under the
+    /// source, such as /proc or network mounts
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "one_file_system",
+        help_heading = "Transfer"
+    )]
+    cross_file_systems: bool,
+
     /// Preserve metadata (timestamps, permissions) [default: true]
     #[arg(
         short = 'p',
@@ -177,6 +229,51 @@ struct TransferArgs {
     /// Symbolic link mode
     #[arg(long = "symlink", value_enum, global = true, help_heading = "Transfer")]
     symlink: Option<SymlinkModeArg>,
+
+    /// FIFOs, sockets and device nodes: skip them, count each as a failed
+    /// file, or recreate them
+    #[arg(long = "special", value_enum, global = true, help_heading = "Transfer")]
+    special: Option<SpecialFileModeArg>,
+
+    /// Write a directory source into a single archive (tar, tar.zst, zip)
+    #[arg(
+        long = "archive",
+        value_name = "FORMAT",
+        value_enum,
+        global = true,
+        help_heading = "Transfer"
+    )]
+    archive: Option<ArchiveFormatArg>,
+
+    /// Unpack a .tar or .tar.zst source into the destination directory
+    #[arg(
+        long = "extract",
+        global = true,
+        conflicts_with = "archive",
+        help_heading = "Transfer"
+    )]
+    extract: bool,
+
+    /// Start a single-file copy at this byte offset of the source and
+    /// destination (e.g. to finish an external partial copy)
+    #[arg(
+        long,
+        value_name = "BYTES",
+        global = true,
+        conflicts_with = "extract",
+        help_heading = "Transfer"
+    )]
+    offset: Option<u64>,
+
+    /// Copy only this many bytes from --offset (default: to the end)
+    #[arg(
+        long,
+        value_name = "BYTES",
+        global = true,
+        conflicts_with = "extract",
+        help_heading = "Transfer"
+    )]
+    length: Option<u64>,
 }
 
 #[derive(Args)]
@@ -202,6 +299,19 @@ struct ReliabilityArgs {
     #[arg(long, global = true, help_heading = "Reliability")]
     exponential_backoff: bool,
 
+    /// Cap retries across all files: N credits, refilled over a minute;
+    /// once spent, failing files stop retrying
+    #[arg(long, value_name = "N", global = true, help_heading = "Reliability")]
+    retry_budget: Option<u32>,
+
+    /// Write a resume checkpoint at least every N seconds (0 = off, default 5)
+    #[arg(long, value_name = "SECS", global = true, help_heading = "Reliability")]
+    checkpoint_interval: Option<u64>,
+
+    /// Also write a resume checkpoint every N MB copied (0 = off)
+    #[arg(long, value_name = "MB", global = true, help_heading = "Reliability")]
+    checkpoint_bytes: Option<u64>,
+
     /// Error handling mode (abort, skip, partial)
     #[arg(long, value_enum, global = true, help_heading = "Reliability")]
     error_mode: Option<ErrorModeArg>,
@@ -209,6 +319,62 @@ struct ReliabilityArgs {
     /// Skip checksum verification
     #[arg(long, global = true, help_heading = "Reliability")]
     no_verify: bool,
+
+    /// Re-read a random P% of copied files after a directory copy and fail
+    /// on any mismatch (sample:P)
+    #[arg(
+        long,
+        value_name = "MODE",
+        value_parser = orbit::core::verify_sample::VerifySample::parse,
+        global = true,
+        help_heading = "Reliability"
+    )]
+    verify: Option<orbit::core::verify_sample::VerifySample>,
+
+    /// Seed for --verify sample:P, to reproduce a previous sample
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "verify",
+        global = true,
+        help_heading = "Reliability"
+    )]
+    verify_seed: Option<u64>,
+
+    /// Before a directory copy, check the destination root takes a probe
+    /// write/delete and has free inodes (on by default)
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "no_preflight",
+        help_heading = "Reliability"
+    )]
+    preflight: bool,
+
+    /// Skip the destination preflight check
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "preflight",
+        help_heading = "Reliability"
+    )]
+    no_preflight: bool,
+
+    /// Write a `sha256sum -c` / `b3sum -c` compatible list of copied files'
+    /// hashes (default: <dest>.sha256 or <dest>.b3 beside the destination)
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        require_equals = true,
+        global = true,
+        help_heading = "Reliability"
+    )]
+    checksum_manifest: Option<Option<PathBuf>>,
+
+    /// Hash algorithm for --checksum-manifest
+    #[arg(long, value_enum, global = true, help_heading = "Reliability")]
+    checksum_algorithm: Option<ChecksumAlgorithmArg>,
 }
 
 #[derive(Args)]
@@ -243,11 +409,24 @@ struct PerformanceArgs {
     #[arg(long, global = true, help_heading = "Performance")]
     max_bandwidth: Option<u64>,
 
+    /// Time-of-day bandwidth limits in local time, e.g. "09:00-17:00=10,22:00-06:00=0"
+    /// (MB/s, 0 = unlimited). --max-bandwidth applies outside every window.
+    #[arg(long, global = true, value_parser = validate_bwlimit_schedule, help_heading = "Performance")]
+    bwlimit_schedule: Option<String>,
+
     /// Number of parallel file operations / workers (0 = auto)
     /// For network backends (S3, SMB, etc.) auto = 256; for local = CPU count.
+    /// "auto-adaptive" tunes directory-copy concurrency from measured throughput.
     /// Alias: --parallel
-    #[arg(long, global = true, alias = "parallel", help_heading = "Performance")]
-    workers: Option<usize>,
+    #[arg(
+        long,
+        global = true,
+        alias = "parallel",
+        value_name = "N|auto-adaptive",
+        value_parser = parse_workers,
+        help_heading = "Performance"
+    )]
+    workers: Option<WorkersArg>,
 
     /// Per-operation concurrency for multipart transfers
     /// Controls how many parts of a single large file transfer in parallel.
@@ -298,6 +477,11 @@ struct FilteringArgs {
         help_heading = "Filtering"
     )]
     filter_from: Option<PathBuf>,
+
+    /// Hidden files and directories: walk them, or leave them out unless an
+    /// --include pattern names them
+    #[arg(long = "hidden", value_enum, global = true, help_heading = "Filtering")]
+    hidden: Option<HiddenModeArg>,
 }
 
 #[derive(Args)]
@@ -306,6 +490,25 @@ struct ConditionalCopyArgs {
     #[arg(long, short = 'n', global = true, help_heading = "Conditional Copy")]
     no_clobber: bool,
 
+    /// Overwrite existing destination files, overriding the config file
+    #[arg(
+        long,
+        global = true,
+        conflicts_with_all = ["no_clobber", "interactive"],
+        help_heading = "Conditional Copy"
+    )]
+    force: bool,
+
+    /// Ask before overwriting each existing destination file (skips them
+    /// when not on a terminal)
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "no_clobber",
+        help_heading = "Conditional Copy"
+    )]
+    interactive: bool,
+
     /// Only copy if source and destination sizes differ
     #[arg(long, global = true, help_heading = "Conditional Copy")]
     if_size_differ: bool,
@@ -329,6 +532,10 @@ struct OutputArgs {
     #[arg(long = "show-progress", global = true, help_heading = "Output")]
     show_progress: bool,
 
+    /// Progress format: interactive bars, or a JSON event stream on stderr
+    #[arg(long = "progress", value_enum, global = true, help_heading = "Output")]
+    progress: Option<ProgressFormatArg>,
+
     /// Dry run - show what would be copied
     #[arg(long, global = true, help_heading = "Output")]
     dry_run: bool,
@@ -508,6 +715,16 @@ struct AdvancedArgs {
     )]
     manifest_dir: Option<PathBuf>,
 
+    /// Only transfer files changed since a prior manifest directory
+    /// (size + digest), then write the updated manifest set
+    #[arg(
+        long,
+        global = true,
+        value_name = "MANIFEST_DIR",
+        help_heading = "Advanced"
+    )]
+    since: Option<PathBuf>,
+
     /// Check mode for change detection (mod-time, size, checksum, delta)
     #[arg(
         long,
@@ -544,6 +761,16 @@ struct AdvancedArgs {
     )]
     sparse: Option<SparseModeArg>,
 
+    /// Append-only tailing for logs/WALs: move only newly appended bytes (never, auto, always)
+    #[arg(
+        long,
+        value_enum,
+        global = true,
+        help_heading = "Advanced",
+        hide = true
+    )]
+    tail: Option<TailModeArg>,
+
     /// Preserve hardlinks during directory transfers (-H)
     #[arg(
         long = "preserve-hardlinks",
@@ -670,6 +897,37 @@ enum Commands {
         limit: usize,
     },
 
+    /// Run content-defined chunking over a file and print the chunks
+    ///
+    /// Prints `offset,length,hash` for every chunk, then size statistics.
+    /// With --json, every chunk and the summary are NDJSON lines.
+    Chunk {
+        /// File to chunk
+        file: PathBuf,
+
+        /// Target average chunk size, a power of two (e.g. 64KiB, 1MiB)
+        #[arg(long = "avg-size", default_value = "64KiB", value_parser = orbit::commands::chunk::parse_byte_size)]
+        avg_size: usize,
+
+        /// Minimum chunk size (default: avg / 8)
+        #[arg(long = "min-size", alias = "min", value_parser = orbit::commands::chunk::parse_byte_size)]
+        min_size: Option<usize>,
+
+        /// Maximum chunk size (default: avg * 4)
+        #[arg(long = "max-size", alias = "max", value_parser = orbit::commands::chunk::parse_byte_size)]
+        max_size: Option<usize>,
+    },
+
+    /// Estimate dedup savings by chunking every file under a directory
+    ///
+    /// Reports total and unique bytes, the dedup ratio and the most
+    /// duplicated chunks.
+    #[command(name = "dedup-report")]
+    DedupReport {
+        /// Directory to scan
+        dir: PathBuf,
+    },
+
     /// Show transfer statistics
     Stats,
 
@@ -680,6 +938,10 @@ enum Commands {
     Capabilities,
 
     /// Diagnose common issues (config, permissions, connectivity)
+    ///
+    /// Checks required secrets, audit log writability, compiled protocol
+    /// support, zero-copy for --source/--dest and backend reachability.
+    /// Exits non-zero if any check fails.
     Doctor {
         /// Probe a backend URI for live connectivity (repeatable).
         ///
@@ -688,6 +950,14 @@ enum Commands {
         /// backend is also auto-probed.
         #[arg(long = "target", value_name = "URI")]
         target: Vec<String>,
+
+        /// Planned transfer source, checked for existence and zero-copy support
+        #[arg(long, value_name = "PATH")]
+        source: Option<String>,
+
+        /// Planned transfer destination, checked against the source for zero-copy
+        #[arg(long, value_name = "PATH")]
+        dest: Option<String>,
     },
 
     /// Generate shell completions
@@ -700,6 +970,10 @@ enum Commands {
     #[command(subcommand)]
     Manifest(ManifestCommands),
 
+    /// Audit log operations (HMAC chain verification)
+    #[command(subcommand)]
+    Audit(AuditCommands),
+
     /// Execute batch commands from stdin or a file (one command per line)
     ///
     /// Each line may be a full orbit invocation or a shorthand:
@@ -713,8 +987,9 @@ enum Commands {
         file: Option<PathBuf>,
 
         /// Number of parallel workers (default: 256)
-        #[arg(long, default_value = "256")]
-        workers: usize,
+        // Shares the `workers` id with the global flag, so it must share its parser too
+        #[arg(long, default_value = "256", value_parser = parse_workers)]
+        workers: WorkersArg,
     },
 
     /// Stream an S3 object to stdout
@@ -932,6 +1207,71 @@ impl From<SymlinkModeArg> for SymlinkMode {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum SpecialFileModeArg {
+    Skip,
+    Error,
+    Recreate,
+}
+
+impl From<SpecialFileModeArg> for SpecialFileMode {
+    fn from(mode: SpecialFileModeArg) -> Self {
+        match mode {
+            SpecialFileModeArg::Skip => SpecialFileMode::Skip,
+            SpecialFileModeArg::Error => SpecialFileMode::Error,
+            SpecialFileModeArg::Recreate => SpecialFileMode::Recreate,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum HiddenModeArg {
+    Include,
+    Exclude,
+}
+
+impl From<HiddenModeArg> for HiddenMode {
+    fn from(mode: HiddenModeArg) -> Self {
+        match mode {
+            HiddenModeArg::Include => HiddenMode::Include,
+            HiddenModeArg::Exclude => HiddenMode::Exclude,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ArchiveFormatArg {
+    Tar,
+    #[value(name = "tar.zst")]
+    TarZst,
+    Zip,
+}
+
+impl From<ArchiveFormatArg> for ArchiveFormat {
+    fn from(format: ArchiveFormatArg) -> Self {
+        match format {
+            ArchiveFormatArg::Tar => ArchiveFormat::Tar,
+            ArchiveFormatArg::TarZst => ArchiveFormat::TarZst,
+            ArchiveFormatArg::Zip => ArchiveFormat::Zip,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ProgressFormatArg {
+    Bar,
+    Json,
+}
+
+impl From<ProgressFormatArg> for ProgressFormat {
+    fn from(format: ProgressFormatArg) -> Self {
+        match format {
+            ProgressFormatArg::Bar => ProgressFormat::Bar,
+            ProgressFormatArg::Json => ProgressFormat::Json,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum AuditFormatArg {
     Json,
@@ -967,6 +1307,38 @@ impl From<CheckModeArg> for orbit::core::delta::CheckMode {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum DeleteModeArg {
+    Delete,
+    Quarantine,
+    Trash,
+}
+
+impl From<DeleteModeArg> for DeleteMode {
+    fn from(arg: DeleteModeArg) -> Self {
+        match arg {
+            DeleteModeArg::Delete => DeleteMode::Delete,
+            DeleteModeArg::Quarantine => DeleteMode::Quarantine,
+            DeleteModeArg::Trash => DeleteMode::Trash,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ChecksumAlgorithmArg {
+    Sha256,
+    Blake3,
+}
+
+impl From<ChecksumAlgorithmArg> for ChecksumAlgorithm {
+    fn from(arg: ChecksumAlgorithmArg) -> Self {
+        match arg {
+            ChecksumAlgorithmArg::Sha256 => ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithmArg::Blake3 => ChecksumAlgorithm::Blake3,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum ErrorModeArg {
     Abort,
@@ -984,6 +1356,26 @@ impl From<ErrorModeArg> for ErrorMode {
     }
 }
 
+/// Value of `--workers` / `--parallel`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WorkersArg {
+    Count(usize),
+    AutoAdaptive,
+}
+
+/// Clap value parser for `--workers`: a count or `auto-adaptive`
+fn parse_workers(value: &str) -> std::result::Result<WorkersArg, String> {
+    if value.eq_ignore_ascii_case("auto-adaptive") {
+        return Ok(WorkersArg::AutoAdaptive);
+    }
+    value.parse().map(WorkersArg::Count).map_err(|_| {
+        format!(
+            "expected a worker count or 'auto-adaptive', got '{}'",
+            value
+        )
+    })
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum LogLevelArg {
     Error,
@@ -1022,6 +1414,23 @@ impl From<SparseModeArg> for orbit::core::sparse::SparseMode {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum TailModeArg {
+    Never,
+    Auto,
+    Always,
+}
+
+impl From<TailModeArg> for orbit::core::tail::TailMode {
+    fn from(arg: TailModeArg) -> Self {
+        match arg {
+            TailModeArg::Never => orbit::core::tail::TailMode::Never,
+            TailModeArg::Auto => orbit::core::tail::TailMode::Auto,
+            TailModeArg::Always => orbit::core::tail::TailMode::Always,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum InplaceSafetyArg {
     Reflink,
@@ -1109,6 +1518,11 @@ fn resolve_output_modes(inputs: OutputModeInputs) -> (bool, bool) {
     (human_readable, show_stats)
 }
 
+/// Clap value parser for `--bwlimit-schedule`
+fn validate_bwlimit_schedule(spec: &str) -> std::result::Result<String, String> {
+    parse_bandwidth_schedule(spec).map(|_| spec.to_string())
+}
+
 /// Build a resolved transfer config from base config + CLI flags.
 ///
 /// This is the single place where profile selection, auto-network merge,
@@ -1189,6 +1603,21 @@ fn resolve_transfer_config(
     if cli.transfer.recursive {
         config.recursive = true;
     }
+    if let Some(depth) = cli.transfer.max_depth {
+        config.max_depth = Some(depth as usize);
+    }
+    if cli.transfer.one_file_system {
+        config.one_file_system = true;
+    }
+    if cli.transfer.cross_file_systems {
+        config.one_file_system = false;
+    }
+    if let Some(special) = cli.transfer.special {
+        config.special_files = special.into();
+    }
+    if let Some(hidden) = cli.filtering.hidden {
+        config.hidden_files = hidden.into();
+    }
 
     // ── Progress ─────────────────────────────────────────────────
     // JSON mode suppresses progress to keep stdout machine-readable.
@@ -1197,11 +1626,39 @@ fn resolve_transfer_config(
     } else if cli.output.no_progress || quiet || json_output {
         config.show_progress = false;
     }
+    if let Some(format) = cli.output.progress {
+        config.progress_format = format.into();
+    }
+    // The JSON event stream replaces the interactive bars on stderr.
+    if config.progress_format == ProgressFormat::Json {
+        config.show_progress = false;
+    }
 
     // ── Core transfer overrides ──────────────────────────────────
     if let Some(mode) = cli.transfer.mode {
         config.copy_mode = mode.into();
     }
+    if let Some(mode) = cli.transfer.delete_mode {
+        config.delete_mode = mode.into();
+    }
+    if let Some(secs) = cli.transfer.mtime_tolerance {
+        config.mtime_tolerance_secs = secs;
+    }
+    if cli.transfer.checksum_ambiguous_mtime {
+        config.checksum_ambiguous_mtime = true;
+    }
+    if let Some(format) = cli.transfer.archive {
+        config.archive = Some(format.into());
+    }
+    if cli.transfer.extract {
+        config.extract = true;
+    }
+    if cli.transfer.offset.is_some() || cli.transfer.length.is_some() {
+        config.byte_range = Some(orbit::core::range::ByteRange {
+            offset: cli.transfer.offset.unwrap_or(0),
+            length: cli.transfer.length,
+        });
+    }
     if cli.reliability.resume {
         config.resume_enabled = true;
     }
@@ -1214,14 +1671,33 @@ fn resolve_transfer_config(
     if cli.reliability.exponential_backoff {
         config.exponential_backoff = true;
     }
+    if let Some(credits) = cli.reliability.retry_budget {
+        config.retry_budget = Some(std::sync::Arc::new(RetryBudget::per_minute(credits)));
+    }
+    if let Some(secs) = cli.reliability.checkpoint_interval {
+        config.checkpoint_interval_secs = secs;
+    }
+    if let Some(mb) = cli.reliability.checkpoint_bytes {
+        config.checkpoint_interval_bytes = mb.saturating_mul(1024 * 1024);
+    }
     if let Some(size) = cli.performance.chunk_size {
         config.chunk_size = size.saturating_mul(1024);
     }
     if let Some(bw) = cli.performance.max_bandwidth {
         config.max_bandwidth = bw.saturating_mul(1024 * 1024);
     }
-    if let Some(w) = cli.performance.workers {
-        config.parallel = w;
+    if let Some(ref spec) = cli.performance.bwlimit_schedule {
+        // Already validated by clap
+        config.bandwidth_schedule = parse_bandwidth_schedule(spec).unwrap_or_default();
+    }
+    match cli.performance.workers {
+        // An explicit count overrides `adaptive_concurrency` from the config file
+        Some(WorkersArg::Count(w)) => {
+            config.parallel = w;
+            config.adaptive_concurrency = false;
+        }
+        Some(WorkersArg::AutoAdaptive) => config.adaptive_concurrency = true,
+        None => {}
     }
     if let Some(c) = cli.performance.concurrency {
         config.concurrency = c;
@@ -1241,6 +1717,35 @@ fn resolve_transfer_config(
     if cli.reliability.no_verify {
         config.verify_checksum = false;
     }
+    if cli.reliability.preflight {
+        config.preflight = true;
+    } else if cli.reliability.no_preflight {
+        config.preflight = false;
+    }
+    if let Some(mut sample) = cli.reliability.verify {
+        sample.seed = cli.reliability.verify_seed;
+        config.verify_sample = Some(sample);
+    }
+    if let Some(path) = cli.reliability.checksum_manifest.clone() {
+        config.checksum_manifest = true;
+        config.checksum_manifest_path = path.or(config.checksum_manifest_path);
+    }
+    if let Some(algorithm) = cli.reliability.checksum_algorithm {
+        config.checksum_algorithm = algorithm.into();
+    }
+
+    // ── Overwrite policy ─────────────────────────────────────────
+    if cli.conditional.no_clobber {
+        config.overwrite = OverwritePolicy::Never;
+    } else if cli.conditional.force {
+        config.no_clobber = false;
+        config.if_source_newer = false;
+        config.overwrite = OverwritePolicy::Always;
+    } else if cli.conditional.interactive {
+        config.overwrite = OverwritePolicy::Prompt;
+    } else if cli.conditional.if_source_newer {
+        config.overwrite = OverwritePolicy::IfSourceNewer;
+    }
 
     // ── Compression ──────────────────────────────────────────────
     if cli.performance.zstd {
@@ -1406,6 +1911,25 @@ fn run() -> Result<()> {
                 shorthand_source = Some(source.clone());
                 shorthand_dest = Some(dest.clone());
             }
+            // Doctor checks the audit log the transfer would use, which
+            // needs the global flags and loaded config
+            Commands::Doctor {
+                target,
+                source,
+                dest,
+            } => {
+                let options = orbit::commands::doctor::DoctorOptions {
+                    targets: target.clone(),
+                    source: source.clone(),
+                    dest: dest.clone(),
+                    audit_log: cli
+                        .observability
+                        .audit_log
+                        .clone()
+                        .or_else(|| base_config.audit_log_path.clone()),
+                };
+                return orbit::commands::doctor::run_doctor(&options).into_result();
+            }
             // Non-transfer subcommands: handle and return
             _ => {
                 let json = cli.output.json;
@@ -1563,6 +2087,9 @@ fn run() -> Result<()> {
     if let Some(sparse) = cli.advanced.sparse {
         config.sparse_mode = sparse.into();
     }
+    if let Some(tail) = cli.advanced.tail {
+        config.tail_mode = tail.into();
+    }
     if cli.advanced.preserve_hardlinks {
         config.preserve_hardlinks = true;
     }
@@ -1589,6 +2116,9 @@ fn run() -> Result<()> {
         config.generate_manifest = true;
         config.manifest_output_dir = cli.advanced.manifest_dir;
     }
+    if cli.advanced.since.is_some() {
+        config.since_manifest = cli.advanced.since;
+    }
 
     // Show zero-copy status if enabled (suppressed in json/quiet mode)
     if config.use_zero_copy
@@ -1683,7 +2213,9 @@ fn run() -> Result<()> {
     }
 
     // Perform the copy
-    let stats = if source_path.is_dir() && config.recursive {
+    let stats = if config.progress_format == ProgressFormat::Json {
+        copy_with_json_progress(&source_path, &dest_path, &config)?
+    } else if source_path.is_dir() && config.recursive {
         copy_directory(&source_path, &dest_path, &config)?
     } else {
         copy_file(&source_path, &dest_path, &config)?
@@ -1702,6 +2234,30 @@ fn run() -> Result<()> {
     Ok(())
 }
 
+/// Run the copy with progress events streamed to stderr as JSON lines.
+fn copy_with_json_progress(
+    source_path: &std::path::Path,
+    dest_path: &std::path::Path,
+    config: &CopyConfig,
+) -> Result<CopyStats> {
+    let (publisher, subscriber) = ProgressPublisher::unbounded();
+    let renderer = JsonProgressRenderer::new(subscriber, std::io::stderr()).spawn();
+
+    let result = if source_path.is_dir() && config.recursive {
+        copy_directory_impl(source_path, dest_path, config, Some(&publisher))
+    } else {
+        copy_file_impl(source_path, dest_path, config, Some(&publisher))
+    };
+
+    // Dropping the publisher closes the channel so the renderer drains and exits
+    drop(publisher);
+    if let Ok(Err(e)) = renderer.join() {
+        tracing::warn!("JSON progress renderer failed: {}", e);
+    }
+
+    result
+}
+
 /// Handle non-transfer subcommands. Transfer shorthands (cp, sync, backup, mirror, explain)
 /// are handled in run() by falling through to the unified transfer path.
 fn handle_subcommand(command: Commands, json_output: bool) -> Result<()> {
@@ -1710,6 +2266,22 @@ fn handle_subcommand(command: Commands, json_output: bool) -> Result<()> {
         Commands::History { audit_file, limit } => {
             orbit::commands::history::run_history(audit_file.as_ref(), limit, json_output)
         }
+        Commands::Chunk {
+            file,
+            avg_size,
+            min_size,
+            max_size,
+        } => {
+            let options = orbit::commands::chunk::ChunkOptions {
+                avg_size,
+                min_size,
+                max_size,
+            };
+            orbit::commands::chunk::run_chunk(&file, &options, json_output)
+        }
+        Commands::DedupReport { dir } => {
+            orbit::commands::dedup_report::run_dedup_report(&dir, json_output)
+        }
         Commands::Stats => {
             let stats = TransferStats::default();
             stats.print();
@@ -1723,10 +2295,6 @@ fn handle_subcommand(command: Commands, json_output: bool) -> Result<()> {
             print_capabilities();
             Ok(())
         }
-        Commands::Doctor { target } => {
-            orbit::commands::doctor::run_doctor(&target);
-            Ok(())
-        }
         Commands::Completions { shell } => {
             use clap::CommandFactory;
             use clap_complete::generate;
@@ -1737,9 +2305,14 @@ fn handle_subcommand(command: Commands, json_output: bool) -> Result<()> {
         Commands::Manifest(manifest_cmd) => {
             orbit::commands::manifest::handle_manifest_command(manifest_cmd)
         }
-        Commands::Run { file, workers } => {
-            orbit::commands::batch::handle_run_command(file, workers)
-        }
+        Commands::Audit(audit_cmd) => orbit::commands::audit::handle_audit_command(audit_cmd),
+        Commands::Run { file, workers } => match workers {
+            WorkersArg::Count(workers) => orbit::commands::batch::handle_run_command(file, workers),
+            WorkersArg::AutoAdaptive => Err(OrbitError::Config(
+                "orbit run needs a fixed --workers count; auto-adaptive applies to directory copies"
+                    .to_string(),
+            )),
+        },
         #[cfg(feature = "s3-cli")]
         Commands::Cat { uri } => orbit::commands::s3::handle_cat_command(&uri),
         #[cfg(feature = "s3-cli")]
@@ -1785,14 +2358,15 @@ fn handle_subcommand(command: Commands, json_output: bool) -> Result<()> {
         Commands::Mb { bucket } => orbit::commands::s3::handle_mb_command(&bucket),
         #[cfg(feature = "s3-cli")]
         Commands::Rb { bucket } => orbit::commands::s3::handle_rb_command(&bucket),
-        // Transfer shorthands are handled in run() — this arm is unreachable
-        // because run() extracts them before calling handle_subcommand().
+        // Transfer shorthands and doctor are handled in run() — this arm is
+        // unreachable because run() extracts them before calling handle_subcommand().
         Commands::Cp { .. }
         | Commands::Sync { .. }
         | Commands::Backup { .. }
         | Commands::MirrorCmd { .. }
-        | Commands::Explain { .. } => {
-            unreachable!("transfer shorthands handled in run()")
+        | Commands::Explain { .. }
+        | Commands::Doctor { .. } => {
+            unreachable!("transfer shorthands and doctor handled in run()")
         }
     }
 }
@@ -2128,7 +2702,7 @@ mod tests {
         let cli =
             Cli::try_parse_from(["orbit", "-s", "src.txt", "-d", "dst.txt", "--workers", "64"])
                 .unwrap();
-        assert_eq!(cli.performance.workers, Some(64));
+        assert_eq!(cli.performance.workers, Some(WorkersArg::Count(64)));
     }
 
     #[test]
@@ -2143,7 +2717,305 @@ mod tests {
             "32",
         ])
         .unwrap();
-        assert_eq!(cli.performance.workers, Some(32));
+        assert_eq!(cli.performance.workers, Some(WorkersArg::Count(32)));
+    }
+
+    #[test]
+    fn test_parallel_auto_adaptive() {
+        let cli = Cli::try_parse_from([
+            "orbit",
+            "-s",
+            "src",
+            "-d",
+            "dst",
+            "--parallel",
+            "auto-adaptive",
+        ])
+        .unwrap();
+        assert_eq!(cli.performance.workers, Some(WorkersArg::AutoAdaptive));
+
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert!(config.adaptive_concurrency);
+        assert_eq!(config.parallel, 0);
+
+        assert!(
+            Cli::try_parse_from(["orbit", "-s", "src", "-d", "dst", "--parallel", "lots",])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parallel_count_overrides_adaptive_config() {
+        let cli =
+            Cli::try_parse_from(["orbit", "-s", "src", "-d", "dst", "--parallel", "8"]).unwrap();
+        let base = CopyConfig {
+            adaptive_concurrency: true,
+            ..CopyConfig::default()
+        };
+
+        let (config, _, _) = resolve_transfer_config(&cli, base, false, None, None, false, true);
+        assert!(!config.adaptive_concurrency);
+        assert_eq!(config.parallel, 8);
+    }
+
+    #[test]
+    fn test_max_depth_and_one_file_system_flags() {
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "src", "dst", "--max-depth", "2", "-x"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.max_depth, Some(2));
+        assert!(config.one_file_system);
+
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.max_depth, None);
+        assert!(config.one_file_system);
+
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "src", "dst", "--cross-file-systems"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert!(!config.one_file_system);
+
+        assert!(
+            Cli::try_parse_from(["orbit", "cp", "src", "dst", "-x", "--cross-file-systems"])
+                .is_err()
+        );
+
+        assert!(Cli::try_parse_from(["orbit", "cp", "src", "dst", "--max-depth", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_special_flag() {
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.special_files, SpecialFileMode::Skip);
+
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "src", "dst", "--special", "recreate"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.special_files, SpecialFileMode::Recreate);
+
+        assert!(Cli::try_parse_from(["orbit", "cp", "src", "dst", "--special", "copy"]).is_err());
+    }
+
+    #[test]
+    fn test_hidden_flag() {
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.hidden_files, HiddenMode::Include);
+
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "src", "dst", "--hidden", "exclude"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.hidden_files, HiddenMode::Exclude);
+
+        assert!(Cli::try_parse_from(["orbit", "cp", "src", "dst", "--hidden", "skip"]).is_err());
+    }
+
+    #[test]
+    fn test_extract_flag() {
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "bundle.tar.zst", "out", "--extract"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert!(config.extract);
+
+        assert!(Cli::try_parse_from([
+            "orbit",
+            "cp",
+            "src",
+            "dst",
+            "--extract",
+            "--archive",
+            "tar"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_offset_and_length_flags() {
+        let cli = Cli::try_parse_from([
+            "orbit", "cp", "big.img", "out.img", "--offset", "4096", "--length", "1024",
+        ])
+        .unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(
+            config.byte_range,
+            Some(orbit::core::range::ByteRange {
+                offset: 4096,
+                length: Some(1024),
+            })
+        );
+
+        let cli = Cli::try_parse_from(["orbit", "cp", "big.img", "out.img"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.byte_range, None);
+
+        assert!(
+            Cli::try_parse_from(["orbit", "cp", "a", "b", "--offset", "1", "--extract"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_mtime_tolerance_flags() {
+        let cli = Cli::try_parse_from([
+            "orbit",
+            "cp",
+            "src",
+            "dst",
+            "--mode",
+            "sync",
+            "--mtime-tolerance",
+            "30",
+            "--checksum-ambiguous-mtime",
+        ])
+        .unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.mtime_tolerance_secs, 30);
+        assert!(config.checksum_ambiguous_mtime);
+
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(config.mtime_tolerance_secs, 2);
+        assert!(!config.checksum_ambiguous_mtime);
+    }
+
+    #[test]
+    fn test_overwrite_policy_flags() {
+        let resolve = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(["orbit", "cp", "src", "dst"].iter().chain(args)).unwrap();
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true).0
+        };
+
+        assert_eq!(resolve(&[]).overwrite, OverwritePolicy::Always);
+        assert_eq!(resolve(&["--no-clobber"]).overwrite, OverwritePolicy::Never);
+        assert_eq!(
+            resolve(&["--if-source-newer"]).overwrite,
+            OverwritePolicy::IfSourceNewer
+        );
+        assert_eq!(
+            resolve(&["--interactive"]).overwrite,
+            OverwritePolicy::Prompt
+        );
+
+        // --force overrides a config file that disables overwriting
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst", "--force"]).unwrap();
+        let file_config = CopyConfig {
+            no_clobber: true,
+            if_source_newer: true,
+            overwrite: OverwritePolicy::Never,
+            ..Default::default()
+        };
+        let (config, _, _) =
+            resolve_transfer_config(&cli, file_config, false, None, None, false, true);
+        assert_eq!(config.overwrite, OverwritePolicy::Always);
+        assert!(!config.no_clobber);
+        assert!(!config.if_source_newer);
+
+        assert!(Cli::try_parse_from(["orbit", "cp", "a", "b", "--force", "--no-clobber"]).is_err());
+    }
+
+    #[test]
+    fn test_preflight_flags() {
+        let resolve = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(["orbit", "cp", "src", "dst"].iter().chain(args)).unwrap();
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true).0
+        };
+        assert!(resolve(&[]).preflight);
+        assert!(!resolve(&["--no-preflight"]).preflight);
+
+        let cli = Cli::try_parse_from(["orbit", "cp", "src", "dst", "--preflight"]).unwrap();
+        let file_config = CopyConfig {
+            preflight: false,
+            ..Default::default()
+        };
+        let (config, _, _) =
+            resolve_transfer_config(&cli, file_config, false, None, None, false, true);
+        assert!(config.preflight);
+    }
+
+    #[test]
+    fn test_verify_sample_flag() {
+        let cli = Cli::try_parse_from([
+            "orbit",
+            "cp",
+            "src",
+            "dst",
+            "--verify",
+            "sample:2.5",
+            "--verify-seed",
+            "99",
+        ])
+        .unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert_eq!(
+            config.verify_sample,
+            Some(orbit::core::verify_sample::VerifySample {
+                percent: 2.5,
+                seed: Some(99),
+            })
+        );
+
+        assert!(Cli::try_parse_from(["orbit", "cp", "a", "b", "--verify", "sample:0"]).is_err());
+        assert!(Cli::try_parse_from(["orbit", "cp", "a", "b", "--verify-seed", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_checksum_manifest_flag() {
+        // A bare flag must not swallow the positional that follows it
+        let cli =
+            Cli::try_parse_from(["orbit", "cp", "--checksum-manifest", "src", "dst"]).unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert!(config.checksum_manifest);
+        assert_eq!(config.checksum_manifest_path, None);
+        assert_eq!(config.checksum_algorithm, ChecksumAlgorithm::Sha256);
+
+        let cli = Cli::try_parse_from([
+            "orbit",
+            "-s",
+            "src",
+            "-d",
+            "dst",
+            "--checksum-manifest=sums.txt",
+            "--checksum-algorithm",
+            "blake3",
+        ])
+        .unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        assert!(config.checksum_manifest);
+        assert_eq!(
+            config.checksum_manifest_path,
+            Some(PathBuf::from("sums.txt"))
+        );
+        assert_eq!(config.checksum_algorithm, ChecksumAlgorithm::Blake3);
+    }
+
+    #[test]
+    fn test_retry_budget_flag() {
+        let cli = Cli::try_parse_from(["orbit", "-s", "src", "-d", "dst", "--retry-budget", "30"])
+            .unwrap();
+        let (config, _, _) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, true);
+        let metrics = config.retry_budget.unwrap().metrics();
+        assert_eq!(metrics.capacity, 30);
+        assert_eq!(metrics.granted, 0);
     }
 
     #[test]
@@ -2219,7 +3091,7 @@ mod tests {
         match cli.command {
             Some(Commands::Run { file, workers }) => {
                 assert!(file.is_none());
-                assert_eq!(workers, 256);
+                assert_eq!(workers, WorkersArg::Count(256));
             }
             _ => panic!("Expected Run subcommand"),
         }
@@ -2231,7 +3103,7 @@ mod tests {
         match cli.command {
             Some(Commands::Run { file, workers }) => {
                 assert_eq!(file, Some(PathBuf::from("commands.txt")));
-                assert_eq!(workers, 256);
+                assert_eq!(workers, WorkersArg::Count(256));
             }
             _ => panic!("Expected Run subcommand"),
         }
@@ -2243,7 +3115,7 @@ mod tests {
         match cli.command {
             Some(Commands::Run { file, workers }) => {
                 assert!(file.is_none());
-                assert_eq!(workers, 128);
+                assert_eq!(workers, WorkersArg::Count(128));
             }
             _ => panic!("Expected Run subcommand"),
         }
@@ -2341,7 +3213,7 @@ mod tests {
             "--recursive",
         ])
         .unwrap();
-        assert_eq!(cli.performance.workers, Some(128));
+        assert_eq!(cli.performance.workers, Some(WorkersArg::Count(128)));
         assert_eq!(cli.performance.concurrency, Some(8));
         assert!(cli.output.stat);
         assert!(cli.output.human_readable);
@@ -2537,7 +3409,7 @@ mod tests {
         let cli = Cli::try_parse_from(["orbit", "doctor"]).unwrap();
         assert!(matches!(
             cli.command,
-            Some(Commands::Doctor { ref target }) if target.is_empty()
+            Some(Commands::Doctor { ref target, .. }) if target.is_empty()
         ));
     }
 
@@ -2545,7 +3417,7 @@ mod tests {
     fn test_doctor_with_target() {
         let cli = Cli::try_parse_from(["orbit", "doctor", "--target", "s3://my-bucket"]).unwrap();
         match cli.command {
-            Some(Commands::Doctor { target }) => {
+            Some(Commands::Doctor { target, .. }) => {
                 assert_eq!(target, vec!["s3://my-bucket".to_string()]);
             }
             _ => panic!("Expected Doctor subcommand with target"),
@@ -2564,13 +3436,27 @@ mod tests {
         ])
         .unwrap();
         match cli.command {
-            Some(Commands::Doctor { target }) => {
+            Some(Commands::Doctor { target, .. }) => {
                 assert_eq!(target, vec!["s3://a".to_string(), "ssh://b/c".to_string()]);
             }
             _ => panic!("Expected Doctor subcommand with repeated targets"),
         }
     }
 
+    #[test]
+    fn test_doctor_with_source_and_dest() {
+        let cli =
+            Cli::try_parse_from(["orbit", "doctor", "--source", "/data", "--dest", "/backup"])
+                .unwrap();
+        match cli.command {
+            Some(Commands::Doctor { source, dest, .. }) => {
+                assert_eq!(source.as_deref(), Some("/data"));
+                assert_eq!(dest.as_deref(), Some("/backup"));
+            }
+            _ => panic!("Expected Doctor subcommand with source and dest"),
+        }
+    }
+
     /// Regression: `--target` with no URI must be a parse error, not a
     /// silent no-op. Previously `num_args = 0..` allowed empty values.
     #[test]
@@ -3112,7 +3998,7 @@ mod tests {
         );
         assert_eq!(
             cli.performance.workers,
-            Some(16),
+            Some(WorkersArg::Count(16)),
             "--workers should be parsed for sync"
         );
     }
@@ -3505,6 +4391,12 @@ mod tests {
             "update",
             "--retry-delay",
             "9",
+            "--delete-mode",
+            "quarantine",
+            "--checkpoint-interval",
+            "30",
+            "--checkpoint-bytes",
+            "64",
             "--chunk-size",
             "2",
             "--max-bandwidth",
@@ -3526,6 +4418,9 @@ mod tests {
 
         assert_eq!(config.copy_mode, CopyMode::Update);
         assert_eq!(config.retry_delay_secs, 9);
+        assert_eq!(config.delete_mode, DeleteMode::Quarantine);
+        assert_eq!(config.checkpoint_interval_secs, 30);
+        assert_eq!(config.checkpoint_interval_bytes, 64 * 1024 * 1024);
         assert_eq!(config.chunk_size, 2 * 1024);
         assert_eq!(config.max_bandwidth, 3 * 1024 * 1024);
         assert_eq!(config.concurrency, 8);
@@ -3558,6 +4453,35 @@ mod tests {
         assert!(!disabled.use_zero_copy);
     }
 
+    #[test]
+    fn test_resolve_transfer_config_bwlimit_schedule() {
+        let cli = Cli::try_parse_from([
+            "orbit",
+            "-s",
+            "/src",
+            "-d",
+            "/dst",
+            "--bwlimit-schedule",
+            "09:00-17:00=10,22:00-06:00=0",
+        ])
+        .unwrap();
+        let (config, _json_output, _quiet) =
+            resolve_transfer_config(&cli, CopyConfig::default(), false, None, None, false, false);
+        assert_eq!(config.bandwidth_schedule.len(), 2);
+        assert_eq!(config.bandwidth_schedule[0].limit, 10 * 1024 * 1024);
+
+        assert!(Cli::try_parse_from([
+            "orbit",
+            "-s",
+            "/src",
+            "-d",
+            "/dst",
+            "--bwlimit-schedule",
+            "business-hours=10",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_handle_subcommand_simple_variants_return_ok() {
         assert!(handle_subcommand(Commands::Stats, false).is_ok());
@@ -3566,7 +4490,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "transfer shorthands handled in run()")]
+    #[should_panic(expected = "transfer shorthands and doctor handled in run()")]
     fn test_handle_subcommand_shorthand_is_unreachable() {
         let _ = handle_subcommand(
             Commands::Sync {
@@ -9,12 +9,29 @@ use orbit_core_audit::TelemetryLogger;
 use orbit_core_manifest::{
     CargoManifest, Chunking, Encryption, Endpoint, FileRef, FlightPlan, Policy, WindowMeta,
 };
-use orbit_core_starmap::{ChunkMeta, StarMapBuilder};
+use orbit_core_starmap::{ChunkMeta, HashAlgorithm, StarMapBuilder};
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
+/// File references held in memory before they are spilled to disk
+const FILE_REF_BUFFER: usize = 1024;
+
+/// Temporary NDJSON of file references, assembled into the flight plan by
+/// [`ManifestGenerator::finalize`]
+const FILE_REFS_SPILL: &str = "job.files.ndjson.tmp";
+
+/// Files handed to each worker per batch by
+/// [`ManifestGenerator::generate_file_manifests`]
+const FILES_PER_JOB_BATCH: usize = 8;
+
 /// Manifest generator for transfer operations
+///
+/// Cargo manifests and star maps are written as each file is processed.
+/// File references are buffered up to a fixed count and then appended to a
+/// temporary NDJSON file, so memory stays bounded however many files a job
+/// has; `finalize` streams them into the flight plan.
 pub struct ManifestGenerator {
     /// Job ID for this transfer
     job_id: String,
@@ -28,6 +45,14 @@ pub struct ManifestGenerator {
     telemetry: TelemetryLogger,
     /// Total bytes processed across all files
     total_bytes: u64,
+    /// File references not yet spilled to disk
+    pending_refs: Vec<FileRef>,
+    /// Most references kept in `pending_refs`
+    ref_buffer: usize,
+    /// Spill file for file references, opened on first overflow
+    spill: Option<BufWriter<File>>,
+    /// Files added so far
+    file_count: u32,
 }
 
 impl ManifestGenerator {
@@ -36,10 +61,11 @@ impl ManifestGenerator {
         // Generate job ID
         let job_id = format!("job-{}", chrono::Utc::now().format("%Y-%m-%dT%H_%M_%SZ"));
 
-        // Determine output directory
+        // Determine output directory; `--since` refreshes its baseline in place
         let output_dir = config
             .manifest_output_dir
             .clone()
+            .or_else(|| config.since_manifest.clone())
             .unwrap_or_else(|| PathBuf::from("/var/lib/orbit/manifests").join(&job_id));
 
         // Create output directory
@@ -70,11 +96,80 @@ impl ManifestGenerator {
             flight_plan,
             telemetry,
             total_bytes: 0,
+            pending_refs: Vec::new(),
+            ref_buffer: FILE_REF_BUFFER,
+            spill: None,
+            file_count: 0,
         })
     }
 
     /// Generate manifest for a single file
     pub fn generate_file_manifest(&mut self, file_path: &Path, relative_path: &str) -> Result<()> {
+        let file_size = std::fs::metadata(file_path).map_err(OrbitError::Io)?.len();
+        self.log_file_start(relative_path, file_size)?;
+
+        let (file_size, file_ref) = self.build_file_manifest(file_path, relative_path)?;
+        self.total_bytes += file_size;
+        self.add_file_ref(file_ref)
+    }
+
+    /// Generate manifests for `(path, relative path)` pairs on up to `jobs`
+    /// threads
+    ///
+    /// Files are chunked and hashed in parallel a batch at a time, while
+    /// file references are added from this thread in input order, so the
+    /// flight plan is identical to a serial run and memory stays bounded by
+    /// the batch size.
+    pub fn generate_file_manifests<I>(&mut self, files: I, jobs: usize) -> Result<()>
+    where
+        I: IntoIterator<Item = (PathBuf, String)>,
+    {
+        use rayon::prelude::*;
+
+        if jobs <= 1 {
+            for (path, relative_path) in files {
+                self.generate_file_manifest(&path, &relative_path)?;
+            }
+            return Ok(());
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .map_err(|e| OrbitError::Parallel(e.to_string()))?;
+
+        let mut files = files.into_iter().peekable();
+        let batch_size = jobs * FILES_PER_JOB_BATCH;
+        while files.peek().is_some() {
+            let batch: Vec<(PathBuf, String)> = files.by_ref().take(batch_size).collect();
+            for (path, relative_path) in &batch {
+                let file_size = std::fs::metadata(path).map_err(OrbitError::Io)?.len();
+                self.log_file_start(relative_path, file_size)?;
+            }
+
+            let built: Vec<(u64, FileRef)> = pool.install(|| {
+                batch
+                    .par_iter()
+                    .map(|(path, relative_path)| self.build_file_manifest(path, relative_path))
+                    .collect::<Result<_>>()
+            })?;
+            for (file_size, file_ref) in built {
+                self.total_bytes += file_size;
+                self.add_file_ref(file_ref)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn log_file_start(&mut self, relative_path: &str, file_size: u64) -> Result<()> {
+        self.telemetry
+            .log_file_start(&self.job_id, relative_path, file_size)
+            .map_err(|e| OrbitError::Other(format!("Telemetry error: {}", e)))
+    }
+
+    /// Chunk one file and write its cargo manifest and star map, returning
+    /// the file's size and its flight plan reference
+    fn build_file_manifest(&self, file_path: &Path, relative_path: &str) -> Result<(u64, FileRef)> {
         // Open file
         let mut file = File::open(file_path).map_err(OrbitError::Io)?;
 
@@ -82,18 +177,11 @@ impl ManifestGenerator {
         let metadata = file.metadata().map_err(OrbitError::Io)?;
         let file_size = metadata.len();
 
-        // Accumulate total bytes
-        self.total_bytes += file_size;
-
-        // Log file start
-        self.telemetry
-            .log_file_start(&self.job_id, relative_path, file_size)
-            .map_err(|e| OrbitError::Other(format!("Telemetry error: {}", e)))?;
-
         // Create chunking configuration
+        let chunk_kib = self.chunk_kib_for(file_size);
         let chunking = match &self.chunking_strategy {
-            ChunkingStrategy::Cdc { avg_kib, algo } => Chunking::cdc(*avg_kib, algo),
-            ChunkingStrategy::Fixed { size_kib } => Chunking::fixed(*size_kib),
+            ChunkingStrategy::Cdc { algo, .. } => Chunking::cdc(chunk_kib, algo),
+            ChunkingStrategy::Fixed { .. } => Chunking::fixed(chunk_kib),
         };
 
         // Create cargo manifest
@@ -151,10 +239,62 @@ impl ManifestGenerator {
         let starmap_path = self.output_dir.join(&starmap_filename);
         std::fs::write(&starmap_path, starmap_data).map_err(OrbitError::Io)?;
 
-        // Add file reference to flight plan
-        self.flight_plan
-            .add_file(FileRef::new(relative_path, &cargo_filename).with_starmap(&starmap_filename));
+        Ok((
+            file_size,
+            FileRef::new(relative_path, &cargo_filename).with_starmap(&starmap_filename),
+        ))
+    }
+
+    /// Add a file unchanged since `prior` without re-hashing it
+    ///
+    /// The prior cargo manifest and star map are reused as-is, copied into
+    /// the output directory when it differs from the baseline's.
+    pub fn carry_forward(&mut self, prior: &PriorManifest, relative_path: &str) -> Result<()> {
+        let entry = prior.files.get(relative_path).ok_or_else(|| {
+            OrbitError::Other(format!("{} is not in the prior manifest", relative_path))
+        })?;
+
+        if prior.dir != self.output_dir {
+            let mut names = vec![entry.file_ref.cargo.as_str()];
+            names.extend(entry.file_ref.starmap.as_deref());
+            for name in names {
+                std::fs::copy(prior.dir.join(name), self.output_dir.join(name))
+                    .map_err(OrbitError::Io)?;
+            }
+        }
+
+        self.total_bytes += entry.cargo.size;
+        self.add_file_ref(entry.file_ref.clone())
+    }
+
+    /// Queue a file reference, spilling the buffer to disk when it is full
+    fn add_file_ref(&mut self, file_ref: FileRef) -> Result<()> {
+        self.pending_refs.push(file_ref);
+        self.file_count += 1;
+        if self.pending_refs.len() >= self.ref_buffer {
+            self.spill_refs()?;
+        }
+        Ok(())
+    }
 
+    /// Append buffered file references to the spill file
+    fn spill_refs(&mut self) -> Result<()> {
+        if self.pending_refs.is_empty() {
+            return Ok(());
+        }
+        let spill = match &mut self.spill {
+            Some(spill) => spill,
+            None => {
+                let file =
+                    File::create(self.output_dir.join(FILE_REFS_SPILL)).map_err(OrbitError::Io)?;
+                self.spill.insert(BufWriter::new(file))
+            }
+        };
+        for file_ref in self.pending_refs.drain(..) {
+            serde_json::to_writer(&mut *spill, &file_ref)
+                .map_err(|e| OrbitError::Other(format!("Failed to spill file ref: {}", e)))?;
+            spill.write_all(b"\n").map_err(OrbitError::Io)?;
+        }
         Ok(())
     }
 
@@ -163,33 +303,72 @@ impl ManifestGenerator {
         // Finalize flight plan
         self.flight_plan.finalize(job_digest.to_string());
 
-        // Save flight plan
+        // Save flight plan, streaming spilled file refs ahead of buffered ones
         let flight_plan_path = self.output_dir.join("job.flightplan.json");
-        self.flight_plan
-            .save(&flight_plan_path)
-            .map_err(|e| OrbitError::Other(format!("Failed to save flight plan: {}", e)))?;
+        let spill_path = self.output_dir.join(FILE_REFS_SPILL);
+        let spilled = match self.spill.take() {
+            Some(spill) => {
+                spill
+                    .into_inner()
+                    .map_err(|e| OrbitError::Io(e.into_error()))?;
+                let reader = BufReader::new(File::open(&spill_path).map_err(OrbitError::Io)?);
+                Some(reader.lines().map(|line| {
+                    Ok::<_, orbit_core_manifest::Error>(serde_json::from_str::<FileRef>(&line?)?)
+                }))
+            }
+            None => None,
+        };
+        let buffered = std::mem::take(&mut self.pending_refs).into_iter().map(Ok);
+        let saved = self.flight_plan.save_with_files(
+            &flight_plan_path,
+            spilled.into_iter().flatten().chain(buffered),
+        );
+        if spill_path.exists() {
+            std::fs::remove_file(&spill_path).map_err(OrbitError::Io)?;
+        }
+        saved.map_err(|e| OrbitError::Other(format!("Failed to save flight plan: {}", e)))?;
 
         // Log job completion with accumulated total bytes
-        let file_count = self.flight_plan.files.len() as u32;
         self.telemetry
-            .log_job_complete(&self.job_id, job_digest, file_count, self.total_bytes)
+            .log_job_complete(&self.job_id, job_digest, self.file_count, self.total_bytes)
             .map_err(|e| OrbitError::Other(format!("Telemetry error: {}", e)))?;
 
         Ok(())
     }
 
+    /// Chunk size (KiB) used for a file of the given size.
+    ///
+    /// Fixed chunking always uses the configured size. CDC chunking never goes
+    /// below the size tier from [`orbit_core_cdc::ChunkConfig::for_file_size`],
+    /// so huge files get larger chunks and a smaller index.
+    fn chunk_kib_for(&self, file_size: u64) -> u32 {
+        match &self.chunking_strategy {
+            ChunkingStrategy::Cdc { avg_kib, .. } => {
+                let tier_kib =
+                    (orbit_core_cdc::ChunkConfig::for_file_size(file_size).avg_size / 1024) as u32;
+                (*avg_kib).max(tier_kib)
+            }
+            ChunkingStrategy::Fixed { size_kib } => *size_kib,
+        }
+    }
+
     /// Chunk a file and generate windows
+    ///
+    /// Window roots are folded in as each chunk is hashed, so only the
+    /// windows overlapping the current chunk are held open.
     fn chunk_file(
         &self,
         file: &mut File,
         file_size: u64,
     ) -> Result<(Vec<ChunkMeta>, Vec<InternalWindowMeta>)> {
-        let chunk_size = match &self.chunking_strategy {
-            ChunkingStrategy::Cdc { avg_kib, .. } => (*avg_kib as usize) * 1024,
-            ChunkingStrategy::Fixed { size_kib } => (*size_kib as usize) * 1024,
-        };
-
-        let mut chunks = Vec::new();
+        let chunk_size = self.chunk_kib_for(file_size) as usize * 1024;
+        let total_chunks = file_size.div_ceil(chunk_size as u64) as u32;
+        let layout = window_layout(total_chunks);
+
+        let mut chunks = Vec::with_capacity(total_chunks as usize);
+        let mut windows = Vec::with_capacity(layout.len());
+        let mut open: Vec<(u32, WindowMerkleBuilder)> = Vec::new();
+        let mut next_window = 0usize;
         let mut offset = 0u64;
         let mut buffer = vec![0u8; chunk_size];
 
@@ -197,7 +376,9 @@ impl ManifestGenerator {
         file.seek(SeekFrom::Start(0)).map_err(OrbitError::Io)?;
 
         loop {
-            let bytes_read = file.read(&mut buffer).map_err(OrbitError::Io)?;
+            // Fill whole chunks so every chunk but the last is `chunk_size`,
+            // which the window layout and `hash_window` rely on
+            let bytes_read = read_full(file, &mut buffer)?;
 
             if bytes_read == 0 {
                 break;
@@ -206,11 +387,38 @@ impl ManifestGenerator {
             // Calculate BLAKE3 hash of chunk
             let hash = blake3::hash(&buffer[..bytes_read]);
             let content_id = *hash.as_bytes();
+            let index = chunks.len() as u32;
+            orbit_observability::metrics::record_chunk(bytes_read);
+
+            while next_window < layout.len() && layout[next_window].0 == index {
+                open.push((next_window as u32, WindowMerkleBuilder::new()));
+                next_window += 1;
+            }
+            for (_, builder) in open.iter_mut() {
+                builder.push(&content_id);
+            }
+            let mut i = 0;
+            while i < open.len() {
+                let (first_chunk, count) = layout[open[i].0 as usize];
+                if index + 1 == first_chunk + count as u32 {
+                    let (id, builder) = open.remove(i);
+                    windows.push(InternalWindowMeta {
+                        id,
+                        first_chunk,
+                        count,
+                        merkle_root: builder.finalize(),
+                        overlap: Some(WINDOW_OVERLAP),
+                    });
+                } else {
+                    i += 1;
+                }
+            }
 
             chunks.push(ChunkMeta {
                 offset,
                 length: bytes_read as u32,
                 content_id,
+                algorithm: HashAlgorithm::Blake3,
             });
 
             offset += bytes_read as u64;
@@ -224,47 +432,43 @@ impl ManifestGenerator {
             )));
         }
 
-        // Create windows (64 chunks per window, 4 chunk overlap)
-        let mut windows = Vec::new();
-        let chunks_per_window = 64u32;
-        let overlap = 4u16;
-
-        let mut window_id = 0u32;
-        let mut first_chunk = 0u32;
-
-        while first_chunk < chunks.len() as u32 {
-            let remaining = chunks.len() as u32 - first_chunk;
-            let count = remaining.min(chunks_per_window) as u16;
-
-            // Calculate merkle root for this window (simplified - just hash the chunk IDs)
-            let window_chunks: Vec<_> = chunks
-                .iter()
-                .skip(first_chunk as usize)
-                .take(count as usize)
-                .collect();
+        windows.sort_by_key(|w| w.id);
+        Ok((chunks, windows))
+    }
+}
 
-            let mut hasher = blake3::Hasher::new();
-            for chunk in &window_chunks {
-                hasher.update(&chunk.content_id);
-            }
-            let merkle_root = *hasher.finalize().as_bytes();
-
-            windows.push(InternalWindowMeta {
-                id: window_id,
-                first_chunk,
-                count,
-                merkle_root,
-                overlap: Some(overlap),
-            });
+/// Chunks per window
+const CHUNKS_PER_WINDOW: u32 = 64;
+
+/// Chunks shared between consecutive windows
+const WINDOW_OVERLAP: u16 = 4;
+
+/// `(first_chunk, count)` of each window over `total_chunks` chunks
+fn window_layout(total_chunks: u32) -> Vec<(u32, u16)> {
+    let mut layout = Vec::new();
+    let mut first_chunk = 0u32;
+    while first_chunk < total_chunks {
+        let count = (total_chunks - first_chunk).min(CHUNKS_PER_WINDOW) as u16;
+        layout.push((first_chunk, count));
+        // Ensure we always advance by at least 1 chunk to prevent infinite loops
+        // when count <= overlap (e.g., small files with few chunks)
+        first_chunk += (count as u32).saturating_sub(WINDOW_OVERLAP as u32).max(1);
+    }
+    layout
+}
 
-            window_id += 1;
-            // Ensure we always advance by at least 1 chunk to prevent infinite loops
-            // when count <= overlap (e.g., small files with few chunks)
-            first_chunk += (count as u32).saturating_sub(overlap as u32).max(1);
+/// Read until `buffer` is full or the file ends
+fn read_full(file: &mut File, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(OrbitError::Io(e)),
         }
-
-        Ok((chunks, windows))
     }
+    Ok(filled)
 }
 
 /// Internal window metadata with byte array merkle root
@@ -277,6 +481,246 @@ struct InternalWindowMeta {
     pub overlap: Option<u16>,
 }
 
+/// Incremental Merkle root of a window (simplified - a BLAKE3 hash over
+/// the chunk IDs in order)
+///
+/// Chunk IDs are folded in as they are produced, so an open window costs
+/// one hasher no matter how many chunks it spans.
+#[derive(Debug, Clone, Default)]
+pub struct WindowMerkleBuilder {
+    hasher: blake3::Hasher,
+    chunks: u64,
+}
+
+impl WindowMerkleBuilder {
+    /// Start an empty window
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in the next chunk's content ID
+    pub fn push(&mut self, chunk_id: &[u8; 32]) {
+        self.hasher.update(chunk_id);
+        self.chunks += 1;
+    }
+
+    /// Number of chunks folded in so far
+    pub fn len(&self) -> u64 {
+        self.chunks
+    }
+
+    /// Whether no chunks have been folded in
+    pub fn is_empty(&self) -> bool {
+        self.chunks == 0
+    }
+
+    /// The window's root
+    pub fn finalize(&self) -> [u8; 32] {
+        *self.hasher.finalize().as_bytes()
+    }
+}
+
+/// Outcome of re-hashing a file against its cargo manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepVerifyReport {
+    /// Number of windows re-hashed
+    pub windows_checked: usize,
+    /// IDs of windows whose Merkle root does not match the manifest
+    pub mismatched_windows: Vec<u32>,
+    /// Whether the file size matches the manifest
+    pub size_matches: bool,
+}
+
+impl DeepVerifyReport {
+    /// True when the size and every window match
+    pub fn is_ok(&self) -> bool {
+        self.size_matches && self.mismatched_windows.is_empty()
+    }
+}
+
+/// Re-hash every window of `path` and compare it with `cargo`.
+///
+/// Windows are hashed on up to `max_parallel` threads, each reading its own
+/// byte range one chunk at a time, so memory stays at roughly
+/// `max_parallel * chunk size` no matter how large the file is. A
+/// `max_parallel` of 1 hashes windows serially on the calling thread.
+pub fn verify_file_windows(
+    path: &Path,
+    cargo: &CargoManifest,
+    max_parallel: usize,
+) -> Result<DeepVerifyReport> {
+    use rayon::prelude::*;
+
+    let file_size = std::fs::metadata(path).map_err(OrbitError::Io)?.len();
+    let chunk_size = window_chunk_size(cargo)?;
+
+    let check = |window: &WindowMeta| -> Result<Option<u32>> {
+        let root = hash_window(path, file_size, chunk_size, window)?;
+        Ok((hex::encode(root) != window.merkle_root).then_some(window.id))
+    };
+
+    let results: Vec<Option<u32>> = if max_parallel <= 1 {
+        cargo.windows.iter().map(check).collect::<Result<_>>()?
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(max_parallel)
+            .build()
+            .map_err(|e| OrbitError::Parallel(e.to_string()))?;
+        pool.install(|| cargo.windows.par_iter().map(check).collect::<Result<_>>())?
+    };
+
+    Ok(DeepVerifyReport {
+        windows_checked: cargo.windows.len(),
+        mismatched_windows: results.into_iter().flatten().collect(),
+        size_matches: file_size == cargo.size,
+    })
+}
+
+/// Re-hash only the windows of `path` listed in `window_ids`.
+///
+/// Used by sampled verification, which checks a random subset of a large
+/// file's windows instead of reading all of it. Windows are hashed serially.
+pub fn verify_window_subset(
+    path: &Path,
+    cargo: &CargoManifest,
+    window_ids: &[u32],
+) -> Result<DeepVerifyReport> {
+    let file_size = std::fs::metadata(path).map_err(OrbitError::Io)?.len();
+    let chunk_size = window_chunk_size(cargo)?;
+
+    let mut windows_checked = 0;
+    let mut mismatched_windows = Vec::new();
+    for window in cargo.windows.iter().filter(|w| window_ids.contains(&w.id)) {
+        windows_checked += 1;
+        let root = hash_window(path, file_size, chunk_size, window)?;
+        if hex::encode(root) != window.merkle_root {
+            mismatched_windows.push(window.id);
+        }
+    }
+
+    Ok(DeepVerifyReport {
+        windows_checked,
+        mismatched_windows,
+        size_matches: file_size == cargo.size,
+    })
+}
+
+/// Chunk size the windows of `cargo` were hashed with, in bytes
+fn window_chunk_size(cargo: &CargoManifest) -> Result<u64> {
+    let chunk_kib = cargo
+        .chunking
+        .fixed_kib
+        .or(cargo.chunking.avg_kib)
+        .ok_or_else(|| {
+            OrbitError::Other(format!(
+                "Cargo manifest for {} has no chunk size",
+                cargo.path
+            ))
+        })?;
+    Ok(chunk_kib as u64 * 1024)
+}
+
+/// Hash the chunks of one window, reading only that window's byte range
+fn hash_window(
+    path: &Path,
+    file_size: u64,
+    chunk_size: u64,
+    window: &WindowMeta,
+) -> Result<[u8; 32]> {
+    let mut file = File::open(path).map_err(OrbitError::Io)?;
+    let start = window.first_chunk as u64 * chunk_size;
+    file.seek(SeekFrom::Start(start)).map_err(OrbitError::Io)?;
+
+    let mut buffer = vec![0u8; chunk_size as usize];
+    let mut root = WindowMerkleBuilder::new();
+    let mut offset = start;
+    for _ in 0..window.count {
+        let len = chunk_size.min(file_size.saturating_sub(offset)) as usize;
+        if len == 0 {
+            break;
+        }
+        file.read_exact(&mut buffer[..len])
+            .map_err(OrbitError::Io)?;
+        root.push(blake3::hash(&buffer[..len]).as_bytes());
+        offset += len as u64;
+    }
+
+    Ok(root.finalize())
+}
+
+/// A previously generated flight plan and its cargo manifests, used by
+/// `--since` to detect files that have not changed since that scan
+#[derive(Debug)]
+pub struct PriorManifest {
+    dir: PathBuf,
+    files: HashMap<String, PriorFile>,
+}
+
+#[derive(Debug)]
+struct PriorFile {
+    file_ref: FileRef,
+    cargo: CargoManifest,
+}
+
+impl PriorManifest {
+    /// Load `job.flightplan.json` and every cargo manifest it references
+    ///
+    /// Everything is read up front, so the same directory can safely receive
+    /// the updated manifest set while the copy runs.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let plan_path = dir.join("job.flightplan.json");
+        let plan = FlightPlan::load(&plan_path).map_err(|e| {
+            OrbitError::Config(format!(
+                "Failed to load prior flight plan {}: {}",
+                plan_path.display(),
+                e
+            ))
+        })?;
+
+        let mut files = HashMap::with_capacity(plan.files.len());
+        for file_ref in plan.files {
+            let cargo = CargoManifest::load(dir.join(&file_ref.cargo)).map_err(|e| {
+                OrbitError::Config(format!(
+                    "Failed to load cargo manifest {}: {}",
+                    file_ref.cargo, e
+                ))
+            })?;
+            files.insert(file_ref.path.clone(), PriorFile { file_ref, cargo });
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            files,
+        })
+    }
+
+    /// Number of files in the prior manifest
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether the prior manifest lists no files
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Whether `path` still matches its entry for `relative_path`
+    ///
+    /// The size is compared first; only a same-size file is re-hashed with
+    /// the chunking recorded in its cargo manifest and checked window by
+    /// window. Files missing from the manifest are always changed.
+    pub fn is_unchanged(&self, path: &Path, relative_path: &str) -> Result<bool> {
+        let Some(entry) = self.files.get(relative_path) else {
+            return Ok(false);
+        };
+        let size = std::fs::metadata(path).map_err(OrbitError::Io)?.len();
+        if size != entry.cargo.size {
+            return Ok(false);
+        }
+        Ok(verify_file_windows(path, &entry.cargo, 1)?.is_ok())
+    }
+}
+
 /// Check if manifest generation is enabled
 pub fn should_generate_manifest(config: &CopyConfig) -> bool {
     config.generate_manifest && config.manifest_output_dir.is_some()
@@ -322,6 +766,73 @@ mod tests {
         assert!(generator.is_ok());
     }
 
+    #[test]
+    fn test_chunk_kib_follows_size_tiers() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let dest = temp_dir.path().join("dest");
+
+        let config = CopyConfig {
+            manifest_output_dir: Some(temp_dir.path().join("manifests")),
+            chunking_strategy: ChunkingStrategy::Cdc {
+                avg_kib: 256,
+                algo: "gear".to_string(),
+            },
+            ..Default::default()
+        };
+        let generator = ManifestGenerator::new(&source, &dest, &config).unwrap();
+
+        // The configured average is a floor; tiers only grow it
+        assert_eq!(generator.chunk_kib_for(1024), 256);
+        assert_eq!(generator.chunk_kib_for(5 * GIB), 256);
+        assert_eq!(generator.chunk_kib_for(20 * GIB), 1024);
+        assert_eq!(generator.chunk_kib_for(200 * GIB), 4096);
+
+        let config = CopyConfig {
+            manifest_output_dir: Some(temp_dir.path().join("manifests")),
+            chunking_strategy: ChunkingStrategy::Fixed { size_kib: 64 },
+            ..Default::default()
+        };
+        let generator = ManifestGenerator::new(&source, &dest, &config).unwrap();
+        assert_eq!(generator.chunk_kib_for(200 * GIB), 64);
+    }
+
+    #[test]
+    fn test_file_refs_stream_to_disk_with_bounded_buffer() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        std::fs::create_dir_all(&source).unwrap();
+        let manifests = temp_dir.path().join("manifests");
+        let config = CopyConfig {
+            manifest_output_dir: Some(manifests.clone()),
+            chunking_strategy: ChunkingStrategy::Fixed { size_kib: 1 },
+            ..Default::default()
+        };
+        let mut generator = ManifestGenerator::new(&source, &source, &config).unwrap();
+        generator.ref_buffer = 16;
+
+        const FILES: usize = 2000;
+        for i in 0..FILES {
+            let name = format!("f{:05}.txt", i);
+            let path = source.join(&name);
+            std::fs::write(&path, i.to_string()).unwrap();
+            generator.generate_file_manifest(&path, &name).unwrap();
+            assert!(generator.pending_refs.len() < 16);
+        }
+        assert!(manifests.join(FILE_REFS_SPILL).exists());
+        generator.finalize("sha256:pending").unwrap();
+
+        let plan = FlightPlan::load(manifests.join("job.flightplan.json")).unwrap();
+        assert_eq!(plan.files.len(), FILES);
+        for (i, file) in plan.files.iter().enumerate() {
+            assert_eq!(file.path, format!("f{:05}.txt", i));
+            assert_eq!(file.cargo, format!("f{:05}.txt.cargo.json", i));
+        }
+        assert!(plan.is_finalized());
+        assert!(!manifests.join(FILE_REFS_SPILL).exists());
+    }
+
     #[test]
     fn test_chunk_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -350,4 +861,146 @@ mod tests {
         assert_eq!(chunks[0].length, 1024);
         assert!(!windows.is_empty());
     }
+
+    #[test]
+    fn test_incremental_window_root_matches_batch_hash() {
+        let ids: Vec<[u8; 32]> = (0..10_000u32)
+            .map(|i| *blake3::hash(&i.to_le_bytes()).as_bytes())
+            .collect();
+
+        let mut builder = WindowMerkleBuilder::new();
+        assert!(builder.is_empty());
+        for id in &ids {
+            builder.push(id);
+        }
+        assert_eq!(builder.len(), 10_000);
+
+        let batch = blake3::hash(&ids.concat());
+        assert_eq!(builder.finalize(), *batch.as_bytes());
+    }
+
+    #[test]
+    fn test_chunk_file_window_roots_match_chunk_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.bin");
+        let data: Vec<u8> = (0..300 * 1024 + 17u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let config = CopyConfig {
+            manifest_output_dir: Some(temp_dir.path().join("manifests")),
+            chunking_strategy: ChunkingStrategy::Fixed { size_kib: 1 },
+            ..Default::default()
+        };
+        let generator = ManifestGenerator::new(&path, &path, &config).unwrap();
+
+        let mut file = File::open(&path).unwrap();
+        let (chunks, windows) = generator.chunk_file(&mut file, data.len() as u64).unwrap();
+
+        assert_eq!(chunks.len(), 301);
+        assert_eq!(windows.len(), window_layout(301).len());
+        for (i, window) in windows.iter().enumerate() {
+            assert_eq!(window.id, i as u32);
+            let ids: Vec<u8> = chunks
+                [window.first_chunk as usize..(window.first_chunk + window.count as u32) as usize]
+                .iter()
+                .flat_map(|c| c.content_id)
+                .collect();
+            assert_eq!(window.merkle_root, *blake3::hash(&ids).as_bytes());
+        }
+        // Consecutive windows share the configured overlap
+        assert_eq!(windows[1].first_chunk, 60);
+    }
+
+    #[test]
+    fn test_parallel_plan_matches_serial() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("src");
+        std::fs::create_dir_all(source.join("nested")).unwrap();
+        let mut files = Vec::new();
+        for i in 0..24u32 {
+            let relative = if i % 3 == 0 {
+                format!("nested/file{:02}.bin", i)
+            } else {
+                format!("file{:02}.bin", i)
+            };
+            let data: Vec<u8> = (0..(20 + i * 7) * 1024)
+                .map(|b| (b.wrapping_mul(i + 1) % 251) as u8)
+                .collect();
+            std::fs::write(source.join(&relative), data).unwrap();
+            files.push((source.join(&relative), relative));
+        }
+
+        let plan = |jobs: usize| {
+            let manifests = temp_dir.path().join(format!("manifests-{}", jobs));
+            let config = CopyConfig {
+                manifest_output_dir: Some(manifests.clone()),
+                chunking_strategy: ChunkingStrategy::Fixed { size_kib: 1 },
+                ..Default::default()
+            };
+            let mut generator = ManifestGenerator::new(&source, &source, &config).unwrap();
+            generator
+                .generate_file_manifests(files.clone(), jobs)
+                .unwrap();
+            generator.finalize("sha256:pending").unwrap();
+
+            let plan = FlightPlan::load(manifests.join("job.flightplan.json")).unwrap();
+            let windows: Vec<_> = plan
+                .files
+                .iter()
+                .map(|f| {
+                    CargoManifest::load(manifests.join(&f.cargo))
+                        .unwrap()
+                        .windows
+                })
+                .collect();
+            (plan.files, windows)
+        };
+
+        let (serial_refs, serial_windows) = plan(1);
+        let (parallel_refs, parallel_windows) = plan(4);
+        assert_eq!(serial_refs.len(), files.len());
+        assert_eq!(serial_refs, parallel_refs);
+        assert_eq!(serial_windows, parallel_windows);
+        let paths: Vec<&str> = parallel_refs.iter().map(|f| f.path.as_str()).collect();
+        let expected: Vec<&str> = files.iter().map(|(_, rel)| rel.as_str()).collect();
+        assert_eq!(paths, expected);
+    }
+
+    #[test]
+    fn test_parallel_deep_verify_matches_serial_and_flags_corrupt_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("data.bin");
+        let data: Vec<u8> = (0..200 * 1024u32).map(|i| (i % 253) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let manifests = temp_dir.path().join("manifests");
+        let config = CopyConfig {
+            manifest_output_dir: Some(manifests.clone()),
+            chunking_strategy: ChunkingStrategy::Fixed { size_kib: 1 },
+            ..Default::default()
+        };
+        let mut generator = ManifestGenerator::new(&source, &source, &config).unwrap();
+        generator
+            .generate_file_manifest(&source, "data.bin")
+            .unwrap();
+        let cargo = CargoManifest::load(manifests.join("data.bin.cargo.json")).unwrap();
+        assert!(cargo.windows.len() > 2);
+
+        let serial = verify_file_windows(&source, &cargo, 1).unwrap();
+        let parallel = verify_file_windows(&source, &cargo, 4).unwrap();
+        assert!(serial.is_ok());
+        assert_eq!(serial, parallel);
+        assert_eq!(parallel.windows_checked, cargo.windows.len());
+
+        // Chunk 30 lies only in window 0 (window 1 starts at chunk 60)
+        let mut corrupted = data.clone();
+        corrupted[30 * 1024 + 7] ^= 0xFF;
+        std::fs::write(&source, &corrupted).unwrap();
+
+        let serial = verify_file_windows(&source, &cargo, 1).unwrap();
+        let parallel = verify_file_windows(&source, &cargo, 4).unwrap();
+        assert_eq!(serial, parallel);
+        assert_eq!(parallel.mismatched_windows, vec![0]);
+        assert!(parallel.size_matches);
+    }
 }
@@ -60,11 +60,11 @@
 use super::error::{S3Error, S3Result};
 use rand::Rng;
 use std::future::Future;
-use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
 use tokio::time::sleep;
 
+pub use crate::backend::{CircuitBreaker, CircuitState};
+
 /// Retry policy configuration
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
@@ -184,87 +184,6 @@ pub enum BackoffStrategy {
     ExponentialWithJitter,
 }
 
-/// Circuit breaker state
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum CircuitState {
-    /// Circuit is closed, requests flow normally
-    Closed,
-
-    /// Circuit is open, requests fail fast
-    Open,
-
-    /// Circuit is half-open, testing if service recovered
-    HalfOpen,
-}
-
-/// Circuit breaker for preventing cascading failures
-pub struct CircuitBreaker {
-    state: Arc<RwLock<CircuitState>>,
-    failure_count: Arc<RwLock<u32>>,
-    last_failure: Arc<RwLock<Option<Instant>>>,
-    threshold: u32,
-    timeout: Duration,
-}
-
-impl CircuitBreaker {
-    /// Create a new circuit breaker
-    pub fn new(threshold: u32, timeout: Duration) -> Self {
-        Self {
-            state: Arc::new(RwLock::new(CircuitState::Closed)),
-            failure_count: Arc::new(RwLock::new(0)),
-            last_failure: Arc::new(RwLock::new(None)),
-            threshold,
-            timeout,
-        }
-    }
-
-    /// Check if request should be allowed
-    pub async fn allow_request(&self) -> bool {
-        let state = *self.state.read().await;
-
-        match state {
-            CircuitState::Closed => true,
-            CircuitState::Open => {
-                // Check if timeout elapsed
-                if let Some(last) = *self.last_failure.read().await {
-                    if last.elapsed() >= self.timeout {
-                        // Transition to half-open
-                        *self.state.write().await = CircuitState::HalfOpen;
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            }
-            CircuitState::HalfOpen => true,
-        }
-    }
-
-    /// Record a successful request
-    pub async fn record_success(&self) {
-        *self.failure_count.write().await = 0;
-        *self.state.write().await = CircuitState::Closed;
-    }
-
-    /// Record a failed request
-    pub async fn record_failure(&self) {
-        let mut count = self.failure_count.write().await;
-        *count += 1;
-        *self.last_failure.write().await = Some(Instant::now());
-
-        if *count >= self.threshold {
-            *self.state.write().await = CircuitState::Open;
-        }
-    }
-
-    /// Get current state
-    pub async fn state(&self) -> CircuitState {
-        *self.state.read().await
-    }
-}
-
 /// Classify errors as retryable or fatal
 pub fn is_retryable_error(error: &S3Error) -> bool {
     match error {
@@ -487,45 +406,6 @@ mod tests {
         assert_eq!(policy.calculate_delay(3), Duration::from_millis(300));
     }
 
-    #[tokio::test]
-    async fn test_circuit_breaker_opens() {
-        let cb = CircuitBreaker::new(3, Duration::from_secs(1));
-
-        // Should allow requests initially
-        assert!(cb.allow_request().await);
-        assert_eq!(cb.state().await, CircuitState::Closed);
-
-        // Record failures
-        for _ in 0..3 {
-            cb.record_failure().await;
-        }
-
-        // Circuit should be open
-        assert_eq!(cb.state().await, CircuitState::Open);
-        assert!(!cb.allow_request().await);
-    }
-
-    #[tokio::test]
-    async fn test_circuit_breaker_recovery() {
-        let cb = CircuitBreaker::new(2, Duration::from_millis(50));
-
-        // Trigger circuit breaker
-        cb.record_failure().await;
-        cb.record_failure().await;
-        assert_eq!(cb.state().await, CircuitState::Open);
-
-        // Wait for timeout
-        sleep(Duration::from_millis(60)).await;
-
-        // Should transition to half-open
-        assert!(cb.allow_request().await);
-        assert_eq!(cb.state().await, CircuitState::HalfOpen);
-
-        // Success should close it
-        cb.record_success().await;
-        assert_eq!(cb.state().await, CircuitState::Closed);
-    }
-
     #[tokio::test]
     async fn test_retry_with_success() {
         use std::sync::atomic::{AtomicU32, Ordering};
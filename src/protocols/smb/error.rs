@@ -20,7 +20,7 @@ pub enum SmbError {
     NotFound(String),
 
     /// Encryption required but not negotiated
-    #[error("encryption required but not negotiated")]
+    #[error("encryption required but not negotiated: server does not offer SMB3 encryption")]
     EncryptionRequired,
 
     /// I/O error
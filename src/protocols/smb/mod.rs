@@ -81,6 +81,13 @@ pub trait SmbClient: Send + Sync {
 
     /// Disconnect from the server
     async fn disconnect(&mut self) -> Result<()>;
+
+    /// Dialect, encryption and signing negotiated with the server
+    ///
+    /// None when not connected or the client cannot report it.
+    fn connection_info(&self) -> Option<SmbConnectionInfo> {
+        None
+    }
 }
 
 /// Factory function to create an SMB client
@@ -17,8 +17,8 @@ use smb::{
         file_util::{GetLen, ReadAt, WriteAt},
         Directory,
     },
-    Client, ClientConfig, CreateOptions, FileAccessMask, FileAttributes, FileCreateArgs,
-    FileNamesInformation, Resource, UncPath,
+    Client, ClientConfig, CreateOptions, Dialect, EncryptionCipher, FileAccessMask, FileAttributes,
+    FileCreateArgs, FileNamesInformation, Resource, SigningAlgorithmId, UncPath,
 };
 
 /// Native SMB client implementation
@@ -26,6 +26,7 @@ pub struct NativeSmbClient {
     client: Client,
     target: SmbTarget,
     connected: bool,
+    connection_info: Option<SmbConnectionInfo>,
 }
 
 impl NativeSmbClient {
@@ -42,6 +43,7 @@ impl NativeSmbClient {
             client,
             target: t.clone(),
             connected: false,
+            connection_info: None,
         };
 
         // Perform the connection handshake
@@ -110,14 +112,27 @@ impl NativeSmbClient {
                 .await
             {
                 Ok(_) => {
+                    let info = self.negotiated_info(port).await;
+                    Self::enforce_negotiated(info.as_ref(), self.target.security)?;
+                    self.connection_info = info;
                     self.connected = true;
                     tracing::info!(
-                        "SMB Connected to {} (Attempt {})",
+                        "SMB Connected to {} (Attempt {}, {:?})",
                         self.target.host,
-                        attempt
+                        attempt,
+                        self.connection_info
                     );
                     return Ok(());
                 }
+                // Retrying cannot change what the server offers
+                Err(e) if Self::encryption_refused(&e) => {
+                    tracing::error!(
+                        "SMB server {} refused encryption: {:?}",
+                        self.target.host,
+                        e
+                    );
+                    return Err(SmbError::EncryptionRequired);
+                }
                 Err(e) => {
                     if attempt >= 3 {
                         tracing::error!("SMB Connection failed after 3 attempts: {:?}", e);
@@ -130,6 +145,99 @@ impl NativeSmbClient {
         }
     }
 
+    /// Whether `share_connect` failed because required encryption is unavailable
+    fn encryption_refused(err: &smb::Error) -> bool {
+        match err {
+            smb::Error::NegotiationError(msg) | smb::Error::InvalidMessage(msg) => {
+                msg.starts_with("Encryption is required")
+            }
+            _ => false,
+        }
+    }
+
+    /// Check the negotiated state against a security policy
+    ///
+    /// Fails closed: when the negotiated state cannot be read back,
+    /// `RequireEncryption` cannot be confirmed and the connection is refused.
+    pub(crate) fn enforce_negotiated(
+        info: Option<&SmbConnectionInfo>,
+        security: SmbSecurity,
+    ) -> Result<(), SmbError> {
+        match info {
+            Some(info) => info.enforce(security),
+            None if security == SmbSecurity::RequireEncryption => {
+                tracing::error!("SMB negotiated state unavailable; cannot confirm encryption");
+                Err(SmbError::EncryptionRequired)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Read back what the server negotiated for the established connection
+    async fn negotiated_info(&self, port: u16) -> Option<SmbConnectionInfo> {
+        let conn = self
+            .client
+            .get_connection(&format!("{}:{}", self.target.host, port))
+            .await
+            .ok()?;
+        let info = conn.conn_info()?;
+        let negotiated = &info.negotiation;
+        Some(Self::describe_negotiation(
+            negotiated.dialect_rev,
+            negotiated.caps.encryption(),
+            negotiated.encryption_cipher,
+            negotiated.signing_algo,
+            self.target.security,
+            matches!(self.target.auth, SmbAuth::Anonymous),
+        ))
+    }
+
+    /// Summarize negotiated properties
+    ///
+    /// Ciphers and signing algorithms are only negotiated explicitly from
+    /// SMB 3.1.1. Older dialects report the MS-SMB2 default signing
+    /// algorithm, but no cipher, since none was negotiated.
+    pub(crate) fn describe_negotiation(
+        dialect: Dialect,
+        encryption_cap: bool,
+        cipher: Option<EncryptionCipher>,
+        signing: Option<SigningAlgorithmId>,
+        security: SmbSecurity,
+        anonymous: bool,
+    ) -> SmbConnectionInfo {
+        let encryption_available = dialect.is_smb3() && (encryption_cap || cipher.is_some());
+        let cipher = match cipher {
+            Some(EncryptionCipher::Aes128Ccm) => Some("AES-128-CCM"),
+            Some(EncryptionCipher::Aes128Gcm) => Some("AES-128-GCM"),
+            Some(EncryptionCipher::Aes256Ccm) => Some("AES-256-CCM"),
+            Some(EncryptionCipher::Aes256Gcm) => Some("AES-256-GCM"),
+            None => None,
+        };
+        let signing = match signing {
+            _ if anonymous => None,
+            Some(SigningAlgorithmId::HmacSha256) => Some("HMAC-SHA256"),
+            Some(SigningAlgorithmId::AesCmac) => Some("AES-128-CMAC"),
+            Some(SigningAlgorithmId::AesGmac) => Some("AES-128-GMAC"),
+            None if dialect.is_smb3() => Some("AES-128-CMAC"),
+            None => Some("HMAC-SHA256"),
+        };
+        let dialect = match dialect {
+            Dialect::Smb0202 => "2.0.2",
+            Dialect::Smb021 => "2.1",
+            Dialect::Smb030 => "3.0",
+            Dialect::Smb0302 => "3.0.2",
+            Dialect::Smb0311 => "3.1.1",
+        };
+
+        SmbConnectionInfo {
+            dialect: dialect.to_string(),
+            encrypted: encryption_available && security == SmbSecurity::RequireEncryption,
+            encryption_available,
+            cipher: cipher.map(String::from),
+            signing: signing.map(String::from),
+        }
+    }
+
     /// Validate SMB target configuration
     pub(crate) fn validate_target(t: &SmbTarget) -> Result<(), SmbError> {
         if t.host.is_empty() || t.share.is_empty() {
@@ -394,12 +502,20 @@ impl super::SmbClient for NativeSmbClient {
             size,
             is_dir,
             modified: None, // Timestamps require FileBasicInformation query (todo)
-            encrypted: matches!(self.target.security, SmbSecurity::RequireEncryption),
+            encrypted: self
+                .connection_info
+                .as_ref()
+                .is_some_and(|info| info.encrypted),
         })
     }
 
     async fn disconnect(&mut self) -> Result<(), SmbError> {
         self.connected = false;
+        self.connection_info = None;
         Ok(())
     }
+
+    fn connection_info(&self) -> Option<SmbConnectionInfo> {
+        self.connection_info.clone()
+    }
 }
@@ -179,6 +179,7 @@ mod smb_impl_tests {
 /// export SMB_TEST_USER=testuser
 /// export SMB_TEST_PASS=testpass
 /// export SMB_TEST_ENABLED=1
+/// export SMB_TEST_SERVER_ENCRYPTS=1  # 0 if the server lacks SMB3 encryption
 ///
 /// cargo test --features smb-native -- --ignored
 /// ```
@@ -402,4 +403,122 @@ mod integration_tests {
         // Clean up
         client.remove(filename).await.ok();
     }
+
+    /// Whether the test server offers SMB3 encryption, if stated
+    fn server_encrypts() -> Option<bool> {
+        match env::var("SMB_TEST_SERVER_ENCRYPTS").ok()?.as_str() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_real_require_encryption_unavailable() {
+        let (Some(mut target), Some(false)) = (get_test_config(), server_encrypts()) else {
+            println!("Skipping integration test - unencrypted SMB server not configured");
+            return;
+        };
+        target.security = SmbSecurity::RequireEncryption;
+
+        let result = client_for(&target).await;
+        assert!(
+            matches!(result, Err(SmbError::EncryptionRequired)),
+            "expected EncryptionRequired, got {:?}",
+            result.err()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_real_require_encryption_reports_encrypted() {
+        let (Some(mut target), Some(true)) = (get_test_config(), server_encrypts()) else {
+            println!("Skipping integration test - encrypting SMB server not configured");
+            return;
+        };
+        target.security = SmbSecurity::RequireEncryption;
+
+        let client = client_for(&target).await.expect("Failed to connect");
+        let info = client.connection_info().expect("No connection info");
+        assert!(info.encrypted);
+        assert!(info.cipher.is_some());
+        assert!(info.dialect.starts_with('3'));
+
+        let meta = client
+            .metadata("")
+            .await
+            .expect("Failed to stat share root");
+        assert!(meta.encrypted);
+    }
+}
+
+#[cfg(all(test, feature = "smb-native"))]
+mod negotiation_tests {
+    use super::super::native::NativeSmbClient;
+    use super::super::*;
+    use smb::{Dialect, EncryptionCipher, SigningAlgorithmId};
+
+    #[test]
+    fn test_smb311_reports_negotiated_cipher() {
+        let info = NativeSmbClient::describe_negotiation(
+            Dialect::Smb0311,
+            false,
+            Some(EncryptionCipher::Aes256Gcm),
+            Some(SigningAlgorithmId::AesGmac),
+            SmbSecurity::RequireEncryption,
+            false,
+        );
+        assert_eq!(info.dialect, "3.1.1");
+        assert!(info.encrypted);
+        assert_eq!(info.cipher.as_deref(), Some("AES-256-GCM"));
+        assert_eq!(info.signing.as_deref(), Some("AES-128-GMAC"));
+        assert!(info.enforce(SmbSecurity::RequireEncryption).is_ok());
+    }
+
+    #[test]
+    fn test_smb2_cannot_satisfy_required_encryption() {
+        let info = NativeSmbClient::describe_negotiation(
+            Dialect::Smb021,
+            false,
+            None,
+            None,
+            SmbSecurity::RequireEncryption,
+            false,
+        );
+        assert!(!info.encryption_available);
+        assert!(!info.encrypted);
+        assert_eq!(info.cipher, None);
+        assert_eq!(info.signing.as_deref(), Some("HMAC-SHA256"));
+        assert!(matches!(
+            info.enforce(SmbSecurity::RequireEncryption),
+            Err(SmbError::EncryptionRequired)
+        ));
+    }
+
+    #[test]
+    fn test_opportunistic_does_not_claim_encryption() {
+        let info = NativeSmbClient::describe_negotiation(
+            Dialect::Smb0302,
+            true,
+            None,
+            None,
+            SmbSecurity::Opportunistic,
+            true,
+        );
+        assert!(info.encryption_available);
+        assert!(!info.encrypted);
+        assert_eq!(info.cipher, None);
+        assert_eq!(info.signing, None);
+    }
+
+    #[test]
+    fn test_missing_negotiation_fails_closed() {
+        assert!(matches!(
+            NativeSmbClient::enforce_negotiated(None, SmbSecurity::RequireEncryption),
+            Err(SmbError::EncryptionRequired)
+        ));
+        assert!(NativeSmbClient::enforce_negotiated(None, SmbSecurity::Opportunistic).is_ok());
+        assert!(NativeSmbClient::enforce_negotiated(None, SmbSecurity::SignOnly).is_ok());
+    }
 }
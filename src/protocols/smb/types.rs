@@ -1,5 +1,6 @@
 //! SMB protocol types and configuration structures
 
+use super::error::SmbError;
 use std::time::SystemTime;
 
 /// SMB target configuration
@@ -199,6 +200,38 @@ pub struct SmbMetadata {
     pub encrypted: bool,
 }
 
+/// Security negotiated for an SMB connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmbConnectionInfo {
+    /// Negotiated dialect, e.g. "3.1.1"
+    pub dialect: String,
+
+    /// Is all traffic on this connection guaranteed to be encrypted?
+    ///
+    /// Only `RequireEncryption` forces encryption; under `Opportunistic`
+    /// the server decides per share, so this stays false.
+    pub encrypted: bool,
+
+    /// Did the server offer SMB3 encryption?
+    pub encryption_available: bool,
+
+    /// Encryption cipher, when one was negotiated (SMB 3.1.1)
+    pub cipher: Option<String>,
+
+    /// Signing algorithm, or None for unsigned (anonymous) sessions
+    pub signing: Option<String>,
+}
+
+impl SmbConnectionInfo {
+    /// Check the negotiated state against a security policy
+    pub fn enforce(&self, security: SmbSecurity) -> Result<(), SmbError> {
+        if security == SmbSecurity::RequireEncryption && !self.encrypted {
+            return Err(SmbError::EncryptionRequired);
+        }
+        Ok(())
+    }
+}
+
 impl Default for SmbTarget {
     fn default() -> Self {
         Self {
@@ -242,4 +275,24 @@ mod tests {
         assert_ne!(required, sign_only);
         assert_ne!(sign_only, opportunistic);
     }
+
+    #[test]
+    fn test_connection_info_enforce() {
+        let mut info = SmbConnectionInfo {
+            dialect: "3.1.1".to_string(),
+            encrypted: false,
+            encryption_available: false,
+            cipher: None,
+            signing: Some("AES-128-GMAC".to_string()),
+        };
+        assert!(matches!(
+            info.enforce(SmbSecurity::RequireEncryption),
+            Err(SmbError::EncryptionRequired)
+        ));
+        assert!(info.enforce(SmbSecurity::Opportunistic).is_ok());
+        assert!(info.enforce(SmbSecurity::SignOnly).is_ok());
+
+        info.encrypted = true;
+        assert!(info.enforce(SmbSecurity::RequireEncryption).is_ok());
+    }
 }
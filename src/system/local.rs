@@ -22,7 +22,7 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 /// use std::path::Path;
 ///
 /// #[tokio::main]
-/// async fn main() -> anyhow::Result<()> {
+/// async fn main() -> orbit_core_interface::Result<()> {
 ///     let system = LocalSystem;
 ///     let exists = system.exists(Path::new("/tmp/test.txt")).await;
 ///     println!("File exists: {}", exists);
@@ -151,46 +151,65 @@ impl OrbitSystem for LocalSystem {
     }
 
     async fn calculate_hash(&self, path: &Path, offset: u64, len: u64) -> Result<[u8; 32]> {
-        use tokio::io::AsyncSeekExt;
+        hash_range(path, offset, len, blake3::Hasher::new()).await
+    }
 
-        let mut file = fs::File::open(path).await.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                OrbitSystemError::NotFound(path.to_path_buf())
-            } else if e.kind() == std::io::ErrorKind::PermissionDenied {
-                OrbitSystemError::PermissionDenied(path.to_path_buf())
-            } else {
-                OrbitSystemError::Io(e)
-            }
-        })?;
+    async fn calculate_keyed_hash(
+        &self,
+        path: &Path,
+        offset: u64,
+        len: u64,
+        key: &[u8; 32],
+    ) -> Result<[u8; 32]> {
+        hash_range(path, offset, len, blake3::Hasher::new_keyed(key)).await
+    }
+}
 
-        // Seek to the specified offset
-        file.seek(std::io::SeekFrom::Start(offset))
-            .await
-            .map_err(OrbitSystemError::Io)?;
+/// Feed `len` bytes of `path` starting at `offset` into `hasher`
+async fn hash_range(
+    path: &Path,
+    offset: u64,
+    len: u64,
+    mut hasher: blake3::Hasher,
+) -> Result<[u8; 32]> {
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = fs::File::open(path).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            OrbitSystemError::NotFound(path.to_path_buf())
+        } else if e.kind() == std::io::ErrorKind::PermissionDenied {
+            OrbitSystemError::PermissionDenied(path.to_path_buf())
+        } else {
+            OrbitSystemError::Io(e)
+        }
+    })?;
 
-        // Read the specified length and hash it
-        let mut hasher = blake3::Hasher::new();
-        let mut remaining = len;
-        let mut buffer = vec![0u8; 8192]; // 8KB buffer for reading
+    // Seek to the specified offset
+    file.seek(std::io::SeekFrom::Start(offset))
+        .await
+        .map_err(OrbitSystemError::Io)?;
 
-        while remaining > 0 {
-            let to_read = std::cmp::min(remaining, buffer.len() as u64) as usize;
-            let n = file
-                .read(&mut buffer[..to_read])
-                .await
-                .map_err(OrbitSystemError::Io)?;
+    // Read the specified length and hash it
+    let mut remaining = len;
+    let mut buffer = vec![0u8; 8192]; // 8KB buffer for reading
 
-            if n == 0 {
-                // EOF reached before reading the requested length
-                break;
-            }
+    while remaining > 0 {
+        let to_read = std::cmp::min(remaining, buffer.len() as u64) as usize;
+        let n = file
+            .read(&mut buffer[..to_read])
+            .await
+            .map_err(OrbitSystemError::Io)?;
 
-            hasher.update(&buffer[..n]);
-            remaining -= n as u64;
+        if n == 0 {
+            // EOF reached before reading the requested length
+            break;
         }
 
-        Ok(*hasher.finalize().as_bytes())
+        hasher.update(&buffer[..n]);
+        remaining -= n as u64;
     }
+
+    Ok(*hasher.finalize().as_bytes())
 }
 
 #[cfg(test)]
@@ -264,6 +283,38 @@ mod tests {
         assert_eq!(hash2, *expected_hash.as_bytes());
     }
 
+    #[tokio::test]
+    async fn test_calculate_keyed_hash() {
+        let system = LocalSystem;
+
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"Hello, World!").unwrap();
+        temp.flush().unwrap();
+
+        let key = [7u8; 32];
+        let keyed = system
+            .calculate_keyed_hash(temp.path(), 7, 5, &key)
+            .await
+            .unwrap();
+        let plain = system.calculate_hash(temp.path(), 7, 5).await.unwrap();
+
+        // Keyed mode must not collide with the plain content hash
+        assert_ne!(keyed, plain);
+        assert_eq!(keyed, *blake3::keyed_hash(&key, b"World").as_bytes());
+
+        // Stable for a fixed key, different under another key
+        let again = system
+            .calculate_keyed_hash(temp.path(), 7, 5, &key)
+            .await
+            .unwrap();
+        assert_eq!(keyed, again);
+        let other = system
+            .calculate_keyed_hash(temp.path(), 7, 5, &[8u8; 32])
+            .await
+            .unwrap();
+        assert_ne!(keyed, other);
+    }
+
     #[tokio::test]
     async fn test_read_write() {
         let system = LocalSystem;
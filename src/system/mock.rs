@@ -2,11 +2,15 @@
 //!
 //! This provides an in-memory implementation of OrbitSystem that can be used
 //! in unit tests without requiring actual filesystem operations.
+//!
+//! Available to other crates through the `mock` feature. Every trait call is
+//! recorded in an ordered call log, and individual calls can be scripted to
+//! return a fixed value or error for a given path.
 
 use orbit_core_interface::{FileMetadata, OrbitSystem, OrbitSystemError, Result};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::SystemTime;
 use tokio::io::{AsyncRead, AsyncWrite};
 
@@ -18,30 +22,142 @@ struct MockFile {
     is_dir: bool,
 }
 
+/// `OrbitSystem` method recorded in the call log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockMethod {
+    Exists,
+    Metadata,
+    ReadDir,
+    Reader,
+    Writer,
+    ReadHeader,
+    CalculateHash,
+    CalculateKeyedHash,
+}
+
+/// A single recorded `OrbitSystem` call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// Method that was called
+    pub method: MockMethod,
+    /// Path the call was made with
+    pub path: PathBuf,
+    /// Numeric arguments after the path (`len` for `read_header`,
+    /// `offset, len` for `calculate_hash` and `calculate_keyed_hash`, empty
+    /// otherwise; keys are never recorded)
+    pub args: Vec<u64>,
+}
+
+impl MockCall {
+    /// Convenience constructor for comparing against [`MockSystem::calls`]
+    pub fn new(method: MockMethod, path: impl Into<PathBuf>, args: &[u64]) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            args: args.to_vec(),
+        }
+    }
+}
+
+/// Scripted result for a method/path pair
+///
+/// The variant must match the method it is scripted for (e.g. `Hash` for
+/// `CalculateHash`); a mismatched reply surfaces as `OrbitSystemError::System`.
+/// `Error` works for every method except `Exists`, which reports `false`.
+#[derive(Debug)]
+pub enum MockReply {
+    Exists(bool),
+    Metadata(FileMetadata),
+    Entries(Vec<FileMetadata>),
+    /// Content returned by `reader` and `read_header`
+    Data(Vec<u8>),
+    Hash([u8; 32]),
+    Error(OrbitSystemError),
+}
+
+impl Clone for MockReply {
+    fn clone(&self) -> Self {
+        match self {
+            MockReply::Exists(exists) => MockReply::Exists(*exists),
+            MockReply::Metadata(meta) => MockReply::Metadata(meta.clone()),
+            MockReply::Entries(entries) => MockReply::Entries(entries.clone()),
+            MockReply::Data(data) => MockReply::Data(data.clone()),
+            MockReply::Hash(hash) => MockReply::Hash(*hash),
+            MockReply::Error(err) => MockReply::Error(clone_error(err)),
+        }
+    }
+}
+
+/// `OrbitSystemError` is not `Clone`; rebuild it so a scripted error can be
+/// returned on every call
+fn clone_error(err: &OrbitSystemError) -> OrbitSystemError {
+    match err {
+        OrbitSystemError::NotFound(path) => OrbitSystemError::NotFound(path.clone()),
+        OrbitSystemError::PermissionDenied(path) => {
+            OrbitSystemError::PermissionDenied(path.clone())
+        }
+        OrbitSystemError::Io(e) => {
+            OrbitSystemError::Io(std::io::Error::new(e.kind(), e.to_string()))
+        }
+        OrbitSystemError::System(msg) => OrbitSystemError::System(msg.clone()),
+        OrbitSystemError::Timeout(msg) => OrbitSystemError::Timeout(msg.clone()),
+        OrbitSystemError::ConnectionReset(msg) => OrbitSystemError::ConnectionReset(msg.clone()),
+        OrbitSystemError::Throttled {
+            message,
+            retry_after,
+        } => OrbitSystemError::Throttled {
+            message: message.clone(),
+            retry_after: *retry_after,
+        },
+    }
+}
+
+fn mismatched_reply(method: MockMethod, reply: &MockReply) -> OrbitSystemError {
+    OrbitSystemError::System(format!(
+        "scripted reply {:?} does not match {:?}",
+        reply, method
+    ))
+}
+
 /// Mock filesystem implementation for testing
 ///
 /// This implementation stores files in memory and allows tests to run
-/// without touching the actual filesystem.
+/// without touching the actual filesystem. Clones share the same files,
+/// call log and scripts.
 ///
 /// # Example
 ///
 /// ```rust
-/// use orbit::system::MockSystem;
-/// use orbit_core_interface::OrbitSystem;
+/// use orbit::system::{MockCall, MockMethod, MockReply, MockSystem};
+/// use orbit_core_interface::{OrbitSystem, OrbitSystemError};
 /// use std::path::Path;
 ///
-/// #[tokio::test]
-/// async fn test_with_mock() {
-///     let system = MockSystem::new();
-///     system.add_file("/test.txt", b"Hello, World!");
+/// # async fn example() {
+/// let system = MockSystem::new();
+/// system.add_file("/test.txt", b"Hello, World!");
+/// system.script(
+///     MockMethod::Metadata,
+///     "/locked.txt",
+///     MockReply::Error(OrbitSystemError::PermissionDenied("/locked.txt".into())),
+/// );
+///
+/// assert!(system.exists(Path::new("/test.txt")).await);
+/// assert!(system.metadata(Path::new("/locked.txt")).await.is_err());
 ///
-///     let exists = system.exists(Path::new("/test.txt")).await;
-///     assert!(exists);
-/// }
+/// assert_eq!(
+///     system.calls(),
+///     vec![
+///         MockCall::new(MockMethod::Exists, "/test.txt", &[]),
+///         MockCall::new(MockMethod::Metadata, "/locked.txt", &[]),
+///     ]
+/// );
+/// # }
 /// ```
 #[derive(Debug, Clone)]
 pub struct MockSystem {
     files: Arc<RwLock<HashMap<PathBuf, MockFile>>>,
+    calls: Arc<Mutex<Vec<MockCall>>>,
+    scripts: Arc<Mutex<HashMap<(MockMethod, PathBuf), MockReply>>>,
 }
 
 impl MockSystem {
@@ -49,6 +165,8 @@ impl MockSystem {
     pub fn new() -> Self {
         Self {
             files: Arc::new(RwLock::new(HashMap::new())),
+            calls: Arc::new(Mutex::new(Vec::new())),
+            scripts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -88,6 +206,45 @@ impl MockSystem {
     pub fn get_data(&self, path: &Path) -> Option<Vec<u8>> {
         self.files.read().unwrap().get(path).map(|f| f.data.clone())
     }
+
+    /// Script the reply for every `method` call on `path`
+    ///
+    /// Replaces any earlier script for the same method and path. Scripted
+    /// replies take precedence over the in-memory files.
+    pub fn script(&self, method: MockMethod, path: impl Into<PathBuf>, reply: MockReply) {
+        self.scripts
+            .lock()
+            .unwrap()
+            .insert((method, path.into()), reply);
+    }
+
+    /// Remove all scripted replies
+    pub fn clear_scripts(&self) {
+        self.scripts.lock().unwrap().clear();
+    }
+
+    /// Calls made so far, in the order they were made
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Forget all recorded calls
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    /// Log a call and return its scripted reply, if any
+    fn record(&self, method: MockMethod, path: &Path, args: &[u64]) -> Option<MockReply> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockCall::new(method, path, args));
+        self.scripts
+            .lock()
+            .unwrap()
+            .get(&(method, path.to_path_buf()))
+            .cloned()
+    }
 }
 
 impl Default for MockSystem {
@@ -99,10 +256,21 @@ impl Default for MockSystem {
 #[async_trait::async_trait]
 impl OrbitSystem for MockSystem {
     async fn exists(&self, path: &Path) -> bool {
+        match self.record(MockMethod::Exists, path, &[]) {
+            Some(MockReply::Exists(exists)) => return exists,
+            Some(_) => return false,
+            None => {}
+        }
         self.files.read().unwrap().contains_key(path)
     }
 
     async fn metadata(&self, path: &Path) -> Result<FileMetadata> {
+        match self.record(MockMethod::Metadata, path, &[]) {
+            Some(MockReply::Metadata(meta)) => return Ok(meta),
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::Metadata, &reply)),
+            None => {}
+        }
         let files = self.files.read().unwrap();
         let file = files
             .get(path)
@@ -117,6 +285,12 @@ impl OrbitSystem for MockSystem {
     }
 
     async fn read_dir(&self, path: &Path) -> Result<Vec<FileMetadata>> {
+        match self.record(MockMethod::ReadDir, path, &[]) {
+            Some(MockReply::Entries(entries)) => return Ok(entries),
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::ReadDir, &reply)),
+            None => {}
+        }
         let files = self.files.read().unwrap();
 
         // Check if directory exists
@@ -143,6 +317,16 @@ impl OrbitSystem for MockSystem {
     }
 
     async fn reader(&self, path: &Path) -> Result<Box<dyn AsyncRead + Unpin + Send>> {
+        match self.record(MockMethod::Reader, path, &[]) {
+            Some(MockReply::Data(data)) => {
+                return Ok(Box::new(tokio::io::BufReader::new(std::io::Cursor::new(
+                    data,
+                ))))
+            }
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::Reader, &reply)),
+            None => {}
+        }
         let files = self.files.read().unwrap();
         let file = files
             .get(path)
@@ -154,13 +338,19 @@ impl OrbitSystem for MockSystem {
     }
 
     async fn writer(&self, path: &Path) -> Result<Box<dyn AsyncWrite + Unpin + Send>> {
+        match self.record(MockMethod::Writer, path, &[]) {
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::Writer, &reply)),
+            None => {}
+        }
         // For the mock, we create a buffer that will be written back to the HashMap
         // when dropped. For simplicity, we'll just create a vector.
         // In a real implementation, we'd use a custom writer that updates the HashMap.
 
         // For now, create parent directories if needed
+        // (checked directly so the call log only shows the caller's calls)
         if let Some(parent) = path.parent() {
-            if !self.exists(parent).await {
+            if !self.files.read().unwrap().contains_key(parent) {
                 self.add_dir(parent);
             }
         }
@@ -173,6 +363,12 @@ impl OrbitSystem for MockSystem {
     }
 
     async fn read_header(&self, path: &Path, len: usize) -> Result<Vec<u8>> {
+        match self.record(MockMethod::ReadHeader, path, &[len as u64]) {
+            Some(MockReply::Data(data)) => return Ok(data[..len.min(data.len())].to_vec()),
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::ReadHeader, &reply)),
+            None => {}
+        }
         let files = self.files.read().unwrap();
         let file = files
             .get(path)
@@ -183,6 +379,12 @@ impl OrbitSystem for MockSystem {
     }
 
     async fn calculate_hash(&self, path: &Path, offset: u64, len: u64) -> Result<[u8; 32]> {
+        match self.record(MockMethod::CalculateHash, path, &[offset, len]) {
+            Some(MockReply::Hash(hash)) => return Ok(hash),
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::CalculateHash, &reply)),
+            None => {}
+        }
         let files = self.files.read().unwrap();
         let file = files
             .get(path)
@@ -199,6 +401,30 @@ impl OrbitSystem for MockSystem {
         let hash = blake3::hash(&file.data[start..end]);
         Ok(*hash.as_bytes())
     }
+
+    async fn calculate_keyed_hash(
+        &self,
+        path: &Path,
+        offset: u64,
+        len: u64,
+        key: &[u8; 32],
+    ) -> Result<[u8; 32]> {
+        match self.record(MockMethod::CalculateKeyedHash, path, &[offset, len]) {
+            Some(MockReply::Hash(hash)) => return Ok(hash),
+            Some(MockReply::Error(err)) => return Err(err),
+            Some(reply) => return Err(mismatched_reply(MockMethod::CalculateKeyedHash, &reply)),
+            None => {}
+        }
+        let files = self.files.read().unwrap();
+        let file = files
+            .get(path)
+            .ok_or_else(|| OrbitSystemError::NotFound(path.to_path_buf()))?;
+
+        let start = std::cmp::min(offset as usize, file.data.len());
+        let end = std::cmp::min(start.saturating_add(len as usize), file.data.len());
+
+        Ok(*blake3::keyed_hash(key, &file.data[start..end]).as_bytes())
+    }
 }
 
 #[cfg(test)]
@@ -260,6 +486,29 @@ mod tests {
         assert_eq!(hash2, *expected.as_bytes());
     }
 
+    #[tokio::test]
+    async fn test_mock_keyed_hash_does_not_record_key() {
+        let system = MockSystem::new();
+        system.add_file("/test.txt", b"Hello, World!");
+
+        let key = [42u8; 32];
+        let keyed = system
+            .calculate_keyed_hash(Path::new("/test.txt"), 0, 5, &key)
+            .await
+            .unwrap();
+        assert_eq!(keyed, *blake3::keyed_hash(&key, b"Hello").as_bytes());
+        assert_ne!(keyed, *blake3::hash(b"Hello").as_bytes());
+
+        assert_eq!(
+            system.calls(),
+            vec![MockCall::new(
+                MockMethod::CalculateKeyedHash,
+                "/test.txt",
+                &[0, 5]
+            )]
+        );
+    }
+
     #[tokio::test]
     async fn test_mock_directories() {
         let system = MockSystem::new();
@@ -307,4 +556,111 @@ mod tests {
         assert!(!system.exists(Path::new("/file1.txt")).await);
         assert!(!system.exists(Path::new("/file2.txt")).await);
     }
+
+    #[tokio::test]
+    async fn test_mock_call_log_is_ordered() {
+        let system = MockSystem::new();
+        system.add_dir("/data");
+        system.add_file("/data/a.bin", b"0123456789");
+
+        assert!(system.exists(Path::new("/data/a.bin")).await);
+        system.metadata(Path::new("/data/a.bin")).await.unwrap();
+        system.read_dir(Path::new("/data")).await.unwrap();
+        system
+            .read_header(Path::new("/data/a.bin"), 4)
+            .await
+            .unwrap();
+        system
+            .calculate_hash(Path::new("/data/a.bin"), 2, 6)
+            .await
+            .unwrap();
+        system
+            .write_all(Path::new("/out/b.bin"), b"x")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            system.calls(),
+            vec![
+                MockCall::new(MockMethod::Exists, "/data/a.bin", &[]),
+                MockCall::new(MockMethod::Metadata, "/data/a.bin", &[]),
+                MockCall::new(MockMethod::ReadDir, "/data", &[]),
+                MockCall::new(MockMethod::ReadHeader, "/data/a.bin", &[4]),
+                MockCall::new(MockMethod::CalculateHash, "/data/a.bin", &[2, 6]),
+                MockCall::new(MockMethod::Writer, "/out/b.bin", &[]),
+            ]
+        );
+
+        // Clones share the log
+        let clone = system.clone();
+        clone.clear_calls();
+        assert!(system.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_scripted_errors_propagate() {
+        let system = MockSystem::new();
+        system.add_file("/data/a.bin", b"content");
+        system.script(
+            MockMethod::Reader,
+            "/data/a.bin",
+            MockReply::Error(OrbitSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "link dropped",
+            ))),
+        );
+
+        // Every call fails, including through the extension helpers
+        for _ in 0..2 {
+            match system.read_all(Path::new("/data/a.bin")).await {
+                Err(OrbitSystemError::Io(e)) => {
+                    assert_eq!(e.kind(), std::io::ErrorKind::ConnectionReset)
+                }
+                other => panic!(
+                    "expected scripted I/O error, got {:?}",
+                    other.map(|d| d.len())
+                ),
+            }
+        }
+
+        // Only the scripted method and path are affected
+        assert_eq!(
+            system
+                .read_header(Path::new("/data/a.bin"), 3)
+                .await
+                .unwrap(),
+            b"con"
+        );
+
+        system.clear_scripts();
+        assert_eq!(
+            system.read_all(Path::new("/data/a.bin")).await.unwrap(),
+            b"content"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_scripted_values() {
+        let system = MockSystem::new();
+        system.script(MockMethod::Exists, "/remote/x", MockReply::Exists(true));
+        system.script(
+            MockMethod::CalculateHash,
+            "/remote/x",
+            MockReply::Hash([7; 32]),
+        );
+        system.script(MockMethod::Metadata, "/remote/x", MockReply::Hash([7; 32]));
+
+        assert!(system.exists(Path::new("/remote/x")).await);
+        assert_eq!(
+            system
+                .calculate_hash(Path::new("/remote/x"), 0, 10)
+                .await
+                .unwrap(),
+            [7; 32]
+        );
+        assert!(matches!(
+            system.metadata(Path::new("/remote/x")).await,
+            Err(OrbitSystemError::System(_))
+        ));
+    }
 }
@@ -2,14 +2,15 @@
 //!
 //! This module provides concrete implementations of the `OrbitSystem` trait:
 //! - `LocalSystem`: Direct filesystem access for standalone mode
-//! - `MockSystem`: In-memory implementation for testing (in tests module)
+//! - `MockSystem`: In-memory implementation with a call log for testing
+//!   (unit tests, or other crates via the `mock` feature)
 
 mod local;
 
 pub use local::LocalSystem;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 pub mod mock;
 
-#[cfg(test)]
-pub use mock::MockSystem;
+#[cfg(any(test, feature = "mock"))]
+pub use mock::{MockCall, MockMethod, MockReply, MockSystem};
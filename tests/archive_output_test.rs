@@ -0,0 +1,176 @@
+//! Integration tests for `--archive` output mode
+
+use orbit::config::{ArchiveFormat, CopyConfig};
+use orbit::copy_directory;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Build a small tree and return its expected member -> contents map
+fn create_tree(root: &Path) -> BTreeMap<String, Vec<u8>> {
+    let files: Vec<(&str, Vec<u8>)> = vec![
+        ("top.txt", b"top level file".to_vec()),
+        ("docs/readme.md", b"# readme\n".to_vec()),
+        (
+            "docs/nested/deep.bin",
+            (0..20_000u32).map(|i| (i % 251) as u8).collect(),
+        ),
+        ("empty.txt", Vec::new()),
+    ];
+
+    let mut expected = BTreeMap::new();
+    for (rel, data) in files {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &data).unwrap();
+        expected.insert(rel.to_string(), data);
+    }
+    fs::create_dir_all(root.join("empty_dir")).unwrap();
+    expected
+}
+
+fn archive_config(format: ArchiveFormat) -> CopyConfig {
+    CopyConfig {
+        recursive: true,
+        archive: Some(format),
+        show_progress: false,
+        ..Default::default()
+    }
+}
+
+fn read_tar<R: Read>(reader: R) -> (BTreeMap<String, Vec<u8>>, Vec<String>) {
+    let mut files = BTreeMap::new();
+    let mut dirs = Vec::new();
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let name = entry.path().unwrap().to_string_lossy().into_owned();
+        if entry.header().entry_type().is_dir() {
+            dirs.push(name.trim_end_matches('/').to_string());
+        } else {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            files.insert(name, data);
+        }
+    }
+    (files, dirs)
+}
+
+#[test]
+fn test_archive_tar_round_trip() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("src");
+    let expected = create_tree(&source);
+    let dest = temp.path().join("out/backup.tar");
+
+    let stats = copy_directory(&source, &dest, &archive_config(ArchiveFormat::Tar)).unwrap();
+    assert_eq!(stats.files_copied, expected.len() as u64);
+
+    let (files, dirs) = read_tar(File::open(&dest).unwrap());
+    assert_eq!(files, expected);
+    assert!(dirs.contains(&"empty_dir".to_string()));
+    assert!(dirs.contains(&"docs/nested".to_string()));
+    assert!(!temp.path().join("out/backup.tar.orbit-part").exists());
+}
+
+#[test]
+fn test_archive_tar_zst_round_trip() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("src");
+    let expected = create_tree(&source);
+    let dest = temp.path().join("backup.tar.zst");
+
+    copy_directory(&source, &dest, &archive_config(ArchiveFormat::TarZst)).unwrap();
+
+    let decoder = zstd::Decoder::new(File::open(&dest).unwrap()).unwrap();
+    let (files, dirs) = read_tar(decoder);
+    assert_eq!(files, expected);
+    assert!(dirs.contains(&"empty_dir".to_string()));
+}
+
+#[test]
+fn test_archive_zip_round_trip() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("src");
+    let expected = create_tree(&source);
+    let dest = temp.path().join("backup.zip");
+
+    copy_directory(&source, &dest, &archive_config(ArchiveFormat::Zip)).unwrap();
+
+    let mut archive = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+    let mut files = BTreeMap::new();
+    let mut dirs = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).unwrap();
+        let name = entry.name().to_string();
+        if entry.is_dir() {
+            dirs.push(name.trim_end_matches('/').to_string());
+        } else {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            files.insert(name, data);
+        }
+    }
+    assert_eq!(files, expected);
+    assert!(dirs.contains(&"empty_dir".to_string()));
+}
+
+#[test]
+fn test_archive_into_existing_directory_uses_source_name() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("photos");
+    create_tree(&source);
+    let dest = temp.path().join("dest");
+    fs::create_dir_all(&dest).unwrap();
+
+    copy_directory(&source, &dest, &archive_config(ArchiveFormat::Zip)).unwrap();
+    assert!(dest.join("photos.zip").is_file());
+}
+
+#[test]
+fn test_archive_respects_exclude_filters() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("src");
+    create_tree(&source);
+    let dest = temp.path().join("filtered.tar");
+
+    let config = CopyConfig {
+        exclude_patterns: vec!["*.bin".to_string()],
+        ..archive_config(ArchiveFormat::Tar)
+    };
+    copy_directory(&source, &dest, &config).unwrap();
+
+    let (files, _) = read_tar(File::open(&dest).unwrap());
+    assert!(files.contains_key("top.txt"));
+    assert!(!files.contains_key("docs/nested/deep.bin"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_archive_tar_preserves_permissions_and_mtime() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("src");
+    fs::create_dir_all(&source).unwrap();
+    let script = source.join("run.sh");
+    fs::write(&script, b"#!/bin/sh\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o750)).unwrap();
+    let mtime = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+    filetime::set_file_mtime(&script, mtime).unwrap();
+
+    let dest = temp.path().join("perm.tar");
+    copy_directory(&source, &dest, &archive_config(ArchiveFormat::Tar)).unwrap();
+
+    let mut archive = tar::Archive::new(File::open(&dest).unwrap());
+    let entry = archive
+        .entries()
+        .unwrap()
+        .map(|e| e.unwrap())
+        .find(|e| e.path().unwrap().to_string_lossy() == "run.sh")
+        .expect("run.sh in archive");
+    assert_eq!(entry.header().mode().unwrap() & 0o777, 0o750);
+    assert_eq!(entry.header().mtime().unwrap(), 1_600_000_000);
+}
@@ -0,0 +1,90 @@
+use assert_cmd::Command;
+use orbit_observability::{EventCapture, EventPayload, TraceContext};
+use predicates::str::contains;
+use tempfile::tempdir;
+
+const SECRET: &str = "audit-verify-cli-secret";
+
+fn signed_log(count: usize) -> Vec<String> {
+    let capture = EventCapture::with_secret(SECRET.as_bytes());
+    let logger = capture.logger();
+    let ctx = TraceContext::new_root();
+    for i in 0..count {
+        logger
+            .emit_with_context(
+                &ctx,
+                EventPayload::Custom {
+                    event_type: format!("event_{}", i),
+                    data: serde_json::json!({ "value": i }),
+                },
+            )
+            .unwrap();
+    }
+    capture
+        .events()
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect()
+}
+
+fn audit_verify(path: &std::path::Path) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("orbit")
+        .unwrap()
+        .env("ORBIT_AUDIT_SECRET", SECRET)
+        .arg("audit")
+        .arg("verify")
+        .arg(path)
+        .assert()
+}
+
+#[test]
+fn test_audit_verify_valid_log_exits_zero() {
+    let tmp = tempdir().unwrap();
+    let log = tmp.path().join("audit.jsonl");
+    std::fs::write(&log, signed_log(4).join("\n")).unwrap();
+
+    audit_verify(&log)
+        .success()
+        .stdout(contains("4 events in 1 segment(s)"));
+}
+
+#[test]
+fn test_audit_verify_tampered_log_exit_codes() {
+    let tmp = tempdir().unwrap();
+    let lines = signed_log(4);
+
+    let mut edited = lines.clone();
+    edited[2] = edited[2].replace("event_2", "event_9");
+    let log = tmp.path().join("edited.jsonl");
+    std::fs::write(&log, edited.join("\n")).unwrap();
+    audit_verify(&log).code(3);
+
+    let mut removed = lines.clone();
+    removed.remove(1);
+    let log = tmp.path().join("removed.jsonl");
+    std::fs::write(&log, removed.join("\n")).unwrap();
+    audit_verify(&log).code(2);
+
+    let mut garbled = lines;
+    garbled.push("not json".to_string());
+    let log = tmp.path().join("garbled.jsonl");
+    std::fs::write(&log, garbled.join("\n")).unwrap();
+    audit_verify(&log).code(4);
+}
+
+#[test]
+fn test_audit_verify_requires_secret() {
+    let tmp = tempdir().unwrap();
+    let log = tmp.path().join("audit.jsonl");
+    std::fs::write(&log, signed_log(1).join("\n")).unwrap();
+
+    Command::cargo_bin("orbit")
+        .unwrap()
+        .env_remove("ORBIT_AUDIT_SECRET")
+        .arg("audit")
+        .arg("verify")
+        .arg(&log)
+        .assert()
+        .failure()
+        .stderr(contains("ORBIT_AUDIT_SECRET"));
+}
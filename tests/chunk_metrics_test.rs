@@ -0,0 +1,69 @@
+//! Chunk-size metrics exported from the chunking paths
+//!
+//! Kept in its own test binary: the Prometheus registry is process-wide, so
+//! chunking in concurrently running tests would skew the counts.
+
+use orbit::config::{ChunkingStrategy, CopyConfig};
+use orbit::core::batch::{record_create_file, JournalEntry, TransferJournal};
+use orbit::manifest_integration::ManifestGenerator;
+use orbit_core_cdc::testing::xorshift_bytes;
+use orbit_observability::metrics;
+use tempfile::tempdir;
+
+/// Value of an unlabelled sample such as `orbit_chunks_total 12`
+fn sample(text: &str, name: &str) -> f64 {
+    text.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+        .unwrap_or_else(|| panic!("{} not exported", name))
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_chunk_histogram_counts_every_chunk() {
+    let _ = metrics::registry();
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("input.bin");
+    let data = xorshift_bytes(3 * 1024 * 1024, 0x9E37_79B9_7F4A_7C15);
+    std::fs::write(&path, &data).unwrap();
+
+    // CDC path (batch journal recording)
+    let mut journal = TransferJournal::new(dir.path().into(), dir.path().into());
+    record_create_file(&mut journal, &path, "input.bin".as_ref()).unwrap();
+    let cdc_chunks = match &journal.entries[0] {
+        JournalEntry::CreateFile { chunk_hashes, .. } => chunk_hashes.len(),
+        other => panic!("unexpected entry {:?}", other),
+    };
+    assert!(cdc_chunks > 1);
+
+    let text = metrics::metrics_text();
+    assert_eq!(
+        sample(&text, "orbit_chunk_size_bytes_count"),
+        cdc_chunks as f64
+    );
+    assert_eq!(sample(&text, "orbit_chunks_total"), cdc_chunks as f64);
+    assert_eq!(
+        sample(&text, "orbit_chunk_size_bytes_sum"),
+        data.len() as f64
+    );
+
+    // Fixed-size manifest path: 3 MiB in 64 KiB chunks
+    let config = CopyConfig {
+        manifest_output_dir: Some(dir.path().join("manifests")),
+        chunking_strategy: ChunkingStrategy::Fixed { size_kib: 64 },
+        ..Default::default()
+    };
+    let mut generator = ManifestGenerator::new(dir.path(), dir.path(), &config).unwrap();
+    generator
+        .generate_file_manifest(&path, "input.bin")
+        .unwrap();
+
+    let text = metrics::metrics_text();
+    let total = (cdc_chunks + 48) as f64;
+    assert_eq!(sample(&text, "orbit_chunk_size_bytes_count"), total);
+    assert_eq!(sample(&text, "orbit_chunks_total"), total);
+    assert_eq!(
+        sample(&text, "orbit_chunk_size_bytes_bucket{le=\"+Inf\"}"),
+        total
+    );
+}
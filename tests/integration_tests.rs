@@ -201,6 +201,45 @@ fn test_parallel_directory_copy() {
     }
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sparse_always_preserves_holes() {
+    use std::os::unix::fs::{FileExt, MetadataExt};
+
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("disk.img");
+    let dest = dir.path().join("disk-copy.img");
+
+    // 32 MiB image with 64 KiB of data at the start, middle and end
+    let size: u64 = 32 * 1024 * 1024;
+    let block = vec![0x5Au8; 64 * 1024];
+    let file = std::fs::File::create(&source).unwrap();
+    file.set_len(size).unwrap();
+    for offset in [0, size / 2, size - block.len() as u64] {
+        file.write_all_at(&block, offset).unwrap();
+    }
+    file.sync_all().unwrap();
+    drop(file);
+
+    let config = CopyConfig {
+        sparse_mode: SparseMode::Always,
+        ..Default::default()
+    };
+    let stats = copy_file(&source, &dest, &config).unwrap();
+
+    assert_eq!(stats.bytes_copied, size);
+    let meta = std::fs::metadata(&dest).unwrap();
+    assert_eq!(meta.len(), size);
+    let allocated = meta.blocks() * 512;
+    assert!(
+        allocated < size / 8,
+        "destination allocates {} bytes for a {} byte file",
+        allocated,
+        size
+    );
+    assert!(std::fs::read(&source).unwrap() == std::fs::read(&dest).unwrap());
+}
+
 // ============================================================================
 // Zero-Copy Tests
 // ============================================================================
@@ -159,3 +159,82 @@ fn test_progress_events_directory_copy() {
 
     println!("✓ Directory copy emitted all expected events");
 }
+
+#[test]
+fn test_json_progress_stream_directory_copy() {
+    use orbit::cli_progress::JsonProgressRenderer;
+    use std::collections::HashMap;
+
+    let temp = TempDir::new().unwrap();
+    let source_dir = temp.path().join("source");
+    let dest_dir = temp.path().join("dest");
+
+    fs::create_dir_all(source_dir.join("nested")).unwrap();
+    let files = [
+        ("a.bin", 1024usize),
+        ("b.bin", 4096),
+        ("nested/c.bin", 10 * 1024),
+    ];
+    for (name, size) in &files {
+        fs::write(source_dir.join(name), vec![7u8; *size]).unwrap();
+    }
+
+    let (publisher, subscriber) = ProgressPublisher::unbounded();
+    let renderer = JsonProgressRenderer::new(subscriber, Vec::new()).spawn();
+
+    let config = CopyConfig {
+        recursive: true,
+        show_progress: false,
+        use_zero_copy: false,
+        ..Default::default()
+    };
+
+    use orbit::core::copy_directory_impl;
+    let stats = copy_directory_impl(&source_dir, &dest_dir, &config, Some(&publisher)).unwrap();
+
+    drop(publisher);
+    let output = renderer.join().unwrap().unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // Group the NDJSON lines by path, preserving order
+    let mut per_file: HashMap<String, Vec<(String, u64, u64)>> = HashMap::new();
+    for line in output.lines() {
+        let value: serde_json::Value = serde_json::from_str(line).unwrap();
+        let event = value["event"].as_str().unwrap().to_string();
+        let path = value["path"].as_str().unwrap().to_string();
+        let bytes = value["bytes"].as_u64().unwrap();
+        let total = value["total"].as_u64().unwrap();
+        per_file
+            .entry(path)
+            .or_default()
+            .push((event, bytes, total));
+    }
+
+    assert_eq!(per_file.len(), files.len(), "one event sequence per file");
+
+    let mut done_bytes = 0u64;
+    for (name, size) in &files {
+        let path = source_dir.join(name).to_string_lossy().to_string();
+        let events = per_file
+            .get(&path)
+            .unwrap_or_else(|| panic!("no events for {}", path));
+
+        let (first, _, first_total) = &events[0];
+        assert_eq!(first, "file_start");
+        assert_eq!(*first_total, *size as u64);
+
+        let (last, last_bytes, last_total) = events.last().unwrap();
+        assert_eq!(last, "file_done");
+        assert_eq!(*last_bytes, *size as u64);
+        assert_eq!(*last_total, *size as u64);
+
+        for (event, _, _) in &events[1..events.len() - 1] {
+            assert_eq!(event, "file_progress");
+        }
+        done_bytes += last_bytes;
+    }
+
+    let expected_total: u64 = files.iter().map(|(_, size)| *size as u64).sum();
+    assert_eq!(done_bytes, expected_total);
+    assert_eq!(stats.bytes_copied, expected_total);
+}
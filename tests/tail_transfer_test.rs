@@ -0,0 +1,36 @@
+//! Integration tests for append-only (`--tail`) transfers
+
+use orbit::config::CopyConfig;
+use orbit::copy_file;
+use orbit::core::tail::TailMode;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use tempfile::TempDir;
+
+#[test]
+fn test_copy_file_tails_appended_wal_records() {
+    let temp = TempDir::new().unwrap();
+    let source = temp.path().join("000001.wal");
+    let dest = temp.path().join("replica/000001.wal");
+    fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+    let config = CopyConfig {
+        tail_mode: TailMode::Auto,
+        show_progress: false,
+        ..Default::default()
+    };
+
+    fs::write(&source, vec![7u8; 64 * 1024]).unwrap();
+    let stats = copy_file(&source, &dest, &config).unwrap();
+    assert_eq!(stats.bytes_copied, 64 * 1024);
+
+    OpenOptions::new()
+        .append(true)
+        .open(&source)
+        .unwrap()
+        .write_all(b"commit 42")
+        .unwrap();
+    let stats = copy_file(&source, &dest, &config).unwrap();
+    assert_eq!(stats.bytes_copied, 9);
+    assert_eq!(fs::read(&dest).unwrap(), fs::read(&source).unwrap());
+}